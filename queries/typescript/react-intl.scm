@@ -0,0 +1,87 @@
+;; useIntl hook / createIntl
+;; const intl = useIntl()
+(variable_declarator
+  name: (identifier) @i18n.get_trans_fn_name
+  value:
+    (call_expression
+      function: (identifier) @_use_intl (#match? @_use_intl "^(useIntl|createIntl)$")
+    )
+) @i18n.get_trans_fn
+
+;; Destructured formatMessage
+;; const { formatMessage } = useIntl()
+(variable_declarator
+  name: (object_pattern
+    (shorthand_property_identifier_pattern) @i18n.get_trans_fn_name
+      (#eq? @i18n.get_trans_fn_name "formatMessage")
+  )
+  value:
+    (call_expression
+      function: (identifier) @_use_intl (#match? @_use_intl "^(useIntl|createIntl)$")
+    )
+) @i18n.get_trans_fn
+
+;; Renamed formatMessage
+;; const { formatMessage: fm } = useIntl()
+(variable_declarator
+  name: (object_pattern
+    (pair_pattern
+      key: (property_identifier) @_format_message (#eq? @_format_message "formatMessage")
+      value: (identifier) @i18n.get_trans_fn_name
+    )
+  )
+  value:
+    (call_expression
+      function: (identifier) @_use_intl (#match? @_use_intl "^(useIntl|createIntl)$")
+    )
+) @i18n.get_trans_fn
+
+;; Call formatMessage: intl.formatMessage({ id: "key" }) / formatMessage({ id: "key" })
+(call_expression
+  function: [
+    (identifier) @i18n.call_trans_fn_name
+    (member_expression
+      property: (property_identifier) @_format_message (#eq? @_format_message "formatMessage")
+    ) @i18n.call_trans_fn_name
+  ]
+  arguments: (arguments
+    .
+    (object
+      (pair
+        key: (property_identifier) @_id (#eq? @_id "id")
+        value: (string (string_fragment)? @i18n.trans_key) @i18n.trans_key_arg
+      )
+    )
+  ) @i18n.trans_args
+) @i18n.call_trans_fn
+
+;; Re-query helper: extracts key from a message descriptor object.
+;; Used when re-querying captured nodes from the defineMessages patterns below.
+;; Not collected during initial scan (no @i18n.call_trans_fn capture).
+(object
+  (pair
+    key: (property_identifier) @_id_requery (#eq? @_id_requery "id")
+    value: (string (string_fragment)? @i18n.trans_key) @i18n.trans_key_arg
+  )
+) @i18n.trans_args
+
+;; defineMessages({ name: { id: "key" }, ... })
+;; Each message descriptor is captured as a separate call_trans_fn.
+(call_expression
+  function: (identifier) @_define_messages (#eq? @_define_messages "defineMessages")
+  arguments: (arguments
+    (object
+      (pair
+        value: (object) @i18n.call_trans_fn
+      )
+    )
+  )
+)
+
+;; defineMessage({ id: "key" })
+(call_expression
+  function: (identifier) @_define_message (#eq? @_define_message "defineMessage")
+  arguments: (arguments
+    (object) @i18n.call_trans_fn
+  )
+)
@@ -0,0 +1,230 @@
+//! Headless diagnostics for CI (`js-i18n-language-server check`).
+//!
+//! Indexes a workspace the same way the language server does and collects the diagnostics
+//! that editors would show, without an LSP client.
+
+mod output;
+
+use std::collections::HashMap;
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::Mutex;
+use tower_lsp::lsp_types::{
+    Diagnostic,
+    DiagnosticSeverity,
+};
+
+pub use self::output::render;
+use crate::config::{
+    ConfigError,
+    ConfigManager,
+};
+use crate::db::I18nDatabaseImpl;
+use crate::ide::diagnostics::DiagnosticConfig;
+use crate::indexer::types::IndexerError;
+use crate::indexer::workspace::WorkspaceIndexer;
+use crate::types::PositionEncoding;
+
+#[derive(Error, Debug)]
+pub enum CheckError {
+    #[error("Workspace not found: {0}")]
+    WorkspaceNotFound(PathBuf),
+
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+
+    #[error(transparent)]
+    Indexer(#[from] IndexerError),
+}
+
+/// Output format of the `check` command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// `path:line:column: severity[code] message`
+    #[default]
+    Human,
+    Json,
+    /// SARIF 2.1.0, for code scanning integrations
+    Sarif,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            "sarif" => Ok(Self::Sarif),
+            _ => Err(format!("Unknown format '{s}' (expected human, json or sarif)")),
+        }
+    }
+}
+
+/// Diagnostics of a single file.
+#[derive(Debug, Clone)]
+pub struct FileReport {
+    /// Path relative to the workspace root
+    pub path: PathBuf,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Result of checking a workspace. Files without diagnostics are omitted.
+#[derive(Debug, Clone, Default)]
+pub struct CheckReport {
+    pub files: Vec<FileReport>,
+}
+
+impl CheckReport {
+    /// Counts diagnostics of the given severity.
+    #[must_use]
+    pub fn count(&self, severity: DiagnosticSeverity) -> usize {
+        self.files
+            .iter()
+            .flat_map(|file| &file.diagnostics)
+            .filter(|diagnostic| diagnostic.severity == Some(severity))
+            .count()
+    }
+
+    /// Returns `true` if any error-severity diagnostic was reported.
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        self.count(DiagnosticSeverity::ERROR) > 0
+    }
+}
+
+/// Indexes the workspace at `root` and collects source and translation file diagnostics.
+///
+/// Settings are loaded from the workspace's `.js-i18n.json`. Positions are reported in UTF-16
+/// code units, as SARIF expects.
+pub async fn run(root: &Path) -> Result<CheckReport, CheckError> {
+    let root =
+        root.canonicalize().map_err(|_| CheckError::WorkspaceNotFound(root.to_path_buf()))?;
+
+    let mut config_manager = ConfigManager::new();
+    config_manager.load_settings(Some(root.clone()))?;
+
+    let db = I18nDatabaseImpl::default();
+    let source_files = Arc::new(Mutex::new(HashMap::new()));
+    let translations = Arc::new(Mutex::new(Vec::new()));
+    WorkspaceIndexer::new()
+        .index_workspace(
+            db.clone(),
+            &root,
+            &config_manager,
+            Arc::clone(&source_files),
+            Arc::clone(&translations),
+            None::<fn(u32, u32)>,
+        )
+        .await?;
+
+    let config = DiagnosticConfig::new(config_manager.get_settings(), PositionEncoding::Utf16);
+    let source_files = source_files.lock().await;
+    let translations = translations.lock().await;
+
+    let mut reports: Vec<(PathBuf, Vec<Diagnostic>)> = source_files
+        .iter()
+        .map(|(path, source_file)| {
+            let visible = crate::ide::scope::visible_translations(&db, &translations, path);
+            (path.clone(), config.source_file_diagnostics(&db, *source_file, &visible))
+        })
+        .collect();
+
+    if config.has_translation_file_diagnostics() {
+        let source_file_vec: Vec<_> = source_files.values().copied().collect();
+        reports.extend(translations.iter().filter(|t| !t.is_component_local(&db)).map(
+            |translation| {
+                (
+                    PathBuf::from(translation.file_path(&db)),
+                    config.translation_file_diagnostics(
+                        &db,
+                        *translation,
+                        &translations,
+                        &source_file_vec,
+                    ),
+                )
+            },
+        ));
+    }
+    drop(translations);
+    drop(source_files);
+
+    let mut files: Vec<FileReport> = reports
+        .into_iter()
+        .filter(|(_, diagnostics)| !diagnostics.is_empty())
+        .map(|(path, mut diagnostics)| {
+            diagnostics.sort_by_key(|d| (d.range.start.line, d.range.start.character));
+            let path = path.strip_prefix(&root).map(Path::to_path_buf).unwrap_or(path);
+            FileReport { path, diagnostics }
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(CheckReport { files })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[rstest]
+    #[case::human("human", OutputFormat::Human)]
+    #[case::json("json", OutputFormat::Json)]
+    #[case::sarif("sarif", OutputFormat::Sarif)]
+    fn output_format_from_str(#[case] input: &str, #[case] expected: OutputFormat) {
+        assert_that!(input.parse::<OutputFormat>(), ok(eq(&expected)));
+    }
+
+    #[rstest]
+    fn output_format_rejects_unknown() {
+        assert_that!("xml".parse::<OutputFormat>(), err(contains_substring("xml")));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn run_reports_source_and_translation_diagnostics() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            ".js-i18n.json",
+            r#"{ "diagnostics": { "icuSyntax": { "enabled": true } } }"#,
+        );
+        write(dir.path(), "locales/en.json", r#"{ "hello": "Hello", "broken": "{name" }"#);
+        write(dir.path(), "locales/ja.json", r#"{ "hello": "こんにちは", "broken": "{name}" }"#);
+        write(dir.path(), "src/app.ts", "t('hello');\nt('missing');\n");
+
+        let report = run(dir.path()).await.unwrap();
+
+        let paths: Vec<String> =
+            report.files.iter().map(|f| f.path.to_string_lossy().to_string()).collect();
+        assert_that!(
+            paths,
+            elements_are![eq("locales/en.json"), eq("locales/ja.json"), eq("src/app.ts")]
+        );
+        assert_that!(report.has_errors(), eq(true));
+        assert_that!(report.count(DiagnosticSeverity::WARNING), eq(1));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn run_fails_for_missing_workspace() {
+        let result = run(Path::new("/nonexistent/workspace")).await;
+        assert_that!(result, err(anything()));
+    }
+}
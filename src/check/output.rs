@@ -0,0 +1,285 @@
+//! Rendering of check reports as human-readable text, JSON and SARIF.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+
+use serde_json::{
+    Value,
+    json,
+};
+use tower_lsp::lsp_types::{
+    Diagnostic,
+    DiagnosticSeverity,
+    NumberOrString,
+};
+
+use super::{
+    CheckReport,
+    OutputFormat,
+};
+
+/// Renders a report in the given format.
+#[must_use]
+pub fn render(report: &CheckReport, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Human => render_human(report),
+        OutputFormat::Json => render_json(report),
+        OutputFormat::Sarif => render_sarif(report),
+    }
+}
+
+const fn severity_label(severity: Option<DiagnosticSeverity>) -> &'static str {
+    match severity {
+        Some(DiagnosticSeverity::ERROR) => "error",
+        Some(DiagnosticSeverity::INFORMATION) => "information",
+        Some(DiagnosticSeverity::HINT) => "hint",
+        _ => "warning",
+    }
+}
+
+fn code(diagnostic: &Diagnostic) -> String {
+    match &diagnostic.code {
+        Some(NumberOrString::String(code)) => code.clone(),
+        Some(NumberOrString::Number(code)) => code.to_string(),
+        None => String::new(),
+    }
+}
+
+fn render_human(report: &CheckReport) -> String {
+    let mut output = String::new();
+    for file in &report.files {
+        for diagnostic in &file.diagnostics {
+            let _ = writeln!(
+                output,
+                "{}:{}:{}: {}[{}] {}",
+                file.path.display(),
+                diagnostic.range.start.line + 1,
+                diagnostic.range.start.character + 1,
+                severity_label(diagnostic.severity),
+                code(diagnostic),
+                diagnostic.message
+            );
+        }
+    }
+
+    let errors = report.count(DiagnosticSeverity::ERROR);
+    let warnings = report.count(DiagnosticSeverity::WARNING);
+    let others =
+        report.files.iter().map(|f| f.diagnostics.len()).sum::<usize>() - errors - warnings;
+    let _ = writeln!(
+        output,
+        "{}{errors} error(s), {warnings} warning(s), {others} other(s) in {} file(s)",
+        if output.is_empty() { "" } else { "\n" },
+        report.files.len()
+    );
+    output
+}
+
+fn render_json(report: &CheckReport) -> String {
+    let files: Vec<Value> = report
+        .files
+        .iter()
+        .map(|file| {
+            let diagnostics: Vec<Value> = file
+                .diagnostics
+                .iter()
+                .map(|diagnostic| {
+                    json!({
+                        "severity": severity_label(diagnostic.severity),
+                        "code": code(diagnostic),
+                        "message": diagnostic.message,
+                        "range": {
+                            "start": {
+                                "line": diagnostic.range.start.line + 1,
+                                "column": diagnostic.range.start.character + 1,
+                            },
+                            "end": {
+                                "line": diagnostic.range.end.line + 1,
+                                "column": diagnostic.range.end.character + 1,
+                            },
+                        },
+                    })
+                })
+                .collect();
+            json!({ "path": file.path.to_string_lossy(), "diagnostics": diagnostics })
+        })
+        .collect();
+
+    let output = json!({
+        "files": files,
+        "summary": {
+            "errors": report.count(DiagnosticSeverity::ERROR),
+            "warnings": report.count(DiagnosticSeverity::WARNING),
+            "information": report.count(DiagnosticSeverity::INFORMATION),
+            "hints": report.count(DiagnosticSeverity::HINT),
+        },
+    });
+    serde_json::to_string_pretty(&output).unwrap_or_default()
+}
+
+const fn sarif_level(severity: Option<DiagnosticSeverity>) -> &'static str {
+    match severity {
+        Some(DiagnosticSeverity::ERROR) => "error",
+        Some(DiagnosticSeverity::INFORMATION | DiagnosticSeverity::HINT) => "note",
+        _ => "warning",
+    }
+}
+
+fn render_sarif(report: &CheckReport) -> String {
+    let rule_ids: BTreeSet<String> =
+        report.files.iter().flat_map(|file| &file.diagnostics).map(code).collect();
+    let rules: Vec<Value> = rule_ids.iter().map(|id| json!({ "id": id })).collect();
+
+    let results: Vec<Value> = report
+        .files
+        .iter()
+        .flat_map(|file| {
+            // SARIF URIs use forward slashes
+            let uri = file.path.to_string_lossy().replace('\\', "/");
+            file.diagnostics.iter().map(move |diagnostic| {
+                json!({
+                    "ruleId": code(diagnostic),
+                    "level": sarif_level(diagnostic.severity),
+                    "message": { "text": diagnostic.message },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": uri, "uriBaseId": "%SRCROOT%" },
+                            "region": {
+                                "startLine": diagnostic.range.start.line + 1,
+                                "startColumn": diagnostic.range.start.character + 1,
+                                "endLine": diagnostic.range.end.line + 1,
+                                "endColumn": diagnostic.range.end.character + 1,
+                            },
+                        },
+                    }],
+                })
+            })
+        })
+        .collect();
+
+    let output = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    });
+    serde_json::to_string_pretty(&output).unwrap_or_default()
+}
+
+#[cfg(test)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+    use std::path::PathBuf;
+
+    use googletest::prelude::*;
+    use rstest::*;
+    use tower_lsp::lsp_types::{
+        Position,
+        Range,
+    };
+
+    use super::*;
+    use crate::check::FileReport;
+
+    fn report() -> CheckReport {
+        let diagnostic = |line, severity, code: &str, message: &str| Diagnostic {
+            range: Range {
+                start: Position { line, character: 2 },
+                end: Position { line, character: 9 },
+            },
+            severity: Some(severity),
+            code: Some(NumberOrString::String(code.to_string())),
+            message: message.to_string(),
+            ..Default::default()
+        };
+        CheckReport {
+            files: vec![
+                FileReport {
+                    path: PathBuf::from("locales/en.json"),
+                    diagnostics: vec![diagnostic(
+                        0,
+                        DiagnosticSeverity::ERROR,
+                        "icu-syntax-error",
+                        "Invalid ICU message",
+                    )],
+                },
+                FileReport {
+                    path: PathBuf::from("src/app.ts"),
+                    diagnostics: vec![diagnostic(
+                        4,
+                        DiagnosticSeverity::WARNING,
+                        "missing-translation",
+                        "Missing translation",
+                    )],
+                },
+            ],
+        }
+    }
+
+    #[rstest]
+    fn renders_human() {
+        assert_that!(
+            render(&report(), OutputFormat::Human),
+            eq("locales/en.json:1:3: error[icu-syntax-error] Invalid ICU message\n\
+                src/app.ts:5:3: warning[missing-translation] Missing translation\n\
+                \n\
+                1 error(s), 1 warning(s), 0 other(s) in 2 file(s)\n")
+        );
+    }
+
+    #[rstest]
+    fn renders_human_without_diagnostics() {
+        assert_that!(
+            render(&CheckReport::default(), OutputFormat::Human),
+            eq("0 error(s), 0 warning(s), 0 other(s) in 0 file(s)\n")
+        );
+    }
+
+    #[rstest]
+    fn renders_json() {
+        let output: Value =
+            serde_json::from_str(&render(&report(), OutputFormat::Json)).unwrap_or_default();
+
+        assert_that!(output["summary"]["errors"], eq(&json!(1)));
+        assert_that!(output["files"][1]["path"], eq(&json!("src/app.ts")));
+        assert_that!(
+            output["files"][1]["diagnostics"][0],
+            eq(&json!({
+                "severity": "warning",
+                "code": "missing-translation",
+                "message": "Missing translation",
+                "range": {
+                    "start": { "line": 5, "column": 3 },
+                    "end": { "line": 5, "column": 10 },
+                },
+            }))
+        );
+    }
+
+    #[rstest]
+    fn renders_sarif() {
+        let output: Value =
+            serde_json::from_str(&render(&report(), OutputFormat::Sarif)).unwrap_or_default();
+
+        assert_that!(output["version"], eq(&json!("2.1.0")));
+        let run = &output["runs"][0];
+        assert_that!(
+            run["tool"]["driver"]["rules"],
+            eq(&json!([{ "id": "icu-syntax-error" }, { "id": "missing-translation" }]))
+        );
+        assert_that!(run["results"][0]["level"], eq(&json!("error")));
+        assert_that!(
+            run["results"][1]["locations"][0]["physicalLocation"]["region"],
+            eq(&json!({ "startLine": 5, "startColumn": 3, "endLine": 5, "endColumn": 10 }))
+        );
+    }
+}
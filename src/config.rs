@@ -16,8 +16,13 @@ pub use matcher::{
 pub use types::{
     ConfigError,
     DiagnosticsConfig,
+    HardcodedStringConfig,
     I18nSettings,
+    IcuSyntaxConfig,
+    InterpolationConfig,
     MissingTranslationConfig,
+    PlaceholderConsistencyConfig,
+    PluralCategoriesConfig,
     ServerSettings,
     Severity,
     TranslationFilesConfig,
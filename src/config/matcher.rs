@@ -118,6 +118,21 @@ impl FileMatcher {
         self.source_include_set.is_match(relative_path) && !self.exclude_set.is_match(relative_path)
     }
 
+    /// Returns true if the directory's contents match `excludePatterns` (e.g., `node_modules`
+    /// for `**/node_modules/**`).
+    ///
+    /// The path must be absolute and under the workspace root.
+    #[must_use]
+    pub fn is_excluded_directory(&self, absolute_path: &Path) -> bool {
+        let Some(relative_path) = absolute_path.strip_prefix(&self.workspace_root).ok() else {
+            return false;
+        };
+
+        // `dir/**` matches the entries of `dir`, not `dir` itself, so probe with an entry
+        self.exclude_set.is_match(relative_path)
+            || self.exclude_set.is_match(relative_path.join("_"))
+    }
+
     /// Returns true if the path matches `translationFiles.includePatterns`
     /// but not `excludePatterns` or `translationFiles.excludePatterns`.
     ///
@@ -196,6 +211,19 @@ mod tests {
         assert!(!matcher.is_source_file(Path::new("/workspace/dist/bundle.ts")));
     }
 
+    #[rstest]
+    fn is_excluded_directory_matches_directory_contents() {
+        let settings = create_settings(&["**/*.ts"], &["**/node_modules/**", "dist"], &[]);
+        let matcher =
+            FileMatcher::new(PathBuf::from("/workspace"), &settings).expect("valid patterns");
+
+        assert!(matcher.is_excluded_directory(Path::new("/workspace/node_modules")));
+        assert!(matcher.is_excluded_directory(Path::new("/workspace/packages/a/node_modules")));
+        assert!(matcher.is_excluded_directory(Path::new("/workspace/dist")));
+        assert!(!matcher.is_excluded_directory(Path::new("/workspace/src")));
+        assert!(!matcher.is_excluded_directory(Path::new("/other/node_modules")));
+    }
+
     #[rstest]
     fn is_source_file_outside_workspace() {
         let settings = I18nSettings::default();
@@ -138,12 +138,104 @@ impl Default for UnusedTranslationConfig {
     }
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InterpolationConfig {
+    pub enabled: bool,
+    pub severity: Severity,
+}
+
+impl Default for InterpolationConfig {
+    fn default() -> Self {
+        Self { enabled: true, severity: Severity::Warning }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PlaceholderConsistencyConfig {
+    pub enabled: bool,
+    pub severity: Severity,
+}
+
+impl Default for PlaceholderConsistencyConfig {
+    fn default() -> Self {
+        Self { enabled: true, severity: Severity::Warning }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct IcuSyntaxConfig {
+    /// `None` enables the check only when every source file uses an ICU library, since
+    /// braces are plain text in i18next values.
+    pub enabled: Option<bool>,
+    pub severity: Severity,
+}
+
+impl Default for IcuSyntaxConfig {
+    fn default() -> Self {
+        Self { enabled: None, severity: Severity::Error }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PluralCategoriesConfig {
+    pub enabled: bool,
+    pub severity: Severity,
+}
+
+impl Default for PluralCategoriesConfig {
+    fn default() -> Self {
+        Self { enabled: true, severity: Severity::Warning }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HardcodedStringConfig {
+    pub enabled: bool,
+    pub severity: Severity,
+    /// JSX and template attributes whose static values are reported.
+    pub attributes: Vec<String>,
+    /// Glob patterns for strings to exclude.
+    pub ignore_patterns: Vec<String>,
+    /// Strings shorter than this (in characters, after trimming) are not reported.
+    pub min_length: usize,
+}
+
+impl Default for HardcodedStringConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            severity: Severity::Information,
+            attributes: ["title", "placeholder", "aria-label", "alt"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            ignore_patterns: Vec::new(),
+            min_length: 2,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase", default)]
 #[derive(Default)]
 pub struct DiagnosticsConfig {
     pub missing_translation: MissingTranslationConfig,
     pub unused_translation: UnusedTranslationConfig,
+    /// Checks interpolation variables passed at call sites against translation values.
+    pub interpolation: InterpolationConfig,
+    /// Compares placeholders in translation files against the primary language.
+    pub placeholder_consistency: PlaceholderConsistencyConfig,
+    /// Reports ICU `MessageFormat` syntax errors in translation files.
+    pub icu_syntax: IcuSyntaxConfig,
+    /// Checks plural variants against the CLDR plural categories of each language.
+    pub plural_categories: PluralCategoriesConfig,
+    /// Reports JSX, Vue and Svelte markup text that is not translated.
+    pub hardcoded_string: HardcodedStringConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -204,6 +296,11 @@ impl I18nSettings {
             "translationFiles.excludePatterns",
             &mut errors,
         );
+        validate_glob_patterns(
+            &self.diagnostics.hardcoded_string.ignore_patterns,
+            "diagnostics.hardcodedString.ignorePatterns",
+            &mut errors,
+        );
 
         let mt = &self.diagnostics.missing_translation;
         if mt.required_languages.is_some() && mt.optional_languages.is_some() {
@@ -336,18 +433,51 @@ mod tests {
         assert_that!(config.missing_translation.severity, eq(Severity::Warning));
         assert_that!(config.unused_translation.enabled, eq(true));
         assert_that!(config.unused_translation.severity, eq(Severity::Hint));
+        assert_that!(config.interpolation.enabled, eq(true));
+        assert_that!(config.interpolation.severity, eq(Severity::Warning));
+        assert_that!(config.placeholder_consistency.enabled, eq(true));
+        assert_that!(config.icu_syntax.enabled, none());
+        assert_that!(config.icu_syntax.severity, eq(Severity::Error));
+        assert_that!(config.plural_categories.enabled, eq(true));
+        assert_that!(config.hardcoded_string.enabled, eq(false));
+        assert_that!(config.hardcoded_string.severity, eq(Severity::Information));
+        assert_that!(config.hardcoded_string.attributes, len(eq(4)));
+    }
+
+    #[rstest]
+    fn validate_hardcoded_string_ignore_patterns() {
+        let settings = I18nSettings {
+            diagnostics: DiagnosticsConfig {
+                hardcoded_string: HardcodedStringConfig {
+                    ignore_patterns: vec!["[invalid".to_string()],
+                    ..HardcodedStringConfig::default()
+                },
+                ..DiagnosticsConfig::default()
+            },
+            ..I18nSettings::default()
+        };
+
+        assert_that!(
+            settings.validate(),
+            err(contains(field!(
+                ValidationError.field_path,
+                eq("diagnostics.hardcodedString.ignorePatterns[0]")
+            )))
+        );
     }
 
     #[rstest]
     fn deserialize_diagnostics_config_nested() {
         let json = r#"{
             "missingTranslation": { "enabled": false },
-            "unusedTranslation": { "severity": "error" }
+            "unusedTranslation": { "severity": "error" },
+            "interpolation": { "enabled": false }
         }"#;
         let config: DiagnosticsConfig = serde_json::from_str(json).unwrap();
 
         assert_that!(config.missing_translation.enabled, eq(false));
         assert_that!(config.unused_translation.severity, eq(Severity::Error));
+        assert_that!(config.interpolation.enabled, eq(false));
     }
 
     #[rstest]
@@ -1,12 +1,26 @@
 //! Salsa database definitions.
 
+use crate::syntax::tree_cache::SyntaxTreeCache;
+
 #[salsa::db]
-pub trait I18nDatabase: salsa::Database + Send {}
+pub trait I18nDatabase: salsa::Database + Send {
+    /// Syntax trees of open documents, reused when reparsing after incremental edits.
+    fn syntax_trees(&self) -> &SyntaxTreeCache;
+}
 
 #[salsa::db]
 #[derive(Clone, Default)]
 pub struct I18nDatabaseImpl {
     storage: salsa::Storage<Self>,
+    syntax_trees: SyntaxTreeCache,
+}
+
+impl I18nDatabaseImpl {
+    /// Creates an empty database that keeps the syntax trees of open documents.
+    #[must_use]
+    pub fn with_syntax_trees(syntax_trees: SyntaxTreeCache) -> Self {
+        Self { storage: salsa::Storage::default(), syntax_trees }
+    }
 }
 
 impl std::fmt::Debug for I18nDatabaseImpl {
@@ -19,4 +33,8 @@ impl std::fmt::Debug for I18nDatabaseImpl {
 impl salsa::Database for I18nDatabaseImpl {}
 
 #[salsa::db]
-impl I18nDatabase for I18nDatabaseImpl {}
+impl I18nDatabase for I18nDatabaseImpl {
+    fn syntax_trees(&self) -> &SyntaxTreeCache {
+        &self.syntax_trees
+    }
+}
@@ -2,6 +2,7 @@
 
 pub mod i18next;
 pub mod next_intl;
+pub mod react_intl;
 pub mod svelte_i18n;
 pub mod vue_i18n;
 
@@ -14,7 +15,7 @@ use crate::input::source::ProgrammingLanguage;
 pub enum PluralStrategy {
     /// i18next: plural variants use key suffixes (`_one`, `_other`, etc.)
     SuffixBased,
-    /// `ICU` `MessageFormat` (`next-intl`, `svelte-i18n`): plurals are embedded in
+    /// `ICU` `MessageFormat` (`next-intl`, `react-intl`, `svelte-i18n`): plurals are embedded in
     /// values, not keys. No suffix-based plural handling needed.
     Icu,
 }
@@ -57,11 +58,15 @@ pub struct ParsedTransFnArgs {
 pub fn applicable_libraries(lang: ProgrammingLanguage) -> &'static [&'static dyn I18nLibrary] {
     match lang {
         ProgrammingLanguage::Jsx | ProgrammingLanguage::Tsx => {
-            &[&i18next::I18next, &next_intl::NextIntl]
-        }
-        ProgrammingLanguage::JavaScript | ProgrammingLanguage::TypeScript => {
-            &[&i18next::I18next, &next_intl::NextIntl, &svelte_i18n::SvelteI18n, &vue_i18n::VueI18n]
+            &[&i18next::I18next, &next_intl::NextIntl, &react_intl::ReactIntl]
         }
+        ProgrammingLanguage::JavaScript | ProgrammingLanguage::TypeScript => &[
+            &i18next::I18next,
+            &next_intl::NextIntl,
+            &react_intl::ReactIntl,
+            &svelte_i18n::SvelteI18n,
+            &vue_i18n::VueI18n,
+        ],
         ProgrammingLanguage::Svelte => &[&svelte_i18n::SvelteI18n],
         ProgrammingLanguage::Vue => &[&vue_i18n::VueI18n],
     }
@@ -145,6 +150,19 @@ impl FrameworkConfig {
         }
     }
 
+    /// Merged plural strategy of a project whose source files are in `languages`.
+    ///
+    /// Same rule as for a single language: `SuffixBased` wins if any language uses it.
+    #[must_use]
+    pub fn project_plural_strategy(
+        languages: impl IntoIterator<Item = ProgrammingLanguage>,
+    ) -> PluralStrategy {
+        let suffix_based = languages
+            .into_iter()
+            .any(|lang| Self::for_language(lang).plural_strategy == PluralStrategy::SuffixBased);
+        if suffix_based { PluralStrategy::SuffixBased } else { PluralStrategy::Icu }
+    }
+
     /// Delegate argument parsing to the first library that handles this `func_name`.
     #[must_use]
     pub fn parse_get_trans_fn_args(
@@ -167,10 +185,10 @@ mod tests {
     // --- applicable_libraries ---
 
     #[rstest]
-    #[case::jsx(ProgrammingLanguage::Jsx, 2)]
-    #[case::tsx(ProgrammingLanguage::Tsx, 2)]
-    #[case::js(ProgrammingLanguage::JavaScript, 4)]
-    #[case::ts(ProgrammingLanguage::TypeScript, 4)]
+    #[case::jsx(ProgrammingLanguage::Jsx, 3)]
+    #[case::tsx(ProgrammingLanguage::Tsx, 3)]
+    #[case::js(ProgrammingLanguage::JavaScript, 5)]
+    #[case::ts(ProgrammingLanguage::TypeScript, 5)]
     #[case::svelte(ProgrammingLanguage::Svelte, 1)]
     #[case::vue(ProgrammingLanguage::Vue, 1)]
     fn applicable_libraries_count(#[case] lang: ProgrammingLanguage, #[case] expected: usize) {
@@ -197,6 +215,15 @@ mod tests {
         assert!(config.allowed_trans_fn_methods.contains(&"raw"));
     }
 
+    #[rstest]
+    fn tsx_config_has_react_intl_globals_and_methods() {
+        let config = FrameworkConfig::for_language(ProgrammingLanguage::Tsx);
+
+        assert!(config.known_global_trans_fns.contains(&"intl.formatMessage"));
+        assert!(config.known_global_trans_fns.contains(&"this.props.intl.formatMessage"));
+        assert!(config.allowed_trans_fn_methods.contains(&"formatMessage"));
+    }
+
     #[rstest]
     fn js_config_includes_all_frameworks() {
         let config = FrameworkConfig::for_language(ProgrammingLanguage::JavaScript);
@@ -204,6 +231,7 @@ mod tests {
         assert!(config.known_global_trans_fns.contains(&"i18next.t"));
         assert!(config.known_global_trans_fns.contains(&"$_"));
         assert!(config.known_global_trans_fns.contains(&"$t"));
+        assert!(config.known_global_trans_fns.contains(&"intl.formatMessage"));
         assert!(config.allowed_trans_fn_methods.contains(&"rich"));
     }
 
@@ -213,6 +241,7 @@ mod tests {
 
         assert!(config.known_global_trans_fns.contains(&"$_"));
         assert!(!config.known_global_trans_fns.contains(&"i18next.t"));
+        assert!(!config.known_global_trans_fns.contains(&"intl.formatMessage"));
         assert_that!(config.allowed_trans_fn_methods, is_empty());
     }
 
@@ -256,6 +285,20 @@ mod tests {
         assert_that!(config.plural_strategy, eq(PluralStrategy::SuffixBased));
     }
 
+    #[rstest]
+    #[case::svelte_and_vue(&[ProgrammingLanguage::Svelte, ProgrammingLanguage::Vue], PluralStrategy::Icu)]
+    #[case::with_tsx(&[ProgrammingLanguage::Svelte, ProgrammingLanguage::Tsx], PluralStrategy::SuffixBased)]
+    #[case::no_source_files(&[], PluralStrategy::Icu)]
+    fn project_plural_strategy_merges_languages(
+        #[case] languages: &[ProgrammingLanguage],
+        #[case] expected: PluralStrategy,
+    ) {
+        assert_that!(
+            FrameworkConfig::project_plural_strategy(languages.iter().copied()),
+            eq(expected)
+        );
+    }
+
     // --- parse_get_trans_fn_args delegation ---
 
     #[rstest]
@@ -0,0 +1,25 @@
+//! react-intl (`FormatJS`) library support.
+
+use super::{
+    I18nLibrary,
+    PluralStrategy,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReactIntl;
+
+impl I18nLibrary for ReactIntl {
+    // `intl` injected via `injectIntl()` props or created with `createIntl()`
+    fn known_global_trans_fns(&self) -> &'static [&'static str] {
+        &["intl.formatMessage", "props.intl.formatMessage", "this.props.intl.formatMessage"]
+    }
+
+    // intl.formatMessage() where `intl` comes from useIntl()
+    fn allowed_trans_fn_methods(&self) -> &'static [&'static str] {
+        &["formatMessage"]
+    }
+
+    fn plural_strategy(&self) -> PluralStrategy {
+        PluralStrategy::Icu
+    }
+}
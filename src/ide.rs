@@ -2,15 +2,27 @@
 
 pub mod backend;
 pub mod code_actions;
+pub mod code_lens;
 pub mod completion;
 pub mod diagnostics;
+pub mod document_symbol;
+pub mod edit_translation;
+pub mod extract;
+pub mod file_watcher;
 pub mod goto_definition;
 mod handlers;
 pub mod hover;
+pub mod icu;
+pub mod inline;
+pub mod interpolation;
 pub mod key_match;
+pub mod move_key;
 pub mod namespace;
 pub mod plural;
+pub mod pull_diagnostics;
 pub mod references;
 pub mod rename;
+pub mod scope;
 pub mod state;
 pub mod virtual_text;
+pub mod workspace_symbol;
@@ -7,6 +7,8 @@ use std::path::{
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::types::LineIndex;
+
 /// Timeout for waiting translation index completion.
 pub(crate) const TRANSLATIONS_INDEX_TIMEOUT: Duration = Duration::from_millis(500);
 
@@ -45,11 +47,15 @@ use tower_lsp::{
     LanguageServer,
 };
 
-use super::diagnostics::DiagnosticOptions;
+use super::diagnostics::DiagnosticConfig;
+use super::file_watcher::FileWatcher;
 use super::handlers;
 use super::state::ServerState;
 use crate::config::ConfigManager;
-use crate::db::I18nDatabaseImpl;
+use crate::db::{
+    I18nDatabase,
+    I18nDatabaseImpl,
+};
 use crate::indexer::workspace::WorkspaceIndexer;
 
 /// Namespace-aware context for a translation key at a cursor position.
@@ -66,16 +72,31 @@ pub struct KeyContext {
     pub declared_namespaces: Option<Vec<String>>,
     /// Namespace of the JSON translation file (JSON files only).
     pub translation_namespace: Option<String>,
+    /// Whether the key is a pattern built from a dynamic expression (source files only).
+    ///
+    /// Such keys stand for several keys, so they can't be edited, deleted or moved.
+    pub dynamic: bool,
 }
 
 impl KeyContext {
+    /// Creates the context of a key usage in a source file.
+    pub fn from_usage(db: &dyn I18nDatabase, usage: crate::ir::key_usage::KeyUsage<'_>) -> Self {
+        Self {
+            key_text: usage.key(db).text(db).clone(),
+            declared_namespace: usage.namespace(db),
+            declared_namespaces: usage.namespaces(db),
+            translation_namespace: None,
+            dynamic: usage.dynamic(db),
+        }
+    }
+
     /// Splits the key text by namespace separator and filters translations.
     ///
     /// Returns `(key_part, filtered_translations)` where `key_part` has the
     /// namespace prefix stripped (or the full key if no separator matched).
     pub fn filter_translations(
         &self,
-        db: &dyn crate::db::I18nDatabase,
+        db: &dyn I18nDatabase,
         translations: &[crate::input::translation::Translation],
         namespace_separator: Option<&str>,
         default_namespace: Option<&str>,
@@ -116,14 +137,6 @@ impl KeyContext {
     }
 }
 
-/// Settings needed for diagnostic generation, extracted from config.
-struct DiagnosticConfig {
-    options: DiagnosticOptions,
-    key_separator: String,
-    namespace_separator: Option<String>,
-    default_namespace: Option<String>,
-}
-
 /// LSP Backend
 #[derive(Clone)]
 pub struct Backend {
@@ -158,26 +171,9 @@ impl Backend {
         self.workspace_indexer.wait_for_translations_indexed(TRANSLATIONS_INDEX_TIMEOUT).await
     }
 
-    async fn get_diagnostic_config(&self) -> DiagnosticConfig {
+    pub(crate) async fn get_diagnostic_config(&self) -> DiagnosticConfig {
         let settings = self.config_manager.lock().await.get_settings().clone();
-        let mt = &settings.diagnostics.missing_translation;
-        DiagnosticConfig {
-            options: DiagnosticOptions {
-                enabled: mt.enabled,
-                severity: mt.severity,
-                required_languages: mt
-                    .required_languages
-                    .as_ref()
-                    .map(|v| v.iter().cloned().collect()),
-                optional_languages: mt
-                    .optional_languages
-                    .as_ref()
-                    .map(|v| v.iter().cloned().collect()),
-            },
-            key_separator: settings.key_separator,
-            namespace_separator: settings.namespace_separator,
-            default_namespace: settings.default_namespace,
-        }
+        DiagnosticConfig::new(&settings, self.get_position_encoding().await)
     }
 
     /// Resets state and initializes index. Creates new Salsa database to clear old cache.
@@ -186,9 +182,10 @@ impl Backend {
     /// state (new DB + old SourceFile/Translation IDs → boxcar panic).
     async fn reset_state(&self) {
         let (mut db, mut source_files, mut translations) = self.state.lock_all().await;
-        *db = I18nDatabaseImpl::default();
+        *db = I18nDatabaseImpl::with_syntax_trees(db.syntax_trees().clone());
         source_files.clear();
         translations.clear();
+        *self.state.index_generation.lock().await += 1;
         drop((db, source_files, translations));
         self.workspace_indexer.reset_indexing_state();
     }
@@ -225,55 +222,122 @@ impl Backend {
         self.config_manager.lock().await.get_settings().key_separator.clone()
     }
 
+    pub(crate) async fn get_position_encoding(&self) -> crate::types::PositionEncoding {
+        *self.state.position_encoding.lock().await
+    }
+
     /// Collects all translation keys referenced in source files.
     pub(crate) async fn collect_used_keys(
         &self,
         key_separator: &str,
-    ) -> std::collections::HashSet<String> {
+    ) -> crate::ide::diagnostics::UsedKeys {
         let db = self.state.db.lock().await;
         let source_files = self.state.source_files.lock().await;
         let source_file_vec: Vec<_> = source_files.values().copied().collect();
         drop(source_files);
 
-        let mut keys = std::collections::HashSet::new();
+        let mut keys = crate::ide::diagnostics::UsedKeys::default();
         for source_file in source_file_vec {
             let key_usages =
                 crate::syntax::analyze_source(&*db, source_file, key_separator.to_owned());
             for usage in key_usages {
-                keys.insert(usage.key(&*db).text(&*db).clone());
+                keys.insert(usage.key(&*db).text(&*db).clone(), usage.dynamic(&*db));
             }
         }
         keys
     }
 
+    /// Collects translation decorations for a source file.
+    ///
+    /// Language priority: `language` > `currentLanguage` > `primaryLanguages` > first available.
+    /// Returns `None` if the file is not indexed.
+    pub(crate) async fn get_translation_decorations(
+        &self,
+        file_path: &Path,
+        language: Option<String>,
+    ) -> Option<Vec<crate::ide::virtual_text::TranslationDecoration>> {
+        let config = self.config_manager.lock().await;
+        let settings = config.get_settings();
+        let primary_languages = settings.primary_languages.clone();
+        let key_separator = settings.key_separator.clone();
+        let namespace_separator = settings.namespace_separator.clone();
+        let default_namespace = settings.default_namespace.clone();
+        drop(config);
+        let encoding = self.get_position_encoding().await;
+
+        let db = self.state.db.lock().await;
+        let source_file = self.state.source_files.lock().await.get(file_path).copied()?;
+
+        let translations = {
+            let translations = self.state.translations.lock().await;
+            crate::ide::scope::visible_translations(&*db, &translations, file_path)
+        };
+
+        let current_language = self.state.current_language.lock().await.clone();
+        let language = language.or_else(|| {
+            collect_sorted_languages(
+                &*db,
+                &translations,
+                current_language.as_deref(),
+                primary_languages.as_deref(),
+            )
+            .into_iter()
+            .next()
+        });
+
+        let decorations = crate::ide::virtual_text::get_translation_decorations(
+            &*db,
+            source_file,
+            &translations,
+            language.as_deref(),
+            &key_separator,
+            namespace_separator.as_deref(),
+            default_namespace.as_deref(),
+            encoding,
+        );
+        drop(db);
+        Some(decorations)
+    }
+
     /// Notifies the client that decorations should be refreshed.
     pub(crate) async fn send_decorations_changed(&self) {
         self.client.send_notification::<DecorationsChanged>(()).await;
     }
 
+    /// Notifies the client that translation previews of every document should be refreshed.
+    ///
+    /// Used when translations or the current language change. Edits to a source file only need
+    /// [`Self::send_decorations_changed`], since clients re-request its inlay hints themselves.
+    pub(crate) async fn send_translations_changed(&self) {
+        self.send_decorations_changed().await;
+
+        if *self.state.inlay_hint_refresh_supported.lock().await
+            && let Err(error) = self.client.inlay_hint_refresh().await
+        {
+            tracing::debug!(%error, "Failed to request inlay hint refresh");
+        }
+    }
+
     /// Gets translation key context at cursor position from `SourceFile` or `Translation`.
     ///
     /// Returns a `KeyContext` with namespace information for filtering translations.
     pub(crate) async fn get_key_at_position(
         &self,
         file_path: &Path,
-        position: crate::types::SourcePosition,
+        position: tower_lsp::lsp_types::Position,
     ) -> Option<KeyContext> {
         let key_separator = self.get_key_separator().await;
+        let encoding = self.get_position_encoding().await;
         // Acquire db before source_files to prevent stale IDs after reset_state()
         let (db, source_files) = self.state.lock_db_and_source_files().await;
         let source_file = source_files.get(file_path).copied();
         drop(source_files);
 
         if let Some(source_file) = source_file {
-            crate::syntax::key_usage_at_position(&*db, source_file, position, key_separator).map(
-                |usage| KeyContext {
-                    key_text: usage.key(&*db).text(&*db).clone(),
-                    declared_namespace: usage.namespace(&*db),
-                    declared_namespaces: usage.namespaces(&*db),
-                    translation_namespace: None,
-                },
-            )
+            let position =
+                LineIndex::new(source_file.text(&*db), encoding).to_source_position(position);
+            crate::syntax::key_usage_at_position(&*db, source_file, position, key_separator)
+                .map(|usage| KeyContext::from_usage(&*db, usage))
         } else {
             tracing::debug!("Source file not found, trying Translation: {}", file_path.display());
 
@@ -284,11 +348,14 @@ impl Backend {
                 .iter()
                 .find(|t| t.file_path(&*db) == file_path_str.as_ref())
                 .and_then(|t| {
+                    let position =
+                        LineIndex::new(t.json_text(&*db), encoding).to_source_position(position);
                     t.key_at_position(&*db, position).map(|key| KeyContext {
                         key_text: key.text(&*db).clone(),
                         declared_namespace: None,
                         declared_namespaces: None,
                         translation_namespace: t.namespace(&*db).clone(),
+                        dynamic: false,
                     })
                 });
             drop(translations);
@@ -296,11 +363,30 @@ impl Backend {
         }
     }
 
+    /// Whether the client pulls diagnostics, in which case none are published.
+    pub(crate) async fn uses_pull_diagnostics(&self) -> bool {
+        *self.state.pull_diagnostics_supported.lock().await
+    }
+
+    /// Asks the client to pull diagnostics again after the index changed.
+    pub(crate) async fn refresh_pulled_diagnostics(&self) {
+        if *self.state.diagnostic_refresh_supported.lock().await
+            && let Err(error) = self.client.workspace_diagnostic_refresh().await
+        {
+            tracing::debug!(%error, "Failed to request diagnostic refresh");
+        }
+    }
+
     /// Sends diagnostics to all opened files.
     #[tracing::instrument(skip(self))]
     pub(crate) async fn send_diagnostics_to_opened_files(&self) {
         use crate::input::source::ProgrammingLanguage;
 
+        if self.uses_pull_diagnostics().await {
+            self.refresh_pulled_diagnostics().await;
+            return;
+        }
+
         // Collect all diagnostics while holding locks, then send after releasing.
         // This prevents stale SourceFile IDs from reset_state() racing with per-file lock cycles.
         let diagnostics_to_send: Vec<(
@@ -317,7 +403,7 @@ impl Backend {
             let translations = self.state.translations.lock().await;
 
             opened_files
-                .iter()
+                .keys()
                 .filter_map(|uri| {
                     let file_path = Self::uri_to_path(uri)?;
                     if ProgrammingLanguage::from_uri(uri.as_str()).is_none() {
@@ -328,15 +414,9 @@ impl Backend {
                         tracing::debug!("Source file not found: {}", file_path.display());
                         None
                     })?;
-                    let diagnostics = crate::ide::diagnostics::generate_diagnostics(
-                        &*db,
-                        source_file,
-                        &translations,
-                        &config.options,
-                        &config.key_separator,
-                        config.namespace_separator.as_deref(),
-                        config.default_namespace.as_deref(),
-                    );
+                    let visible =
+                        crate::ide::scope::visible_translations(&*db, &translations, &file_path);
+                    let diagnostics = config.source_file_diagnostics(&*db, source_file, &visible);
                     Some((uri.clone(), diagnostics))
                 })
                 .collect()
@@ -348,17 +428,21 @@ impl Backend {
         }
     }
 
-    /// Sends unused key diagnostics to translation files.
+    /// Sends unused key, placeholder consistency, ICU syntax and plural category diagnostics to
+    /// translation files.
     #[tracing::instrument(skip(self))]
-    pub(crate) async fn send_unused_key_diagnostics(&self) {
-        let settings = self.config_manager.lock().await.get_settings().clone();
-
-        if !settings.diagnostics.unused_translation.enabled {
-            tracing::debug!("Unused translation diagnostics disabled, skipping");
+    pub(crate) async fn send_translation_diagnostics(&self) {
+        if self.uses_pull_diagnostics().await {
+            self.refresh_pulled_diagnostics().await;
             return;
         }
 
-        let key_separator = &settings.key_separator;
+        let config = self.get_diagnostic_config().await;
+
+        if !config.has_translation_file_diagnostics() {
+            tracing::debug!("Translation file diagnostics disabled, skipping");
+            return;
+        }
 
         let source_file_vec: Vec<crate::input::source::SourceFile> =
             self.state.source_files.lock().await.values().copied().collect();
@@ -371,24 +455,21 @@ impl Backend {
             tracing::debug!(
                 translation_count = translations.len(),
                 source_file_count = source_file_vec.len(),
-                "Sending unused key diagnostics"
+                "Sending translation file diagnostics"
             );
 
+            // Component-local messages live in source files; don't publish to those URIs
             translations
                 .iter()
+                .filter(|translation| !translation.is_component_local(&*db))
                 .map(|translation| {
-                    let diagnostics = crate::ide::diagnostics::generate_unused_key_diagnostics(
+                    let diagnostics = config.translation_file_diagnostics(
                         &*db,
                         *translation,
+                        &translations,
                         &source_file_vec,
-                        key_separator,
-                        &settings.diagnostics.unused_translation.ignore_patterns,
-                        settings.diagnostics.unused_translation.severity,
-                        settings.namespace_separator.as_deref(),
-                        settings.default_namespace.as_deref(),
                     );
-                    let file_path = translation.file_path(&*db).clone();
-                    (file_path, diagnostics)
+                    (translation.file_path(&*db).clone(), diagnostics)
                 })
                 .collect()
         };
@@ -396,24 +477,23 @@ impl Backend {
         for (file_path, diagnostics) in diagnostics_to_send {
             if let Ok(uri) = tower_lsp::lsp_types::Url::from_file_path(&file_path) {
                 self.client.publish_diagnostics(uri, diagnostics, None).await;
-                tracing::debug!(file_path = %file_path, "Unused key diagnostics sent");
+                tracing::debug!(file_path = %file_path, "Translation file diagnostics sent");
             } else {
                 tracing::warn!(file_path = %file_path, "Failed to convert file path to URI");
             }
         }
     }
 
-    /// Updates or creates source file and generates diagnostics.
+    /// Updates or creates the source file from `text`, without publishing diagnostics.
     ///
-    /// # Arguments
-    /// * `force_create` - If true, ignores existing `SourceFile` and creates new one
-    #[tracing::instrument(skip(self, text), fields(uri = %uri))]
-    pub(crate) async fn update_and_diagnose(
+    /// Returns the file path, or `None` if the file isn't a workspace source file or the update
+    /// was queued until indexing completes.
+    pub(crate) async fn update_source_file(
         &self,
-        uri: tower_lsp::lsp_types::Url,
+        uri: &tower_lsp::lsp_types::Url,
         text: String,
         force_create: bool,
-    ) {
+    ) -> Option<PathBuf> {
         use salsa::Setter;
 
         use crate::input::source::{
@@ -421,15 +501,11 @@ impl Backend {
             SourceFile,
         };
 
-        tracing::debug!(uri = %uri, force_create, "Updating source file and diagnosing");
-
-        let Some(file_path) = Self::uri_to_path(&uri) else {
-            return;
-        };
+        let file_path = Self::uri_to_path(uri)?;
 
         let Some(language) = ProgrammingLanguage::from_uri(uri.as_str()) else {
             tracing::debug!("Skipping SourceFile creation for unsupported file type: {}", uri);
-            return;
+            return None;
         };
 
         if !self.workspace_indexer.is_workspace_active() {
@@ -437,7 +513,7 @@ impl Backend {
                 "Skipping file: workspace is not active for i18n: {}",
                 file_path.display()
             );
-            return;
+            return None;
         }
 
         if !self.is_source_file(&file_path).await {
@@ -445,7 +521,7 @@ impl Backend {
                 "Skipping SourceFile creation for file not matching includePatterns: {}",
                 file_path.display()
             );
-            return;
+            return None;
         }
 
         // Queue update during indexing to avoid Salsa deadlock.
@@ -456,8 +532,12 @@ impl Backend {
                 uri = %uri,
                 "Queueing SourceFile update during indexing to avoid Salsa lock contention"
             );
-            self.state.pending_updates.lock().await.push((uri, text, force_create));
-            return;
+            self.state.pending_updates.lock().await.push((uri.clone(), text, force_create));
+            return None;
+        }
+
+        if language == ProgrammingLanguage::Vue {
+            self.reload_component_translations(&file_path, &text).await;
         }
 
         // Update SourceFile without holding source_files lock during Salsa operations
@@ -482,8 +562,33 @@ impl Backend {
             }
         }
 
+        Some(file_path)
+    }
+
+    /// Updates or creates source file and generates diagnostics.
+    ///
+    /// # Arguments
+    /// * `force_create` - If true, ignores existing `SourceFile` and creates new one
+    #[tracing::instrument(skip(self, text), fields(uri = %uri))]
+    pub(crate) async fn update_and_diagnose(
+        &self,
+        uri: tower_lsp::lsp_types::Url,
+        text: String,
+        force_create: bool,
+    ) {
+        tracing::debug!(uri = %uri, force_create, "Updating source file and diagnosing");
+
+        let Some(file_path) = self.update_source_file(&uri, text, force_create).await else {
+            return;
+        };
+
         tracing::debug!(uri = %uri, "Source file updated");
 
+        if self.uses_pull_diagnostics().await {
+            self.refresh_pulled_diagnostics().await;
+            return;
+        }
+
         if !self.wait_for_translations().await {
             tracing::debug!(uri = %uri, "Skipping diagnostics - translations not indexed yet");
             return;
@@ -501,22 +606,17 @@ impl Backend {
                 return;
             };
             drop(source_files);
-            let translations = self.state.translations.lock().await;
-            crate::ide::diagnostics::generate_diagnostics(
-                &*db,
-                source_file,
-                &translations,
-                &config.options,
-                &config.key_separator,
-                config.namespace_separator.as_deref(),
-                config.default_namespace.as_deref(),
-            )
+            let translations = {
+                let translations = self.state.translations.lock().await;
+                crate::ide::scope::visible_translations(&*db, &translations, &file_path)
+            };
+            config.source_file_diagnostics(&*db, source_file, &translations)
         };
 
         self.client.publish_diagnostics(uri.clone(), diagnostics, None).await;
         tracing::debug!(uri = %uri, "Diagnostics generated and sent");
 
-        self.send_unused_key_diagnostics().await;
+        self.send_translation_diagnostics().await;
     }
 
     /// Returns workspace folders stored during `initialize`.
@@ -595,7 +695,7 @@ impl Backend {
                 match index_result {
                     Ok(()) => {
                         self.send_progress_end(&token, "Reindexing complete").await;
-                        self.send_decorations_changed().await;
+                        self.send_translations_changed().await;
                         tracing::info!("Workspace reindex complete");
                     }
                     Err(error) => {
@@ -636,16 +736,22 @@ impl Backend {
     }
 
     /// Updates translation from buffer content (for unsaved changes).
-    #[tracing::instrument(skip(self, content), fields(file_path = %file_path.display()))]
-    pub(crate) async fn update_translation_from_content(&self, file_path: &Path, content: &str) {
+    #[tracing::instrument(skip(self, uri, content), fields(file_path = %file_path.display()))]
+    pub(crate) async fn update_translation_from_content(
+        &self,
+        uri: &tower_lsp::lsp_types::Url,
+        file_path: &Path,
+        content: &str,
+    ) {
         let config_manager = self.config_manager.lock().await;
         let key_separator = config_manager.get_settings().key_separator.clone();
         drop(config_manager);
 
         let db = self.state.db.lock().await;
 
-        match crate::input::translation::load_translation_from_content(
+        match crate::input::translation::load_translation_from_document(
             &*db,
+            uri.as_str(),
             file_path,
             content,
             &key_separator,
@@ -667,6 +773,23 @@ impl Backend {
         }
     }
 
+    /// Replaces the component-local translations (`<i18n>` blocks) of a Vue SFC.
+    #[tracing::instrument(skip(self, text), fields(file_path = %file_path.display()))]
+    pub(crate) async fn reload_component_translations(&self, file_path: &Path, text: &str) {
+        let key_separator = self.get_key_separator().await;
+
+        let db = self.state.db.lock().await;
+        let local_translations =
+            crate::syntax::vue::load_i18n_block_translations(&*db, file_path, text, &key_separator);
+
+        let mut translations = self.state.translations.lock().await;
+        let file_path_str = file_path.to_string_lossy().to_string();
+        translations.retain(|t| t.file_path(&*db) != &file_path_str);
+        translations.extend(local_translations);
+        drop(translations);
+        drop(db);
+    }
+
     /// Removes translation entry for the specified file.
     #[tracing::instrument(skip(self), fields(file_path = %file_path.display()))]
     pub(crate) async fn remove_translation_file(&self, file_path: &Path) {
@@ -682,8 +805,26 @@ impl Backend {
         }
     }
 
+    /// Removes the source file and its component-local translations.
+    #[tracing::instrument(skip(self), fields(file_path = %file_path.display()))]
+    pub(crate) async fn remove_source_file(&self, file_path: &Path) {
+        let removed = self.state.source_files.lock().await.remove(file_path);
+        if removed.is_some() {
+            tracing::debug!("Removed source file: {:?}", file_path);
+        }
+        self.remove_translation_file(file_path).await;
+    }
+
     /// Registers file watchers for config and translation files.
+    ///
+    /// Clients without dynamic registration get a server-side watcher instead, which also covers
+    /// source files.
     pub(crate) async fn register_file_watchers(&self) {
+        if !*self.state.watched_files_registration_supported.lock().await {
+            self.start_file_watcher().await;
+            return;
+        }
+
         let translation_patterns = {
             let config_manager = self.config_manager.lock().await;
             config_manager.get_settings().translation_files.include_patterns.clone()
@@ -722,6 +863,34 @@ impl Backend {
         }
     }
 
+    /// Starts the server-side watcher for the workspace folders, unless it's already running.
+    async fn start_file_watcher(&self) {
+        let roots: Vec<PathBuf> = self
+            .get_workspace_folders()
+            .await
+            .iter()
+            .filter_map(|folder| folder.uri.to_file_path().ok())
+            .collect();
+
+        let matcher = self.config_manager.lock().await.file_matcher().cloned();
+
+        let mut file_watcher = self.state.file_watcher.lock().await;
+        if file_watcher.is_some() {
+            return;
+        }
+        match FileWatcher::start(self.clone(), &roots, matcher.as_ref()) {
+            Ok(watcher) => {
+                *file_watcher = Some(watcher);
+                drop(file_watcher);
+                tracing::debug!(?roots, "Started server-side file watcher");
+            }
+            Err(error) => {
+                drop(file_watcher);
+                tracing::warn!(%error, "Failed to start file watcher");
+            }
+        }
+    }
+
     pub(crate) fn is_config_file(file_path: &Path) -> bool {
         file_path.file_name().is_some_and(|name| name == ".js-i18n.json")
     }
@@ -828,7 +997,7 @@ impl Backend {
         self.reindex_workspace().await;
 
         self.send_diagnostics_to_opened_files().await;
-        self.send_unused_key_diagnostics().await;
+        self.send_translation_diagnostics().await;
     }
 }
 
@@ -900,6 +1069,20 @@ impl LanguageServer for Backend {
     // Features
     // -------------------------------------------------------------------------
 
+    async fn diagnostic(
+        &self,
+        params: tower_lsp::lsp_types::DocumentDiagnosticParams,
+    ) -> Result<tower_lsp::lsp_types::DocumentDiagnosticReportResult> {
+        handlers::diagnostic::handle_document_diagnostic(self, params).await
+    }
+
+    async fn workspace_diagnostic(
+        &self,
+        params: tower_lsp::lsp_types::WorkspaceDiagnosticParams,
+    ) -> Result<tower_lsp::lsp_types::WorkspaceDiagnosticReportResult> {
+        handlers::diagnostic::handle_workspace_diagnostic(self, params).await
+    }
+
     async fn completion(
         &self,
         params: tower_lsp::lsp_types::CompletionParams,
@@ -942,6 +1125,27 @@ impl LanguageServer for Backend {
         handlers::features::handle_rename(self, params).await
     }
 
+    async fn document_symbol(
+        &self,
+        params: tower_lsp::lsp_types::DocumentSymbolParams,
+    ) -> Result<Option<tower_lsp::lsp_types::DocumentSymbolResponse>> {
+        handlers::features::handle_document_symbol(self, params).await
+    }
+
+    async fn symbol(
+        &self,
+        params: tower_lsp::lsp_types::WorkspaceSymbolParams,
+    ) -> Result<Option<Vec<tower_lsp::lsp_types::SymbolInformation>>> {
+        handlers::features::handle_workspace_symbol(self, params).await
+    }
+
+    async fn inlay_hint(
+        &self,
+        params: tower_lsp::lsp_types::InlayHintParams,
+    ) -> Result<Option<Vec<tower_lsp::lsp_types::InlayHint>>> {
+        handlers::features::handle_inlay_hint(self, params).await
+    }
+
     async fn code_action(
         &self,
         params: tower_lsp::lsp_types::CodeActionParams,
@@ -949,6 +1153,20 @@ impl LanguageServer for Backend {
         handlers::code_action::handle_code_action(self, params).await
     }
 
+    async fn code_lens(
+        &self,
+        params: tower_lsp::lsp_types::CodeLensParams,
+    ) -> Result<Option<Vec<tower_lsp::lsp_types::CodeLens>>> {
+        handlers::code_lens::handle_code_lens(self, params).await
+    }
+
+    async fn code_lens_resolve(
+        &self,
+        params: tower_lsp::lsp_types::CodeLens,
+    ) -> Result<tower_lsp::lsp_types::CodeLens> {
+        handlers::code_lens::handle_code_lens_resolve(self, params).await
+    }
+
     async fn execute_command(
         &self,
         params: tower_lsp::lsp_types::ExecuteCommandParams,
@@ -965,7 +1183,7 @@ impl LanguageServer for Backend {
 /// 3. Others (alphabetical)
 #[must_use]
 pub fn collect_sorted_languages(
-    db: &dyn crate::db::I18nDatabase,
+    db: &dyn I18nDatabase,
     translations: &[crate::input::translation::Translation],
     current_language: Option<&str>,
     primary_languages: Option<&[String]>,
@@ -1121,6 +1339,7 @@ mod tests {
             declared_namespace: None,
             declared_namespaces: None,
             translation_namespace: None,
+            dynamic: false,
         };
 
         let (key_part, filtered) = ctx.filter_translations(&db, &translations, Some(":"), None);
@@ -1140,6 +1359,7 @@ mod tests {
             declared_namespace: Some("errors".to_string()),
             declared_namespaces: None,
             translation_namespace: None,
+            dynamic: false,
         };
 
         let (key_part, filtered) = ctx.filter_translations(&db, &translations, Some(":"), None);
@@ -1159,6 +1379,7 @@ mod tests {
             declared_namespace: None,
             declared_namespaces: Some(vec!["errors".to_string(), "common".to_string()]),
             translation_namespace: None,
+            dynamic: false,
         };
 
         let (key_part, filtered) = ctx.filter_translations(&db, &translations, Some(":"), None);
@@ -1179,6 +1400,7 @@ mod tests {
             declared_namespace: None,
             declared_namespaces: None,
             translation_namespace: None,
+            dynamic: false,
         };
 
         let (key_part, filtered) =
@@ -1198,6 +1420,7 @@ mod tests {
             declared_namespace: None,
             declared_namespaces: None,
             translation_namespace: None,
+            dynamic: false,
         };
 
         let (key_part, filtered) = ctx.filter_translations(&db, &translations, None, None);
@@ -1216,6 +1439,7 @@ mod tests {
             declared_namespace: None,
             declared_namespaces: None,
             translation_namespace: Some("common".to_string()),
+            dynamic: false,
         };
 
         let (key_part, filtered) = ctx.filter_translations(&db, &translations, None, None);
@@ -1234,6 +1458,7 @@ mod tests {
             declared_namespace: Some("common".to_string()),
             declared_namespaces: None,
             translation_namespace: None,
+            dynamic: false,
         };
 
         let (key_part, filtered) = ctx.filter_translations(&db, &translations, Some(":"), None);
@@ -1243,4 +1468,39 @@ mod tests {
         assert_that!(filtered.len(), eq(1));
         assert_that!(filtered[0].namespace(&db).as_deref(), some(eq("errors")));
     }
+
+    #[rstest]
+    #[case::static_key("t('errors.notFound');", "errors.notFound", false)]
+    #[case::dynamic_key("t(`errors.${code}`);", "errors.*", true)]
+    fn key_context_from_usage(
+        #[case] source: &str,
+        #[case] expected_key: &str,
+        #[case] expected_dynamic: bool,
+    ) {
+        use crate::input::source::{
+            ProgrammingLanguage,
+            SourceFile,
+        };
+        use crate::types::SourcePosition;
+
+        let db = I18nDatabaseImpl::default();
+        let source_file = SourceFile::new(
+            &db,
+            "file:///src/app.ts".to_string(),
+            source.to_string(),
+            ProgrammingLanguage::TypeScript,
+        );
+        let usage = crate::syntax::key_usage_at_position(
+            &db,
+            source_file,
+            SourcePosition { line: 0, character: 5 },
+            ".".to_string(),
+        )
+        .unwrap();
+
+        let ctx = KeyContext::from_usage(&db, usage);
+
+        assert_that!(ctx.key_text, eq(expected_key));
+        assert_that!(ctx.dynamic, eq(expected_dynamic));
+    }
 }
@@ -1,7 +1,11 @@
 //! Code action generation for translation keys
 
+mod po;
+mod yaml;
+
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fmt::Write as _;
 
 use jsonc_parser::ParseOptions;
 use jsonc_parser::cst::{
@@ -22,10 +26,16 @@ use tower_lsp::lsp_types::{
 
 use crate::db::I18nDatabase;
 use crate::ide::namespace::filter_by_namespace;
-use crate::input::translation::Translation;
+use crate::input::translation::{
+    Translation,
+    TranslationFormat,
+};
 use crate::syntax::analyzer::extractor::parse_key_with_namespace;
 
 /// Create a `TextEdit` that replaces the entire file content.
+///
+/// The end column is the byte length of the last line. This is never shorter than the line
+/// in any position encoding, and clients clamp it to the line length.
 #[allow(clippy::cast_possible_truncation)]
 #[must_use]
 pub(crate) fn create_full_file_text_edit(original_text: &str, new_text: String) -> TextEdit {
@@ -44,6 +54,19 @@ pub(crate) fn create_full_file_text_edit(original_text: &str, new_text: String)
     }
 }
 
+pub use po::{
+    delete_keys_from_po_text,
+    insert_key_to_po_text,
+    rename_key_in_po_text,
+    update_key_in_po_text,
+};
+pub use yaml::{
+    delete_keys_from_yaml_text,
+    insert_key_to_yaml_text,
+    rename_key_in_yaml_text,
+    update_key_in_yaml_text,
+};
+
 /// Result of CST-based key insertion or update, preserving original formatting.
 #[derive(Debug, Clone)]
 pub struct KeyEditResult {
@@ -76,11 +99,12 @@ pub fn extract_missing_languages(diagnostics: &[Diagnostic]) -> HashSet<String>
 }
 
 /// Generate code actions for all languages, sorted by priority (primary > missing > others).
-/// Returns commands with `{ lang, key }` args; the client handles value input.
+/// Returns commands with `{ lang, key, namespace? }` args; the client handles value input.
 #[must_use]
 #[allow(clippy::implicit_hasher)]
 pub fn generate_code_actions(
     key: &str,
+    namespace: Option<&str>,
     all_languages: &[String],
     missing_languages: &HashSet<String>,
     primary_language: Option<&str>,
@@ -105,13 +129,17 @@ pub fn generate_code_actions(
             } else {
                 format!("Edit translation for {lang}")
             };
+            let mut arguments = serde_json::json!({
+                "lang": lang,
+                "key": key,
+            });
+            if let (Some(namespace), Some(object)) = (namespace, arguments.as_object_mut()) {
+                object.insert("namespace".to_string(), namespace.into());
+            }
             CodeActionOrCommand::Command(Command {
                 title,
                 command: "i18n.executeClientEditTranslation".to_string(),
-                arguments: Some(vec![serde_json::json!({
-                    "lang": lang,
-                    "key": key,
-                })]),
+                arguments: Some(vec![arguments]),
             })
         })
         .collect()
@@ -310,6 +338,69 @@ pub fn delete_keys_from_json_text(
     })
 }
 
+/// Insert a key into translation file text of the given format.
+#[must_use]
+pub fn insert_key_to_text(
+    format: TranslationFormat,
+    text: &str,
+    key: &str,
+    value: &str,
+    separator: &str,
+) -> Option<KeyEditResult> {
+    match format {
+        TranslationFormat::Json => insert_key_to_json_text(text, key, value, separator),
+        TranslationFormat::Yaml => insert_key_to_yaml_text(text, key, value, separator),
+        TranslationFormat::Po => insert_key_to_po_text(text, key, value, separator),
+    }
+}
+
+/// Update an existing key in translation file text of the given format.
+#[must_use]
+pub fn update_key_in_text(
+    format: TranslationFormat,
+    text: &str,
+    key: &str,
+    value: &str,
+    separator: &str,
+) -> Option<KeyEditResult> {
+    match format {
+        TranslationFormat::Json => update_key_in_json_text(text, key, value, separator),
+        TranslationFormat::Yaml => update_key_in_yaml_text(text, key, value, separator),
+        TranslationFormat::Po => update_key_in_po_text(text, key, value, separator),
+    }
+}
+
+/// Rename a key in translation file text of the given format.
+#[must_use]
+pub fn rename_key_in_text(
+    format: TranslationFormat,
+    text: &str,
+    old_key: &str,
+    new_key: &str,
+    separator: &str,
+) -> Option<KeyEditResult> {
+    match format {
+        TranslationFormat::Json => rename_key_in_json_text(text, old_key, new_key, separator),
+        TranslationFormat::Yaml => rename_key_in_yaml_text(text, old_key, new_key, separator),
+        TranslationFormat::Po => rename_key_in_po_text(text, old_key, new_key, separator),
+    }
+}
+
+/// Delete keys from translation file text of the given format.
+#[must_use]
+pub fn delete_keys_from_text(
+    format: TranslationFormat,
+    text: &str,
+    keys_to_delete: &[String],
+    separator: &str,
+) -> Option<KeyDeletionResult> {
+    match format {
+        TranslationFormat::Json => delete_keys_from_json_text(text, keys_to_delete, separator),
+        TranslationFormat::Yaml => delete_keys_from_yaml_text(text, keys_to_delete, separator),
+        TranslationFormat::Po => delete_keys_from_po_text(text, keys_to_delete, separator),
+    }
+}
+
 fn delete_single_key(root_obj: &jsonc_parser::cst::CstObject, key: &str, separator: &str) -> bool {
     let parts: Vec<&str> = key.split(separator).collect();
 
@@ -356,26 +447,24 @@ fn cleanup_empty_objects(obj: &jsonc_parser::cst::CstObject) {
     }
 }
 
-/// Generate a code action to delete a translation key from all translation files.
-/// Returns `None` if the key is not found in any translation.
+/// Builds edits deleting `key_part` from each of `translations` that defines it.
 #[must_use]
-pub fn generate_delete_key_code_action(
+pub(crate) fn delete_key_edits(
     db: &dyn I18nDatabase,
-    key: &str,
-    translations: &[Translation],
+    key_part: &str,
+    translations: &[&Translation],
     key_separator: &str,
-    namespace_separator: Option<&str>,
-) -> Option<CodeActionOrCommand> {
-    let (ns, key_part) = parse_key_with_namespace(key, namespace_separator);
-
-    let target_translations = filter_by_namespace(db, translations, ns.as_deref());
-
+) -> HashMap<Url, Vec<TextEdit>> {
     let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
 
-    for translation in &target_translations {
+    for translation in translations {
         let json_text = translation.json_text(db);
-        let result =
-            delete_keys_from_json_text(json_text, std::slice::from_ref(&key_part), key_separator);
+        let result = delete_keys_from_text(
+            translation.format(db),
+            json_text,
+            &[key_part.to_string()],
+            key_separator,
+        );
         if let Some(result) = result {
             if result.deleted_count == 0 {
                 continue;
@@ -387,6 +476,23 @@ pub fn generate_delete_key_code_action(
             }
         }
     }
+    changes
+}
+
+/// Generate a code action to delete a translation key from all translation files.
+/// Returns `None` if the key is not found in any translation.
+#[must_use]
+pub fn generate_delete_key_code_action(
+    db: &dyn I18nDatabase,
+    key: &str,
+    translations: &[Translation],
+    key_separator: &str,
+    namespace_separator: Option<&str>,
+) -> Option<CodeActionOrCommand> {
+    let (ns, key_part) = parse_key_with_namespace(key, namespace_separator);
+
+    let target_translations = filter_by_namespace(db, translations, ns.as_deref());
+    let changes = delete_key_edits(db, &key_part, &target_translations, key_separator);
 
     if changes.is_empty() {
         return None;
@@ -400,6 +506,74 @@ pub fn generate_delete_key_code_action(
     }))
 }
 
+/// Generate a quick fix that adds the plural variants reported by a `missing-plural-category`
+/// diagnostic.
+///
+/// Suffix-based diagnostics (with `ordinal` in their data) insert the missing keys, and ICU
+/// diagnostics insert the missing branches before `other`. New variants start with the
+/// message of the `other` variant.
+#[must_use]
+pub fn generate_add_plural_categories_code_action(
+    db: &dyn I18nDatabase,
+    translation: &Translation,
+    diagnostic: &Diagnostic,
+    key_separator: &str,
+) -> Option<CodeActionOrCommand> {
+    let data = diagnostic.data.as_ref()?;
+    let key = data.get("key")?.as_str()?;
+    let format = translation.format(db);
+    let text = translation.json_text(db);
+    let keys = translation.keys(db);
+
+    let (title, new_text) = if let Some(ordinal) =
+        data.get("ordinal").and_then(serde_json::Value::as_bool)
+    {
+        let suffix = if ordinal { "_ordinal_" } else { "_" };
+        let other_value =
+            keys.get(&format!("{key}{suffix}other")).map(String::as_str).unwrap_or_default();
+        let mut new_text = text.clone();
+        for category in data.get("missing")?.as_array()?.iter().filter_map(|v| v.as_str()) {
+            let variant = format!("{key}{suffix}{category}");
+            new_text = insert_key_to_text(format, &new_text, &variant, other_value, key_separator)?
+                .new_text;
+        }
+        (format!("Add missing plural variants of '{key}'"), new_text)
+    } else {
+        let mut value = keys.get(key)?.clone();
+        let mut gaps = crate::ide::plural::find_icu_plural_gaps(&value, &translation.language(db));
+        gaps.retain(|gap| !gap.missing.is_empty());
+        if gaps.is_empty() {
+            return None;
+        }
+        // Insert from the end so that earlier offsets stay valid
+        gaps.sort_by_key(|gap| std::cmp::Reverse(gap.other_offset));
+        for gap in gaps {
+            let mut branches = String::new();
+            for category in &gap.missing {
+                let _ = write!(branches, "{} {{{}}} ", category.as_str(), gap.other_text);
+            }
+            value.insert_str(gap.other_offset, &branches);
+        }
+        let new_text = update_key_in_text(format, text, key, &value, key_separator)?.new_text;
+        (format!("Add missing plural branches to '{key}'"), new_text)
+    };
+
+    let uri = Url::from_file_path(translation.file_path(db)).ok()?;
+    let edit = create_full_file_text_edit(text, new_text);
+
+    Some(CodeActionOrCommand::CodeAction(CodeAction {
+        title,
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(uri, vec![edit])])),
+            ..Default::default()
+        }),
+        is_preferred: Some(true),
+        ..Default::default()
+    }))
+}
+
 #[cfg(test)]
 #[allow(
     clippy::unwrap_used,
@@ -453,7 +627,7 @@ mod tests {
         let languages = vec!["en".to_string(), "ja".to_string()];
         let missing = HashSet::new();
 
-        let actions = generate_code_actions("common.hello", &languages, &missing, None);
+        let actions = generate_code_actions("common.hello", None, &languages, &missing, None);
 
         assert_that!(actions, len(eq(2)));
         // Both are "Edit" since none are missing
@@ -472,7 +646,7 @@ mod tests {
         let languages = vec!["en".to_string(), "ja".to_string()];
         let missing: HashSet<String> = ["ja".to_string()].into();
 
-        let actions = generate_code_actions("common.hello", &languages, &missing, None);
+        let actions = generate_code_actions("common.hello", None, &languages, &missing, None);
 
         let titles: Vec<_> = actions
             .iter()
@@ -491,7 +665,7 @@ mod tests {
         let languages = vec!["en".to_string(), "ja".to_string(), "zh".to_string()];
         let missing = HashSet::new();
 
-        let actions = generate_code_actions("common.hello", &languages, &missing, Some("ja"));
+        let actions = generate_code_actions("common.hello", None, &languages, &missing, Some("ja"));
 
         let first_title = match &actions[0] {
             CodeActionOrCommand::Command(c) => &c.title,
@@ -505,7 +679,7 @@ mod tests {
         let languages = vec!["en".to_string()];
         let missing = HashSet::new();
 
-        let actions = generate_code_actions("greeting.hello", &languages, &missing, None);
+        let actions = generate_code_actions("greeting.hello", None, &languages, &missing, None);
 
         let args = match &actions[0] {
             CodeActionOrCommand::Command(c) => c.arguments.as_ref().unwrap(),
@@ -516,6 +690,21 @@ mod tests {
         assert_that!(arg["key"].as_str().unwrap(), eq("greeting.hello"));
     }
 
+    #[rstest]
+    fn generate_code_actions_args_include_namespace() {
+        let languages = vec!["en".to_string()];
+        let missing = HashSet::new();
+
+        let actions = generate_code_actions("hello", Some("checkout"), &languages, &missing, None);
+
+        let args = match &actions[0] {
+            CodeActionOrCommand::Command(c) => c.arguments.as_ref().unwrap(),
+            _ => panic!("expected Command"),
+        };
+        assert_that!(args[0]["key"].as_str().unwrap(), eq("hello"));
+        assert_that!(args[0]["namespace"].as_str().unwrap(), eq("checkout"));
+    }
+
     #[rstest]
     fn test_insert_key_flat() {
         let json = r#"{
@@ -1060,4 +1249,84 @@ mod tests {
         assert_that!(new_text, not(contains_substring("\"common\"")));
         assert_that!(new_text, not(contains_substring("\"hello\"")));
     }
+
+    #[rstest]
+    fn delete_key_action_yaml_translation() {
+        let db = I18nDatabaseImpl::default();
+        let yaml = "# English\ncommon:\n  hello: Hello\n  bye: Bye\n";
+        let en = create_translation_with_json(
+            &db,
+            "en",
+            None,
+            "/locales/en.yaml",
+            HashMap::from([
+                ("common.hello".to_string(), "Hello".to_string()),
+                ("common.bye".to_string(), "Bye".to_string()),
+            ]),
+            yaml,
+        );
+
+        let result = generate_delete_key_code_action(&db, "common.hello", &[en], ".", None);
+
+        let CodeActionOrCommand::CodeAction(action) = result.unwrap() else {
+            panic!("expected CodeAction")
+        };
+        let changes = action.edit.unwrap().changes.unwrap();
+        let en_uri = Url::from_file_path("/locales/en.yaml").unwrap();
+        assert_that!(changes[&en_uri][0].new_text, eq("# English\ncommon:\n  bye: Bye\n"));
+    }
+
+    fn add_plural_categories(file_path: &str, content: &str) -> Option<String> {
+        let db = I18nDatabaseImpl::default();
+        let translation = crate::input::translation::load_translation_from_content(
+            &db,
+            std::path::Path::new(file_path),
+            content,
+            ".",
+        )
+        .unwrap();
+        let diagnostics = crate::ide::plural::generate_plural_category_diagnostics(
+            &db,
+            translation,
+            crate::config::Severity::Warning,
+            crate::types::PositionEncoding::Utf16,
+        );
+        let action = generate_add_plural_categories_code_action(
+            &db,
+            &translation,
+            diagnostics.first()?,
+            ".",
+        )?;
+        let CodeActionOrCommand::CodeAction(action) = action else {
+            return None;
+        };
+        let changes = action.edit?.changes?;
+        changes.into_values().next()?.into_iter().next().map(|edit| edit.new_text)
+    }
+
+    #[rstest]
+    fn add_plural_categories_inserts_suffix_variants() {
+        let content = "{\n  \"items_one\": \"{{count}} предмет\",\n  \"items_other\": \"{{count}} предмета\"\n}";
+
+        let new_text = add_plural_categories("/locales/ru.json", content).unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&new_text).unwrap();
+        assert_that!(json["items_few"], eq(&serde_json::json!("{{count}} предмета")));
+        assert_that!(json["items_many"], eq(&serde_json::json!("{{count}} предмета")));
+    }
+
+    #[rstest]
+    fn add_plural_categories_inserts_icu_branches() {
+        let content = r#"{ "cart": "{count, plural, one {# товар} other {# товаров}}" }"#;
+
+        let new_text = add_plural_categories("/locales/ru.json", content).unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&new_text).unwrap();
+        assert_that!(
+            json["cart"],
+            eq(&serde_json::json!(
+                "{count, plural, one {# товар} few {# товаров} many {# товаров} other {# товаров}}"
+            ))
+        );
+    }
 }
@@ -0,0 +1,553 @@
+//! In-place edits for gettext PO translation files.
+//!
+//! Only the strings of the affected entry are rewritten; translator comments, `#:`
+//! references and the order of entries are left untouched.
+
+use std::ops::Range;
+
+use super::{
+    KeyDeletionResult,
+    KeyEditResult,
+};
+use crate::input::translation::po::{
+    PoCatalog,
+    PoEntry,
+    parse_po,
+};
+
+/// A text replacement; ranges of one edit batch never overlap.
+type Edit = (Range<usize>, String);
+
+fn apply_edits(text: &str, mut edits: Vec<Edit>) -> String {
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    let mut result = text.to_string();
+    for (range, replacement) in edits {
+        result.replace_range(range, &replacement);
+    }
+    result
+}
+
+/// Formats a value as a PO string, splitting after line breaks like `msgmerge` does.
+fn format_po_string(value: &str) -> String {
+    let escape = |s: &str| {
+        let mut escaped = String::with_capacity(s.len() + 2);
+        escaped.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => escaped.push_str("\\\""),
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\t' => escaped.push_str("\\t"),
+                '\r' => escaped.push_str("\\r"),
+                c => escaped.push(c),
+            }
+        }
+        escaped.push('"');
+        escaped
+    };
+
+    let is_multiline = value.trim_end_matches('\n').contains('\n');
+    if !is_multiline {
+        return escape(value);
+    }
+
+    let lines: Vec<String> = value.split_inclusive('\n').map(escape).collect();
+    format!("\"\"\n{}", lines.join("\n"))
+}
+
+/// Finds the entry for `key`. The index is the plural form for suffixed keys
+/// (e.g., `items_one`), `None` for the base key.
+fn find_entry<'c>(
+    catalog: &'c PoCatalog,
+    key: &str,
+    separator: &str,
+) -> Option<(&'c PoEntry, Option<usize>)> {
+    catalog.entries.iter().filter(|e| !e.is_header()).find_map(|entry| {
+        let base = entry.base_key(separator);
+        if base == key {
+            return Some((entry, None));
+        }
+        entry.msgid_plural.as_ref()?;
+        let suffix = key.strip_prefix(base.as_str())?;
+        let index = catalog.plural_suffixes(entry).iter().position(|s| *s == suffix)?;
+        Some((entry, Some(index)))
+    })
+}
+
+/// Splits a key into context and msgid, using the longest context already in the catalog.
+fn split_context<'k>(
+    catalog: &PoCatalog,
+    key: &'k str,
+    separator: &str,
+) -> (Option<String>, &'k str) {
+    catalog
+        .contexts()
+        .into_iter()
+        .find_map(|ctxt| {
+            let msgid = key.strip_prefix(ctxt)?.strip_prefix(separator)?;
+            (!msgid.is_empty()).then(|| (Some(ctxt.to_string()), msgid))
+        })
+        .unwrap_or((None, key))
+}
+
+/// Removes `fuzzy` from the flags line, dropping the line if no flags remain.
+fn clear_fuzzy(text: &str, entry: &PoEntry) -> Option<Edit> {
+    if !entry.fuzzy {
+        return None;
+    }
+    let range = entry.flags_line.clone()?;
+    let line = text.get(range.clone())?;
+    let flags: Vec<&str> = line
+        .trim_end()
+        .strip_prefix("#,")?
+        .split(',')
+        .map(str::trim)
+        .filter(|flag| !flag.is_empty() && *flag != "fuzzy")
+        .collect();
+    let newline = &line[line.trim_end_matches(['\n', '\r']).len()..];
+
+    if flags.is_empty() {
+        Some((range, String::new()))
+    } else {
+        Some((range, format!("#, {}{newline}", flags.join(", "))))
+    }
+}
+
+/// Builds the edits that set the value of `key` in `entry`.
+fn value_edits(
+    text: &str,
+    entry: &PoEntry,
+    index: Option<usize>,
+    value: &str,
+) -> Option<Vec<Edit>> {
+    // The base key of a plural entry has no single value
+    if entry.msgid_plural.is_some() && index.is_none() {
+        return None;
+    }
+
+    let mut edits = Vec::new();
+    let existing = entry.msgstr(index).or_else(|| index.is_none().then(|| entry.msgstr.first())?);
+    if let Some(msgstr) = existing {
+        edits.push((msgstr.field.string.range.clone(), format_po_string(value)));
+    } else {
+        // Plural form not present yet: add it after the last msgstr
+        let index = index?;
+        let end = entry.msgstr.last().map_or(entry.range.end, |m| m.field.line_range.end);
+        let newline = if text.get(..end).is_some_and(|t| t.ends_with('\n')) { "" } else { "\n" };
+        edits.push((end..end, format!("{newline}msgstr[{index}] {}\n", format_po_string(value))));
+    }
+    edits.extend(clear_fuzzy(text, entry));
+
+    Some(edits)
+}
+
+/// Update an existing key's translation, clearing its `fuzzy` flag.
+#[must_use]
+pub fn update_key_in_po_text(
+    po_text: &str,
+    key: &str,
+    value: &str,
+    separator: &str,
+) -> Option<KeyEditResult> {
+    let catalog = parse_po(po_text).ok()?;
+    let (entry, index) = find_entry(&catalog, key, separator)?;
+    let edits = value_edits(po_text, entry, index, value)?;
+
+    Some(KeyEditResult { new_text: apply_edits(po_text, edits) })
+}
+
+/// Insert a key with a value as a new entry at the end of the file.
+/// Updates the translation if the key already exists.
+#[must_use]
+pub fn insert_key_to_po_text(
+    po_text: &str,
+    key: &str,
+    value: &str,
+    separator: &str,
+) -> Option<KeyEditResult> {
+    let catalog = parse_po(po_text).ok()?;
+    if find_entry(&catalog, key, separator).is_some() {
+        return update_key_in_po_text(po_text, key, value, separator);
+    }
+
+    let (ctxt, msgid) = split_context(&catalog, key, separator);
+    let ctxt_line =
+        ctxt.map(|ctxt| format!("msgctxt {}\n", format_po_string(&ctxt))).unwrap_or_default();
+    let entry = format!(
+        "{ctxt_line}msgid {}\nmsgstr {}\n",
+        format_po_string(msgid),
+        format_po_string(value)
+    );
+
+    let separator_lines = if po_text.is_empty() || po_text.ends_with("\n\n") {
+        ""
+    } else if po_text.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+
+    Some(KeyEditResult { new_text: format!("{po_text}{separator_lines}{entry}") })
+}
+
+/// Range that removes an entry together with the blank line that follows it.
+fn entry_removal_range(text: &str, entry: &PoEntry) -> Range<usize> {
+    let Range { start, end } = entry.range.clone();
+    let rest = text.get(end..).unwrap_or_default();
+    let blank = ["\r\n", "\n"].iter().find(|nl| rest.starts_with(**nl)).map_or(0, |nl| nl.len());
+    start..end + blank
+}
+
+/// Delete keys from a PO file.
+///
+/// Entries are removed with their comments. Deleting only some plural forms of an entry
+/// clears those `msgstr[n]` instead.
+#[must_use]
+pub fn delete_keys_from_po_text(
+    po_text: &str,
+    keys_to_delete: &[String],
+    separator: &str,
+) -> Option<KeyDeletionResult> {
+    let catalog = parse_po(po_text).ok()?;
+
+    // Group the requested keys by entry
+    let mut targets: Vec<(&PoEntry, Option<Vec<usize>>)> = Vec::new();
+    let mut deleted_keys = Vec::new();
+    for key in keys_to_delete {
+        let Some((entry, index)) = find_entry(&catalog, key, separator) else {
+            continue;
+        };
+        deleted_keys.push(key.clone());
+
+        if !targets.iter().any(|(e, _)| std::ptr::eq(*e, entry)) {
+            targets.push((entry, Some(Vec::new())));
+        }
+        let (_, slot) = targets.iter_mut().find(|(e, _)| std::ptr::eq(*e, entry))?;
+        match (index, slot.as_mut()) {
+            (Some(index), Some(forms)) => forms.push(index),
+            _ => *slot = None,
+        }
+    }
+
+    let mut removals: Vec<Range<usize>> = Vec::new();
+    let mut edits: Vec<Edit> = Vec::new();
+    for (entry, forms) in targets {
+        let forms = forms.filter(|forms| {
+            entry.msgstr.iter().any(|m| m.index.is_some_and(|i| !forms.contains(&i)))
+        });
+        match forms {
+            // Some plural forms remain: clear the deleted ones
+            Some(forms) => edits.extend(
+                entry
+                    .msgstr
+                    .iter()
+                    .filter(|m| m.index.is_some_and(|i| forms.contains(&i)))
+                    .map(|m| (m.field.string.range.clone(), "\"\"".to_string())),
+            ),
+            None => removals.push(entry_removal_range(po_text, entry)),
+        }
+    }
+
+    // Removal ranges may touch when adjacent entries are deleted together
+    removals.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in removals {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    // Entries removed at the end of the file leave the preceding blank line behind
+    if let Some(last) = merged.last_mut()
+        && last.end == po_text.len()
+        && po_text.get(..last.start).is_some_and(|t| t.ends_with("\n\n"))
+    {
+        last.start -= 1;
+    }
+    edits.extend(merged.into_iter().map(|range| (range, String::new())));
+
+    Some(KeyDeletionResult {
+        new_text: apply_edits(po_text, edits),
+        deleted_count: deleted_keys.len(),
+        deleted_keys,
+    })
+}
+
+/// Rename a key by rewriting the entry's `msgid` (and `msgctxt`) in place.
+///
+/// Plural keys are renamed through their base key; renaming `items_one` to `things_one`
+/// renames the whole `items` entry.
+#[must_use]
+pub fn rename_key_in_po_text(
+    po_text: &str,
+    old_key: &str,
+    new_key: &str,
+    separator: &str,
+) -> Option<KeyEditResult> {
+    if old_key == new_key {
+        return None;
+    }
+
+    let catalog = parse_po(po_text).ok()?;
+    let (entry, index) = find_entry(&catalog, old_key, separator)?;
+
+    let new_base = match index {
+        Some(index) => {
+            let suffix = catalog.plural_suffixes(entry).get(index)?;
+            new_key.strip_suffix(suffix)?
+        }
+        None => new_key,
+    };
+    if new_base.is_empty() || find_entry(&catalog, new_base, separator).is_some() {
+        return None;
+    }
+
+    // Keep the current context when the new key is still under it
+    let old_ctxt = entry.msgctxt.as_ref().map(|c| c.string.value.as_str());
+    let (new_ctxt, new_msgid) = old_ctxt
+        .and_then(|ctxt| new_base.strip_prefix(ctxt)?.strip_prefix(separator))
+        .filter(|msgid| !msgid.is_empty())
+        .map_or_else(
+            || split_context(&catalog, new_base, separator),
+            |msgid| (old_ctxt.map(str::to_string), msgid),
+        );
+
+    let mut edits = vec![(entry.msgid.string.range.clone(), format_po_string(new_msgid))];
+    match (&entry.msgctxt, new_ctxt) {
+        (Some(field), Some(ctxt)) if field.string.value != ctxt => {
+            edits.push((field.string.range.clone(), format_po_string(&ctxt)));
+        }
+        (Some(field), None) => edits.push((field.line_range.clone(), String::new())),
+        (None, Some(ctxt)) => {
+            let at = entry.msgid.line_range.start;
+            edits.push((at..at, format!("msgctxt {}\n", format_po_string(&ctxt))));
+        }
+        _ => {}
+    }
+
+    Some(KeyEditResult { new_text: apply_edits(po_text, edits) })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+    use crate::input::translation::po::parse_po_translation;
+
+    const PO: &str = r#"msgid ""
+msgstr ""
+"Language: ja\n"
+"Plural-Forms: nplurals=2; plural=(n != 1);\n"
+
+# Greeting on the top page
+#: src/App.tsx:10
+msgid "hello"
+msgstr "こんにちは"
+
+#: src/Menu.tsx:3
+msgctxt "menu"
+msgid "open"
+msgstr "開く"
+
+msgid "item"
+msgid_plural "items"
+msgstr[0] "{{count}} 件"
+msgstr[1] "{{count}} 件"
+"#;
+
+    fn keys_of(po: &str) -> std::collections::HashMap<String, String> {
+        parse_po_translation(po, ".", false).unwrap().keys
+    }
+
+    // --- update ---
+
+    #[rstest]
+    fn update_keeps_comments_and_references() {
+        let result = update_key_in_po_text(PO, "hello", "やあ", ".").unwrap();
+
+        assert_that!(
+            result.new_text,
+            contains_substring(
+                "# Greeting on the top page\n#: src/App.tsx:10\nmsgid \"hello\"\nmsgstr \"やあ\"\n"
+            )
+        );
+        assert_that!(result.new_text.len(), eq(PO.len() - "こんにちは".len() + "やあ".len()));
+    }
+
+    #[rstest]
+    fn update_context_and_plural_keys() {
+        let result = update_key_in_po_text(PO, "menu.open", "開ける", ".").unwrap();
+        let result =
+            update_key_in_po_text(&result.new_text, "item_other", "{{count}} 個", ".").unwrap();
+
+        let keys = keys_of(&result.new_text);
+        assert_that!(keys.get("menu.open"), some(eq("開ける")));
+        assert_that!(keys.get("item_one"), some(eq("{{count}} 件")));
+        assert_that!(keys.get("item_other"), some(eq("{{count}} 個")));
+    }
+
+    #[rstest]
+    fn update_clears_fuzzy_flag() {
+        let po = "#: a.ts:1\n#, fuzzy, c-format\nmsgid \"a\"\nmsgstr \"old\"\n";
+
+        let result = update_key_in_po_text(po, "a", "new", ".").unwrap();
+
+        assert_that!(result.new_text, eq("#: a.ts:1\n#, c-format\nmsgid \"a\"\nmsgstr \"new\"\n"));
+    }
+
+    #[rstest]
+    fn update_multiline_value() {
+        let po = "msgid \"a\"\nmsgstr \"\"\n\"old\"\n";
+
+        let result = update_key_in_po_text(po, "a", "line 1\nline \"2\"", ".").unwrap();
+
+        assert_that!(
+            result.new_text,
+            eq("msgid \"a\"\nmsgstr \"\"\n\"line 1\\n\"\n\"line \\\"2\\\"\"\n")
+        );
+        assert_that!(keys_of(&result.new_text).get("a"), some(eq("line 1\nline \"2\"")));
+    }
+
+    #[rstest]
+    #[case::missing("missing")]
+    #[case::plural_base("item")]
+    fn update_returns_none(#[case] key: &str) {
+        assert_that!(update_key_in_po_text(PO, key, "x", "."), none());
+    }
+
+    // --- insert ---
+
+    #[rstest]
+    fn insert_appends_entry() {
+        let result = insert_key_to_po_text(PO, "bye", "さようなら", ".").unwrap();
+
+        assert_that!(result.new_text, ends_with("件\"\n\nmsgid \"bye\"\nmsgstr \"さようなら\"\n"));
+    }
+
+    #[rstest]
+    fn insert_uses_existing_context() {
+        let result = insert_key_to_po_text(PO, "menu.close", "閉じる", ".").unwrap();
+
+        assert_that!(
+            result.new_text,
+            ends_with("msgctxt \"menu\"\nmsgid \"close\"\nmsgstr \"閉じる\"\n")
+        );
+        assert_that!(keys_of(&result.new_text).get("menu.close"), some(eq("閉じる")));
+    }
+
+    #[rstest]
+    fn insert_existing_key_updates() {
+        let result = insert_key_to_po_text(PO, "hello", "どうも", ".").unwrap();
+
+        assert_that!(keys_of(&result.new_text).get("hello"), some(eq("どうも")));
+        assert_that!(result.new_text.matches("msgid \"hello\"").count(), eq(1));
+    }
+
+    #[rstest]
+    fn insert_missing_plural_form() {
+        let po = "msgid \"item\"\nmsgid_plural \"items\"\nmsgstr[0] \"one item\"\n";
+
+        let result = insert_key_to_po_text(po, "item_other", "many items", ".").unwrap();
+
+        assert_that!(
+            result.new_text,
+            ends_with("msgstr[0] \"one item\"\nmsgstr[1] \"many items\"\n")
+        );
+    }
+
+    #[rstest]
+    #[case::empty("", "msgid \"a\"\nmsgstr \"A\"\n")]
+    #[case::no_trailing_newline(
+        "msgid \"b\"\nmsgstr \"B\"",
+        "msgid \"b\"\nmsgstr \"B\"\n\nmsgid \"a\"\nmsgstr \"A\"\n"
+    )]
+    fn insert_blank_line_handling(#[case] po: &str, #[case] expected: &str) {
+        let result = insert_key_to_po_text(po, "a", "A", ".").unwrap();
+
+        assert_that!(result.new_text, eq(expected));
+    }
+
+    // --- delete ---
+
+    #[rstest]
+    fn delete_removes_entry_with_comments() {
+        let result = delete_keys_from_po_text(PO, &["hello".to_string()], ".").unwrap();
+
+        assert_that!(result.new_text, not(contains_substring("Greeting on the top page")));
+        assert_that!(result.new_text, not(contains_substring("msgid \"hello\"")));
+        assert_that!(result.new_text, contains_substring("\n\n#: src/Menu.tsx:3\nmsgctxt"));
+        assert_that!(result.deleted_count, eq(1));
+    }
+
+    #[rstest]
+    fn delete_adjacent_entries_and_plural_forms() {
+        let keys = vec![
+            "menu.open".to_string(),
+            "item_one".to_string(),
+            "item_other".to_string(),
+            "missing".to_string(),
+        ];
+
+        let result = delete_keys_from_po_text(PO, &keys, ".").unwrap();
+
+        assert_that!(result.new_text, ends_with("msgstr \"こんにちは\"\n"));
+        assert_that!(result.deleted_count, eq(3));
+        let keys = keys_of(&result.new_text);
+        assert_that!(keys.len(), eq(1));
+        assert_that!(keys.contains_key("hello"), eq(true));
+    }
+
+    #[rstest]
+    fn delete_single_plural_form_clears_it() {
+        let result = delete_keys_from_po_text(PO, &["item_one".to_string()], ".").unwrap();
+
+        assert_that!(
+            result.new_text,
+            contains_substring("msgstr[0] \"\"\nmsgstr[1] \"{{count}} 件\"")
+        );
+    }
+
+    // --- rename ---
+
+    #[rstest]
+    fn rename_rewrites_msgid_in_place() {
+        let result = rename_key_in_po_text(PO, "hello", "greeting", ".").unwrap();
+
+        assert_that!(
+            result.new_text,
+            contains_substring("#: src/App.tsx:10\nmsgid \"greeting\"\nmsgstr \"こんにちは\"\n")
+        );
+    }
+
+    #[rstest]
+    #[case::keep_context("menu.open", "menu.launch", "msgctxt \"menu\"\nmsgid \"launch\"\n")]
+    #[case::drop_context("menu.open", "open", "#: src/Menu.tsx:3\nmsgid \"open\"\n")]
+    #[case::add_context("hello", "menu.hello", "msgctxt \"menu\"\nmsgid \"hello\"\n")]
+    fn rename_context(#[case] old: &str, #[case] new: &str, #[case] expected: &str) {
+        let result = rename_key_in_po_text(PO, old, new, ".").unwrap();
+
+        assert_that!(result.new_text, contains_substring(expected));
+        assert_that!(keys_of(&result.new_text).contains_key(new), eq(true));
+    }
+
+    #[rstest]
+    fn rename_plural_key_renames_entry() {
+        let result = rename_key_in_po_text(PO, "item_one", "entry_one", ".").unwrap();
+
+        let keys = keys_of(&result.new_text);
+        assert_that!(keys.get("entry_one"), some(eq("{{count}} 件")));
+        assert_that!(keys.contains_key("entry_other"), eq(true));
+    }
+
+    #[rstest]
+    #[case::same("hello", "hello")]
+    #[case::missing("missing", "other")]
+    #[case::exists("hello", "menu.open")]
+    #[case::plural_suffix_mismatch("item_one", "entry")]
+    fn rename_returns_none(#[case] old: &str, #[case] new: &str) {
+        assert_that!(rename_key_in_po_text(PO, old, new, "."), none());
+    }
+}
@@ -0,0 +1,726 @@
+//! Format-preserving edits for YAML translation files.
+//!
+//! Edits splice text at node positions found with tree-sitter-yaml, so comments,
+//! key order and quoting style elsewhere in the file are left untouched.
+
+use std::fmt::Write as _;
+use std::ops::Range;
+
+use tree_sitter::Node;
+
+use super::{
+    KeyDeletionResult,
+    KeyEditResult,
+};
+use crate::input::translation::yaml::{
+    content_mapping,
+    is_empty_document,
+    locale_root,
+    mapping_pairs,
+    node_mapping,
+    pair_key,
+    parse_yaml_translation,
+    parse_yaml_tree,
+    scalar_node,
+};
+
+/// Indentation used for new nested keys when the file has none to copy.
+const DEFAULT_INDENT: usize = 2;
+
+/// Where new keys are attached.
+#[derive(Clone, Copy)]
+enum Parent<'t> {
+    /// An existing block or flow mapping.
+    Mapping(Node<'t>),
+    /// A pair without value (e.g., `en:`); children go on the following lines.
+    EmptyPair(Node<'t>),
+    /// Empty document: keys are written at the top level.
+    Document,
+}
+
+/// Result of resolving a key path against the document.
+enum Lookup<'t> {
+    /// Every segment exists; holds the pair of the last segment.
+    Found(Node<'t>),
+    /// Segments from `depth` on are missing and belong under `parent`.
+    Missing { parent: Parent<'t>, depth: usize },
+}
+
+/// Resolves a key path. Returns `None` if the path runs into a scalar or sequence.
+fn lookup<'t>(root: Node<'t>, source: &[u8], parts: &[&str]) -> Option<Lookup<'t>> {
+    let mut parent = match (content_mapping(root, source), locale_root(root, source)) {
+        (Some(mapping), _) => Parent::Mapping(mapping),
+        (None, Some((_, pair))) => Parent::EmptyPair(pair),
+        (None, None) if is_empty_document(root) => Parent::Document,
+        (None, None) => return None,
+    };
+
+    for (depth, part) in parts.iter().enumerate() {
+        let Parent::Mapping(mapping) = parent else {
+            return Some(Lookup::Missing { parent, depth });
+        };
+        let Some(pair) =
+            mapping_pairs(mapping).find(|p| pair_key(*p, source).as_deref() == Some(part))
+        else {
+            return Some(Lookup::Missing { parent, depth });
+        };
+
+        if depth + 1 == parts.len() {
+            return Some(Lookup::Found(pair));
+        }
+
+        parent = match pair.child_by_field_name("value") {
+            None => Parent::EmptyPair(pair),
+            Some(value) => Parent::Mapping(node_mapping(value)?),
+        };
+    }
+
+    None
+}
+
+fn splice(text: &str, range: Range<usize>, replacement: &str) -> String {
+    let mut result = String::with_capacity(text.len() + replacement.len());
+    result.push_str(text.get(..range.start).unwrap_or(text));
+    result.push_str(replacement);
+    result.push_str(text.get(range.end..).unwrap_or(""));
+    result
+}
+
+/// Byte offset of the start of the line after `offset` (or end of text).
+fn next_line_start(text: &str, offset: usize) -> usize {
+    if offset == 0 || text.get(..offset).is_some_and(|t| t.ends_with('\n')) {
+        return offset;
+    }
+    text.get(offset..).and_then(|rest| rest.find('\n')).map_or(text.len(), |i| offset + i + 1)
+}
+
+fn line_start(text: &str, offset: usize) -> usize {
+    text.get(..offset).and_then(|t| t.rfind('\n')).map_or(0, |i| i + 1)
+}
+
+/// Detects the indentation width from the first nested block mapping.
+fn detect_indent_unit(node: Node<'_>) -> usize {
+    if node.kind() == "block_mapping_pair"
+        && let Some(child) = node
+            .child_by_field_name("value")
+            .and_then(node_mapping)
+            .and_then(|m| mapping_pairs(m).next())
+    {
+        let unit = child.start_position().column.saturating_sub(node.start_position().column);
+        if unit > 0 {
+            return unit;
+        }
+    }
+
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .map(detect_indent_unit)
+        .find(|&unit| unit != DEFAULT_INDENT)
+        .unwrap_or(DEFAULT_INDENT)
+}
+
+/// Returns true if a value can be written as a plain (unquoted) scalar and read back
+/// as the same string.
+fn is_plain_safe(value: &str, in_flow: bool) -> bool {
+    let Some(first) = value.chars().next() else {
+        return false;
+    };
+
+    if first.is_whitespace()
+        || "-?:,[]{}#&*!|>'\"%@`".contains(first)
+        || value.ends_with(char::is_whitespace)
+        || value.ends_with(':')
+        || value.contains(": ")
+        || value.contains(" #")
+        || value.chars().any(char::is_control)
+        || (in_flow && value.contains([',', '[', ']', '{', '}']))
+    {
+        return false;
+    }
+
+    // Values that YAML would resolve to booleans, null or numbers
+    let lower = value.to_ascii_lowercase();
+    if matches!(lower.as_str(), "true" | "false" | "yes" | "no" | "on" | "off" | "y" | "n")
+        || matches!(lower.as_str(), "null" | "~")
+    {
+        return false;
+    }
+    let looks_numeric = (first.is_ascii_digit() || "+.".contains(first))
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || "._+-:".contains(c));
+
+    !looks_numeric
+}
+
+fn double_quoted(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\t' => result.push_str("\\t"),
+            '\r' => result.push_str("\\r"),
+            c if c.is_control() => {
+                let _ = write!(result, "\\u{:04X}", u32::from(c));
+            }
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Formats a key segment, quoting it only when necessary.
+fn format_key(key: &str, in_flow: bool) -> String {
+    if is_plain_safe(key, in_flow) { key.to_string() } else { double_quoted(key) }
+}
+
+/// Formats a value, keeping the style of the scalar it replaces where possible.
+fn format_value(value: &str, existing: Option<(Node<'_>, &str)>, in_flow: bool) -> String {
+    match existing.map(|(node, text)| (node.kind(), node, text)) {
+        Some(("double_quote_scalar", _, _)) => double_quoted(value),
+        Some(("single_quote_scalar", _, _))
+            if !value.chars().any(char::is_control) && !value.is_empty() =>
+        {
+            format!("'{}'", value.replace('\'', "''"))
+        }
+        Some(("block_scalar", node, text)) => {
+            format_block_scalar(value, node, text).unwrap_or_else(|| double_quoted(value))
+        }
+        _ if is_plain_safe(value, in_flow) => value.to_string(),
+        _ => double_quoted(value),
+    }
+}
+
+/// Writes `value` as a literal block scalar using the indentation of `node`'s content.
+fn format_block_scalar(value: &str, node: Node<'_>, text: &str) -> Option<String> {
+    let body = value.strip_suffix('\n').unwrap_or(value);
+    if body.is_empty()
+        || body.starts_with(char::is_whitespace)
+        || body.ends_with('\n')
+        || body.chars().any(|c| c.is_control() && c != '\n')
+    {
+        return None;
+    }
+
+    let original = text.get(node.byte_range())?;
+    let indent = original
+        .lines()
+        .skip(1)
+        .find(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .filter(|&n| n > 0)
+        .unwrap_or_else(|| line_indent(text, node.start_byte()) + DEFAULT_INDENT);
+
+    let header = if value.ends_with('\n') { "|" } else { "|-" };
+    let pad = " ".repeat(indent);
+    let lines: Vec<String> = body
+        .split('\n')
+        .map(|line| if line.is_empty() { String::new() } else { format!("{pad}{line}") })
+        .collect();
+
+    Some(format!("{header}\n{}", lines.join("\n")))
+}
+
+fn line_indent(text: &str, offset: usize) -> usize {
+    let start = line_start(text, offset);
+    text.get(start..).map_or(0, |line| line.len() - line.trim_start_matches(' ').len())
+}
+
+/// Builds block-style lines for `parts`, the last one holding `value`.
+fn block_lines(parts: &[&str], value: &str, column: usize, unit: usize) -> String {
+    let mut result = String::new();
+    for (i, part) in parts.iter().enumerate() {
+        result.push_str(&" ".repeat(column + i * unit));
+        result.push_str(&format_key(part, false));
+        result.push(':');
+        if i + 1 == parts.len() {
+            result.push(' ');
+            result.push_str(&format_value(value, None, false));
+        }
+        result.push('\n');
+    }
+    result
+}
+
+/// Builds a flow-style entry for `parts` (e.g., `a: {b: value}`).
+fn flow_entry(parts: &[&str], value: &str) -> String {
+    let Some((first, rest)) = parts.split_first() else {
+        return format_value(value, None, true);
+    };
+    let inner = flow_entry(rest, value);
+    if rest.is_empty() {
+        format!("{}: {inner}", format_key(first, true))
+    } else {
+        format!("{}: {{{inner}}}", format_key(first, true))
+    }
+}
+
+/// Inserts block lines at the start of the line after `offset`.
+fn insert_lines_after(text: &str, offset: usize, lines: &str) -> String {
+    let at = next_line_start(text, offset);
+    let prefix = text.get(..at).unwrap_or(text);
+    let separator = if prefix.is_empty() || prefix.ends_with('\n') { "" } else { "\n" };
+    splice(text, at..at, &format!("{separator}{lines}"))
+}
+
+/// Insert a key with a value, creating missing parents. Updates the value if the key exists.
+#[must_use]
+pub fn insert_key_to_yaml_text(
+    yaml_text: &str,
+    key: &str,
+    value: &str,
+    separator: &str,
+) -> Option<KeyEditResult> {
+    let tree = parse_yaml_tree(yaml_text)?;
+    let root = tree.root_node();
+    let source = yaml_text.as_bytes();
+    let parts: Vec<&str> = key.split(separator).collect();
+
+    let (parent, depth) = match lookup(root, source, &parts)? {
+        Lookup::Found(_) => return update_key_in_yaml_text(yaml_text, key, value, separator),
+        Lookup::Missing { parent, depth } => (parent, depth),
+    };
+    let missing = parts.get(depth..)?;
+    let unit = detect_indent_unit(root);
+
+    let new_text = match parent {
+        Parent::Mapping(mapping) if mapping.kind() == "flow_mapping" => {
+            let entry = flow_entry(missing, value);
+            // Append after the last pair, or just before `}` of an empty mapping
+            let (at, entry) = mapping_pairs(mapping).last().map_or_else(
+                || (mapping.end_byte().saturating_sub(1), entry.clone()),
+                |last| (last.end_byte(), format!(", {entry}")),
+            );
+            splice(yaml_text, at..at, &entry)
+        }
+        Parent::Mapping(mapping) => {
+            let first = mapping_pairs(mapping).next()?;
+            let last = mapping_pairs(mapping).last()?;
+            let column = first.start_position().column;
+            insert_lines_after(
+                yaml_text,
+                last.end_byte(),
+                &block_lines(missing, value, column, unit),
+            )
+        }
+        Parent::EmptyPair(pair) => {
+            let column = pair.start_position().column + unit;
+            insert_lines_after(
+                yaml_text,
+                pair.end_byte(),
+                &block_lines(missing, value, column, unit),
+            )
+        }
+        Parent::Document => {
+            insert_lines_after(yaml_text, yaml_text.len(), &block_lines(missing, value, 0, unit))
+        }
+    };
+
+    Some(KeyEditResult { new_text })
+}
+
+/// Update an existing key's value, keeping its quoting style, anchor and trailing comment.
+#[must_use]
+pub fn update_key_in_yaml_text(
+    yaml_text: &str,
+    key: &str,
+    value: &str,
+    separator: &str,
+) -> Option<KeyEditResult> {
+    let tree = parse_yaml_tree(yaml_text)?;
+    let source = yaml_text.as_bytes();
+    let parts: Vec<&str> = key.split(separator).collect();
+
+    let Lookup::Found(pair) = lookup(tree.root_node(), source, &parts)? else {
+        return None;
+    };
+    let in_flow = pair.kind() == "flow_pair";
+
+    let new_text = match pair.child_by_field_name("value") {
+        // `key:` without a value
+        None => {
+            let end = pair.end_byte();
+            splice(yaml_text, end..end, &format!(" {}", format_value(value, None, in_flow)))
+        }
+        Some(value_node) => {
+            let target = match scalar_node(value_node) {
+                Some(scalar) => scalar,
+                // Aliases are replaced by a plain value; mappings and sequences are not leaves
+                None if value_node.named_child(0).is_some_and(|n| n.kind() == "alias") => {
+                    value_node
+                }
+                None => return None,
+            };
+            let existing = scalar_node(value_node).map(|node| (node, yaml_text));
+            splice(yaml_text, target.byte_range(), &format_value(value, existing, in_flow))
+        }
+    };
+
+    Some(KeyEditResult { new_text })
+}
+
+/// Removes a pair from the text, including its line for block mappings.
+fn remove_pair(text: &str, pair: Node<'_>) -> String {
+    if pair.kind() == "flow_pair" {
+        let range = match (pair.next_sibling(), pair.prev_sibling()) {
+            (Some(next), _) if next.kind() == "," => {
+                let after = next.next_sibling().map_or_else(|| next.end_byte(), |n| n.start_byte());
+                pair.start_byte()..after
+            }
+            (_, Some(prev)) if prev.kind() == "," => prev.start_byte()..pair.end_byte(),
+            _ => pair.byte_range(),
+        };
+        return splice(text, range, "");
+    }
+
+    let start = line_start(text, pair.start_byte());
+    let is_own_line = text.get(start..pair.start_byte()).is_some_and(|p| p.trim().is_empty());
+    if is_own_line {
+        splice(text, start..next_line_start(text, pair.end_byte()), "")
+    } else {
+        splice(text, pair.byte_range(), "")
+    }
+}
+
+/// Deletes a single key. Parents left empty are removed too, down to `keep_depth`
+/// (the number of leading path segments that must survive).
+fn delete_single_key(text: &str, key: &str, separator: &str, keep_depth: usize) -> Option<String> {
+    let tree = parse_yaml_tree(text)?;
+    let parts: Vec<&str> = key.split(separator).collect();
+
+    let Lookup::Found(mut target) = lookup(tree.root_node(), text.as_bytes(), &parts)? else {
+        return None;
+    };
+
+    let mut depth = parts.len() - 1;
+    while depth > keep_depth {
+        let Some(mapping) = target.parent() else {
+            break;
+        };
+        let Some(owner) = mapping.parent().and_then(|node| node.parent()) else {
+            break;
+        };
+        if mapping_pairs(mapping).count() != 1
+            || !matches!(owner.kind(), "block_mapping_pair" | "flow_pair")
+        {
+            break;
+        }
+        target = owner;
+        depth -= 1;
+    }
+
+    Some(remove_pair(text, target))
+}
+
+/// Delete keys from YAML, preserving comments and formatting of the remaining entries.
+/// Empty parent mappings are removed after deletion.
+#[must_use]
+pub fn delete_keys_from_yaml_text(
+    yaml_text: &str,
+    keys_to_delete: &[String],
+    separator: &str,
+) -> Option<KeyDeletionResult> {
+    parse_yaml_tree(yaml_text)?;
+
+    // Deepest first so that parents are still present while their children are removed
+    let mut sorted_keys: Vec<_> = keys_to_delete.to_vec();
+    sorted_keys.sort_by_key(|key| std::cmp::Reverse(key.matches(separator).count()));
+
+    let mut new_text = yaml_text.to_string();
+    let mut deleted_keys = Vec::new();
+    for key in &sorted_keys {
+        if let Some(text) = delete_single_key(&new_text, key, separator, 0) {
+            new_text = text;
+            deleted_keys.push(key.clone());
+        }
+    }
+
+    Some(KeyDeletionResult { new_text, deleted_count: deleted_keys.len(), deleted_keys })
+}
+
+/// Rename a key. Keys under the same parent are renamed in place; otherwise the entry is
+/// moved below the deepest common parent.
+#[must_use]
+pub fn rename_key_in_yaml_text(
+    yaml_text: &str,
+    old_key: &str,
+    new_key: &str,
+    separator: &str,
+) -> Option<KeyEditResult> {
+    if old_key == new_key {
+        return None;
+    }
+
+    let old_parts: Vec<&str> = old_key.split(separator).collect();
+    let new_parts: Vec<&str> = new_key.split(separator).collect();
+
+    // Reject if one key is a prefix of the other
+    let common_len = old_parts.iter().zip(new_parts.iter()).take_while(|(a, b)| a == b).count();
+    if common_len == old_parts.len() || common_len == new_parts.len() {
+        return None;
+    }
+
+    let value = parse_yaml_translation(yaml_text, separator).ok()?.keys.get(old_key)?.clone();
+
+    let tree = parse_yaml_tree(yaml_text)?;
+    let root = tree.root_node();
+    let source = yaml_text.as_bytes();
+
+    let Lookup::Found(old_pair) = lookup(root, source, &old_parts)? else {
+        return None;
+    };
+    if matches!(lookup(root, source, &new_parts)?, Lookup::Found(_)) {
+        return None;
+    }
+
+    if old_parts.len() == new_parts.len() && common_len + 1 == old_parts.len() {
+        let key_node = old_pair.child_by_field_name("key")?;
+        let new_name = format_key(new_parts.last()?, old_pair.kind() == "flow_pair");
+        return Some(KeyEditResult {
+            new_text: splice(yaml_text, key_node.byte_range(), &new_name),
+        });
+    }
+
+    let without_old = delete_single_key(yaml_text, old_key, separator, common_len)?;
+    insert_key_to_yaml_text(&without_old, new_key, &value, separator)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+
+    fn keys_of(yaml: &str) -> std::collections::HashMap<String, String> {
+        parse_yaml_translation(yaml, ".").unwrap().keys
+    }
+
+    // --- update ---
+
+    #[rstest]
+    fn update_plain_value_keeps_comments() {
+        let yaml = "# header\ncommon:\n  hello: Hello # greeting\n  bye: Bye\n";
+
+        let result = update_key_in_yaml_text(yaml, "common.hello", "Hi", ".").unwrap();
+
+        assert_that!(
+            result.new_text,
+            eq("# header\ncommon:\n  hello: Hi # greeting\n  bye: Bye\n")
+        );
+    }
+
+    #[rstest]
+    #[case::double("k: \"old\"\n", "new", "k: \"new\"\n")]
+    #[case::single("k: 'old'\n", "it's", "k: 'it''s'\n")]
+    #[case::plain_needs_quotes("k: old\n", "a: b", "k: \"a: b\"\n")]
+    #[case::plain_boolean_like("k: old\n", "yes", "k: \"yes\"\n")]
+    #[case::plain_numeric("k: old\n", "42", "k: \"42\"\n")]
+    #[case::newline("k: old\n", "a\nb", "k: \"a\\nb\"\n")]
+    #[case::empty_value("k:\n", "v", "k: v\n")]
+    #[case::anchor_kept("k: &a old\n", "new", "k: &a new\n")]
+    fn update_value_styles(#[case] yaml: &str, #[case] value: &str, #[case] expected: &str) {
+        let result = update_key_in_yaml_text(yaml, "k", value, ".").unwrap();
+
+        assert_that!(result.new_text, eq(expected));
+        assert_that!(keys_of(&result.new_text).get("k"), some(eq(value)));
+    }
+
+    #[rstest]
+    fn update_block_scalar_keeps_block_style() {
+        let yaml = "intro: |\n    first\n    second\nnext: x\n";
+
+        let result = update_key_in_yaml_text(yaml, "intro", "one\ntwo\n", ".").unwrap();
+
+        assert_that!(result.new_text, eq("intro: |\n    one\n    two\nnext: x\n"));
+        assert_that!(keys_of(&result.new_text).get("intro"), some(eq("one\ntwo\n")));
+    }
+
+    #[rstest]
+    fn update_in_rails_locale_root() {
+        let yaml = "en:\n  greeting: Hello\n";
+
+        let result = update_key_in_yaml_text(yaml, "greeting", "Hey", ".").unwrap();
+
+        assert_that!(result.new_text, eq("en:\n  greeting: Hey\n"));
+    }
+
+    #[rstest]
+    fn update_flow_mapping_value() {
+        let yaml = "buttons: {ok: OK, cancel: Cancel}\n";
+
+        let result = update_key_in_yaml_text(yaml, "buttons.ok", "Yes, sure", ".").unwrap();
+
+        assert_that!(result.new_text, eq("buttons: {ok: \"Yes, sure\", cancel: Cancel}\n"));
+    }
+
+    #[rstest]
+    #[case::missing("common.missing")]
+    #[case::not_a_leaf("common")]
+    fn update_returns_none(#[case] key: &str) {
+        let yaml = "common:\n  hello: Hello\n";
+
+        assert_that!(update_key_in_yaml_text(yaml, key, "x", "."), none());
+    }
+
+    // --- insert ---
+
+    #[rstest]
+    fn insert_into_existing_parent_after_last_child() {
+        let yaml = "common:\n  hello: Hello\n  # footer comment\nother: x\n";
+
+        let result = insert_key_to_yaml_text(yaml, "common.bye", "Bye", ".").unwrap();
+
+        assert_that!(
+            result.new_text,
+            eq("common:\n  hello: Hello\n  bye: Bye\n  # footer comment\nother: x\n")
+        );
+    }
+
+    #[rstest]
+    fn insert_creates_missing_parents_with_detected_indent() {
+        let yaml = "common:\n    hello: Hello\n";
+
+        let result =
+            insert_key_to_yaml_text(yaml, "errors.http.notFound", "Not found", ".").unwrap();
+
+        assert_that!(
+            result.new_text,
+            eq("common:\n    hello: Hello\nerrors:\n    http:\n        notFound: Not found\n")
+        );
+    }
+
+    #[rstest]
+    fn insert_into_rails_locale_root() {
+        let yaml = "ja:\n  hello: こんにちは\n";
+
+        let result = insert_key_to_yaml_text(yaml, "bye", "さようなら", ".").unwrap();
+
+        assert_that!(result.new_text, eq("ja:\n  hello: こんにちは\n  bye: さようなら\n"));
+        assert_that!(keys_of(&result.new_text).get("bye"), some(eq("さようなら")));
+    }
+
+    #[rstest]
+    fn insert_under_empty_locale_root() {
+        let result = insert_key_to_yaml_text("en:\n", "hello", "Hello", ".").unwrap();
+
+        assert_that!(result.new_text, eq("en:\n  hello: Hello\n"));
+    }
+
+    #[rstest]
+    #[case::empty("", "hello: Hello\n")]
+    #[case::comment_only("# translations\n", "# translations\nhello: Hello\n")]
+    fn insert_into_empty_document(#[case] yaml: &str, #[case] expected: &str) {
+        let result = insert_key_to_yaml_text(yaml, "hello", "Hello", ".").unwrap();
+
+        assert_that!(result.new_text, eq(expected));
+    }
+
+    #[rstest]
+    fn insert_without_trailing_newline() {
+        let result = insert_key_to_yaml_text("a: A", "b", "B", ".").unwrap();
+
+        assert_that!(result.new_text, eq("a: A\nb: B\n"));
+    }
+
+    #[rstest]
+    fn insert_into_flow_mapping() {
+        let yaml = "buttons: {ok: OK}\n";
+
+        let result = insert_key_to_yaml_text(yaml, "buttons.cancel", "Cancel", ".").unwrap();
+
+        assert_that!(result.new_text, eq("buttons: {ok: OK, cancel: Cancel}\n"));
+    }
+
+    #[rstest]
+    fn insert_through_scalar_returns_none() {
+        assert_that!(insert_key_to_yaml_text("a: text\n", "a.b", "x", "."), none());
+    }
+
+    // --- delete ---
+
+    #[rstest]
+    fn delete_leaf_keeps_other_comments() {
+        let yaml = "# header\na: A # keep\nb: B # drop\nc: C\n";
+
+        let result = delete_keys_from_yaml_text(yaml, &["b".to_string()], ".").unwrap();
+
+        assert_that!(result.new_text, eq("# header\na: A # keep\nc: C\n"));
+        assert_that!(result.deleted_count, eq(1));
+    }
+
+    #[rstest]
+    fn delete_removes_empty_parents() {
+        let yaml = "common:\n  nested:\n    only: x\n  keep: y\nother: z\n";
+
+        let result =
+            delete_keys_from_yaml_text(yaml, &["common.nested.only".to_string()], ".").unwrap();
+
+        assert_that!(result.new_text, eq("common:\n  keep: y\nother: z\n"));
+    }
+
+    #[rstest]
+    fn delete_multiple_keys_in_rails_file() {
+        let yaml = "en:\n  a:\n    x: X\n    y: Y\n  b: B\n";
+        let keys = vec!["a.x".to_string(), "a.y".to_string(), "missing".to_string()];
+
+        let result = delete_keys_from_yaml_text(yaml, &keys, ".").unwrap();
+
+        assert_that!(result.new_text, eq("en:\n  b: B\n"));
+        assert_that!(result.deleted_count, eq(2));
+        assert_that!(result.deleted_keys, unordered_elements_are![eq("a.x"), eq("a.y")]);
+    }
+
+    #[rstest]
+    #[case::first("m: {a: A, b: B}\n", "m.a", "m: {b: B}\n")]
+    #[case::last("m: {a: A, b: B}\n", "m.b", "m: {a: A}\n")]
+    #[case::only("m: {a: A}\nn: N\n", "m.a", "n: N\n")]
+    fn delete_from_flow_mapping(#[case] yaml: &str, #[case] key: &str, #[case] expected: &str) {
+        let result = delete_keys_from_yaml_text(yaml, &[key.to_string()], ".").unwrap();
+
+        assert_that!(result.new_text, eq(expected));
+    }
+
+    // --- rename ---
+
+    #[rstest]
+    fn rename_same_parent_in_place() {
+        let yaml = "common:\n  hello: Hello # comment\n  bye: Bye\n";
+
+        let result = rename_key_in_yaml_text(yaml, "common.hello", "common.hi", ".").unwrap();
+
+        assert_that!(result.new_text, eq("common:\n  hi: Hello # comment\n  bye: Bye\n"));
+    }
+
+    #[rstest]
+    fn rename_to_different_parent_keeps_common_parent() {
+        let yaml = "common:\n  old:\n    key: Value\n  other: x\n";
+
+        let result =
+            rename_key_in_yaml_text(yaml, "common.old.key", "common.new.key", ".").unwrap();
+
+        assert_that!(result.new_text, eq("common:\n  other: x\n  new:\n    key: Value\n"));
+    }
+
+    #[rstest]
+    fn rename_keeps_pivot_when_emptied() {
+        let yaml = "a:\n  b: B\nz: Z\n";
+
+        let result = rename_key_in_yaml_text(yaml, "a.b", "a.c.d", ".").unwrap();
+
+        assert_that!(keys_of(&result.new_text).get("a.c.d"), some(eq("B")));
+        assert_that!(result.new_text, eq("a:\n  c:\n    d: B\nz: Z\n"));
+    }
+
+    #[rstest]
+    #[case::same("a", "a")]
+    #[case::prefix("a", "a.b")]
+    #[case::missing("x", "y")]
+    #[case::exists("a", "b")]
+    fn rename_returns_none(#[case] old: &str, #[case] new: &str) {
+        assert_that!(rename_key_in_yaml_text("a: A\nb: B\n", old, new, "."), none());
+    }
+}
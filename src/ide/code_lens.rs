@@ -0,0 +1,190 @@
+//! Code lenses on translation file keys: reference counts and missing languages.
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use tower_lsp::lsp_types::{
+    CodeLens,
+    Command,
+    Location,
+    Url,
+};
+
+use crate::db::I18nDatabase;
+use crate::ide::document_symbol::OtherLanguages;
+use crate::input::translation::Translation;
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+};
+
+/// Data attached to an unresolved references lens.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferencesLensData {
+    pub uri: Url,
+    pub key: String,
+}
+
+/// Generates lenses for each key of a translation file.
+///
+/// Every key gets a references lens, resolved later by [`resolve_references_lens`] since
+/// counting usages is expensive. Keys absent from other languages of the same namespace also
+/// get a "missing in" lens that opens the client's edit UI for the first missing language.
+#[must_use]
+pub fn generate_code_lenses(
+    db: &dyn I18nDatabase,
+    translation: Translation,
+    translations: &[Translation],
+    uri: &Url,
+    encoding: PositionEncoding,
+) -> Vec<CodeLens> {
+    let key_ranges = translation.key_ranges(db);
+    let other_languages = OtherLanguages::new(db, translation, translations);
+    let line_index = LineIndex::new(translation.json_text(db), encoding);
+
+    let mut keys: Vec<(&String, _)> = translation
+        .keys(db)
+        .keys()
+        .filter_map(|key| key_ranges.get(key).map(|range| (key, *range)))
+        .collect();
+    keys.sort_by_key(|(_, range)| (range.start.line, range.start.character));
+
+    let mut lenses = Vec::new();
+    for (key, range) in keys {
+        let range = line_index.to_lsp_range(range);
+        let data = ReferencesLensData { uri: uri.clone(), key: key.clone() };
+        lenses.push(CodeLens { range, command: None, data: serde_json::to_value(data).ok() });
+
+        let missing = other_languages.missing(key);
+        if let Some(first) = missing.first() {
+            let mut arguments = serde_json::json!({ "lang": first, "key": key });
+            if let (Some(namespace), Some(object)) =
+                (translation.namespace(db), arguments.as_object_mut())
+            {
+                object.insert("namespace".to_string(), namespace.as_str().into());
+            }
+            lenses.push(CodeLens {
+                range,
+                command: Some(Command {
+                    title: format!("missing in: {}", missing.join(", ")),
+                    command: "i18n.executeClientEditTranslation".to_string(),
+                    arguments: Some(vec![arguments]),
+                }),
+                data: None,
+            });
+        }
+    }
+    lenses
+}
+
+/// Sets the command of a references lens to show `locations`.
+#[must_use]
+pub fn resolve_references_lens(lens: CodeLens, uri: &Url, locations: &[Location]) -> CodeLens {
+    let title = match locations.len() {
+        1 => "1 reference".to_string(),
+        count => format!("{count} references"),
+    };
+    CodeLens {
+        command: Some(Command {
+            title,
+            command: "i18n.executeClientShowReferences".to_string(),
+            arguments: Some(vec![serde_json::json!({
+                "uri": uri,
+                "position": lens.range.start,
+                "locations": locations,
+            })]),
+        }),
+        ..lens
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing)]
+mod tests {
+    use std::path::Path;
+
+    use googletest::prelude::*;
+    use rstest::*;
+    use tower_lsp::lsp_types::{
+        Position,
+        Range,
+    };
+
+    use super::*;
+    use crate::db::I18nDatabaseImpl;
+    use crate::input::translation::load_translation_from_content;
+
+    fn uri() -> Url {
+        Url::parse("file:///locales/en.json").unwrap()
+    }
+
+    fn titles(lenses: &[CodeLens]) -> Vec<Option<String>> {
+        lenses.iter().map(|lens| lens.command.as_ref().map(|c| c.title.clone())).collect()
+    }
+
+    #[rstest]
+    fn generates_references_and_missing_lenses() {
+        let db = I18nDatabaseImpl::default();
+        let en = load_translation_from_content(
+            &db,
+            Path::new("/locales/en.json"),
+            "{\n  \"hello\": \"Hello\",\n  \"bye\": \"Bye\"\n}",
+            ".",
+        )
+        .unwrap();
+        let ja = load_translation_from_content(
+            &db,
+            Path::new("/locales/ja.json"),
+            r#"{ "hello": "やあ" }"#,
+            ".",
+        )
+        .unwrap();
+        let ko =
+            load_translation_from_content(&db, Path::new("/locales/ko.json"), "{}", ".").unwrap();
+
+        let lenses = generate_code_lenses(&db, en, &[en, ja, ko], &uri(), PositionEncoding::Utf16);
+
+        assert_that!(
+            titles(&lenses),
+            elements_are![
+                none(),
+                some(eq("missing in: ko")),
+                none(),
+                some(eq("missing in: ja, ko"))
+            ]
+        );
+        assert_that!(lenses[2].range.start, eq(Position { line: 2, character: 2 }));
+        assert_that!(
+            lenses[2].data,
+            some(eq(&serde_json::json!({ "uri": "file:///locales/en.json", "key": "bye" })))
+        );
+        assert_that!(
+            lenses[3].command.as_ref().unwrap().arguments,
+            some(elements_are![eq(&serde_json::json!({ "lang": "ja", "key": "bye" }))])
+        );
+    }
+
+    #[rstest]
+    #[case::none(0, "0 references")]
+    #[case::one(1, "1 reference")]
+    #[case::many(3, "3 references")]
+    fn resolve_references_lens_counts_locations(#[case] count: usize, #[case] expected: &str) {
+        let range = Range {
+            start: Position { line: 1, character: 2 },
+            end: Position { line: 1, character: 9 },
+        };
+        let location =
+            Location { uri: Url::parse("file:///src/app.ts").unwrap(), range: Range::default() };
+        let lens = CodeLens { range, command: None, data: None };
+
+        let resolved = resolve_references_lens(lens, &uri(), &vec![location; count]);
+
+        let command = resolved.command.unwrap();
+        assert_that!(command.title, eq(expected));
+        assert_that!(command.command, eq("i18n.executeClientShowReferences"));
+        let arguments = &command.arguments.unwrap()[0];
+        assert_that!(arguments["position"], eq(&serde_json::json!({ "line": 1, "character": 2 })));
+        assert_that!(arguments["locations"].as_array().unwrap(), len(eq(count)));
+    }
+}
@@ -1,6 +1,9 @@
 //! Completion implementation
 
-use std::collections::HashMap;
+use std::collections::{
+    HashMap,
+    HashSet,
+};
 
 use tower_lsp::lsp_types::{
     CompletionItem,
@@ -22,11 +25,14 @@ use crate::syntax::analyzer::{
     query_loader::load_queries,
 };
 use crate::types::{
+    LineIndex,
     SourcePosition,
     SourceRange,
 };
 
 /// Quote context for completion
+///
+/// Positions use byte columns until converted with [`QuoteContext::to_lsp`].
 #[derive(Debug, Clone)]
 pub enum QuoteContext {
     /// No quotes - cursor at argument start (e.g., `t(|)`)
@@ -39,6 +45,27 @@ pub enum QuoteContext {
     Selector { body_start: Position, body_end: Position, param_name: String },
 }
 
+impl QuoteContext {
+    /// Converts the byte-based positions to client positions.
+    #[must_use]
+    pub fn to_lsp(&self, line_index: &LineIndex<'_>) -> Self {
+        let convert = |position: Position| line_index.to_lsp_position(position.into());
+        match self {
+            Self::NoQuotes { position } => Self::NoQuotes { position: convert(*position) },
+            Self::InsideQuotes { key_start, key_end, partial_key } => Self::InsideQuotes {
+                key_start: convert(*key_start),
+                key_end: convert(*key_end),
+                partial_key: partial_key.clone(),
+            },
+            Self::Selector { body_start, body_end, param_name } => Self::Selector {
+                body_start: convert(*body_start),
+                body_end: convert(*body_end),
+                param_name: param_name.clone(),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CompletionContext {
     pub partial_key: String,
@@ -104,6 +131,9 @@ fn build_text_edit(
 }
 
 /// Generates completion items for translation keys.
+///
+/// Component-local translations must precede global ones (see
+/// [`crate::ide::scope::visible_translations`]) so that they shadow global values.
 pub fn generate_completions(
     db: &dyn I18nDatabase,
     translations: &[Translation],
@@ -130,10 +160,14 @@ pub fn generate_completions(
         _ => None,
     };
 
+    // (key, language) pairs answered by component-local messages, which shadow global ones
+    let mut local_entries: HashSet<(String, String)> = HashSet::new();
+
     // Collect all translations for each key
     for translation in translations {
         let keys = translation.keys(db);
         let language = translation.language(db);
+        let is_local = translation.is_component_local(db);
 
         for (key, value) in keys {
             if let Some(prefix) = key_prefix
@@ -148,6 +182,14 @@ pub fn generate_completions(
                 continue;
             }
 
+            if is_local {
+                local_entries.insert((key.clone(), language.clone()));
+            } else if !local_entries.is_empty()
+                && local_entries.contains(&(key.clone(), language.clone()))
+            {
+                continue;
+            }
+
             key_translations
                 .entry(key.clone())
                 .or_default()
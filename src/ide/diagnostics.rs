@@ -8,10 +8,18 @@ use tower_lsp::lsp_types::{
     NumberOrString,
 };
 
-use crate::config::Severity;
+use crate::config::{
+    DiagnosticsConfig,
+    HardcodedStringConfig,
+    I18nSettings,
+    Severity,
+};
 use crate::db::I18nDatabase;
 use crate::framework::PluralStrategy;
-use crate::ide::key_match::is_child_key;
+use crate::ide::key_match::{
+    is_child_key,
+    matches_key_pattern,
+};
 use crate::ide::namespace::{
     filter_by_namespace,
     resolve_usage_namespace,
@@ -23,6 +31,10 @@ use crate::ide::plural::{
 use crate::input::source::SourceFile;
 use crate::input::translation::Translation;
 use crate::syntax::analyze_source;
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+};
 
 #[derive(Debug, Clone)]
 pub struct DiagnosticOptions {
@@ -45,6 +57,170 @@ impl Default for DiagnosticOptions {
     }
 }
 
+/// Settings of every diagnostic, resolved from [`I18nSettings`].
+///
+/// Shared by the language server and the `check` command so that both report the same rules.
+#[derive(Debug, Clone)]
+pub struct DiagnosticConfig {
+    pub missing_translation: DiagnosticOptions,
+    pub diagnostics: DiagnosticsConfig,
+    pub key_separator: String,
+    pub namespace_separator: Option<String>,
+    pub default_namespace: Option<String>,
+    pub primary_languages: Vec<String>,
+    pub position_encoding: PositionEncoding,
+}
+
+impl DiagnosticConfig {
+    #[must_use]
+    pub fn new(settings: &I18nSettings, position_encoding: PositionEncoding) -> Self {
+        let mt = &settings.diagnostics.missing_translation;
+        Self {
+            missing_translation: DiagnosticOptions {
+                enabled: mt.enabled,
+                severity: mt.severity,
+                required_languages: mt
+                    .required_languages
+                    .as_ref()
+                    .map(|v| v.iter().cloned().collect()),
+                optional_languages: mt
+                    .optional_languages
+                    .as_ref()
+                    .map(|v| v.iter().cloned().collect()),
+            },
+            diagnostics: settings.diagnostics.clone(),
+            key_separator: settings.key_separator.clone(),
+            namespace_separator: settings.namespace_separator.clone(),
+            default_namespace: settings.default_namespace.clone(),
+            primary_languages: settings.primary_languages.clone().unwrap_or_default(),
+            position_encoding,
+        }
+    }
+
+    /// Generates all diagnostics reported on a source file.
+    ///
+    /// `translations` should be the translations visible from the file
+    /// (see [`crate::ide::scope::visible_translations`]).
+    #[must_use]
+    pub fn source_file_diagnostics(
+        &self,
+        db: &dyn I18nDatabase,
+        source_file: SourceFile,
+        translations: &[Translation],
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = generate_diagnostics(
+            db,
+            source_file,
+            translations,
+            &self.missing_translation,
+            &self.key_separator,
+            self.namespace_separator.as_deref(),
+            self.default_namespace.as_deref(),
+            self.position_encoding,
+        );
+        let interpolation = self.diagnostics.interpolation;
+        if interpolation.enabled {
+            diagnostics.extend(crate::ide::interpolation::generate_interpolation_diagnostics(
+                db,
+                source_file,
+                translations,
+                interpolation.severity,
+                &self.key_separator,
+                self.namespace_separator.as_deref(),
+                self.default_namespace.as_deref(),
+                self.position_encoding,
+            ));
+        }
+        let hardcoded = &self.diagnostics.hardcoded_string;
+        if hardcoded.enabled {
+            diagnostics.extend(generate_hardcoded_string_diagnostics(
+                db,
+                source_file,
+                hardcoded,
+                self.position_encoding,
+            ));
+        }
+        diagnostics
+    }
+
+    /// Returns `true` if any diagnostic is reported on translation files.
+    #[must_use]
+    pub const fn has_translation_file_diagnostics(&self) -> bool {
+        self.diagnostics.unused_translation.enabled
+            || self.diagnostics.placeholder_consistency.enabled
+            || !matches!(self.diagnostics.icu_syntax.enabled, Some(false))
+            || self.diagnostics.plural_categories.enabled
+    }
+
+    /// Generates all diagnostics reported on a translation file.
+    #[must_use]
+    pub fn translation_file_diagnostics(
+        &self,
+        db: &dyn I18nDatabase,
+        translation: Translation,
+        translations: &[Translation],
+        source_files: &[SourceFile],
+    ) -> Vec<Diagnostic> {
+        let encoding = self.position_encoding;
+        let DiagnosticsConfig {
+            unused_translation: unused,
+            placeholder_consistency: consistency,
+            icu_syntax,
+            plural_categories,
+            ..
+        } = &self.diagnostics;
+
+        let mut diagnostics = Vec::new();
+        if unused.enabled {
+            diagnostics.extend(generate_unused_key_diagnostics(
+                db,
+                translation,
+                source_files,
+                &self.key_separator,
+                &unused.ignore_patterns,
+                unused.severity,
+                self.namespace_separator.as_deref(),
+                self.default_namespace.as_deref(),
+                encoding,
+            ));
+        }
+        if consistency.enabled {
+            diagnostics.extend(
+                crate::ide::interpolation::generate_placeholder_consistency_diagnostics(
+                    db,
+                    translation,
+                    translations,
+                    &self.primary_languages,
+                    consistency.severity,
+                    encoding,
+                ),
+            );
+        }
+        let icu_syntax_enabled = icu_syntax.enabled.unwrap_or_else(|| {
+            crate::framework::FrameworkConfig::project_plural_strategy(
+                source_files.iter().map(|file| file.language(db)),
+            ) == PluralStrategy::Icu
+        });
+        if icu_syntax_enabled {
+            diagnostics.extend(crate::ide::icu::generate_icu_syntax_diagnostics(
+                db,
+                translation,
+                icu_syntax.severity,
+                encoding,
+            ));
+        }
+        if plural_categories.enabled {
+            diagnostics.extend(crate::ide::plural::generate_plural_category_diagnostics(
+                db,
+                translation,
+                plural_categories.severity,
+                encoding,
+            ));
+        }
+        diagnostics
+    }
+}
+
 /// Determines which languages to check for missing translations.
 ///
 /// Priority: `required_languages` > `optional_languages` > all languages
@@ -68,6 +244,9 @@ pub fn determine_target_languages<'a>(
 /// Supports reverse prefix matching: `t('nested')` is valid if `nested.key` exists,
 /// allowing object retrieval patterns.
 /// Filters translations by namespace when `namespace_separator` is set.
+/// Dynamic keys (e.g., `` t(`errors.${code}`) ``) are reported only when their pattern matches
+/// no key at all.
+#[allow(clippy::too_many_arguments)]
 pub fn generate_diagnostics(
     db: &dyn I18nDatabase,
     source_file: SourceFile,
@@ -76,6 +255,7 @@ pub fn generate_diagnostics(
     key_separator: &str,
     namespace_separator: Option<&str>,
     default_namespace: Option<&str>,
+    encoding: PositionEncoding,
 ) -> Vec<Diagnostic> {
     if !options.enabled {
         return Vec::new();
@@ -88,6 +268,7 @@ pub fn generate_diagnostics(
     let plural_strategy =
         crate::framework::FrameworkConfig::for_language(source_file.language(db)).plural_strategy;
     let key_usages = analyze_source(db, source_file, key_separator.to_string());
+    let line_index = LineIndex::new(source_file.text(db), encoding);
 
     for usage in key_usages {
         let full_key = usage.key(db).text(db);
@@ -113,6 +294,30 @@ pub fn generate_diagnostics(
         let all_languages: HashSet<String> = filtered.iter().map(|t| t.language(db)).collect();
         let target_languages = determine_target_languages(&all_languages, options);
 
+        if usage.dynamic(db) {
+            let mut target_keys = language_keys
+                .iter()
+                .filter(|(lang, _)| target_languages.contains(lang.as_str()))
+                .flat_map(|(_, keys)| keys)
+                .peekable();
+            let has_targets = target_keys.peek().is_some();
+
+            if has_targets && !target_keys.any(|key| matches_key_pattern(key, &key_part)) {
+                diagnostics.push(Diagnostic {
+                    range: line_index.to_lsp_range(usage.range(db)),
+                    severity: Some(options.severity.to_lsp()),
+                    code: Some(NumberOrString::String("unmatched-dynamic-key".to_string())),
+                    code_description: None,
+                    source: Some("js-i18n".to_string()),
+                    message: format!("No translation keys match dynamic key '{full_key}'"),
+                    related_information: None,
+                    tags: None,
+                    data: Some(serde_json::json!({ "key": full_key })),
+                });
+            }
+            continue;
+        }
+
         let missing_languages: Vec<String> = language_keys
             .iter()
             .filter(|(lang, _)| target_languages.contains(lang.as_str()))
@@ -132,7 +337,7 @@ pub fn generate_diagnostics(
             );
 
             diagnostics.push(Diagnostic {
-                range: range.into(),
+                range: line_index.to_lsp_range(range),
                 severity: Some(options.severity.to_lsp()),
                 code: Some(NumberOrString::String("missing-translation".to_string())),
                 code_description: None,
@@ -166,30 +371,22 @@ pub fn generate_unused_key_diagnostics(
     severity: Severity,
     namespace_separator: Option<&str>,
     default_namespace: Option<&str>,
+    encoding: PositionEncoding,
 ) -> Vec<Diagnostic> {
-    let translation_ns = translation.namespace(db);
-    let mut used_keys: HashSet<String> = HashSet::new();
-    for source_file in source_files {
-        let key_usages = analyze_source(db, *source_file, key_separator.to_string());
-        for usage in key_usages {
-            let (resolved_ns, key_part) =
-                resolve_usage_namespace(db, usage, namespace_separator, default_namespace);
-
-            let ns_matches = match (&resolved_ns, &translation_ns) {
-                (None, _) | (_, None) => true,
-                (Some(rns), Some(tns)) => rns == tns,
-            };
-
-            if ns_matches {
-                used_keys.insert(key_part);
-            }
-        }
-    }
+    let used_keys = used_keys_for_translation(
+        db,
+        translation,
+        source_files,
+        key_separator,
+        namespace_separator,
+        default_namespace,
+    );
 
     let ignore_matcher = build_ignore_matcher(ignore_patterns);
 
     let all_keys = translation.keys(db);
     let key_ranges = translation.key_ranges(db);
+    let line_index = LineIndex::new(translation.json_text(db), encoding);
 
     let mut diagnostics = Vec::new();
     for key in all_keys.keys() {
@@ -203,7 +400,7 @@ pub fn generate_unused_key_diagnostics(
 
         if !is_used && let Some(range) = key_ranges.get(key) {
             diagnostics.push(Diagnostic {
-                range: (*range).into(),
+                range: line_index.to_lsp_range(*range),
                 severity: Some(severity.to_lsp()),
                 code: Some(NumberOrString::String("unused-translation-key".to_string())),
                 code_description: None,
@@ -219,6 +416,96 @@ pub fn generate_unused_key_diagnostics(
     diagnostics
 }
 
+/// Keys used by source files, with the patterns of dynamic keys (`errors.*`) kept apart.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsedKeys {
+    pub keys: HashSet<String>,
+    pub patterns: HashSet<String>,
+}
+
+impl UsedKeys {
+    pub fn insert(&mut self, key: String, dynamic: bool) {
+        if dynamic {
+            self.patterns.insert(key);
+        } else {
+            self.keys.insert(key);
+        }
+    }
+}
+
+impl FromIterator<String> for UsedKeys {
+    /// Collects static keys.
+    fn from_iter<I: IntoIterator<Item = String>>(keys: I) -> Self {
+        Self { keys: keys.into_iter().collect(), patterns: HashSet::new() }
+    }
+}
+
+/// Collects the keys used in `source_files` that resolve to the namespace of `translation`.
+///
+/// Keys are returned without their namespace prefix, ready for [`is_key_used`].
+pub(crate) fn used_keys_for_translation(
+    db: &dyn I18nDatabase,
+    translation: Translation,
+    source_files: &[SourceFile],
+    key_separator: &str,
+    namespace_separator: Option<&str>,
+    default_namespace: Option<&str>,
+) -> UsedKeys {
+    let translation_ns = translation.namespace(db);
+    let mut used_keys = UsedKeys::default();
+    for source_file in source_files {
+        let key_usages = analyze_source(db, *source_file, key_separator.to_string());
+        for usage in key_usages {
+            let (resolved_ns, key_part) =
+                resolve_usage_namespace(db, usage, namespace_separator, default_namespace);
+
+            let ns_matches = match (&resolved_ns, &translation_ns) {
+                (None, _) | (_, None) => true,
+                (Some(rns), Some(tns)) => rns == tns,
+            };
+
+            if ns_matches {
+                used_keys.insert(key_part, usage.dynamic(db));
+            }
+        }
+    }
+    used_keys
+}
+
+/// Generates diagnostics for untranslated JSX, Vue and Svelte markup text and for static values
+/// of the configured attributes.
+#[must_use]
+pub fn generate_hardcoded_string_diagnostics(
+    db: &dyn I18nDatabase,
+    source_file: SourceFile,
+    config: &HardcodedStringConfig,
+    encoding: PositionEncoding,
+) -> Vec<Diagnostic> {
+    let text = source_file.text(db);
+    let ignore_matcher = build_ignore_matcher(&config.ignore_patterns);
+    let line_index = LineIndex::new(text, encoding);
+
+    crate::syntax::hardcoded::find_hardcoded_strings(text, source_file.language(db))
+        .into_iter()
+        .filter(|string| {
+            string.attribute.as_ref().is_none_or(|a| config.attributes.contains(&a.name))
+                && string.text.chars().count() >= config.min_length
+                && !ignore_matcher.as_ref().is_some_and(|m| m.is_match(&string.text))
+        })
+        .map(|string| Diagnostic {
+            range: line_index.to_lsp_range(string.range),
+            severity: Some(config.severity.to_lsp()),
+            code: Some(NumberOrString::String("hardcoded-string".to_string())),
+            code_description: None,
+            source: Some("js-i18n".to_string()),
+            message: format!("Hardcoded string '{}' is not translated", string.text),
+            related_information: None,
+            tags: None,
+            data: Some(serde_json::json!({ "text": string.text })),
+        })
+        .collect()
+}
+
 fn build_ignore_matcher(patterns: &[String]) -> Option<globset::GlobSet> {
     if patterns.is_empty() {
         return None;
@@ -243,17 +530,20 @@ fn build_ignore_matcher(patterns: &[String]) -> Option<globset::GlobSet> {
 /// 1. Exact match
 /// 2. Prefix match: an element in `used_keys` is a prefix of `key` (supports array notation)
 /// 3. Plural suffix match: `key` has a plural suffix and its base key is used
+/// 4. Pattern match: `key` matches a dynamic key pattern in `used_keys`
 ///
 /// # Examples
 /// - `key = "hoge.fuga.piyo"`, `used_keys = {"hoge.fuga"}` -> true (prefix match)
 /// - `key = "items[0]"`, `used_keys = {"items"}` -> true (array prefix match)
 /// - `key = "items_one"`, `used_keys = {"items"}` -> true (plural base key used)
+/// - `key = "errors.notFound"`, `used_keys = {"errors.*"}` -> true (dynamic key pattern)
 pub(crate) fn is_key_used(
     key: &str,
-    used_keys: &HashSet<String>,
+    used_keys: &UsedKeys,
     separator: &str,
     plural_strategy: PluralStrategy,
 ) -> bool {
+    let UsedKeys { keys: used_keys, patterns } = used_keys;
     if used_keys.contains(key) {
         return true;
     }
@@ -263,7 +553,13 @@ pub(crate) fn is_key_used(
         return true;
     }
 
-    get_plural_base_key(key, plural_strategy).is_some_and(|base_key| used_keys.contains(base_key))
+    if get_plural_base_key(key, plural_strategy)
+        .is_some_and(|base_key| used_keys.contains(base_key))
+    {
+        return true;
+    }
+
+    patterns.iter().any(|pattern| matches_key_pattern(key, pattern))
 }
 
 /// Checks if any key starts with the given prefix (reverse prefix match).
@@ -318,7 +614,11 @@ mod tests {
 
     use googletest::prelude::*;
     use rstest::*;
-    use tower_lsp::lsp_types::DiagnosticSeverity;
+    use tower_lsp::lsp_types::{
+        DiagnosticSeverity,
+        Position,
+        Range,
+    };
 
     use super::*;
     use crate::db::I18nDatabaseImpl;
@@ -329,6 +629,50 @@ mod tests {
     use crate::input::translation::Translation;
     use crate::test_utils::create_translation_with_namespace;
 
+    #[rstest]
+    #[case::utf16(PositionEncoding::Utf16, 21)]
+    #[case::utf32(PositionEncoding::Utf32, 20)]
+    #[case::utf8(PositionEncoding::Utf8, 29)]
+    fn test_generate_diagnostics_range_uses_position_encoding(
+        #[case] encoding: PositionEncoding,
+        #[case] expected_start: u32,
+    ) {
+        let db = I18nDatabaseImpl::default();
+
+        // "日本語" is 9 bytes / 3 UTF-16 units, "😀" is 4 bytes / 2 UTF-16 units / 1 code point
+        let source_code = r#"const a = "日本語😀"; t("missing");"#;
+        let source_file = SourceFile::new(
+            &db,
+            "test.ts".to_string(),
+            source_code.to_string(),
+            ProgrammingLanguage::TypeScript,
+        );
+        let translation = Translation::new(
+            &db,
+            "en".to_string(),
+            None,
+            "en.json".to_string(),
+            HashMap::new(),
+            String::new(),
+            HashMap::new(),
+            HashMap::new(),
+        );
+
+        let diagnostics = generate_diagnostics(
+            &db,
+            source_file,
+            &[translation],
+            &DiagnosticOptions::default(),
+            ".",
+            None,
+            None,
+            encoding,
+        );
+
+        assert_that!(diagnostics.len(), eq(1));
+        assert_that!(diagnostics[0].range.start.character, eq(expected_start));
+    }
+
     #[rstest]
     fn test_generate_diagnostics_with_missing_key() {
         let db = I18nDatabaseImpl::default();
@@ -360,8 +704,16 @@ mod tests {
         );
 
         let options = DiagnosticOptions::default();
-        let diagnostics =
-            generate_diagnostics(&db, source_file, &[translation], &options, ".", None, None);
+        let diagnostics = generate_diagnostics(
+            &db,
+            source_file,
+            &[translation],
+            &options,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         assert_that!(diagnostics, not(is_empty()));
         assert_that!(
@@ -412,8 +764,16 @@ mod tests {
         );
 
         let options = DiagnosticOptions::default();
-        let diagnostics =
-            generate_diagnostics(&db, source_file, &[translation], &options, ".", None, None);
+        let diagnostics = generate_diagnostics(
+            &db,
+            source_file,
+            &[translation],
+            &options,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         assert_that!(diagnostics, is_empty());
     }
@@ -469,6 +829,7 @@ mod tests {
             ".",
             None,
             None,
+            PositionEncoding::Utf16,
         );
 
         // common.hello is missing in ja, errors.notFound is missing in en
@@ -545,8 +906,16 @@ mod tests {
         );
 
         let options = DiagnosticOptions { enabled: false, ..DiagnosticOptions::default() };
-        let diagnostics =
-            generate_diagnostics(&db, source_file, &[translation], &options, ".", None, None);
+        let diagnostics = generate_diagnostics(
+            &db,
+            source_file,
+            &[translation],
+            &options,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         assert_that!(diagnostics, is_empty());
     }
@@ -574,8 +943,16 @@ mod tests {
 
         let options =
             DiagnosticOptions { severity: Severity::Error, ..DiagnosticOptions::default() };
-        let diagnostics =
-            generate_diagnostics(&db, source_file, &[translation], &options, ".", None, None);
+        let diagnostics = generate_diagnostics(
+            &db,
+            source_file,
+            &[translation],
+            &options,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         assert_that!(diagnostics, not(is_empty()));
         assert_that!(
@@ -586,7 +963,7 @@ mod tests {
 
     #[rstest]
     fn test_is_key_used_exact_match() {
-        let used_keys: HashSet<String> =
+        let used_keys: UsedKeys =
             ["common.hello", "common.goodbye"].iter().map(|s| s.to_string()).collect();
 
         assert_that!(
@@ -606,7 +983,7 @@ mod tests {
     #[rstest]
     fn test_is_key_used_prefix_match() {
         // When t('hoge.fuga') is used, hoge.fuga.piyo is considered used
-        let used_keys: HashSet<String> = ["hoge.fuga"].iter().map(|s| s.to_string()).collect();
+        let used_keys: UsedKeys = ["hoge.fuga"].iter().map(|s| s.to_string()).collect();
 
         assert_that!(
             is_key_used("hoge.fuga", &used_keys, ".", PluralStrategy::SuffixBased),
@@ -631,9 +1008,128 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn test_is_key_used_pattern_match() {
+        let used_keys = UsedKeys {
+            patterns: ["errors.*", "status.*.label"].iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        };
+
+        assert_that!(
+            is_key_used("errors.notFound", &used_keys, ".", PluralStrategy::SuffixBased),
+            eq(true)
+        );
+        assert_that!(
+            is_key_used("status.active.label", &used_keys, ".", PluralStrategy::SuffixBased),
+            eq(true)
+        );
+        assert_that!(
+            is_key_used("status.active.title", &used_keys, ".", PluralStrategy::SuffixBased),
+            eq(false)
+        );
+        assert_that!(
+            is_key_used("errors", &used_keys, ".", PluralStrategy::SuffixBased),
+            eq(false)
+        );
+    }
+
+    #[rstest]
+    fn test_is_key_used_static_key_with_asterisk_is_not_a_pattern() {
+        let used_keys: UsedKeys = ["notes.*".to_string()].into_iter().collect();
+
+        assert_that!(
+            is_key_used("notes.*", &used_keys, ".", PluralStrategy::SuffixBased),
+            eq(true)
+        );
+        assert_that!(
+            is_key_used("notes.first", &used_keys, ".", PluralStrategy::SuffixBased),
+            eq(false)
+        );
+    }
+
+    #[rstest]
+    fn test_generate_diagnostics_constant_key_at_each_call_site() {
+        let db = I18nDatabaseImpl::default();
+        let source_file = SourceFile::new(
+            &db,
+            "test.ts".to_string(),
+            "const KEYS = { save: 'common.save' } as const;\nt(KEYS.save);\nt(KEYS.save);"
+                .to_string(),
+            ProgrammingLanguage::TypeScript,
+        );
+        let en = create_translation_with_namespace(&db, "en", None, "en.json", HashMap::new());
+
+        let diagnostics = generate_diagnostics(
+            &db,
+            source_file,
+            &[en],
+            &DiagnosticOptions::default(),
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
+
+        assert_that!(
+            diagnostics,
+            elements_are![
+                field!(
+                    Diagnostic.range,
+                    eq(&Range::new(Position::new(1, 2), Position::new(1, 11)))
+                ),
+                field!(
+                    Diagnostic.range,
+                    eq(&Range::new(Position::new(2, 2), Position::new(2, 11)))
+                )
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::matched(r"t(`errors.${code}`)", 0)]
+    #[case::unmatched(r"t(`warnings.${code}`)", 1)]
+    fn test_generate_diagnostics_dynamic_key(#[case] call: &str, #[case] expected: usize) {
+        let db = I18nDatabaseImpl::default();
+        let source_file = SourceFile::new(
+            &db,
+            "test.ts".to_string(),
+            call.to_string(),
+            ProgrammingLanguage::TypeScript,
+        );
+        // `ja` lacks the key: dynamic keys are not checked per language
+        let en = create_translation_with_namespace(
+            &db,
+            "en",
+            None,
+            "en.json",
+            HashMap::from([("errors.notFound".to_string(), "Not found".to_string())]),
+        );
+        let ja = create_translation_with_namespace(&db, "ja", None, "ja.json", HashMap::new());
+
+        let diagnostics = generate_diagnostics(
+            &db,
+            source_file,
+            &[en, ja],
+            &DiagnosticOptions::default(),
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
+
+        assert_that!(diagnostics, len(eq(expected)));
+        assert_that!(
+            diagnostics,
+            each(field!(
+                Diagnostic.code,
+                some(eq(&NumberOrString::String("unmatched-dynamic-key".to_string())))
+            ))
+        );
+    }
+
     #[rstest]
     fn test_is_key_used_with_custom_separator() {
-        let used_keys: HashSet<String> = ["hoge:fuga"].iter().map(|s| s.to_string()).collect();
+        let used_keys: UsedKeys = ["hoge:fuga"].iter().map(|s| s.to_string()).collect();
 
         assert_that!(
             is_key_used("hoge:fuga", &used_keys, ":", PluralStrategy::SuffixBased),
@@ -702,6 +1198,7 @@ mod tests {
             Severity::Hint,
             None,
             None,
+            PositionEncoding::Utf16,
         );
 
         assert_that!(diagnostics, len(eq(1)));
@@ -780,6 +1277,7 @@ mod tests {
             Severity::Hint,
             None,
             None,
+            PositionEncoding::Utf16,
         );
 
         // hoge.fuga and hoge.fuga.piyo are used (prefix match)
@@ -791,6 +1289,68 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn test_generate_unused_key_diagnostics_with_dynamic_key() {
+        let db = I18nDatabaseImpl::default();
+
+        let source_code = r"const msg = t(`errors.${code}`);";
+        let source_file = SourceFile::new(
+            &db,
+            "test.ts".to_string(),
+            source_code.to_string(),
+            ProgrammingLanguage::TypeScript,
+        );
+
+        let keys = HashMap::from([
+            ("errors.notFound".to_string(), "Not found".to_string()),
+            ("other.key".to_string(), "Other".to_string()),
+        ]);
+        let key_ranges = HashMap::from([
+            (
+                "errors.notFound".to_string(),
+                crate::types::SourceRange {
+                    start: crate::types::SourcePosition { line: 1, character: 2 },
+                    end: crate::types::SourcePosition { line: 1, character: 18 },
+                },
+            ),
+            (
+                "other.key".to_string(),
+                crate::types::SourceRange {
+                    start: crate::types::SourcePosition { line: 2, character: 2 },
+                    end: crate::types::SourcePosition { line: 2, character: 13 },
+                },
+            ),
+        ]);
+
+        let translation = Translation::new(
+            &db,
+            "en".to_string(),
+            None,
+            "en.json".to_string(),
+            keys,
+            String::new(),
+            key_ranges,
+            HashMap::new(),
+        );
+
+        let diagnostics = generate_unused_key_diagnostics(
+            &db,
+            translation,
+            &[source_file],
+            ".",
+            &[],
+            Severity::Hint,
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
+
+        assert_that!(
+            diagnostics,
+            elements_are![field!(Diagnostic.message, contains_substring("other.key"))]
+        );
+    }
+
     #[rstest]
     fn test_generate_unused_key_diagnostics_with_ignore_patterns() {
         let db = I18nDatabaseImpl::default();
@@ -841,6 +1401,7 @@ mod tests {
             Severity::Hint,
             None,
             None,
+            PositionEncoding::Utf16,
         );
 
         // debug.info and debug.warn are ignored, only other.unused is reported
@@ -898,6 +1459,7 @@ mod tests {
             Severity::Warning,
             None,
             None,
+            PositionEncoding::Utf16,
         );
 
         assert_that!(diagnostics, len(eq(1)));
@@ -979,8 +1541,16 @@ mod tests {
         );
 
         let options = DiagnosticOptions::default();
-        let diagnostics =
-            generate_diagnostics(&db, source_file, &[translation], &options, ".", None, None);
+        let diagnostics = generate_diagnostics(
+            &db,
+            source_file,
+            &[translation],
+            &options,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         // Since nested.key exists, nested is valid (no diagnostics)
         assert_that!(diagnostics, is_empty());
@@ -1014,8 +1584,16 @@ mod tests {
         );
 
         let options = DiagnosticOptions::default();
-        let diagnostics =
-            generate_diagnostics(&db, source_file, &[translation], &options, ".", None, None);
+        let diagnostics = generate_diagnostics(
+            &db,
+            source_file,
+            &[translation],
+            &options,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         assert_that!(diagnostics, len(eq(1)));
         assert_that!(
@@ -1027,7 +1605,7 @@ mod tests {
     #[rstest]
     fn test_is_key_used_with_array_prefix() {
         // When t('items') is used, items[0] is considered used
-        let used_keys: HashSet<String> = ["items"].iter().map(|s| s.to_string()).collect();
+        let used_keys: UsedKeys = ["items"].iter().map(|s| s.to_string()).collect();
 
         assert_that!(is_key_used("items", &used_keys, ".", PluralStrategy::SuffixBased), eq(true));
         assert_that!(
@@ -1120,8 +1698,16 @@ mod tests {
         let options = DiagnosticOptions::default();
 
         // With namespace_separator=":", "common:hello" should only check common namespace
-        let diagnostics =
-            generate_diagnostics(&db, source_file, &translations, &options, ".", Some(":"), None);
+        let diagnostics = generate_diagnostics(
+            &db,
+            source_file,
+            &translations,
+            &options,
+            ".",
+            Some(":"),
+            None,
+            PositionEncoding::Utf16,
+        );
 
         // "hello" exists in both en/common and ja/common → no diagnostics
         assert_that!(diagnostics, is_empty());
@@ -1158,8 +1744,16 @@ mod tests {
         let translations = vec![errors_en, common_en];
         let options = DiagnosticOptions::default();
 
-        let diagnostics =
-            generate_diagnostics(&db, source_file, &translations, &options, ".", Some(":"), None);
+        let diagnostics = generate_diagnostics(
+            &db,
+            source_file,
+            &translations,
+            &options,
+            ".",
+            Some(":"),
+            None,
+            PositionEncoding::Utf16,
+        );
 
         // "missing" does NOT exist in errors namespace → should report missing for en
         assert_that!(diagnostics, len(eq(1)));
@@ -1204,8 +1798,16 @@ mod tests {
         let options = DiagnosticOptions::default();
 
         // namespace_separator is None but declared namespace from useTranslation is used
-        let diagnostics =
-            generate_diagnostics(&db, source_file, &translations, &options, ".", None, None);
+        let diagnostics = generate_diagnostics(
+            &db,
+            source_file,
+            &translations,
+            &options,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         // "hello" exists in common → no diagnostics
         assert_that!(diagnostics, is_empty());
@@ -1250,6 +1852,7 @@ mod tests {
             ".",
             Some(":"),
             Some("common"), // default namespace
+            PositionEncoding::Utf16,
         );
 
         // "hello" exists in common (default namespace) → no diagnostics
@@ -1304,6 +1907,7 @@ mod tests {
             Severity::Hint,
             Some(":"),
             None,
+            PositionEncoding::Utf16,
         );
 
         // "hello" is used (common:hello matches common namespace), "unused" is unused
@@ -1360,6 +1964,7 @@ mod tests {
             Severity::Hint,
             Some(":"),
             None,
+            PositionEncoding::Utf16,
         );
 
         // "hello" in errors namespace is unused because source targets common namespace
@@ -1369,4 +1974,87 @@ mod tests {
             contains(field!(Diagnostic.message, contains_substring("hello")))
         );
     }
+
+    #[rstest]
+    fn test_generate_hardcoded_string_diagnostics() {
+        let db = I18nDatabaseImpl::default();
+        let source_code = r#"export const A = () => (
+  <div title="Settings" className="container">
+    Save changes
+    <span>OK</span>
+    <b>x</b>
+    <input placeholder="TODO later" />
+  </div>
+);"#;
+        let source_file = SourceFile::new(
+            &db,
+            "test.tsx".to_string(),
+            source_code.to_string(),
+            ProgrammingLanguage::Tsx,
+        );
+        let config = HardcodedStringConfig {
+            enabled: true,
+            ignore_patterns: vec!["TODO*".to_string()],
+            ..HardcodedStringConfig::default()
+        };
+
+        let diagnostics = generate_hardcoded_string_diagnostics(
+            &db,
+            source_file,
+            &config,
+            PositionEncoding::Utf16,
+        );
+
+        let messages: Vec<String> = diagnostics.iter().map(|d| d.message.clone()).collect();
+        assert_that!(
+            messages,
+            elements_are![
+                eq("Hardcoded string 'Settings' is not translated"),
+                eq("Hardcoded string 'Save changes' is not translated"),
+                eq("Hardcoded string 'OK' is not translated"),
+            ]
+        );
+        assert_that!(diagnostics[1].range.start, eq(Position { line: 2, character: 4 }));
+        assert_that!(
+            diagnostics[0].code,
+            some(eq(&NumberOrString::String("hardcoded-string".to_string())))
+        );
+        assert_that!(diagnostics[0].severity, some(eq(DiagnosticSeverity::INFORMATION)));
+    }
+
+    #[rstest]
+    #[case::i18next_project(ProgrammingLanguage::Tsx, None, 0)]
+    #[case::icu_project(ProgrammingLanguage::Svelte, None, 1)]
+    #[case::enabled_explicitly(ProgrammingLanguage::Tsx, Some(true), 1)]
+    #[case::disabled_explicitly(ProgrammingLanguage::Svelte, Some(false), 0)]
+    fn test_icu_syntax_diagnostics_follow_project_libraries(
+        #[case] language: ProgrammingLanguage,
+        #[case] enabled: Option<bool>,
+        #[case] expected: usize,
+    ) {
+        let db = I18nDatabaseImpl::default();
+        let source_file =
+            SourceFile::new(&db, "file:///src/app".to_string(), "t('hint');".to_string(), language);
+        let translation = crate::input::translation::load_translation_from_content(
+            &db,
+            std::path::Path::new("/locales/en.json"),
+            r#"{ "hint": "Use the { key to open a block" }"#,
+            ".",
+        )
+        .unwrap();
+        let mut settings = I18nSettings::default();
+        settings.diagnostics.icu_syntax.enabled = enabled;
+        let config = DiagnosticConfig::new(&settings, PositionEncoding::Utf16);
+
+        let diagnostics =
+            config.translation_file_diagnostics(&db, translation, &[translation], &[source_file]);
+
+        assert_that!(
+            diagnostics
+                .iter()
+                .filter(|d| d.code == Some(NumberOrString::String("icu-syntax-error".to_string())))
+                .count(),
+            eq(expected)
+        );
+    }
 }
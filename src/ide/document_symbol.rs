@@ -0,0 +1,387 @@
+//! Document symbols (outline) for translation files.
+
+use std::collections::{
+    BTreeSet,
+    HashMap,
+    HashSet,
+};
+
+use tower_lsp::lsp_types::{
+    DocumentSymbol,
+    SymbolKind,
+    SymbolTag,
+};
+
+use crate::db::I18nDatabase;
+use crate::framework::PluralStrategy;
+use crate::ide::diagnostics::{
+    UsedKeys,
+    is_key_used,
+};
+use crate::ide::plural::split_plural_suffix;
+use crate::input::translation::Translation;
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+    SourceRange,
+};
+
+/// Builds the symbol tree of a translation file from its nested keys.
+///
+/// Leaf keys show their value as detail. Keys not used in any source file are tagged
+/// deprecated, and keys absent from other languages of the same namespace list those
+/// languages in the detail.
+#[must_use]
+#[allow(clippy::implicit_hasher)]
+pub fn generate_document_symbols(
+    db: &dyn I18nDatabase,
+    translation: Translation,
+    translations: &[Translation],
+    used_keys: &UsedKeys,
+    key_separator: &str,
+    encoding: PositionEncoding,
+) -> Vec<DocumentSymbol> {
+    let key_ranges = translation.key_ranges(db);
+
+    let mut children: HashMap<Option<&str>, Vec<&str>> = HashMap::new();
+    for key in key_ranges.keys() {
+        children.entry(parent_key(key, key_ranges, key_separator)).or_default().push(key);
+    }
+    for keys in children.values_mut() {
+        keys.sort_by_key(|key| key_ranges.get(*key).map(|r| (r.start.line, r.start.character)));
+    }
+
+    let builder = SymbolBuilder {
+        key_ranges,
+        value_ranges: translation.value_ranges(db),
+        values: translation.keys(db),
+        children,
+        other_languages: OtherLanguages::new(db, translation, translations),
+        used_keys,
+        key_separator,
+        line_index: LineIndex::new(translation.json_text(db), encoding),
+    };
+    builder.build_children(None)
+}
+
+struct SymbolBuilder<'a> {
+    key_ranges: &'a HashMap<String, SourceRange>,
+    value_ranges: &'a HashMap<String, SourceRange>,
+    values: &'a HashMap<String, String>,
+    children: HashMap<Option<&'a str>, Vec<&'a str>>,
+    other_languages: OtherLanguages<'a>,
+    used_keys: &'a UsedKeys,
+    key_separator: &'a str,
+    line_index: LineIndex<'a>,
+}
+
+impl SymbolBuilder<'_> {
+    fn build_children(&self, parent: Option<&str>) -> Vec<DocumentSymbol> {
+        self.children
+            .get(&parent)
+            .into_iter()
+            .flatten()
+            .filter_map(|key| self.build(key, parent))
+            .collect()
+    }
+
+    #[allow(deprecated)] // `DocumentSymbol::deprecated` has no default
+    fn build(&self, key: &str, parent: Option<&str>) -> Option<DocumentSymbol> {
+        let selection_range = self.line_index.to_lsp_range(*self.key_ranges.get(key)?);
+        let children = self.build_children(Some(key));
+
+        let end = children
+            .iter()
+            .map(|child| child.range.end)
+            .chain(self.value_ranges.get(key).map(|r| self.line_index.to_lsp_range(*r).end))
+            .fold(selection_range.end, Ord::max);
+        let range = tower_lsp::lsp_types::Range { start: selection_range.start, end };
+
+        let name = parent.map_or(key, |parent| {
+            let rest = key.get(parent.len()..).unwrap_or_default();
+            rest.strip_prefix(self.key_separator).unwrap_or(rest)
+        });
+
+        let value = children.is_empty().then(|| self.values.get(key)).flatten();
+        let kind = if value.is_some() {
+            SymbolKind::STRING
+        } else if !children.is_empty() && children.iter().all(|c| c.name.starts_with('[')) {
+            SymbolKind::ARRAY
+        } else {
+            SymbolKind::OBJECT
+        };
+
+        let is_unused = value.is_some()
+            && !is_key_used(key, self.used_keys, self.key_separator, PluralStrategy::SuffixBased);
+        let missing = if value.is_some() { self.other_languages.missing(key) } else { Vec::new() };
+
+        Some(DocumentSymbol {
+            name: name.to_string(),
+            detail: value.map(|value| format_detail(value, is_unused, &missing)),
+            kind,
+            tags: is_unused.then(|| vec![SymbolTag::DEPRECATED]),
+            deprecated: None,
+            range,
+            selection_range,
+            children: (!children.is_empty()).then_some(children),
+        })
+    }
+}
+
+/// Returns the longest existing key that `key` is nested under (`a.b` for `a.b.c`, `a` for `a[0]`).
+fn parent_key<'a>(
+    key: &str,
+    key_ranges: &'a HashMap<String, SourceRange>,
+    separator: &str,
+) -> Option<&'a str> {
+    let mut boundaries: Vec<usize> = key
+        .match_indices(separator)
+        .chain(key.match_indices('['))
+        .map(|(index, _)| index)
+        .filter(|&index| index > 0)
+        .collect();
+    boundaries.sort_unstable_by(|a, b| b.cmp(a));
+
+    boundaries.into_iter().find_map(|index| {
+        key_ranges.get_key_value(key.get(..index)?).map(|(parent, _)| parent.as_str())
+    })
+}
+
+fn format_detail(value: &str, is_unused: bool, missing: &[&str]) -> String {
+    let mut notes = Vec::new();
+    if is_unused {
+        notes.push("unused".to_string());
+    }
+    if !missing.is_empty() {
+        notes.push(format!("missing: {}", missing.join(", ")));
+    }
+    if notes.is_empty() { value.to_string() } else { format!("{value} ({})", notes.join(", ")) }
+}
+
+/// Keys of the other languages in the namespace of a translation file.
+pub(crate) struct OtherLanguages<'a> {
+    languages: Vec<LanguageKeys<'a>>,
+}
+
+struct LanguageKeys<'a> {
+    language: String,
+    keys: HashSet<&'a str>,
+    /// Base keys of plural variants, since languages have different plural categories.
+    plural_bases: HashSet<&'a str>,
+}
+
+impl<'a> OtherLanguages<'a> {
+    pub(crate) fn new(
+        db: &'a dyn I18nDatabase,
+        translation: Translation,
+        translations: &[Translation],
+    ) -> Self {
+        let language = translation.language(db);
+        let namespace = translation.namespace(db);
+
+        let others: Vec<Translation> = translations
+            .iter()
+            .filter(|t| {
+                !t.is_component_local(db)
+                    && t.namespace(db) == namespace
+                    && t.language(db) != language
+            })
+            .copied()
+            .collect();
+        let languages: BTreeSet<String> = others.iter().map(|t| t.language(db)).collect();
+
+        let languages = languages
+            .into_iter()
+            .map(|language| {
+                let keys: HashSet<&str> = others
+                    .iter()
+                    .filter(|t| t.language(db) == language)
+                    .flat_map(|t| t.keys(db).keys())
+                    .map(String::as_str)
+                    .collect();
+                let plural_bases = keys
+                    .iter()
+                    .filter_map(|key| split_plural_suffix(key))
+                    .map(|(b, ..)| b)
+                    .collect();
+                LanguageKeys { language, keys, plural_bases }
+            })
+            .collect();
+        Self { languages }
+    }
+
+    /// Returns the languages, sorted, that don't define `key`.
+    ///
+    /// A plural variant counts as present when the language has any variant of its base key.
+    pub(crate) fn missing(&self, key: &str) -> Vec<&str> {
+        self.languages
+            .iter()
+            .filter(|other| {
+                !other.keys.contains(key)
+                    && !split_plural_suffix(key)
+                        .is_some_and(|(base, ..)| other.plural_bases.contains(base))
+            })
+            .map(|other| other.language.as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing)]
+mod tests {
+    use std::path::Path;
+
+    use googletest::prelude::*;
+    use rstest::*;
+    use tower_lsp::lsp_types::{
+        Position,
+        Range,
+    };
+
+    use super::*;
+    use crate::db::I18nDatabaseImpl;
+    use crate::input::translation::load_translation_from_content;
+
+    fn load(db: &I18nDatabaseImpl, path: &str, content: &str) -> Translation {
+        load_translation_from_content(db, Path::new(path), content, ".").unwrap()
+    }
+
+    fn names(symbols: &[DocumentSymbol]) -> Vec<String> {
+        symbols.iter().map(|s| s.name.clone()).collect()
+    }
+
+    fn symbols_for(content: &str, used: &[&str]) -> Vec<DocumentSymbol> {
+        let db = I18nDatabaseImpl::default();
+        let translation = load(&db, "/locales/en.json", content);
+        let used_keys: UsedKeys = used.iter().map(ToString::to_string).collect();
+        generate_document_symbols(
+            &db,
+            translation,
+            &[translation],
+            &used_keys,
+            ".",
+            PositionEncoding::Utf16,
+        )
+    }
+
+    #[rstest]
+    fn builds_nested_tree_in_document_order() {
+        let symbols = symbols_for(
+            r#"{
+  "common": {
+    "save": "Save",
+    "cancel": "Cancel"
+  },
+  "title": "Title"
+}"#,
+            &["common", "title"],
+        );
+
+        assert_that!(names(&symbols), elements_are![eq("common"), eq("title")]);
+        let common = &symbols[0];
+        assert_that!(common.kind, eq(SymbolKind::OBJECT));
+        assert_that!(common.detail, none());
+        assert_that!(
+            names(common.children.as_ref().unwrap()),
+            elements_are![eq("save"), eq("cancel")]
+        );
+        assert_that!(
+            common.range,
+            eq(Range {
+                start: Position { line: 1, character: 2 },
+                end: Position { line: 3, character: 22 }
+            })
+        );
+        assert_that!(
+            common.selection_range,
+            eq(Range {
+                start: Position { line: 1, character: 2 },
+                end: Position { line: 1, character: 10 }
+            })
+        );
+
+        let save = &common.children.as_ref().unwrap()[0];
+        assert_that!(save.kind, eq(SymbolKind::STRING));
+        assert_that!(save.detail, some(eq("Save")));
+        assert_that!(save.tags, none());
+    }
+
+    #[rstest]
+    fn keys_containing_separator_without_parent_stay_flat() {
+        let symbols = symbols_for(r#"{ "a.b": "x", "c": { "d.e": "y" } }"#, &["a", "c"]);
+
+        assert_that!(names(&symbols), elements_are![eq("a.b"), eq("c")]);
+        assert_that!(names(symbols[1].children.as_ref().unwrap()), elements_are![eq("d.e")]);
+    }
+
+    #[rstest]
+    fn arrays_use_index_names() {
+        let symbols = symbols_for(r#"{ "steps": ["One", "Two"] }"#, &["steps"]);
+
+        assert_that!(symbols[0].kind, eq(SymbolKind::ARRAY));
+        assert_that!(
+            names(symbols[0].children.as_ref().unwrap()),
+            elements_are![eq("[0]"), eq("[1]")]
+        );
+    }
+
+    #[rstest]
+    fn marks_unused_keys() {
+        let symbols = symbols_for(r#"{ "used": "A", "unused": "B" }"#, &["used"]);
+
+        assert_that!(symbols[0].tags, none());
+        assert_that!(symbols[1].tags, some(elements_are![eq(&SymbolTag::DEPRECATED)]));
+        assert_that!(symbols[1].detail, some(eq("B (unused)")));
+    }
+
+    #[rstest]
+    fn lists_languages_missing_the_key() {
+        let db = I18nDatabaseImpl::default();
+        let en = load(
+            &db,
+            "/locales/en.json",
+            r#"{ "hello": "Hello", "bye": "Bye", "item_one": "item", "item_other": "items" }"#,
+        );
+        let fr = load(&db, "/locales/fr.json", r#"{ "hello": "Bonjour", "item_other": "x" }"#);
+        let ja = load(&db, "/locales/ja.json", r#"{ "hello": "こんにちは" }"#);
+        let used_keys: UsedKeys =
+            ["hello", "bye", "item"].into_iter().map(str::to_string).collect();
+
+        let symbols = generate_document_symbols(
+            &db,
+            en,
+            &[en, fr, ja],
+            &used_keys,
+            ".",
+            PositionEncoding::Utf16,
+        );
+
+        let details: Vec<String> = symbols.iter().filter_map(|s| s.detail.clone()).collect();
+        assert_that!(
+            details,
+            elements_are![
+                eq("Hello"),
+                eq("Bye (missing: fr, ja)"),
+                eq("item (missing: ja)"),
+                eq("items (missing: ja)")
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::nested("a.b.c", Some("a.b"))]
+    #[case::array("a[0]", Some("a"))]
+    #[case::array_child("a[0].b", Some("a[0]"))]
+    #[case::root("a", None)]
+    fn parent_key_finds_longest_existing_prefix(#[case] key: &str, #[case] expected: Option<&str>) {
+        let key_ranges: HashMap<String, SourceRange> = ["a", "a.b", "a[0]"]
+            .into_iter()
+            .map(|k| {
+                let position = crate::types::SourcePosition { line: 0, character: 0 };
+                (k.to_string(), SourceRange { start: position, end: position })
+            })
+            .collect();
+
+        assert_that!(parent_key(key, &key_ranges, "."), eq(expected));
+    }
+}
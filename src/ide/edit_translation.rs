@@ -0,0 +1,453 @@
+//! Edits for `i18n.editTranslation`: choosing the translation file of a language and namespace.
+//!
+//! When a language has no file for the namespace yet, one is created next to the existing
+//! translation files, following their layout.
+
+use std::collections::HashMap;
+use std::path::{
+    Component,
+    Path,
+    PathBuf,
+};
+
+use tower_lsp::lsp_types::{
+    CreateFile,
+    DocumentChangeOperation,
+    DocumentChanges,
+    OneOf,
+    OptionalVersionedTextDocumentIdentifier,
+    Range,
+    ResourceOp,
+    TextDocumentEdit,
+    TextEdit,
+    Url,
+    WorkspaceEdit,
+};
+
+use crate::db::I18nDatabase;
+use crate::ide::code_actions::{
+    create_full_file_text_edit,
+    insert_key_to_text,
+    update_key_in_text,
+};
+use crate::input::translation::{
+    Translation,
+    TranslationFormat,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum EditTranslationError {
+    #[error("No {language} translation file found")]
+    NoTranslationFile { language: String },
+
+    #[error("Cannot create {}: the client doesn't support creating files", path.display())]
+    CreateFileUnsupported { path: PathBuf },
+
+    #[error("Failed to edit translation key '{key}'")]
+    EditFailed { key: String },
+}
+
+/// Computes the edit setting `key` to `value` in the `language` translation of `namespace`.
+///
+/// The key is updated if it exists and inserted otherwise. `namespace` is ignored when no
+/// translation file has a namespace. When `language` has no file for `namespace`, the edit
+/// creates one (see [`conventional_translation_path`]) if `can_create_files`, i.e., the client
+/// accepts `documentChanges` with `create` operations.
+///
+/// # Errors
+///
+/// Returns an error when no file can be found or created, or the file can't be edited.
+#[allow(clippy::too_many_arguments)]
+pub fn compute_edit_translation(
+    db: &dyn I18nDatabase,
+    translations: &[Translation],
+    language: &str,
+    namespace: Option<&str>,
+    key: &str,
+    value: &str,
+    key_separator: &str,
+    can_create_files: bool,
+) -> Result<WorkspaceEdit, EditTranslationError> {
+    let edit_failed = || EditTranslationError::EditFailed { key: key.to_string() };
+    let namespace = namespace.filter(|_| translations.iter().any(|t| t.namespace(db).is_some()));
+    let existing = translations.iter().find(|t| {
+        t.language(db) == language
+            && namespace.is_none_or(|ns| t.namespace(db).as_deref() == Some(ns))
+    });
+
+    if let Some(translation) = existing {
+        let original_text = translation.json_text(db);
+        let format = translation.format(db);
+        let result = if translation.keys(db).contains_key(key) {
+            update_key_in_text(format, original_text, key, value, key_separator)
+        } else {
+            insert_key_to_text(format, original_text, key, value, key_separator)
+        }
+        .ok_or_else(edit_failed)?;
+        let uri = Url::from_file_path(translation.file_path(db)).map_err(|()| edit_failed())?;
+        let edit = create_full_file_text_edit(original_text, result.new_text);
+        return Ok(WorkspaceEdit {
+            changes: Some(HashMap::from([(uri, vec![edit])])),
+            ..Default::default()
+        });
+    }
+
+    let path = namespace
+        .and_then(|namespace| conventional_translation_path(db, translations, language, namespace))
+        .ok_or_else(|| EditTranslationError::NoTranslationFile {
+            language: language.to_string(),
+        })?;
+    if !can_create_files {
+        return Err(EditTranslationError::CreateFileUnsupported { path });
+    }
+    let format = TranslationFormat::from_path(&path);
+    let empty_text = match format {
+        TranslationFormat::Json => "{}\n",
+        TranslationFormat::Yaml | TranslationFormat::Po => "",
+    };
+    let new_text = insert_key_to_text(format, empty_text, key, value, key_separator)
+        .ok_or_else(edit_failed)?;
+    let uri = Url::from_file_path(&path).map_err(|()| edit_failed())?;
+
+    Ok(WorkspaceEdit {
+        document_changes: Some(DocumentChanges::Operations(vec![
+            DocumentChangeOperation::Op(ResourceOp::Create(CreateFile {
+                uri: uri.clone(),
+                options: None,
+                annotation_id: None,
+            })),
+            DocumentChangeOperation::Edit(TextDocumentEdit {
+                text_document: OptionalVersionedTextDocumentIdentifier { uri, version: None },
+                edits: vec![OneOf::Left(TextEdit {
+                    range: Range::default(),
+                    new_text: new_text.new_text,
+                })],
+            }),
+        ])),
+        ..Default::default()
+    })
+}
+
+/// Returns the path a `language` file of `namespace` would have, following existing files.
+///
+/// A file of the same namespace in another language is preferred, with the language replaced
+/// (`locales/ja/checkout.json` → `locales/en/checkout.json`). Otherwise a file of the same
+/// language in another namespace is used, with the namespace replaced
+/// (`locales/en/common.json` → `locales/en/checkout.json`). Both file-per-namespace and
+/// directory-per-namespace layouts are supported.
+#[must_use]
+pub fn conventional_translation_path(
+    db: &dyn I18nDatabase,
+    translations: &[Translation],
+    language: &str,
+    namespace: &str,
+) -> Option<PathBuf> {
+    let files: Vec<&Translation> =
+        translations.iter().filter(|t| !t.is_component_local(db)).collect();
+
+    let same_namespace = files.iter().find_map(|t| {
+        (t.namespace(db).as_deref() == Some(namespace))
+            .then(|| replace_path_segment(Path::new(t.file_path(db)), &t.language(db), language))
+            .flatten()
+    });
+    same_namespace.or_else(|| {
+        files.iter().find_map(|t| {
+            let other = t.namespace(db).as_deref()?;
+            (t.language(db) == language)
+                .then(|| replace_path_segment(Path::new(t.file_path(db)), other, namespace))
+                .flatten()
+        })
+    })
+}
+
+/// Replaces the file stem or the nearest directory named `from` with `to`.
+fn replace_path_segment(path: &Path, from: &str, to: &str) -> Option<PathBuf> {
+    if path.file_stem().is_some_and(|stem| stem == from) {
+        let file_name = path
+            .extension()
+            .map_or_else(|| to.to_string(), |ext| format!("{to}.{}", ext.to_string_lossy()));
+        return Some(path.with_file_name(file_name));
+    }
+
+    let components: Vec<Component<'_>> = path.components().collect();
+    let index = components.iter().rposition(|component| component.as_os_str() == from)?;
+    let mut replaced = PathBuf::new();
+    for (i, component) in components.iter().enumerate() {
+        replaced.push(if i == index { to.as_ref() } else { component.as_os_str() });
+    }
+    Some(replaced)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing, clippy::panic)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+    use crate::db::I18nDatabaseImpl;
+    use crate::test_utils::create_translation_with_json;
+
+    fn translation(
+        db: &I18nDatabaseImpl,
+        language: &str,
+        namespace: Option<&str>,
+        path: &str,
+        keys: &[(&str, &str)],
+        json: &str,
+    ) -> Translation {
+        create_translation_with_json(
+            db,
+            language,
+            namespace,
+            path,
+            keys.iter().map(|(k, v)| ((*k).to_string(), (*v).to_string())).collect(),
+            json,
+        )
+    }
+
+    fn namespaced(db: &I18nDatabaseImpl) -> Vec<Translation> {
+        vec![
+            translation(
+                db,
+                "en",
+                Some("common"),
+                "/locales/en/common.json",
+                &[("save", "Save")],
+                "{\n  \"save\": \"Save\"\n}",
+            ),
+            translation(db, "en", Some("checkout"), "/locales/en/checkout.json", &[], "{}"),
+            translation(db, "ja", Some("common"), "/locales/ja/common.json", &[], "{}"),
+        ]
+    }
+
+    fn single_change(edit: &WorkspaceEdit) -> (String, String) {
+        let changes = edit.changes.as_ref().unwrap();
+        assert_that!(changes.len(), eq(1));
+        let (uri, edits) = changes.iter().next().unwrap();
+        (uri.path().to_string(), edits[0].new_text.clone())
+    }
+
+    #[rstest]
+    fn inserts_into_file_of_namespace() {
+        let db = I18nDatabaseImpl::default();
+        let translations = namespaced(&db);
+
+        let edit = compute_edit_translation(
+            &db,
+            &translations,
+            "en",
+            Some("checkout"),
+            "pay",
+            "Pay",
+            ".",
+            true,
+        )
+        .unwrap();
+
+        let (path, text) = single_change(&edit);
+        assert_that!(path, eq("/locales/en/checkout.json"));
+        assert_that!(text, contains_substring("\"pay\": \"Pay\""));
+    }
+
+    #[rstest]
+    fn updates_existing_key() {
+        let db = I18nDatabaseImpl::default();
+        let translations = namespaced(&db);
+
+        let edit = compute_edit_translation(
+            &db,
+            &translations,
+            "en",
+            Some("common"),
+            "save",
+            "Keep",
+            ".",
+            true,
+        )
+        .unwrap();
+
+        let (path, text) = single_change(&edit);
+        assert_that!(path, eq("/locales/en/common.json"));
+        assert_that!(text, contains_substring("\"save\": \"Keep\""));
+    }
+
+    #[rstest]
+    fn ignores_namespace_without_namespaced_files() {
+        let db = I18nDatabaseImpl::default();
+        let translations = vec![translation(&db, "en", None, "/locales/en.json", &[], "{}")];
+
+        let edit = compute_edit_translation(
+            &db,
+            &translations,
+            "en",
+            Some("translation"),
+            "pay",
+            "Pay",
+            ".",
+            true,
+        )
+        .unwrap();
+
+        assert_that!(single_change(&edit).0, eq("/locales/en.json"));
+    }
+
+    #[rstest]
+    fn creates_missing_namespace_file() {
+        let db = I18nDatabaseImpl::default();
+        let translations = namespaced(&db);
+
+        let edit = compute_edit_translation(
+            &db,
+            &translations,
+            "ja",
+            Some("checkout"),
+            "pay",
+            "支払う",
+            ".",
+            true,
+        )
+        .unwrap();
+
+        let Some(DocumentChanges::Operations(operations)) = edit.document_changes else {
+            panic!("expected document change operations");
+        };
+        let uri = Url::from_file_path("/locales/ja/checkout.json").unwrap();
+        assert_that!(
+            operations[0],
+            eq(&DocumentChangeOperation::Op(ResourceOp::Create(CreateFile {
+                uri: uri.clone(),
+                options: None,
+                annotation_id: None,
+            })))
+        );
+        let DocumentChangeOperation::Edit(text_edit) = &operations[1] else {
+            panic!("expected text edit");
+        };
+        assert_that!(text_edit.text_document.uri, eq(&uri));
+        let OneOf::Left(edit) = &text_edit.edits[0] else {
+            panic!("expected plain text edit");
+        };
+        assert_that!(edit.new_text, contains_substring("\"pay\": \"支払う\""));
+    }
+
+    #[rstest]
+    fn reports_missing_file_when_client_cannot_create_files() {
+        let db = I18nDatabaseImpl::default();
+        let translations = namespaced(&db);
+
+        let result = compute_edit_translation(
+            &db,
+            &translations,
+            "ja",
+            Some("checkout"),
+            "pay",
+            "支払う",
+            ".",
+            false,
+        );
+
+        assert_that!(
+            result,
+            err(matches_pattern!(EditTranslationError::CreateFileUnsupported {
+                path: eq(&PathBuf::from("/locales/ja/checkout.json"))
+            }))
+        );
+    }
+
+    #[rstest]
+    fn edits_existing_file_when_client_cannot_create_files() {
+        let db = I18nDatabaseImpl::default();
+        let translations = namespaced(&db);
+
+        let edit = compute_edit_translation(
+            &db,
+            &translations,
+            "ja",
+            Some("common"),
+            "save",
+            "保存",
+            ".",
+            false,
+        )
+        .unwrap();
+
+        assert_that!(single_change(&edit).0, eq("/locales/ja/common.json"));
+    }
+
+    #[rstest]
+    #[case::yaml("/locales/ja/checkout.yaml", "pay: Pay")]
+    #[case::po("/locales/ja/checkout.po", "msgid \"pay\"\nmsgstr \"Pay\"")]
+    fn creates_missing_file_in_format_of_related_file(
+        #[case] related: &str,
+        #[case] expected: &str,
+    ) {
+        let db = I18nDatabaseImpl::default();
+        let translations = vec![translation(&db, "ja", Some("checkout"), related, &[], "")];
+
+        let edit = compute_edit_translation(
+            &db,
+            &translations,
+            "en",
+            Some("checkout"),
+            "pay",
+            "Pay",
+            ".",
+            true,
+        )
+        .unwrap();
+
+        let Some(DocumentChanges::Operations(operations)) = edit.document_changes else {
+            panic!("expected document change operations");
+        };
+        let DocumentChangeOperation::Edit(text_edit) = &operations[1] else {
+            panic!("expected text edit");
+        };
+        let OneOf::Left(edit) = &text_edit.edits[0] else {
+            panic!("expected plain text edit");
+        };
+        assert_that!(edit.new_text, contains_substring(expected));
+    }
+
+    #[rstest]
+    #[case::same_namespace_file_per_namespace(
+        &[("ja", "checkout", "/locales/ja/checkout.json")],
+        "/locales/en/checkout.json"
+    )]
+    #[case::same_namespace_directory_per_namespace(
+        &[("ja", "checkout", "/locales/checkout/ja.yaml")],
+        "/locales/checkout/en.yaml"
+    )]
+    #[case::same_language(&[("en", "common", "/locales/en/common.json")], "/locales/en/checkout.json")]
+    #[case::same_language_directory_per_namespace(
+        &[("en", "common", "/locales/common/en.json")],
+        "/locales/checkout/en.json"
+    )]
+    fn conventional_path_follows_existing_layout(
+        #[case] files: &[(&str, &str, &str)],
+        #[case] expected: &str,
+    ) {
+        let db = I18nDatabaseImpl::default();
+        let translations: Vec<Translation> = files
+            .iter()
+            .map(|(language, namespace, path)| {
+                translation(&db, language, Some(namespace), path, &[], "{}")
+            })
+            .collect();
+
+        let path = conventional_translation_path(&db, &translations, "en", "checkout");
+
+        assert_that!(path, some(eq(&PathBuf::from(expected))));
+    }
+
+    #[rstest]
+    fn conventional_path_requires_related_file() {
+        let db = I18nDatabaseImpl::default();
+        let translations =
+            vec![translation(&db, "ja", Some("common"), "/locales/ja/common.json", &[], "{}")];
+
+        let path = conventional_translation_path(&db, &translations, "en", "checkout");
+
+        assert_that!(path, none());
+    }
+}
@@ -0,0 +1,1092 @@
+//! Extract a hardcoded string into a translation key.
+//!
+//! Turns a string literal, JSX text or JSX attribute string into a `t('key')` call, adding the
+//! text to the primary language's translation file and empty placeholders to the other
+//! languages. When no translation function is in scope of a React component or hook, the
+//! `useTranslation` (react-i18next) or `useTranslations` (next-intl) hook is added.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use tower_lsp::lsp_types::{
+    CodeAction,
+    CodeActionKind,
+    CodeActionOrCommand,
+    TextEdit,
+    Url,
+    WorkspaceEdit,
+};
+use tree_sitter::{
+    Node,
+    Point,
+};
+
+use crate::db::I18nDatabase;
+use crate::ide::code_actions::{
+    create_full_file_text_edit,
+    insert_key_to_text,
+};
+use crate::ide::key_match::is_child_key;
+use crate::input::source::{
+    ProgrammingLanguage,
+    SourceFile,
+};
+use crate::input::translation::Translation;
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+    SourcePosition,
+    SourceRange,
+};
+
+/// Maximum number of words of the text used in a proposed key.
+const MAX_KEY_WORDS: usize = 4;
+
+/// Library whose hook is added when no translation function is in scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HookLibrary {
+    /// `const { t } = useTranslation();` from `react-i18next`
+    #[default]
+    ReactI18next,
+    /// `const t = useTranslations();` from `next-intl`
+    NextIntl,
+}
+
+impl HookLibrary {
+    /// Picks the library imported by the file, else the one the nearest `package.json` up to
+    /// `workspace_root` depends on, defaulting to react-i18next.
+    fn detect(
+        program: Node<'_>,
+        text: &str,
+        file_path: Option<&Path>,
+        workspace_root: Option<&Path>,
+    ) -> Self {
+        Self::from_imports(program, text)
+            .or_else(|| file_path.and_then(|path| Self::from_package_jsons(path, workspace_root)))
+            .unwrap_or_default()
+    }
+
+    /// The library of the first import from `react-i18next` or `next-intl` (or a submodule).
+    fn from_imports(program: Node<'_>, text: &str) -> Option<Self> {
+        let mut cursor = program.walk();
+        program.named_children(&mut cursor).filter(|s| s.kind() == "import_statement").find_map(
+            |import| {
+                let source =
+                    import.child_by_field_name("source")?.utf8_text(text.as_bytes()).ok()?;
+                let module = source.trim_matches(['"', '\'']);
+                [Self::ReactI18next, Self::NextIntl].into_iter().find(|library| {
+                    module
+                        .strip_prefix(library.module())
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+                })
+            },
+        )
+    }
+
+    /// Walks up from `file_path` to the first `package.json` depending on either library.
+    ///
+    /// Dependencies may be hoisted to the root of a monorepo, so directories above the nearest
+    /// `package.json` are searched too. Stops at `workspace_root` (inclusive) when given.
+    fn from_package_jsons(file_path: &Path, workspace_root: Option<&Path>) -> Option<Self> {
+        let mut dir = file_path.parent();
+        while let Some(d) = dir {
+            let library = std::fs::read_to_string(d.join("package.json"))
+                .ok()
+                .and_then(|content| Self::from_package_json(&content));
+            if library.is_some() {
+                return library;
+            }
+            if Some(d) == workspace_root {
+                break;
+            }
+            dir = d.parent();
+        }
+        None
+    }
+
+    /// The library listed in the dependencies of a `package.json`, preferring next-intl.
+    fn from_package_json(content: &str) -> Option<Self> {
+        let package: serde_json::Value = serde_json::from_str(content).ok()?;
+        let depends_on = |library: Self| {
+            ["dependencies", "devDependencies", "peerDependencies"].iter().any(|field| {
+                package.get(field).and_then(|deps| deps.get(library.module())).is_some()
+            })
+        };
+        [Self::NextIntl, Self::ReactI18next].into_iter().find(|library| depends_on(*library))
+    }
+
+    const fn hook(self) -> &'static str {
+        match self {
+            Self::ReactI18next => "useTranslation",
+            Self::NextIntl => "useTranslations",
+        }
+    }
+
+    const fn module(self) -> &'static str {
+        match self {
+            Self::ReactI18next => "react-i18next",
+            Self::NextIntl => "next-intl",
+        }
+    }
+
+    const fn declaration(self) -> &'static str {
+        match self {
+            Self::ReactI18next => "const { t } = useTranslation();",
+            Self::NextIntl => "const t = useTranslations();",
+        }
+    }
+}
+
+/// Hooks whose result provides a translation function.
+const TRANS_FN_HOOKS: &[&str] = &["useTranslation", "useTranslations", "getTranslations"];
+
+/// Settings that affect the extracted key and edits.
+#[derive(Debug, Clone, Copy)]
+pub struct ExtractOptions<'a> {
+    pub key_separator: &'a str,
+    pub default_namespace: Option<&'a str>,
+    /// Bounds the `package.json` lookup that picks the library of an added hook
+    pub workspace_root: Option<&'a Path>,
+    pub encoding: PositionEncoding,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TargetKind {
+    StringLiteral,
+    JsxText,
+    JsxAttribute,
+    VueText,
+    VueAttribute,
+    SvelteText,
+    SvelteAttribute,
+}
+
+/// A hardcoded string that can be extracted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ExtractTarget {
+    kind: TargetKind,
+    /// Range replaced by the translation call
+    range: SourceRange,
+    /// Text stored as the translation value
+    text: String,
+    /// Quote character of the string literal, if any
+    quote: Option<char>,
+}
+
+/// Translation function available at the extracted string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TransFnInScope {
+    name: String,
+    /// Namespace passed to `useTranslation('ns')`
+    namespace: Option<String>,
+    /// Key prefix passed to next-intl's `useTranslations('Prefix')`
+    key_prefix: Option<String>,
+}
+
+/// A string to extract and how to rewrite its source file.
+struct Extraction {
+    target: ExtractTarget,
+    trans_fn: Option<TransFnInScope>,
+    /// Enclosing component or function, used in the proposed key
+    component: Option<String>,
+    /// Insertions besides the replacement (hook declaration and import, Vue `:` binding)
+    insertions: Vec<(SourcePosition, String)>,
+}
+
+/// Generates the extract refactoring for the string at `position`, if any.
+///
+/// `translations` are the translation files that may receive the key and `languages` the
+/// available languages with the primary language first.
+#[must_use]
+pub fn generate_extract_code_action(
+    db: &dyn I18nDatabase,
+    source_file: SourceFile,
+    uri: &Url,
+    position: SourcePosition,
+    translations: &[Translation],
+    languages: &[String],
+    options: &ExtractOptions<'_>,
+) -> Option<CodeActionOrCommand> {
+    let language = source_file.language(db);
+    let text = source_file.text(db);
+    let Extraction { target, trans_fn, component, insertions } =
+        if matches!(language, ProgrammingLanguage::Svelte | ProgrammingLanguage::Vue) {
+            find_in_template(text, language, position)?
+        } else {
+            let file_path = uri.to_file_path().ok();
+            find_in_script(text, language, position, file_path.as_deref(), options.workspace_root)?
+        };
+
+    let key_prefix = trans_fn.as_ref().and_then(|f| f.key_prefix.as_deref());
+    let namespace =
+        trans_fn.as_ref().and_then(|f| f.namespace.as_deref()).or(options.default_namespace);
+    let targets = target_translations(db, translations, languages, namespace);
+    let (_, primary) = targets.first().copied()?;
+
+    let base_key = if key_prefix.is_some() {
+        key_suffix(&target.text)
+    } else {
+        let file_path = uri.to_file_path().ok()?;
+        propose_key(&file_path, component.as_deref(), &target.text, options.key_separator)
+    };
+    let full_key = |key: &str| {
+        key_prefix.map_or_else(|| key.to_string(), |p| format!("{p}{}{key}", options.key_separator))
+    };
+    let call_key =
+        available_key(primary.keys(db), &base_key, &target.text, &full_key, options.key_separator);
+    let key = full_key(&call_key);
+
+    let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+    for (index, (_, translation)) in targets.iter().enumerate() {
+        if translation.keys(db).contains_key(&key) {
+            continue;
+        }
+        let value = if index == 0 { target.text.as_str() } else { "" };
+        let json_text = translation.json_text(db);
+        let result = insert_key_to_text(
+            translation.format(db),
+            json_text,
+            &key,
+            value,
+            options.key_separator,
+        )?;
+        let uri = Url::from_file_path(translation.file_path(db)).ok()?;
+        changes
+            .entry(uri)
+            .or_default()
+            .push(create_full_file_text_edit(json_text, result.new_text));
+    }
+
+    let line_index = LineIndex::new(text, options.encoding);
+    let fn_name = trans_fn.as_ref().map_or("t", |f| f.name.as_str());
+    let quote = target.quote.unwrap_or_else(|| preferred_quote(text));
+    let call = format!("{fn_name}({quote}{call_key}{quote})");
+    let replacement = match target.kind {
+        TargetKind::StringLiteral => call,
+        TargetKind::JsxText
+        | TargetKind::JsxAttribute
+        | TargetKind::SvelteText
+        | TargetKind::SvelteAttribute => format!("{{{call}}}"),
+        TargetKind::VueText => format!("{{{{ {call} }}}}"),
+        TargetKind::VueAttribute => format!("\"{call}\""),
+    };
+    let mut source_edits =
+        vec![TextEdit { range: line_index.to_lsp_range(target.range), new_text: replacement }];
+    source_edits.extend(insertions.into_iter().map(|(position, new_text)| {
+        let position = line_index.to_lsp_position(position);
+        TextEdit { range: tower_lsp::lsp_types::Range { start: position, end: position }, new_text }
+    }));
+    changes.entry(uri.clone()).or_default().extend(source_edits);
+
+    Some(CodeActionOrCommand::CodeAction(CodeAction {
+        title: format!("Extract to translation key '{key}'"),
+        kind: Some(CodeActionKind::REFACTOR_EXTRACT),
+        edit: Some(WorkspaceEdit { changes: Some(changes), ..Default::default() }),
+        ..Default::default()
+    }))
+}
+
+/// Finds a string literal, JSX text or JSX attribute string at `position` in JS/TS code.
+fn find_in_script(
+    text: &str,
+    language: ProgrammingLanguage,
+    position: SourcePosition,
+    file_path: Option<&Path>,
+    workspace_root: Option<&Path>,
+) -> Option<Extraction> {
+    let mut parser = tree_sitter::Parser::new();
+    parser.set_language(&language.tree_sitter_language()).ok()?;
+    let tree = parser.parse(text, None)?;
+    let point = Point { row: position.line as usize, column: position.character as usize };
+    let node = tree.root_node().descendant_for_point_range(point, point)?;
+
+    let target = find_extract_target(node, text)?;
+    let trans_fn = find_trans_fn_in_scope(node, text);
+    let insertions = if trans_fn.is_none() {
+        hook_edits(node, text, |program| {
+            HookLibrary::detect(program, text, file_path, workspace_root)
+        })
+    } else {
+        Vec::new()
+    };
+    Some(Extraction {
+        target,
+        trans_fn,
+        component: component_name(node, text).map(str::to_string),
+        insertions,
+    })
+}
+
+/// Finds template text or a static attribute value at `position` in a Vue or Svelte file.
+///
+/// Templates use the globally available `$t` (vue-i18n, svelte-i18n), or `$_` in Svelte files
+/// that already use it.
+fn find_in_template(
+    text: &str,
+    language: ProgrammingLanguage,
+    position: SourcePosition,
+) -> Option<Extraction> {
+    let string = crate::syntax::hardcoded::find_hardcoded_strings(text, language)
+        .into_iter()
+        .find(|string| string.range.contains(position))?;
+
+    let is_vue = language == ProgrammingLanguage::Vue;
+    let kind = match (&string.attribute, is_vue) {
+        (None, true) => TargetKind::VueText,
+        (None, false) => TargetKind::SvelteText,
+        (Some(_), true) => TargetKind::VueAttribute,
+        (Some(_), false) => TargetKind::SvelteAttribute,
+    };
+    // `alt="Close"` becomes `:alt="$t('key')"`
+    let insertions = string
+        .attribute
+        .as_ref()
+        .filter(|_| is_vue)
+        .map(|attribute| (attribute.name_range.start, ":".to_string()))
+        .into_iter()
+        .collect();
+    let name = if !is_vue && text.contains("$_(") { "$_" } else { "$t" };
+
+    Some(Extraction {
+        target: ExtractTarget { kind, range: string.range, text: string.text, quote: Some('\'') },
+        trans_fn: Some(TransFnInScope {
+            name: name.to_string(),
+            namespace: None,
+            key_prefix: None,
+        }),
+        component: None,
+        insertions,
+    })
+}
+
+/// Finds the string literal, JSX text or JSX attribute string containing `node`.
+fn find_extract_target(node: Node<'_>, text: &str) -> Option<ExtractTarget> {
+    let node = if node.kind() == "string_fragment" || matches!(node.kind(), "\"" | "'") {
+        node.parent()?
+    } else {
+        node
+    };
+
+    match node.kind() {
+        "jsx_text" => {
+            let raw = node.utf8_text(text.as_bytes()).ok()?;
+            let leading = raw.len() - raw.trim_start().len();
+            let trimmed = raw.trim();
+            let start = node.start_byte() + leading;
+            let value = trimmed.split_whitespace().collect::<Vec<_>>().join(" ");
+            is_user_facing(&value).then(|| ExtractTarget {
+                kind: TargetKind::JsxText,
+                range: SourceRange {
+                    start: position_at(text, start),
+                    end: position_at(text, start + trimmed.len()),
+                },
+                text: value,
+                quote: None,
+            })
+        }
+        "string" => {
+            let parent = node.parent()?;
+            if matches!(parent.kind(), "import_statement" | "export_statement")
+                || is_require_or_trans_fn_argument(parent, text)
+                || parent.kind() == "pair" && parent.child_by_field_name("key") == Some(node)
+            {
+                return None;
+            }
+            let raw = node.utf8_text(text.as_bytes()).ok()?;
+            let quote = raw.chars().next()?;
+            let value = raw.get(1..raw.len().checked_sub(1)?)?;
+            if value.contains('\\') || !is_user_facing(value) {
+                return None;
+            }
+            let kind = if parent.kind() == "jsx_attribute" {
+                TargetKind::JsxAttribute
+            } else {
+                TargetKind::StringLiteral
+            };
+            Some(ExtractTarget {
+                kind,
+                range: SourceRange::from_node(&node),
+                text: value.to_string(),
+                quote: (kind == TargetKind::StringLiteral).then_some(quote),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn is_user_facing(value: &str) -> bool {
+    value.chars().any(char::is_alphabetic)
+}
+
+/// Returns true for strings passed to `require()` or a translation function.
+fn is_require_or_trans_fn_argument(parent: Node<'_>, text: &str) -> bool {
+    if parent.kind() != "arguments" {
+        return false;
+    }
+    let Some(callee) = parent.parent().and_then(|call| call.child_by_field_name("function")) else {
+        return false;
+    };
+    let name = callee.utf8_text(text.as_bytes()).unwrap_or_default();
+    let name = name.rsplit('.').next().unwrap_or(name);
+    matches!(name, "require" | "t" | "$t" | "$_" | "formatMessage")
+        || TRANS_FN_HOOKS.contains(&name)
+}
+
+/// Converts a byte offset into a source position.
+#[allow(clippy::cast_possible_truncation)]
+fn position_at(text: &str, offset: usize) -> SourcePosition {
+    let before = text.get(..offset).unwrap_or(text);
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    SourcePosition { line: line as u32, character: (offset - line_start) as u32 }
+}
+
+/// Returns the name of the nearest named function, class or function variable around `node`.
+fn component_name<'a>(node: Node<'_>, text: &'a str) -> Option<&'a str> {
+    std::iter::successors(node.parent(), Node::parent).find_map(|ancestor| {
+        let name = match ancestor.kind() {
+            "function_declaration" | "class_declaration" => ancestor.child_by_field_name("name"),
+            "variable_declarator" => ancestor
+                .child_by_field_name("value")
+                .filter(|value| matches!(value.kind(), "arrow_function" | "function_expression"))
+                .and_then(|_| ancestor.child_by_field_name("name")),
+            _ => None,
+        }?;
+        name.utf8_text(text.as_bytes()).ok()
+    })
+}
+
+/// Proposes a key from the component (or file) name and the text, e.g. `settings.saveChanges`.
+fn propose_key(file_path: &Path, component: Option<&str>, text: &str, separator: &str) -> String {
+    let prefix = component.map(lower_camel).filter(|p| !p.is_empty()).or_else(|| {
+        let stem = file_path.file_stem()?.to_str()?;
+        // SvelteKit route files are named `+page.svelte` and `+layout.svelte`
+        let stem = if matches!(stem.trim_start_matches('+'), "index" | "page" | "layout") {
+            file_path.parent()?.file_name()?.to_str()?
+        } else {
+            stem
+        };
+        Some(lower_camel(stem)).filter(|p| !p.is_empty())
+    });
+    let suffix = key_suffix(text);
+    prefix.map_or_else(|| suffix.clone(), |prefix| format!("{prefix}{separator}{suffix}"))
+}
+
+/// Derives the last key segment from the first ASCII words of the text.
+fn key_suffix(text: &str) -> String {
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .take(MAX_KEY_WORDS)
+        .collect();
+    let suffix = lower_camel(&words.join(" "));
+    if suffix.is_empty() || suffix.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("text{suffix}")
+    } else {
+        suffix
+    }
+}
+
+/// Converts `Save changes`, `save-changes` or `SaveChanges` to `saveChanges`.
+fn lower_camel(text: &str) -> String {
+    let mut result = String::new();
+    for word in text.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty()) {
+        let mut chars = word.chars();
+        let Some(first) = chars.next() else {
+            continue;
+        };
+        if result.is_empty() {
+            result.push(first.to_ascii_lowercase());
+        } else {
+            result.push(first.to_ascii_uppercase());
+        }
+        let rest: String = chars.collect();
+        // Keep camelCase words, but lowercase all-caps words ("SAVE" -> "save")
+        if rest.chars().all(|c| !c.is_ascii_lowercase()) {
+            result.push_str(&rest.to_ascii_lowercase());
+        } else {
+            result.push_str(&rest);
+        }
+    }
+    result
+}
+
+/// Returns `key`, or `key2`, `key3`, ... if it is already taken by a different value.
+fn available_key(
+    keys: &HashMap<String, String>,
+    key: &str,
+    value: &str,
+    full_key: &dyn Fn(&str) -> String,
+    separator: &str,
+) -> String {
+    // Bounded because a string value at a parent key (`a` for `a.save`) blocks every candidate
+    (1..=keys.len() + 1)
+        .map(|n| if n == 1 { key.to_string() } else { format!("{key}{n}") })
+        .find(|candidate| {
+            let full = full_key(candidate);
+            keys.get(&full).map_or_else(
+                || {
+                    !keys.keys().any(|k| {
+                        is_child_key(k, &full, separator) || is_child_key(&full, k, separator)
+                    })
+                },
+                |existing| existing == value,
+            )
+        })
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Picks one translation file per language, in the order of `languages`.
+fn target_translations<'a>(
+    db: &dyn I18nDatabase,
+    translations: &'a [Translation],
+    languages: &'a [String],
+    namespace: Option<&str>,
+) -> Vec<(&'a str, &'a Translation)> {
+    languages
+        .iter()
+        .filter_map(|language| {
+            let candidates: Vec<&Translation> = translations
+                .iter()
+                .filter(|t| !t.is_component_local(db) && t.language(db) == *language)
+                .collect();
+            let translation = candidates
+                .iter()
+                .find(|t| namespace.is_some() && t.namespace(db).as_deref() == namespace)
+                .or_else(|| candidates.first())?;
+            Some((language.as_str(), *translation))
+        })
+        .collect()
+}
+
+/// Finds a translation function declared by a hook in an enclosing function, or imported
+/// from i18next.
+fn find_trans_fn_in_scope(node: Node<'_>, text: &str) -> Option<TransFnInScope> {
+    for ancestor in std::iter::successors(node.parent(), Node::parent) {
+        let body = match ancestor.kind() {
+            "function_declaration"
+            | "function_expression"
+            | "arrow_function"
+            | "method_definition" => ancestor.child_by_field_name("body"),
+            "program" => {
+                return imports_global_t(ancestor, text).then(|| TransFnInScope {
+                    name: "t".to_string(),
+                    namespace: None,
+                    key_prefix: None,
+                });
+            }
+            _ => None,
+        };
+        let Some(body) = body.filter(|b| b.kind() == "statement_block") else {
+            continue;
+        };
+        let mut cursor = body.walk();
+        for statement in body.named_children(&mut cursor) {
+            if statement.start_byte() > node.start_byte() {
+                break;
+            }
+            if let Some(trans_fn) = trans_fn_from_declaration(statement, text) {
+                return Some(trans_fn);
+            }
+        }
+    }
+    None
+}
+
+/// Parses `const { t } = useTranslation('ns')` or `const t = useTranslations('Prefix')`.
+fn trans_fn_from_declaration(statement: Node<'_>, text: &str) -> Option<TransFnInScope> {
+    if !matches!(statement.kind(), "lexical_declaration" | "variable_declaration") {
+        return None;
+    }
+    let mut cursor = statement.walk();
+    statement.named_children(&mut cursor).find_map(|declarator| {
+        let mut value = declarator.child_by_field_name("value")?;
+        if value.kind() == "await_expression" {
+            value = value.named_child(0)?;
+        }
+        if value.kind() != "call_expression" {
+            return None;
+        }
+        let hook = value.child_by_field_name("function")?.utf8_text(text.as_bytes()).ok()?;
+        if !TRANS_FN_HOOKS.contains(&hook) {
+            return None;
+        }
+        let first_arg = value
+            .child_by_field_name("arguments")
+            .and_then(|args| args.named_child(0))
+            .filter(|arg| arg.kind() == "string")
+            .and_then(|arg| arg.utf8_text(text.as_bytes()).ok())
+            .map(|arg| arg.trim_matches(['"', '\'']).to_string());
+
+        let name_node = declarator.child_by_field_name("name")?;
+        let name = match name_node.kind() {
+            "identifier" => name_node.utf8_text(text.as_bytes()).ok()?.to_string(),
+            "object_pattern" => t_from_object_pattern(name_node, text)?,
+            _ => return None,
+        };
+        let (namespace, key_prefix) =
+            if hook == "useTranslation" { (first_arg, None) } else { (None, first_arg) };
+        Some(TransFnInScope { name, namespace, key_prefix })
+    })
+}
+
+/// Returns the local name bound to `t` in `{ t }` or `{ t: translate }`.
+fn t_from_object_pattern(pattern: Node<'_>, text: &str) -> Option<String> {
+    let mut cursor = pattern.walk();
+    pattern.named_children(&mut cursor).find_map(|property| match property.kind() {
+        "shorthand_property_identifier_pattern" => {
+            let name = property.utf8_text(text.as_bytes()).ok()?;
+            (name == "t").then(|| name.to_string())
+        }
+        "pair_pattern" => {
+            let key = property.child_by_field_name("key")?.utf8_text(text.as_bytes()).ok()?;
+            let value = property.child_by_field_name("value")?;
+            (key == "t" && value.kind() == "identifier")
+                .then(|| value.utf8_text(text.as_bytes()).ok().map(str::to_string))
+                .flatten()
+        }
+        _ => None,
+    })
+}
+
+/// Returns true if the program has `import { t } from 'i18next'`.
+fn imports_global_t(program: Node<'_>, text: &str) -> bool {
+    let mut cursor = program.walk();
+    program.named_children(&mut cursor).filter(|s| s.kind() == "import_statement").any(|import| {
+        let source = import
+            .child_by_field_name("source")
+            .and_then(|s| s.utf8_text(text.as_bytes()).ok())
+            .unwrap_or_default();
+        let clause = import.utf8_text(text.as_bytes()).unwrap_or_default();
+        source.trim_matches(['"', '\'']) == "i18next"
+            && clause.split(['{', '}', ',']).any(|specifier| specifier.trim() == "t")
+    })
+}
+
+/// Edits that declare the hook in the enclosing component (or custom hook) and import it.
+///
+/// Returns no edits outside of components, or when the component has an expression body.
+/// The library is only detected once a component body is found.
+fn hook_edits(
+    node: Node<'_>,
+    text: &str,
+    detect_library: impl FnOnce(Node<'_>) -> HookLibrary,
+) -> Vec<(SourcePosition, String)> {
+    let Some(body) = enclosing_component_body(node, text) else {
+        return Vec::new();
+    };
+    let program = std::iter::successors(Some(node), Node::parent).last().unwrap_or(node);
+    let library = detect_library(program);
+
+    let mut cursor = body.walk();
+    let first_statement = body.named_children(&mut cursor).next();
+    let indent = first_statement.map_or_else(
+        || {
+            let line_start =
+                text.get(..body.start_byte()).and_then(|t| t.rfind('\n')).map_or(0, |i| i + 1);
+            let line = text.get(line_start..).unwrap_or_default();
+            let indent: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+            format!("{indent}  ")
+        },
+        |statement| {
+            let line_start =
+                text.get(..statement.start_byte()).and_then(|t| t.rfind('\n')).map_or(0, |i| i + 1);
+            text.get(line_start..statement.start_byte()).unwrap_or_default().to_string()
+        },
+    );
+    let mut edits = vec![(
+        position_at(text, body.start_byte() + 1),
+        format!("\n{indent}{}", library.declaration()),
+    )];
+
+    if !imports_hook(program, text, library) {
+        edits.push(import_edit(program, text, library));
+    }
+    edits
+}
+
+/// Returns the block body of the nearest function named like a component (`Settings`) or
+/// hook (`useSettings`).
+fn enclosing_component_body<'a>(node: Node<'a>, text: &str) -> Option<Node<'a>> {
+    std::iter::successors(node.parent(), Node::parent).find_map(|ancestor| {
+        let (name, function) = match ancestor.kind() {
+            "function_declaration" => (ancestor.child_by_field_name("name")?, ancestor),
+            "variable_declarator" => {
+                let value = ancestor.child_by_field_name("value")?;
+                if !matches!(value.kind(), "arrow_function" | "function_expression") {
+                    return None;
+                }
+                (ancestor.child_by_field_name("name")?, value)
+            }
+            _ => return None,
+        };
+        let name = name.utf8_text(text.as_bytes()).ok()?;
+        let is_component = name.starts_with(|c: char| c.is_ascii_uppercase())
+            || name
+                .strip_prefix("use")
+                .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()));
+        if !is_component {
+            return None;
+        }
+        function.child_by_field_name("body").filter(|body| body.kind() == "statement_block")
+    })
+}
+
+fn imports_hook(program: Node<'_>, text: &str, library: HookLibrary) -> bool {
+    let mut cursor = program.walk();
+    program.named_children(&mut cursor).filter(|s| s.kind() == "import_statement").any(|import| {
+        import
+            .utf8_text(text.as_bytes())
+            .unwrap_or_default()
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .any(|word| word == library.hook())
+    })
+}
+
+/// Inserts the hook import after the last import, or after leading directives (`'use client'`).
+fn import_edit(program: Node<'_>, text: &str, library: HookLibrary) -> (SourcePosition, String) {
+    let quote = preferred_quote(text);
+    let import =
+        format!("import {{ {} }} from {quote}{}{quote};", library.hook(), library.module());
+
+    let mut cursor = program.walk();
+    let statements: Vec<Node<'_>> = program.named_children(&mut cursor).collect();
+    let anchor = statements.iter().rev().find(|s| s.kind() == "import_statement").or_else(|| {
+        statements
+            .iter()
+            .take_while(|s| {
+                s.kind() == "expression_statement"
+                    && s.named_child(0).is_some_and(|expr| expr.kind() == "string")
+            })
+            .last()
+    });
+    anchor.map_or_else(
+        || (SourcePosition { line: 0, character: 0 }, format!("{import}\n")),
+        |anchor| (position_at(text, anchor.end_byte()), format!("\n{import}")),
+    )
+}
+
+/// Returns the quote character used by most imports, defaulting to `'`.
+fn preferred_quote(text: &str) -> char {
+    if text.matches("from \"").count() > text.matches("from '").count() { '"' } else { '\'' }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing, clippy::panic)]
+mod tests {
+    use std::collections::HashMap;
+
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+    use crate::db::I18nDatabaseImpl;
+    use crate::input::translation::load_translation_from_content;
+
+    const OPTIONS: ExtractOptions<'static> = ExtractOptions {
+        key_separator: ".",
+        default_namespace: None,
+        workspace_root: None,
+        encoding: PositionEncoding::Utf16,
+    };
+
+    struct Fixture {
+        db: I18nDatabaseImpl,
+        source_file: SourceFile,
+        translations: Vec<Translation>,
+    }
+
+    impl Fixture {
+        fn new(path: &str, source: &str, en: &str) -> Self {
+            let db = I18nDatabaseImpl::default();
+            let language = ProgrammingLanguage::from_uri(path).unwrap();
+            let uri = Url::from_file_path(path).unwrap();
+            let source_file = SourceFile::new(&db, uri.to_string(), source.to_string(), language);
+            let translations = vec![
+                load_translation_from_content(&db, Path::new("/app/locales/en.json"), en, ".")
+                    .unwrap(),
+                load_translation_from_content(&db, Path::new("/app/locales/ja.json"), "{}", ".")
+                    .unwrap(),
+            ];
+            Self { db, source_file, translations }
+        }
+
+        /// Runs the action at the first occurrence of `needle` and returns the title and the
+        /// edited source and translation texts.
+        fn extract(&self, needle: &str) -> Option<(String, HashMap<String, String>)> {
+            let text = self.source_file.text(&self.db);
+            let position = position_at(text, text.find(needle).unwrap());
+            let uri = Url::parse(self.source_file.uri(&self.db)).unwrap();
+            let languages = vec!["en".to_string(), "ja".to_string()];
+            let CodeActionOrCommand::CodeAction(action) = generate_extract_code_action(
+                &self.db,
+                self.source_file,
+                &uri,
+                position,
+                &self.translations,
+                &languages,
+                &OPTIONS,
+            )?
+            else {
+                panic!("expected a code action");
+            };
+
+            let changes = action.edit.unwrap().changes.unwrap();
+            let files = changes
+                .into_iter()
+                .map(|(uri, edits)| {
+                    let path = uri.to_file_path().unwrap().to_string_lossy().to_string();
+                    let original = if uri.as_str() == self.source_file.uri(&self.db) {
+                        text.clone()
+                    } else {
+                        self.translations
+                            .iter()
+                            .find(|t| *t.file_path(&self.db) == path)
+                            .map(|t| t.json_text(&self.db).clone())
+                            .unwrap()
+                    };
+                    (path, apply_edits(&original, edits))
+                })
+                .collect();
+            Some((action.title, files))
+        }
+    }
+
+    fn apply_edits(text: &str, mut edits: Vec<TextEdit>) -> String {
+        let line_index = LineIndex::new(text, PositionEncoding::Utf8);
+        let offset = |position| {
+            let position = line_index.to_source_position(position);
+            let line_start: usize =
+                text.split_inclusive('\n').take(position.line as usize).map(str::len).sum();
+            line_start + position.character as usize
+        };
+        edits.sort_by_key(|edit| {
+            std::cmp::Reverse((edit.range.start.line, edit.range.start.character))
+        });
+        let mut result = text.to_string();
+        for edit in edits {
+            let start = offset(edit.range.start);
+            let end = offset(edit.range.end).min(result.len());
+            result.replace_range(start..end, &edit.new_text);
+        }
+        result
+    }
+
+    #[rstest]
+    fn extracts_jsx_text_and_adds_hook() {
+        let fixture = Fixture::new(
+            "/app/src/settings.tsx",
+            "import React from 'react';\n\nexport function Settings() {\n  return <p>Save changes</p>;\n}\n",
+            "{}",
+        );
+
+        let (title, files) = fixture.extract("Save changes").unwrap();
+
+        assert_that!(title, eq("Extract to translation key 'settings.saveChanges'"));
+        assert_that!(
+            files["/app/src/settings.tsx"],
+            eq("import React from 'react';\nimport { useTranslation } from 'react-i18next';\n\n\
+                export function Settings() {\n  const { t } = useTranslation();\n  \
+                return <p>{t('settings.saveChanges')}</p>;\n}\n")
+        );
+        assert_that!(
+            files["/app/locales/en.json"],
+            eq("{\n  \"settings\": {\n    \"saveChanges\": \"Save changes\"\n  }\n}")
+        );
+        assert_that!(
+            files["/app/locales/ja.json"],
+            eq("{\n  \"settings\": {\n    \"saveChanges\": \"\"\n  }\n}")
+        );
+    }
+
+    #[rstest]
+    fn extracts_string_literal_using_trans_fn_in_scope() {
+        let fixture = Fixture::new(
+            "/app/src/Toolbar.tsx",
+            "const Toolbar = () => {\n  const { t: translate } = useTranslation();\n  \
+             const label = \"Undo\";\n  return <button>{label}</button>;\n};\n",
+            "{}",
+        );
+
+        let (_, files) = fixture.extract("Undo").unwrap();
+
+        assert_that!(
+            files["/app/src/Toolbar.tsx"],
+            contains_substring("const label = translate(\"toolbar.undo\");")
+        );
+        assert_that!(
+            files["/app/src/Toolbar.tsx"],
+            not(contains_substring("useTranslation();\n  const { t }"))
+        );
+    }
+
+    #[rstest]
+    fn extracts_jsx_attribute_with_next_intl_prefix() {
+        let fixture = Fixture::new(
+            "/app/src/search.tsx",
+            "export default function Search() {\n  const t = useTranslations('Search');\n  \
+             return <input placeholder=\"Find items\" />;\n}\n",
+            "{}",
+        );
+
+        let (title, files) = fixture.extract("Find items").unwrap();
+
+        assert_that!(title, eq("Extract to translation key 'Search.findItems'"));
+        assert_that!(
+            files["/app/src/search.tsx"],
+            contains_substring("<input placeholder={t('findItems')} />")
+        );
+    }
+
+    #[rstest]
+    fn extracts_vue_template_text_and_attribute() {
+        let fixture = Fixture::new(
+            "/app/src/SettingsPanel.vue",
+            "<template>\n  <img alt=\"Company logo\" />\n  <p>Save changes</p>\n</template>\n",
+            "{}",
+        );
+
+        let (text_title, text_files) = fixture.extract("Save changes").unwrap();
+        let (_, attribute_files) = fixture.extract("Company logo").unwrap();
+
+        assert_that!(text_title, eq("Extract to translation key 'settingsPanel.saveChanges'"));
+        assert_that!(
+            text_files["/app/src/SettingsPanel.vue"],
+            contains_substring("<p>{{ $t('settingsPanel.saveChanges') }}</p>")
+        );
+        assert_that!(
+            attribute_files["/app/src/SettingsPanel.vue"],
+            contains_substring("<img :alt=\"$t('settingsPanel.companyLogo')\" />")
+        );
+    }
+
+    #[rstest]
+    fn extracts_svelte_template_text_with_existing_trans_fn() {
+        let fixture = Fixture::new(
+            "/app/src/routes/+page.svelte",
+            "<h1>{$_('title')}</h1>\n<p title=\"More info\">Welcome back</p>\n",
+            "{}",
+        );
+
+        let (_, text_files) = fixture.extract("Welcome back").unwrap();
+        let (_, attribute_files) = fixture.extract("More info").unwrap();
+
+        assert_that!(
+            text_files["/app/src/routes/+page.svelte"],
+            contains_substring(">{$_('routes.welcomeBack')}</p>")
+        );
+        assert_that!(
+            attribute_files["/app/src/routes/+page.svelte"],
+            contains_substring("<p title={$_('routes.moreInfo')}>")
+        );
+    }
+
+    #[rstest]
+    fn reuses_key_with_same_value_and_skips_taken_keys() {
+        let fixture = Fixture::new(
+            "/app/src/a.ts",
+            "const a = 'Save';\nconst b = 'Cancel';\n",
+            r#"{ "a": { "save": "Save", "cancel": "Other" } }"#,
+        );
+
+        let (save_title, _) = fixture.extract("Save").unwrap();
+        let (cancel_title, _) = fixture.extract("Cancel").unwrap();
+
+        assert_that!(save_title, eq("Extract to translation key 'a.save'"));
+        assert_that!(cancel_title, eq("Extract to translation key 'a.cancel2'"));
+    }
+
+    #[rstest]
+    #[case::import("import x from 'react';\n", "react")]
+    #[case::trans_fn_argument("t('common.save');\n", "common.save")]
+    #[case::object_key("const o = { 'label': 1 };\n", "label")]
+    #[case::no_letters("const sep = ' - ';\n", " - ")]
+    fn ignores_non_user_facing_strings(#[case] source: &str, #[case] needle: &str) {
+        let fixture = Fixture::new("/app/src/a.ts", source, "{}");
+
+        assert_that!(fixture.extract(needle), none());
+    }
+
+    #[rstest]
+    #[case::sentence("Save changes", "saveChanges")]
+    #[case::punctuation("Don't save!", "donTSave")]
+    #[case::long("One two three four five", "oneTwoThreeFour")]
+    #[case::all_caps("SAVE", "save")]
+    #[case::non_ascii("保存する", "text")]
+    #[case::leading_digit("3 items", "text3Items")]
+    fn key_suffix_from_text(#[case] text: &str, #[case] expected: &str) {
+        assert_that!(key_suffix(text), eq(expected));
+    }
+
+    #[rstest]
+    #[case::component("/src/a.tsx", Some("SettingsPage"), "settingsPage.save")]
+    #[case::file_stem("/src/user-profile.ts", None, "userProfile.save")]
+    #[case::index_file("/src/checkout/index.tsx", None, "checkout.save")]
+    fn propose_key_prefix(
+        #[case] path: &str,
+        #[case] component: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        assert_that!(propose_key(Path::new(path), component, "Save", "."), eq(expected));
+    }
+
+    #[rstest]
+    #[case::react_i18next(
+        "import { useTranslation } from 'react-i18next';",
+        Some(HookLibrary::ReactI18next)
+    )]
+    #[case::next_intl_submodule(
+        "import { getTranslations } from \"next-intl/server\";",
+        Some(HookLibrary::NextIntl)
+    )]
+    #[case::comment("// migrated from 'next-intl'\nimport React from 'react';", None)]
+    #[case::similar_name("import x from 'next-intl-extra';", None)]
+    fn hook_library_from_imports(#[case] source: &str, #[case] expected: Option<HookLibrary>) {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&ProgrammingLanguage::TypeScript.tree_sitter_language()).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+
+        assert_that!(HookLibrary::from_imports(tree.root_node(), source), eq(expected));
+    }
+
+    #[rstest]
+    #[case::next_intl(
+        r#"{"dependencies": {"next": "15", "next-intl": "4"}}"#,
+        Some(HookLibrary::NextIntl)
+    )]
+    #[case::react_i18next(
+        r#"{"devDependencies": {"react-i18next": "15"}}"#,
+        Some(HookLibrary::ReactI18next)
+    )]
+    #[case::both(
+        r#"{"dependencies": {"react-i18next": "15", "next-intl": "4"}}"#,
+        Some(HookLibrary::NextIntl)
+    )]
+    #[case::neither(r#"{"dependencies": {"react": "19"}}"#, None)]
+    #[case::invalid("{", None)]
+    fn hook_library_from_package_json(
+        #[case] content: &str,
+        #[case] expected: Option<HookLibrary>,
+    ) {
+        assert_that!(HookLibrary::from_package_json(content), eq(expected));
+    }
+
+    #[rstest]
+    fn hook_library_from_hoisted_package_json() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("apps/web/src")).unwrap();
+        std::fs::write(dir.path().join("package.json"), r#"{"dependencies": {"next-intl": "4"}}"#)
+            .unwrap();
+        std::fs::write(dir.path().join("apps/web/package.json"), r#"{"name": "web"}"#).unwrap();
+        let file = dir.path().join("apps/web/src/page.tsx");
+
+        assert_that!(
+            HookLibrary::from_package_jsons(&file, Some(dir.path())),
+            some(eq(HookLibrary::NextIntl))
+        );
+        assert_that!(
+            HookLibrary::from_package_jsons(&file, Some(&dir.path().join("apps/web"))),
+            none()
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Server-side file watching for clients that can't register `workspace/didChangeWatchedFiles`.
+
+use std::collections::BTreeMap;
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::sync::{
+    Arc,
+    Mutex,
+    PoisonError,
+    Weak,
+};
+use std::time::Duration;
+
+use ignore::WalkBuilder;
+use notify::{
+    Event,
+    EventKind,
+    RecommendedWatcher,
+    RecursiveMode,
+    Watcher,
+};
+use tokio::sync::mpsc;
+use tower_lsp::lsp_types::{
+    DidChangeWatchedFilesParams,
+    FileChangeType,
+    FileEvent,
+    Url,
+};
+
+use super::backend::Backend;
+use super::handlers;
+use crate::config::FileMatcher;
+
+/// Quiet period after the last file system event before a batch is processed.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Paths changed in a batch, with whether a creation event was seen for them.
+type Batch = BTreeMap<PathBuf, bool>;
+
+/// Watches workspace folders for changes to source, translation and config files.
+///
+/// Each directory is watched on its own, so directories ignored by git or matching
+/// `excludePatterns` (e.g., `node_modules`) don't use up watches. Directories created later
+/// are watched as they appear.
+///
+/// Events are debounced and forwarded in batches to the `workspace/didChangeWatchedFiles`
+/// handler, so a burst of changes such as a git branch switch is reindexed once.
+#[derive(Debug)]
+pub struct FileWatcher {
+    _watcher: Arc<Mutex<RecommendedWatcher>>,
+}
+
+impl FileWatcher {
+    /// Starts watching the directories under `roots`.
+    ///
+    /// Directories that can't be watched are logged and skipped.
+    pub(crate) fn start(
+        backend: Backend,
+        roots: &[PathBuf],
+        matcher: Option<&FileMatcher>,
+    ) -> notify::Result<Self> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let watcher =
+            notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+                Ok(event) if is_content_change(event.kind) => {
+                    let created = matches!(event.kind, EventKind::Create(_));
+                    for path in event.paths {
+                        let _ = tx.send((path, created));
+                    }
+                }
+                Ok(_) => {}
+                Err(error) => tracing::warn!(%error, "File watcher error"),
+            })?;
+        let watcher = Arc::new(Mutex::new(watcher));
+
+        for root in roots {
+            watch_directories(&watcher, root, matcher);
+        }
+
+        tokio::spawn(process_events(backend, Arc::downgrade(&watcher), rx));
+        Ok(Self { _watcher: watcher })
+    }
+}
+
+/// Whether the event may have changed file contents; plain reads are ignored.
+const fn is_content_change(kind: EventKind) -> bool {
+    !matches!(kind, EventKind::Access(_))
+}
+
+/// Watches `dir` and its subdirectories, skipping ignored and excluded ones.
+///
+/// Returns the files found under `dir`.
+fn watch_directories(
+    watcher: &Mutex<RecommendedWatcher>,
+    dir: &Path,
+    matcher: Option<&FileMatcher>,
+) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut watcher = watcher.lock().unwrap_or_else(PoisonError::into_inner);
+
+    for entry in walk(dir, matcher.cloned()).flatten() {
+        let path = entry.path();
+        if !entry.file_type().is_some_and(|ft| ft.is_dir()) {
+            files.push(path.to_path_buf());
+            continue;
+        }
+        if let Err(error) = watcher.watch(path, RecursiveMode::NonRecursive) {
+            tracing::warn!(%error, dir = %path.display(), "Failed to watch directory");
+        }
+    }
+    drop(watcher);
+    files
+}
+
+/// Walks `dir` like the workspace indexer, also skipping `.git` and excluded directories.
+fn walk(dir: &Path, matcher: Option<FileMatcher>) -> ignore::Walk {
+    WalkBuilder::new(dir)
+        .hidden(false)
+        .git_ignore(true)
+        .git_global(true)
+        .git_exclude(true)
+        .follow_links(false)
+        .filter_entry(move |entry| {
+            !entry.file_type().is_some_and(|ft| ft.is_dir())
+                || !(entry.file_name() == ".git"
+                    || matcher.as_ref().is_some_and(|m| m.is_excluded_directory(entry.path())))
+        })
+        .build()
+}
+
+async fn process_events(
+    backend: Backend,
+    watcher: Weak<Mutex<RecommendedWatcher>>,
+    mut rx: mpsc::UnboundedReceiver<(PathBuf, bool)>,
+) {
+    while let Some(mut batch) = next_batch(&mut rx, DEBOUNCE).await {
+        // Files in new directories may have been written before the directory was watched
+        let new_dirs: Vec<PathBuf> = batch
+            .iter()
+            .filter(|(path, created)| **created && path.is_dir())
+            .map(|(path, _)| path.clone())
+            .collect();
+        if !new_dirs.is_empty()
+            && let Some(watcher) = watcher.upgrade()
+        {
+            let matcher = backend.config_manager.lock().await.file_matcher().cloned();
+            for dir in new_dirs {
+                for file in watch_directories(&watcher, &dir, matcher.as_ref()) {
+                    batch.insert(file, true);
+                }
+            }
+        }
+
+        let changes = batch
+            .into_iter()
+            .filter_map(|(path, created)| {
+                let uri = Url::from_file_path(&path).ok()?;
+                Some(FileEvent { uri, typ: change_type(&path, created) })
+            })
+            .collect();
+
+        handlers::workspace::handle_did_change_watched_files(
+            &backend,
+            DidChangeWatchedFilesParams { changes },
+        )
+        .await;
+    }
+}
+
+/// Events of a batch may have been merged, so the final state on disk decides the type.
+fn change_type(path: &Path, created: bool) -> FileChangeType {
+    if !path.exists() {
+        FileChangeType::DELETED
+    } else if created {
+        FileChangeType::CREATED
+    } else {
+        FileChangeType::CHANGED
+    }
+}
+
+/// Waits for the next path, then collects further paths until none arrive for `debounce`.
+///
+/// Returns `None` once the channel is closed and drained.
+async fn next_batch(
+    rx: &mut mpsc::UnboundedReceiver<(PathBuf, bool)>,
+    debounce: Duration,
+) -> Option<Batch> {
+    let mut batch = Batch::new();
+    let mut add = |(path, created): (PathBuf, bool)| {
+        *batch.entry(path).or_default() |= created;
+    };
+    add(rx.recv().await?);
+    while let Ok(Some(event)) = tokio::time::timeout(debounce, rx.recv()).await {
+        add(event);
+    }
+    Some(batch)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use googletest::prelude::*;
+    use notify::event::{
+        AccessKind,
+        CreateKind,
+        ModifyKind,
+        RemoveKind,
+    };
+    use rstest::*;
+
+    use super::*;
+    use crate::config::I18nSettings;
+
+    #[rstest]
+    #[case::create(EventKind::Create(CreateKind::File), true)]
+    #[case::modify(EventKind::Modify(ModifyKind::Any), true)]
+    #[case::remove(EventKind::Remove(RemoveKind::File), true)]
+    #[case::access(EventKind::Access(AccessKind::Any), false)]
+    fn detects_content_changes(#[case] kind: EventKind, #[case] expected: bool) {
+        assert_that!(is_content_change(kind), eq(expected));
+    }
+
+    #[tokio::test]
+    async fn next_batch_collects_distinct_paths() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        for (path, created) in [("/src/b.ts", true), ("/src/a.ts", false), ("/src/b.ts", false)] {
+            tx.send((PathBuf::from(path), created)).unwrap();
+        }
+        drop(tx);
+
+        let batch = next_batch(&mut rx, DEBOUNCE).await;
+
+        assert_that!(
+            batch,
+            some(elements_are![
+                (eq(&PathBuf::from("/src/a.ts")), eq(&false)),
+                (eq(&PathBuf::from("/src/b.ts")), eq(&true))
+            ])
+        );
+        assert_that!(next_batch(&mut rx, DEBOUNCE).await, none());
+    }
+
+    #[tokio::test]
+    async fn next_batch_waits_for_quiet_period() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        tx.send((PathBuf::from("/src/a.ts"), false)).unwrap();
+        let sender = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            tx.send((PathBuf::from("/src/b.ts"), false)).unwrap();
+        });
+
+        let batch = next_batch(&mut rx, Duration::from_millis(200)).await.unwrap();
+        sender.await.unwrap();
+
+        assert_that!(batch.len(), eq(2));
+    }
+
+    #[rstest]
+    fn change_type_follows_creation_and_disk_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("en.json");
+        std::fs::write(&file, "{}").unwrap();
+
+        assert_that!(change_type(&file, true), eq(FileChangeType::CREATED));
+        assert_that!(change_type(&file, false), eq(FileChangeType::CHANGED));
+        assert_that!(change_type(&dir.path().join("gone.json"), true), eq(FileChangeType::DELETED));
+    }
+
+    #[rstest]
+    fn walk_skips_git_and_excluded_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        for sub in ["src", ".git/objects", "node_modules/pkg"] {
+            std::fs::create_dir_all(dir.path().join(sub)).unwrap();
+        }
+        let settings = I18nSettings {
+            exclude_patterns: vec!["**/node_modules/**".to_string()],
+            ..Default::default()
+        };
+        let matcher = FileMatcher::new(dir.path().to_path_buf(), &settings).unwrap();
+
+        let mut dirs: Vec<PathBuf> = walk(dir.path(), Some(matcher))
+            .flatten()
+            .filter_map(|entry| entry.path().strip_prefix(dir.path()).ok().map(Path::to_path_buf))
+            .collect();
+        dirs.sort();
+
+        assert_that!(dirs, elements_are![eq(Path::new("")), eq(Path::new("src"))]);
+    }
+}
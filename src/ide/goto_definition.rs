@@ -2,6 +2,7 @@
 
 use tower_lsp::lsp_types::{
     Location,
+    Range,
     Url,
 };
 
@@ -11,7 +12,11 @@ use crate::ide::key_match::is_child_key;
 use crate::ide::plural::PLURAL_SUFFIXES;
 use crate::input::translation::Translation;
 use crate::interned::TransKey;
-use crate::types::SourceRange;
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+    SourceRange,
+};
 
 /// Find translation key definitions
 ///
@@ -20,6 +25,7 @@ use crate::types::SourceRange;
 /// * `key` - Translation key
 /// * `translations` - All translation data
 /// * `key_separator` - Key separator (e.g., ".")
+/// * `encoding` - Position encoding of the returned ranges
 ///
 /// For parent keys (e.g., `nested`), falls back to the first child key (`nested.key`) if no exact match.
 ///
@@ -30,6 +36,7 @@ pub fn find_definitions(
     key: TransKey<'_>,
     translations: &[Translation],
     key_separator: &str,
+    encoding: PositionEncoding,
 ) -> Vec<Location> {
     let key_text = key.text(db);
     let mut locations = Vec::new();
@@ -54,7 +61,10 @@ pub fn find_definitions(
             continue;
         };
 
-        if let Some(location) = create_location(translation.file_path(db), range) {
+        let line_index = LineIndex::new(translation.json_text(db), encoding);
+        if let Some(location) =
+            create_location(translation.file_path(db), line_index.to_lsp_range(*range))
+        {
             locations.push(location);
         }
     }
@@ -87,12 +97,12 @@ fn find_child_key_range<'a>(
 }
 
 /// Create a Location from file path and range
-fn create_location(file_path: &str, range: &SourceRange) -> Option<Location> {
+fn create_location(file_path: &str, range: Range) -> Option<Location> {
     let Ok(uri) = Url::from_file_path(file_path) else {
         tracing::warn!("Failed to create URI from file path: {}", file_path);
         return None;
     };
-    Some(Location { uri, range: (*range).into() })
+    Some(Location { uri, range })
 }
 
 #[cfg(test)]
@@ -139,7 +149,7 @@ mod tests {
         let key = TransKey::new(&db, "common.hello".to_string());
         let translations = vec![translation];
 
-        let locations = find_definitions(&db, key, &translations, ".");
+        let locations = find_definitions(&db, key, &translations, ".", PositionEncoding::Utf16);
 
         assert_that!(locations.len(), eq(1));
         assert_that!(locations[0].uri.path(), ends_with("en.json"));
@@ -196,7 +206,7 @@ mod tests {
         let key = TransKey::new(&db, "common.hello".to_string());
         let translations = vec![en_translation, ja_translation];
 
-        let locations = find_definitions(&db, key, &translations, ".");
+        let locations = find_definitions(&db, key, &translations, ".", PositionEncoding::Utf16);
 
         // Definitions found in both translation files
         assert_that!(locations.len(), eq(2));
@@ -226,7 +236,7 @@ mod tests {
         let key = TransKey::new(&db, "nonexistent.key".to_string());
         let translations = vec![translation];
 
-        let locations = find_definitions(&db, key, &translations, ".");
+        let locations = find_definitions(&db, key, &translations, ".", PositionEncoding::Utf16);
 
         // No definitions found
         assert_that!(locations, is_empty());
@@ -271,7 +281,7 @@ mod tests {
         let key = TransKey::new(&db, "nested".to_string());
         let translations = vec![translation];
 
-        let locations = find_definitions(&db, key, &translations, ".");
+        let locations = find_definitions(&db, key, &translations, ".", PositionEncoding::Utf16);
 
         assert_that!(locations.len(), eq(1));
         assert_that!(locations[0].uri.path(), ends_with("en.json"));
@@ -319,7 +329,7 @@ mod tests {
         let key = TransKey::new(&db, "nested".to_string());
         let translations = vec![translation];
 
-        let locations = find_definitions(&db, key, &translations, ".");
+        let locations = find_definitions(&db, key, &translations, ".", PositionEncoding::Utf16);
 
         assert_that!(locations.len(), eq(1));
         assert_that!(locations[0].range.start.line, eq(0));
@@ -380,7 +390,7 @@ mod tests {
         let key = TransKey::new(&db, "items".to_string());
         let translations = vec![translation];
 
-        let locations = find_definitions(&db, key, &translations, ".");
+        let locations = find_definitions(&db, key, &translations, ".", PositionEncoding::Utf16);
 
         // First matching plural variant (_one matches before _other in PLURAL_SUFFIXES order)
         assert_that!(locations.len(), eq(1));
@@ -428,7 +438,7 @@ mod tests {
         let key = TransKey::new(&db, "place".to_string());
         let translations = vec![translation];
 
-        let locations = find_definitions(&db, key, &translations, ".");
+        let locations = find_definitions(&db, key, &translations, ".", PositionEncoding::Utf16);
 
         assert_that!(locations.len(), eq(1));
         assert_that!(locations[0].uri.path(), ends_with("en.json"));
@@ -483,7 +493,7 @@ mod tests {
         let key = TransKey::new(&db, "items".to_string());
         let translations = vec![translation];
 
-        let locations = find_definitions(&db, key, &translations, ".");
+        let locations = find_definitions(&db, key, &translations, ".", PositionEncoding::Utf16);
 
         assert_that!(locations.len(), eq(1));
         assert_that!(locations[0].range.start.line, eq(0));
@@ -529,7 +539,7 @@ mod tests {
         let key = TransKey::new(&db, "items".to_string());
         let translations = vec![translation];
 
-        let locations = find_definitions(&db, key, &translations, ".");
+        let locations = find_definitions(&db, key, &translations, ".", PositionEncoding::Utf16);
 
         assert_that!(locations.len(), eq(1));
         assert_that!(locations[0].uri.path(), ends_with("en.json"));
@@ -3,6 +3,8 @@
 #![allow(unreachable_pub)]
 
 pub mod code_action;
+pub mod code_lens;
+pub mod diagnostic;
 pub mod document_sync;
 pub mod execute_command;
 pub mod features;
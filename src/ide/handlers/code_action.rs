@@ -53,19 +53,31 @@ pub async fn handle_code_action(
     }
 
     let position = params.range.start;
-    let source_position = crate::types::SourcePosition::from(position);
-
-    let Some(key_context) = backend.get_key_at_position(&file_path, source_position).await else {
-        return Ok(Some(vec![]));
+    let Some(key_context) = backend.get_key_at_position(&file_path, position).await else {
+        let actions =
+            generate_extract_code_actions(backend, uri, &file_path, diagnostics, position).await;
+        return Ok(Some(actions));
     };
 
+    // Dynamic keys stand for several keys, none of which can be edited, deleted or moved
+    if key_context.dynamic {
+        return Ok(Some(Vec::new()));
+    }
+
     let mut actions: Vec<CodeActionOrCommand> = Vec::new();
+    let settings = backend.config_manager.lock().await.get_settings().clone();
+    let (namespace, key_part) = key_context.resolve_key_and_namespace(
+        settings.namespace_separator.as_deref(),
+        settings.default_namespace.as_deref(),
+    );
 
     // Delete key action (always available, no client opt-in needed)
     {
-        let settings = backend.config_manager.lock().await.get_settings().clone();
         let db = backend.state.db.lock().await;
-        let translations = backend.state.translations.lock().await;
+        let translations = {
+            let translations = backend.state.translations.lock().await;
+            crate::ide::scope::global_translations(&*db, &translations)
+        };
         if let Some(action) = crate::ide::code_actions::generate_delete_key_code_action(
             &*db,
             &key_context.key_text,
@@ -75,18 +87,26 @@ pub async fn handle_code_action(
         ) {
             actions.push(action);
         }
+
+        if let Some(namespace) = &namespace {
+            actions.extend(crate::ide::move_key::generate_move_key_code_actions(
+                &*db,
+                &key_part,
+                namespace,
+                &translations,
+                &settings.key_separator,
+            ));
+        }
     }
 
+    actions.extend(generate_inline_code_actions(backend, uri, &file_path, position).await);
+
     // Edit/Add translation actions (requires client opt-in)
     let code_actions_enabled = *backend.state.code_actions_enabled.lock().await;
     if code_actions_enabled {
         let missing_languages = crate::ide::code_actions::extract_missing_languages(diagnostics);
 
         let (effective_language, sorted_languages) = {
-            let config = backend.config_manager.lock().await;
-            let primary_languages = config.get_settings().primary_languages.clone();
-            drop(config);
-
             let current_language = backend.state.current_language.lock().await.clone();
             let db = backend.state.db.lock().await;
             let translations = backend.state.translations.lock().await;
@@ -95,7 +115,7 @@ pub async fn handle_code_action(
                 &*db,
                 &translations,
                 current_language.as_deref(),
-                primary_languages.as_deref(),
+                settings.primary_languages.as_deref(),
             );
             drop(translations);
             drop(db);
@@ -103,8 +123,10 @@ pub async fn handle_code_action(
             (effective, sorted)
         };
 
+        // The key keeps its namespace prefix for clients that don't forward `namespace`
         let edit_actions = crate::ide::code_actions::generate_code_actions(
             &key_context.key_text,
+            namespace.as_deref(),
             &sorted_languages,
             &missing_languages,
             effective_language.as_deref(),
@@ -115,6 +137,149 @@ pub async fn handle_code_action(
     Ok(Some(actions))
 }
 
+/// Offers replacing the translation call at `position` with the text in the current language.
+async fn generate_inline_code_actions(
+    backend: &Backend,
+    uri: &tower_lsp::lsp_types::Url,
+    file_path: &Path,
+    position: tower_lsp::lsp_types::Position,
+) -> Vec<CodeActionOrCommand> {
+    let settings = backend.config_manager.lock().await.get_settings().clone();
+    let encoding = backend.get_position_encoding().await;
+    let current_language = backend.state.current_language.lock().await.clone();
+
+    let (db, source_files) = backend.state.lock_db_and_source_files().await;
+    let Some(source_file) = source_files.get(file_path).copied() else {
+        return vec![];
+    };
+    let translations = {
+        let translations = backend.state.translations.lock().await;
+        crate::ide::scope::visible_translations(&*db, &translations, file_path)
+    };
+    let Some(language) = crate::ide::backend::collect_sorted_languages(
+        &*db,
+        &translations,
+        current_language.as_deref(),
+        settings.primary_languages.as_deref(),
+    )
+    .into_iter()
+    .next() else {
+        return vec![];
+    };
+
+    let source_position =
+        crate::types::LineIndex::new(source_file.text(&*db), encoding).to_source_position(position);
+    let options = crate::ide::inline::InlineOptions {
+        key_separator: &settings.key_separator,
+        namespace_separator: settings.namespace_separator.as_deref(),
+        default_namespace: settings.default_namespace.as_deref(),
+        encoding,
+    };
+    let actions = crate::ide::inline::generate_inline_code_actions(
+        &*db,
+        source_file,
+        uri,
+        source_position,
+        &translations,
+        &language,
+        &source_files,
+        &options,
+    );
+    drop(source_files);
+    drop(db);
+
+    actions
+}
+
+/// Offers extracting the hardcoded string at `position` into a translation key.
+///
+/// The action is a quick fix for `hardcoded-string` diagnostics at the position.
+async fn generate_extract_code_actions(
+    backend: &Backend,
+    uri: &tower_lsp::lsp_types::Url,
+    file_path: &Path,
+    diagnostics: &[tower_lsp::lsp_types::Diagnostic],
+    position: tower_lsp::lsp_types::Position,
+) -> Vec<CodeActionOrCommand> {
+    let (settings, workspace_root) = {
+        let config_manager = backend.config_manager.lock().await;
+        (config_manager.get_settings().clone(), config_manager.workspace_root().cloned())
+    };
+    let encoding = backend.get_position_encoding().await;
+    let current_language = backend.state.current_language.lock().await.clone();
+
+    let (db, source_files) = backend.state.lock_db_and_source_files().await;
+    let Some(source_file) = source_files.get(file_path).copied() else {
+        return vec![];
+    };
+    drop(source_files);
+
+    let translations = {
+        let translations = backend.state.translations.lock().await;
+        crate::ide::scope::global_translations(&*db, &translations)
+    };
+    let languages = crate::ide::backend::collect_sorted_languages(
+        &*db,
+        &translations,
+        current_language.as_deref(),
+        settings.primary_languages.as_deref(),
+    );
+
+    let source_position =
+        crate::types::LineIndex::new(source_file.text(&*db), encoding).to_source_position(position);
+    let options = crate::ide::extract::ExtractOptions {
+        key_separator: &settings.key_separator,
+        default_namespace: settings.default_namespace.as_deref(),
+        workspace_root: workspace_root.as_deref(),
+        encoding,
+    };
+    let action = crate::ide::extract::generate_extract_code_action(
+        &*db,
+        source_file,
+        uri,
+        source_position,
+        &translations,
+        &languages,
+        &options,
+    );
+    drop(db);
+
+    action
+        .map(|action| promote_to_quickfix_for_hardcoded_string(action, diagnostics, position))
+        .into_iter()
+        .collect()
+}
+
+/// Promote the extract action to QUICKFIX when it fixes a `hardcoded-string` diagnostic.
+fn promote_to_quickfix_for_hardcoded_string(
+    action: CodeActionOrCommand,
+    diagnostics: &[tower_lsp::lsp_types::Diagnostic],
+    position: tower_lsp::lsp_types::Position,
+) -> CodeActionOrCommand {
+    let CodeActionOrCommand::CodeAction(mut ca) = action else {
+        return action;
+    };
+
+    let matching_diag: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| {
+            matches!(&d.code, Some(NumberOrString::String(s)) if s == "hardcoded-string")
+                && d.range.start <= position
+                && position <= d.range.end
+        })
+        .cloned()
+        .collect();
+
+    if !matching_diag.is_empty() {
+        ca.kind = Some(CodeActionKind::QUICKFIX);
+        ca.is_preferred = Some(true);
+        ca.diagnostics = Some(matching_diag);
+    }
+
+    CodeActionOrCommand::CodeAction(ca)
+}
+
+#[allow(clippy::too_many_lines)]
 async fn generate_translation_file_code_actions(
     backend: &Backend,
     uri: &tower_lsp::lsp_types::Url,
@@ -124,6 +289,7 @@ async fn generate_translation_file_code_actions(
 ) -> Result<Option<CodeActionResponse>> {
     let key_separator = backend.get_key_separator().await;
     let used_keys = backend.collect_used_keys(&key_separator).await;
+    let encoding = backend.get_position_encoding().await;
 
     let mut actions: Vec<CodeActionOrCommand> = Vec::new();
 
@@ -138,7 +304,8 @@ async fn generate_translation_file_code_actions(
         };
 
         // Delete key at cursor position
-        let source_position = crate::types::SourcePosition::from(position);
+        let source_position = crate::types::LineIndex::new(translation.json_text(&*db), encoding)
+            .to_source_position(position);
         if let Some(key) = translation.key_at_position(&*db, source_position) {
             let key_text = key.text(&*db).clone();
             if let Some(action) = crate::ide::code_actions::generate_delete_key_code_action(
@@ -158,6 +325,32 @@ async fn generate_translation_file_code_actions(
                     promote_to_quickfix_if_unused(action, is_unused, diagnostics, position);
                 actions.push(action);
             }
+            if let Some(namespace) = translation.namespace(&*db) {
+                let translations = crate::ide::scope::global_translations(&*db, &translations);
+                actions.extend(crate::ide::move_key::generate_move_key_code_actions(
+                    &*db,
+                    &key_text,
+                    namespace,
+                    &translations,
+                    &settings.key_separator,
+                ));
+            }
+        }
+
+        // Add missing plural variants
+        for diagnostic in diagnostics.iter().filter(|d| {
+            matches!(&d.code, Some(NumberOrString::String(s)) if s == "missing-plural-category")
+        }) {
+            if let Some(action) =
+                crate::ide::code_actions::generate_add_plural_categories_code_action(
+                    &*db,
+                    translation,
+                    diagnostic,
+                    &settings.key_separator,
+                )
+            {
+                actions.push(action);
+            }
         }
 
         // Delete unused keys
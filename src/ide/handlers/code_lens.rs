@@ -0,0 +1,95 @@
+//! Code Lens handlers for `textDocument/codeLens` and `codeLens/resolve` requests.
+
+use tower_lsp::jsonrpc::Result;
+use tower_lsp::lsp_types::{
+    CodeLens,
+    CodeLensParams,
+};
+
+use super::super::backend::Backend;
+use crate::ide::code_lens::ReferencesLensData;
+
+pub async fn handle_code_lens(
+    backend: &Backend,
+    params: CodeLensParams,
+) -> Result<Option<Vec<CodeLens>>> {
+    let uri = params.text_document.uri;
+
+    tracing::debug!(uri = %uri, "Code Lens request");
+
+    if !backend.wait_for_translations().await {
+        tracing::debug!("Code Lens request - translations not indexed yet");
+        return Ok(None);
+    }
+
+    let Some(file_path) = Backend::uri_to_path(&uri) else {
+        return Ok(None);
+    };
+    let file_path = file_path.to_string_lossy();
+    let encoding = backend.get_position_encoding().await;
+
+    let db = backend.state.db.lock().await;
+    let translations = backend.state.translations.lock().await;
+
+    let Some(translation) = translations
+        .iter()
+        .find(|t| !t.is_component_local(&*db) && t.file_path(&*db) == file_path.as_ref())
+        .copied()
+    else {
+        return Ok(None);
+    };
+
+    let lenses = crate::ide::code_lens::generate_code_lenses(
+        &*db,
+        translation,
+        &translations,
+        &uri,
+        encoding,
+    );
+    drop(translations);
+    drop(db);
+
+    Ok(Some(lenses))
+}
+
+pub async fn handle_code_lens_resolve(backend: &Backend, lens: CodeLens) -> Result<CodeLens> {
+    let Some(data) =
+        lens.data.clone().and_then(|data| serde_json::from_value::<ReferencesLensData>(data).ok())
+    else {
+        return Ok(lens);
+    };
+
+    tracing::debug!(uri = %data.uri, key = %data.key, "Code Lens resolve request");
+
+    let Some(file_path) = Backend::uri_to_path(&data.uri) else {
+        return Ok(lens);
+    };
+    let file_path = file_path.to_string_lossy();
+
+    let settings = backend.config_manager.lock().await.get_settings().clone();
+    let encoding = backend.get_position_encoding().await;
+
+    let db = backend.state.db.lock().await;
+    let namespace = {
+        let translations = backend.state.translations.lock().await;
+        translations
+            .iter()
+            .find(|t| t.file_path(&*db) == file_path.as_ref())
+            .and_then(|t| t.namespace(&*db).clone())
+    };
+    let source_files = backend.state.source_files.lock().await;
+    let locations = crate::ide::references::find_references(
+        &*db,
+        &data.key,
+        namespace.as_deref(),
+        &source_files,
+        &settings.key_separator,
+        settings.namespace_separator.as_deref(),
+        settings.default_namespace.as_deref(),
+        encoding,
+    );
+    drop(source_files);
+    drop(db);
+
+    Ok(crate::ide::code_lens::resolve_references_lens(lens, &data.uri, &locations))
+}
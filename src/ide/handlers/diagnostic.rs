@@ -0,0 +1,125 @@
+//! Pull diagnostic handlers for `textDocument/diagnostic` and `workspace/diagnostic` requests.
+
+use std::collections::HashMap;
+
+use tower_lsp::jsonrpc::Result;
+use tower_lsp::lsp_types::{
+    DocumentDiagnosticParams,
+    DocumentDiagnosticReport,
+    DocumentDiagnosticReportKind,
+    DocumentDiagnosticReportResult,
+    FullDocumentDiagnosticReport,
+    RelatedFullDocumentDiagnosticReport,
+    RelatedUnchangedDocumentDiagnosticReport,
+    Url,
+    WorkspaceDiagnosticParams,
+    WorkspaceDiagnosticReport,
+    WorkspaceDiagnosticReportResult,
+};
+
+use super::super::backend::Backend;
+use crate::ide::pull_diagnostics::{
+    ResultIds,
+    document_report,
+    file_diagnostics,
+    workspace_document_report,
+    workspace_files,
+};
+
+/// Whether the index is complete enough to compute diagnostics.
+///
+/// Until then, empty reports without a result ID are returned; the client is asked to pull
+/// again once indexing completes.
+async fn is_index_ready(backend: &Backend) -> bool {
+    backend.workspace_indexer.is_indexing_completed() && backend.wait_for_translations().await
+}
+
+pub async fn handle_document_diagnostic(
+    backend: &Backend,
+    params: DocumentDiagnosticParams,
+) -> Result<DocumentDiagnosticReportResult> {
+    let uri = params.text_document.uri;
+
+    tracing::debug!(uri = %uri, "Document diagnostic request");
+
+    let Some(file_path) = Backend::uri_to_path(&uri) else {
+        return Ok(full_report(FullDocumentDiagnosticReport::default()));
+    };
+    if !is_index_ready(backend).await {
+        tracing::debug!("Document diagnostic request - index not ready yet");
+        return Ok(full_report(FullDocumentDiagnosticReport::default()));
+    }
+
+    let config = backend.get_diagnostic_config().await;
+    let (db, source_files, translations) = backend.state.lock_all().await;
+    let generation = *backend.state.index_generation.lock().await;
+
+    let result_id =
+        ResultIds::new(&*db, generation, &source_files, &translations, &config.key_separator)
+            .get(&file_path);
+    let report = document_report(&result_id, params.previous_result_id.as_deref(), || {
+        file_diagnostics(&*db, &config, &file_path, &source_files, &translations)
+    });
+    drop((db, source_files, translations));
+
+    Ok(match report {
+        DocumentDiagnosticReportKind::Full(report) => full_report(report),
+        DocumentDiagnosticReportKind::Unchanged(report) => DocumentDiagnosticReportResult::Report(
+            DocumentDiagnosticReport::Unchanged(RelatedUnchangedDocumentDiagnosticReport {
+                related_documents: None,
+                unchanged_document_diagnostic_report: report,
+            }),
+        ),
+    })
+}
+
+pub async fn handle_workspace_diagnostic(
+    backend: &Backend,
+    params: WorkspaceDiagnosticParams,
+) -> Result<WorkspaceDiagnosticReportResult> {
+    tracing::debug!(
+        previous_result_ids = params.previous_result_ids.len(),
+        "Workspace diagnostic request"
+    );
+
+    if !is_index_ready(backend).await {
+        tracing::debug!("Workspace diagnostic request - index not ready yet");
+        return Ok(WorkspaceDiagnosticReportResult::Report(WorkspaceDiagnosticReport::default()));
+    }
+
+    let previous_result_ids: HashMap<Url, String> = params
+        .previous_result_ids
+        .into_iter()
+        .map(|previous| (previous.uri, previous.value))
+        .collect();
+
+    let config = backend.get_diagnostic_config().await;
+    let (db, source_files, translations) = backend.state.lock_all().await;
+    let generation = *backend.state.index_generation.lock().await;
+
+    let mut result_ids =
+        ResultIds::new(&*db, generation, &source_files, &translations, &config.key_separator);
+    let items = workspace_files(&*db, &source_files, &translations)
+        .into_iter()
+        .filter_map(|file_path| {
+            let uri = Url::from_file_path(&file_path).ok()?;
+            let result_id = result_ids.get(&file_path);
+            let previous_result_id = previous_result_ids.get(&uri).map(String::as_str);
+            Some(workspace_document_report(uri, &result_id, previous_result_id, || {
+                file_diagnostics(&*db, &config, &file_path, &source_files, &translations)
+            }))
+        })
+        .collect();
+    drop((db, source_files, translations));
+
+    Ok(WorkspaceDiagnosticReportResult::Report(WorkspaceDiagnosticReport { items }))
+}
+
+const fn full_report(report: FullDocumentDiagnosticReport) -> DocumentDiagnosticReportResult {
+    DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Full(
+        RelatedFullDocumentDiagnosticReport {
+            related_documents: None,
+            full_document_diagnostic_report: report,
+        },
+    ))
+}
@@ -8,6 +8,11 @@ use tower_lsp::lsp_types::{
 };
 
 use super::super::backend::Backend;
+use crate::db::I18nDatabase;
+use crate::input::document::{
+    DocumentChange,
+    apply_content_changes,
+};
 
 pub async fn handle_did_open(backend: &Backend, params: DidOpenTextDocumentParams) {
     let uri = params.text_document.uri.clone();
@@ -17,8 +22,11 @@ pub async fn handle_did_open(backend: &Backend, params: DidOpenTextDocumentParam
     let text = params.text_document.text;
 
     {
+        let db = backend.state.db.lock().await;
+        db.syntax_trees().open(uri.as_str());
+        drop(db);
         let mut opened_files = backend.state.opened_files.lock().await;
-        opened_files.insert(uri.clone());
+        opened_files.insert(uri.clone(), text.clone());
     }
 
     backend.update_and_diagnose(uri, text, true).await;
@@ -31,18 +39,29 @@ pub async fn handle_did_change(backend: &Backend, params: DidChangeTextDocumentP
         return;
     }
 
-    let Some(change) = params.content_changes.into_iter().next_back() else {
+    let encoding = backend.get_position_encoding().await;
+    let mut opened_files = backend.state.opened_files.lock().await;
+    let Some(text) = opened_files.get_mut(&uri) else {
+        tracing::warn!(uri = %uri, "Ignoring change to a document that is not open");
         return;
     };
-    let new_content = change.text;
+    let change = apply_content_changes(text, params.content_changes, encoding);
+    let new_content = text.clone();
+    drop(opened_files);
+
+    let syntax_trees = backend.state.db.lock().await.syntax_trees().clone();
+    match &change {
+        DocumentChange::Edits(edits) => syntax_trees.edit(uri.as_str(), edits, &new_content),
+        DocumentChange::Replaced => syntax_trees.invalidate(uri.as_str()),
+    }
 
     // Check if this is a translation file
     if let Some(file_path) = Backend::uri_to_path(&uri)
         && backend.is_translation_file(&file_path).await
     {
-        backend.update_translation_from_content(&file_path, &new_content).await;
+        backend.update_translation_from_content(&uri, &file_path, &new_content).await;
         backend.send_diagnostics_to_opened_files().await;
-        backend.send_decorations_changed().await;
+        backend.send_translations_changed().await;
         return;
     }
 
@@ -53,6 +72,10 @@ pub async fn handle_did_change(backend: &Backend, params: DidChangeTextDocumentP
 #[allow(clippy::unused_async)]
 pub async fn handle_did_save(_: &Backend, _: DidSaveTextDocumentParams) {}
 
+/// Reverts the document to its on-disk contents, since unsaved changes are discarded on close.
+///
+/// Files deleted on disk are removed. Diagnostics of files outside the workspace are cleared,
+/// and diagnostics depending on the file are refreshed.
 pub async fn handle_did_close(backend: &Backend, params: DidCloseTextDocumentParams) {
     let uri = params.text_document.uri;
     if uri.scheme() != "file" {
@@ -60,7 +83,54 @@ pub async fn handle_did_close(backend: &Backend, params: DidCloseTextDocumentPar
     }
 
     {
+        let db = backend.state.db.lock().await;
+        db.syntax_trees().close(uri.as_str());
+        drop(db);
         let mut opened_files = backend.state.opened_files.lock().await;
         opened_files.remove(&uri);
     }
+
+    let Some(file_path) = Backend::uri_to_path(&uri) else {
+        return;
+    };
+
+    if backend.is_translation_file(&file_path).await {
+        if file_path.exists() {
+            backend.reload_translation_file(&file_path).await;
+        } else {
+            backend.remove_translation_file(&file_path).await;
+            backend.client.publish_diagnostics(uri, Vec::new(), None).await;
+        }
+        backend.send_diagnostics_to_opened_files().await;
+        backend.send_translation_diagnostics().await;
+        backend.send_translations_changed().await;
+        return;
+    }
+
+    let source_file = backend.state.source_files.lock().await.get(&file_path).copied();
+    let Some(source_file) = source_file else {
+        backend.client.publish_diagnostics(uri, Vec::new(), None).await;
+        return;
+    };
+
+    match tokio::fs::read_to_string(&file_path).await {
+        Ok(disk_text) => {
+            let is_modified = {
+                let db = backend.state.db.lock().await;
+                source_file.text(&*db) != &disk_text
+            };
+            if is_modified {
+                tracing::debug!(uri = %uri, "Reverting closed document to on-disk contents");
+                backend.update_and_diagnose(uri, disk_text, false).await;
+            }
+        }
+        Err(e) => {
+            tracing::debug!(uri = %uri, "Removing closed document missing on disk: {e}");
+            backend.remove_source_file(&file_path).await;
+            backend.client.publish_diagnostics(uri, Vec::new(), None).await;
+            backend.send_diagnostics_to_opened_files().await;
+            backend.send_translation_diagnostics().await;
+        }
+    }
+    backend.send_decorations_changed().await;
 }
@@ -7,6 +7,7 @@ use serde_json::Value;
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::{
     ExecuteCommandParams,
+    MessageType,
     Position,
     TextEdit,
     Url,
@@ -58,14 +59,16 @@ pub async fn handle_execute_command(
     match params.command.as_str() {
         "i18n.editTranslation" => handle_edit_translation(backend, Some(params.arguments)).await,
         "i18n.deleteUnusedKeys" => handle_delete_unused_keys(backend, Some(params.arguments)).await,
+        "i18n.moveKey" => handle_move_key(backend, Some(params.arguments)).await,
         "i18n.getKeyAtPosition" => {
             handle_get_key_at_position(backend, Some(params.arguments)).await
         }
         "i18n.getTranslationValue" => {
             handle_get_translation_value(backend, Some(params.arguments)).await
         }
-        // No-op: handled by the client (code action trigger for edit translation)
-        "i18n.executeClientEditTranslation" => Ok(None),
+        // No-op: handled by the client (code action/lens triggers for edit translation and
+        // showing references)
+        "i18n.executeClientEditTranslation" | "i18n.executeClientShowReferences" => Ok(None),
         "i18n.getDecorations" => handle_get_decorations(backend, Some(params.arguments)).await,
         "i18n.getCurrentLanguage" => handle_get_current_language(backend).await,
         "i18n.setCurrentLanguage" => {
@@ -85,9 +88,13 @@ struct EditTranslationArgs {
     lang: String,
     key: String,
     value: String,
+    namespace: Option<String>,
 }
 
 /// Edit a translation value directly. Inserts the key if it doesn't exist.
+///
+/// The namespace is taken from the arguments, the key's namespace prefix or `defaultNamespace`.
+/// A translation file is created if the language has none for the namespace.
 async fn handle_edit_translation(
     backend: &Backend,
     arguments: Option<Vec<Value>>,
@@ -101,56 +108,51 @@ async fn handle_edit_translation(
     tracing::debug!(
         lang = %parsed_args.lang,
         key = %parsed_args.key,
+        namespace = ?parsed_args.namespace,
         "Executing i18n.editTranslation"
     );
 
-    let key_separator = backend.get_key_separator().await;
-
-    let db = backend.state.db.lock().await;
-    let translations = backend.state.translations.lock().await;
-
-    let Some(translation) = translations.iter().find(|t| t.language(&*db) == parsed_args.lang)
-    else {
-        tracing::warn!(lang = %parsed_args.lang, "translation file not found");
-        return Ok(None);
-    };
-
-    let file_path = translation.file_path(&*db).clone();
-    let key_exists = translation.keys(&*db).contains_key(parsed_args.key.as_str());
-    let original_text = translation.json_text(&*db).clone();
+    let settings = backend.config_manager.lock().await.get_settings().clone();
+    let (explicit_namespace, key) = crate::syntax::analyzer::extractor::parse_key_with_namespace(
+        &parsed_args.key,
+        settings.namespace_separator.as_deref(),
+    );
+    let namespace =
+        parsed_args.namespace.or(explicit_namespace).or_else(|| settings.default_namespace.clone());
 
-    let result = if key_exists {
-        crate::ide::code_actions::update_key_in_json_text(
-            &original_text,
-            &parsed_args.key,
-            &parsed_args.value,
-            &key_separator,
-        )
-    } else {
-        crate::ide::code_actions::insert_key_to_json(
+    let can_create_files = *backend.state.create_files_supported.lock().await;
+    let edit = {
+        let db = backend.state.db.lock().await;
+        let translations = {
+            let translations = backend.state.translations.lock().await;
+            crate::ide::scope::global_translations(&*db, &translations)
+        };
+        let edit = crate::ide::edit_translation::compute_edit_translation(
             &*db,
-            translation,
-            &parsed_args.key,
+            &translations,
+            &parsed_args.lang,
+            namespace.as_deref(),
+            &key,
             &parsed_args.value,
-            &key_separator,
-        )
-    };
-
-    drop(translations);
-    drop(db);
-
-    let Some(result) = result else {
-        tracing::error!("Failed to edit translation key: {}", parsed_args.key);
-        return Ok(None);
+            &settings.key_separator,
+            can_create_files,
+        );
+        drop(db);
+        edit
     };
 
-    let Ok(uri) = Url::from_file_path(&file_path) else {
-        tracing::error!("Failed to convert file path to URI: {}", file_path);
-        return Ok(None);
+    let edit = match edit {
+        Ok(edit) => edit,
+        Err(error) => {
+            tracing::error!(%error, "Failed to edit translation key: {}", parsed_args.key);
+            backend.client.show_message(MessageType::ERROR, error.to_string()).await;
+            return Ok(None);
+        }
     };
 
-    let text_edit = create_full_file_text_edit(&original_text, result.new_text);
-    apply_single_file_edit(backend, uri, text_edit).await;
+    if let Err(e) = backend.client.apply_edit(edit).await {
+        tracing::error!("Failed to apply workspace edit: {e}");
+    }
 
     // State sync (reload, diagnostics, decorations) is handled by the
     // didChange notification that the client sends after applying the edit.
@@ -194,7 +196,7 @@ async fn handle_delete_unused_keys(
     let key_separator = backend.get_key_separator().await;
     let used_keys = backend.collect_used_keys(&key_separator).await;
 
-    let (json_text, unused_keys) = {
+    let (json_text, format, unused_keys) = {
         let db = backend.state.db.lock().await;
         let translations = backend.state.translations.lock().await;
 
@@ -206,6 +208,7 @@ async fn handle_delete_unused_keys(
         };
 
         let json_text = translation.json_text(&*db).clone();
+        let format = translation.format(&*db);
         let all_keys = translation.keys(&*db).clone();
         drop(translations);
         drop(db);
@@ -223,7 +226,7 @@ async fn handle_delete_unused_keys(
             .cloned()
             .collect();
 
-        (json_text, unused)
+        (json_text, format, unused)
     };
 
     if unused_keys.is_empty() {
@@ -234,12 +237,13 @@ async fn handle_delete_unused_keys(
         })));
     }
 
-    let Some(result) = crate::ide::code_actions::delete_keys_from_json_text(
+    let Some(result) = crate::ide::code_actions::delete_keys_from_text(
+        format,
         &json_text,
         &unused_keys,
         &key_separator,
     ) else {
-        tracing::error!("Failed to delete keys from JSON");
+        tracing::error!("Failed to delete keys from translation file");
         return Ok(None);
     };
 
@@ -257,6 +261,79 @@ async fn handle_delete_unused_keys(
     })))
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MoveKeyArgs {
+    key: String,
+    namespace: String,
+    target_namespace: String,
+    new_key: Option<String>,
+}
+
+/// Move a key and its subtree to another namespace, rewriting call sites.
+async fn handle_move_key(
+    backend: &Backend,
+    arguments: Option<Vec<Value>>,
+) -> Result<Option<Value>> {
+    let Some(parsed_args) = parse_command_args::<MoveKeyArgs>(arguments, "i18n.moveKey") else {
+        return Ok(None);
+    };
+
+    tracing::debug!(
+        key = %parsed_args.key,
+        namespace = %parsed_args.namespace,
+        target_namespace = %parsed_args.target_namespace,
+        "Executing i18n.moveKey"
+    );
+
+    let settings = backend.config_manager.lock().await.get_settings().clone();
+    let encoding = backend.get_position_encoding().await;
+
+    let result = {
+        let (db, source_files) = backend.state.lock_db_and_source_files().await;
+        let translations = {
+            let translations = backend.state.translations.lock().await;
+            crate::ide::scope::global_translations(&*db, &translations)
+        };
+        let options = crate::ide::move_key::MoveOptions {
+            key_separator: &settings.key_separator,
+            namespace_separator: settings.namespace_separator.as_deref(),
+            default_namespace: settings.default_namespace.as_deref(),
+            encoding,
+        };
+        let result = crate::ide::move_key::compute_move_edits(
+            &*db,
+            &parsed_args.key,
+            &parsed_args.namespace,
+            &parsed_args.target_namespace,
+            parsed_args.new_key.as_deref().unwrap_or(&parsed_args.key),
+            &translations,
+            &source_files,
+            &options,
+        );
+        drop(source_files);
+        drop(db);
+        result
+    };
+
+    let edit = match result {
+        Ok(edit) => edit,
+        Err(e) => {
+            tracing::warn!("Failed to move key: {e}");
+            return Ok(None);
+        }
+    };
+
+    if let Err(e) = backend.client.apply_edit(edit).await {
+        tracing::error!("Failed to apply workspace edit: {e}");
+    }
+
+    // State sync (reload, diagnostics, decorations) is handled by the
+    // didChange notification that the client sends after applying the edit.
+
+    Ok(None)
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct GetKeyAtPositionArgs {
@@ -295,9 +372,7 @@ async fn handle_get_key_at_position(
         return Ok(None);
     };
 
-    let source_position = crate::types::SourcePosition::from(parsed_args.position);
-
-    let key_context = backend.get_key_at_position(&file_path, source_position).await;
+    let key_context = backend.get_key_at_position(&file_path, parsed_args.position).await;
 
     Ok(key_context.map(|ctx| serde_json::json!({ "key": ctx.key_text })))
 }
@@ -375,50 +450,13 @@ async fn handle_get_decorations(
         return Ok(Some(serde_json::json!([])));
     };
 
-    let config = backend.config_manager.lock().await;
-    let settings = config.get_settings();
-    let primary_languages = settings.primary_languages.clone();
-    let key_separator = settings.key_separator.clone();
-    let namespace_separator = settings.namespace_separator.clone();
-    let default_namespace = settings.default_namespace.clone();
-    drop(config);
-
-    let db = backend.state.db.lock().await;
-    let source_files = backend.state.source_files.lock().await;
-
-    let Some(source_file) = source_files.get(&file_path).copied() else {
+    let Some(decorations) =
+        backend.get_translation_decorations(&file_path, parsed_args.language).await
+    else {
         tracing::debug!("Source file not found: {:?}", file_path);
         return Ok(Some(serde_json::json!([])));
     };
 
-    let translations = backend.state.translations.lock().await;
-
-    // Priority: request arg > currentLanguage > primaryLanguages > first available
-    let current_language = backend.state.current_language.lock().await.clone();
-    let language = parsed_args.language.clone().or_else(|| {
-        let sorted_languages = crate::ide::backend::collect_sorted_languages(
-            &*db,
-            &translations,
-            current_language.as_deref(),
-            primary_languages.as_deref(),
-        );
-        sorted_languages.first().cloned()
-    });
-
-    let decorations = crate::ide::virtual_text::get_translation_decorations(
-        &*db,
-        source_file,
-        &translations,
-        language.as_deref(),
-        &key_separator,
-        namespace_separator.as_deref(),
-        default_namespace.as_deref(),
-    );
-
-    drop(translations);
-    drop(source_files);
-    drop(db);
-
     match serde_json::to_value(&decorations) {
         Ok(value) => Ok(Some(value)),
         Err(e) => {
@@ -483,7 +521,7 @@ async fn handle_set_current_language(
 
     tracing::info!(language = ?parsed_args.language, "current language updated");
 
-    backend.send_decorations_changed().await;
+    backend.send_translations_changed().await;
 
     Ok(None)
 }
@@ -1,25 +1,33 @@
-//! LSP feature handlers: completion, hover, `goto_definition`, references, rename.
+//! LSP feature handlers: completion, hover, `goto_definition`, references, rename, inlay hints,
+//! document and workspace symbols.
 
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::{
     CompletionParams,
     CompletionResponse,
+    DocumentSymbolParams,
+    DocumentSymbolResponse,
     GotoDefinitionParams,
     GotoDefinitionResponse,
     Hover,
     HoverContents,
     HoverParams,
+    InlayHint,
+    InlayHintParams,
     Location,
     MarkupContent,
     MarkupKind,
     PrepareRenameResponse,
     ReferenceParams,
     RenameParams,
+    SymbolInformation,
     TextDocumentPositionParams,
     WorkspaceEdit,
+    WorkspaceSymbolParams,
 };
 
 use super::super::backend::Backend;
+use crate::types::LineIndex;
 
 pub async fn handle_completion(
     backend: &Backend,
@@ -39,6 +47,7 @@ pub async fn handle_completion(
         return Ok(None);
     };
 
+    let encoding = backend.get_position_encoding().await;
     // Acquire config before db to respect lock ordering (config_manager → db → source_files)
     let (key_separator, primary_languages, prefer_selector) = {
         let settings = backend.config_manager.lock().await.get_settings().clone();
@@ -61,13 +70,15 @@ pub async fn handle_completion(
 
     let text = source_file.text(&*db);
     let language = source_file.language(&*db);
+    let line_index = LineIndex::new(text, encoding);
+    let cursor = line_index.to_source_position(position);
 
     // Use tree-sitter based extraction (supports renamed functions, ignores comments)
     let completion_context = crate::ide::completion::extract_completion_context_tree_sitter(
         text,
         language,
-        position.line,
-        position.character,
+        cursor.line,
+        cursor.character,
         &key_separator,
     );
 
@@ -82,7 +93,10 @@ pub async fn handle_completion(
         "Extracted completion context"
     );
 
-    let translations = backend.state.translations.lock().await;
+    let translations = {
+        let translations = backend.state.translations.lock().await;
+        crate::ide::scope::visible_translations(&*db, &translations, &file_path)
+    };
     let partial_key_opt =
         if context.partial_key.is_empty() { None } else { Some(context.partial_key.as_str()) };
 
@@ -100,7 +114,7 @@ pub async fn handle_completion(
         &translations,
         &crate::ide::completion::CompletionOptions {
             partial_key: partial_key_opt,
-            quote_context: &context.quote_context,
+            quote_context: &context.quote_context.to_lsp(&line_index),
             key_prefix: context.key_prefix.as_deref(),
             effective_language: effective_language.as_deref(),
             key_separator: &key_separator,
@@ -108,7 +122,6 @@ pub async fn handle_completion(
         },
     );
     drop(db);
-    drop(translations);
 
     tracing::debug!("Generated {} completion items", items.len());
 
@@ -130,9 +143,7 @@ pub async fn handle_hover(backend: &Backend, params: HoverParams) -> Result<Opti
         return Ok(None);
     };
 
-    let source_position = crate::types::SourcePosition::from(position);
-
-    let Some(key_context) = backend.get_key_at_position(&file_path, source_position).await else {
+    let Some(key_context) = backend.get_key_at_position(&file_path, position).await else {
         tracing::debug!("No translation key found at position");
         return Ok(None);
     };
@@ -148,7 +159,10 @@ pub async fn handle_hover(backend: &Backend, params: HoverParams) -> Result<Opti
 
         let current_language = backend.state.current_language.lock().await.clone();
         let db = backend.state.db.lock().await;
-        let translations = backend.state.translations.lock().await;
+        let translations = {
+            let translations = backend.state.translations.lock().await;
+            crate::ide::scope::visible_translations(&*db, &translations, &file_path)
+        };
 
         let (key_part, filtered) = key_context.filter_translations(
             &*db,
@@ -167,6 +181,7 @@ pub async fn handle_hover(backend: &Backend, params: HoverParams) -> Result<Opti
         crate::ide::hover::generate_hover_content(
             &*db,
             key,
+            key_context.dynamic,
             &filtered,
             &key_separator,
             current_language.as_deref(),
@@ -210,9 +225,7 @@ pub async fn handle_goto_definition(
         return Ok(None);
     };
 
-    let source_position = crate::types::SourcePosition::from(position);
-
-    let Some(key_context) = backend.get_key_at_position(&file_path, source_position).await else {
+    let Some(key_context) = backend.get_key_at_position(&file_path, position).await else {
         tracing::debug!("No translation key found at position");
         return Ok(None);
     };
@@ -224,9 +237,13 @@ pub async fn handle_goto_definition(
         let namespace_separator = settings.namespace_separator.clone();
         let default_namespace = settings.default_namespace.clone();
         drop(config);
+        let encoding = backend.get_position_encoding().await;
 
         let db = backend.state.db.lock().await;
-        let translations = backend.state.translations.lock().await;
+        let translations = {
+            let translations = backend.state.translations.lock().await;
+            crate::ide::scope::visible_translations(&*db, &translations, &file_path)
+        };
 
         let (key_part, filtered) = key_context.filter_translations(
             &*db,
@@ -236,7 +253,13 @@ pub async fn handle_goto_definition(
         );
 
         let key = crate::interned::TransKey::new(&*db, key_part);
-        crate::ide::goto_definition::find_definitions(&*db, key, &filtered, &key_separator)
+        crate::ide::goto_definition::find_definitions(
+            &*db,
+            key,
+            &filtered,
+            &key_separator,
+            encoding,
+        )
     };
 
     tracing::debug!("Found {} definitions for key", locations.len());
@@ -262,9 +285,7 @@ pub async fn handle_references(
         return Ok(None);
     };
 
-    let source_position = crate::types::SourcePosition::from(position);
-
-    let Some(key_context) = backend.get_key_at_position(&file_path, source_position).await else {
+    let Some(key_context) = backend.get_key_at_position(&file_path, position).await else {
         tracing::debug!("No translation key found at position");
         return Ok(None);
     };
@@ -276,6 +297,7 @@ pub async fn handle_references(
         let namespace_separator = settings.namespace_separator.clone();
         let default_namespace = settings.default_namespace.clone();
         drop(config);
+        let encoding = backend.get_position_encoding().await;
 
         let (target_ns, key_part) = key_context.resolve_key_and_namespace(
             namespace_separator.as_deref(),
@@ -292,6 +314,7 @@ pub async fn handle_references(
             &key_separator,
             namespace_separator.as_deref(),
             default_namespace.as_deref(),
+            encoding,
         )
     };
 
@@ -318,24 +341,36 @@ pub async fn handle_prepare_rename(
     };
 
     let key_separator = backend.get_key_separator().await;
+    let encoding = backend.get_position_encoding().await;
     // Acquire db before source_files to prevent stale IDs after reset_state()
     let db = backend.state.db.lock().await;
     let source_file = {
         let source_files = backend.state.source_files.lock().await;
         source_files.get(&file_path).copied()
     };
-    let source_position = crate::types::SourcePosition::from(position);
 
     if let Some(source_file) = source_file {
+        let line_index = LineIndex::new(source_file.text(&*db), encoding);
+        let source_position = line_index.to_source_position(position);
         let usages = crate::syntax::analyze_source(&*db, source_file, key_separator);
 
         for usage in usages {
             let range = usage.range(&*db);
             if range.contains(source_position) {
+                // Dynamic keys cover several keys, so there is nothing to rename
+                if usage.dynamic(&*db) {
+                    return Ok(None);
+                }
                 let key_text = usage.key(&*db).text(&*db).clone();
+                // A constant argument (e.g., `KEYS.save`) has no quotes to strip
+                let range = if usage.definition_range(&*db).is_some() {
+                    range
+                } else {
+                    range.to_unquoted_range()
+                };
 
                 return Ok(Some(PrepareRenameResponse::RangeWithPlaceholder {
-                    range: range.to_unquoted_range(),
+                    range: line_index.to_lsp_range(range),
                     placeholder: key_text,
                 }));
             }
@@ -347,14 +382,19 @@ pub async fn handle_prepare_rename(
 
         if let Some(translation) =
             translations.iter().find(|t| t.file_path(&*db) == file_path_str.as_ref())
-            && let Some(key) = translation.key_at_position(&*db, source_position)
         {
+            let line_index = LineIndex::new(translation.json_text(&*db), encoding);
+            let Some(key) =
+                translation.key_at_position(&*db, line_index.to_source_position(position))
+            else {
+                return Ok(None);
+            };
             let key_text = key.text(&*db).clone();
 
             // Look up key range in key_ranges
             if let Some(range) = translation.key_ranges(&*db).get(&key_text) {
                 return Ok(Some(PrepareRenameResponse::RangeWithPlaceholder {
-                    range: range.to_unquoted_range(),
+                    range: line_index.to_lsp_range(range.to_unquoted_range()),
                     placeholder: key_text,
                 }));
             }
@@ -382,13 +422,12 @@ pub async fn handle_rename(
         return Ok(None);
     };
 
-    let source_position = crate::types::SourcePosition::from(position);
-
-    let Some(key_context) = backend.get_key_at_position(&file_path, source_position).await else {
+    let Some(key_context) = backend.get_key_at_position(&file_path, position).await else {
         return Ok(None);
     };
 
     let settings = backend.config_manager.lock().await.get_settings().clone();
+    let encoding = backend.get_position_encoding().await;
 
     let (target_ns, _) = key_context.resolve_key_and_namespace(
         settings.namespace_separator.as_deref(),
@@ -396,7 +435,10 @@ pub async fn handle_rename(
     );
 
     let db = backend.state.db.lock().await;
-    let translations = backend.state.translations.lock().await;
+    let translations = {
+        let translations = backend.state.translations.lock().await;
+        crate::ide::scope::global_translations(&*db, &translations)
+    };
     let source_files = backend.state.source_files.lock().await;
 
     let edit = crate::ide::rename::compute_rename_edits(
@@ -409,9 +451,120 @@ pub async fn handle_rename(
         &settings.key_separator,
         settings.namespace_separator.as_deref(),
         settings.default_namespace.as_deref(),
+        encoding,
     );
+    drop(source_files);
     drop(db);
-    drop(translations);
 
     Ok(Some(edit))
 }
+
+pub async fn handle_inlay_hint(
+    backend: &Backend,
+    params: InlayHintParams,
+) -> Result<Option<Vec<InlayHint>>> {
+    let uri = params.text_document.uri;
+
+    tracing::debug!(uri = %uri, "Inlay hint request");
+
+    if !backend.wait_for_translations().await {
+        tracing::debug!("Inlay hint request - translations not indexed yet");
+        return Ok(None);
+    }
+
+    let Some(file_path) = Backend::uri_to_path(&uri) else {
+        return Ok(None);
+    };
+
+    let Some(decorations) = backend.get_translation_decorations(&file_path, None).await else {
+        tracing::debug!("Source file not found: {}", file_path.display());
+        return Ok(None);
+    };
+
+    Ok(Some(crate::ide::virtual_text::to_inlay_hints(decorations, params.range)))
+}
+
+pub async fn handle_document_symbol(
+    backend: &Backend,
+    params: DocumentSymbolParams,
+) -> Result<Option<DocumentSymbolResponse>> {
+    let uri = params.text_document.uri;
+
+    tracing::debug!(uri = %uri, "Document symbol request");
+
+    if !backend.wait_for_translations().await {
+        tracing::debug!("Document symbol request - translations not indexed yet");
+        return Ok(None);
+    }
+
+    let Some(file_path) = Backend::uri_to_path(&uri) else {
+        return Ok(None);
+    };
+    let file_path = file_path.to_string_lossy();
+
+    let settings = backend.config_manager.lock().await.get_settings().clone();
+    let encoding = backend.get_position_encoding().await;
+
+    let db = backend.state.db.lock().await;
+    let source_files: Vec<_> = backend.state.source_files.lock().await.values().copied().collect();
+    let translations = backend.state.translations.lock().await;
+
+    let Some(translation) = translations
+        .iter()
+        .find(|t| !t.is_component_local(&*db) && t.file_path(&*db) == file_path.as_ref())
+        .copied()
+    else {
+        return Ok(None);
+    };
+
+    let used_keys = crate::ide::diagnostics::used_keys_for_translation(
+        &*db,
+        translation,
+        &source_files,
+        &settings.key_separator,
+        settings.namespace_separator.as_deref(),
+        settings.default_namespace.as_deref(),
+    );
+    let symbols = crate::ide::document_symbol::generate_document_symbols(
+        &*db,
+        translation,
+        &translations,
+        &used_keys,
+        &settings.key_separator,
+        encoding,
+    );
+    drop(translations);
+    drop(db);
+
+    Ok(Some(DocumentSymbolResponse::Nested(symbols)))
+}
+
+pub async fn handle_workspace_symbol(
+    backend: &Backend,
+    params: WorkspaceSymbolParams,
+) -> Result<Option<Vec<SymbolInformation>>> {
+    tracing::debug!(query = %params.query, "Workspace symbol request");
+
+    if !backend.wait_for_translations().await {
+        tracing::debug!("Workspace symbol request - translations not indexed yet");
+        return Ok(None);
+    }
+
+    let namespace_separator =
+        backend.config_manager.lock().await.get_settings().namespace_separator.clone();
+    let encoding = backend.get_position_encoding().await;
+
+    let db = backend.state.db.lock().await;
+    let translations = backend.state.translations.lock().await;
+    let symbols = crate::ide::workspace_symbol::search_workspace_symbols(
+        &*db,
+        &translations,
+        &params.query,
+        namespace_separator.as_deref(),
+        encoding,
+    );
+    drop(translations);
+    drop(db);
+
+    Ok(Some(symbols))
+}
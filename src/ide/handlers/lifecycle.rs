@@ -3,7 +3,10 @@
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::{
     CodeActionProviderCapability,
+    CodeLensOptions,
     CompletionOptions,
+    DiagnosticOptions,
+    DiagnosticServerCapabilities,
     ExecuteCommandOptions,
     HoverProviderCapability,
     InitializeParams,
@@ -13,6 +16,7 @@ use tower_lsp::lsp_types::{
     OneOf,
     ProgressParams,
     ProgressParamsValue,
+    ResourceOperationKind,
     ServerCapabilities,
     ServerInfo,
     TextDocumentSyncCapability,
@@ -28,6 +32,7 @@ use tower_lsp::lsp_types::{
 
 use super::super::backend::Backend;
 
+#[allow(clippy::too_many_lines)]
 pub async fn handle_initialize(
     backend: &Backend,
     params: InitializeParams,
@@ -54,6 +59,57 @@ pub async fn handle_initialize(
         .unwrap_or(false);
     *backend.state.code_actions_enabled.lock().await = code_actions_enabled;
 
+    let inlay_hint_refresh_supported = params
+        .capabilities
+        .workspace
+        .as_ref()
+        .and_then(|w| w.inlay_hint.as_ref())
+        .and_then(|i| i.refresh_support)
+        .unwrap_or(false);
+    *backend.state.inlay_hint_refresh_supported.lock().await = inlay_hint_refresh_supported;
+
+    let diagnostic_refresh_supported = params
+        .capabilities
+        .workspace
+        .as_ref()
+        .and_then(|w| w.diagnostic.as_ref())
+        .and_then(|d| d.refresh_support)
+        .unwrap_or(false);
+    *backend.state.diagnostic_refresh_supported.lock().await = diagnostic_refresh_supported;
+
+    // Without refresh, pulled diagnostics of other files would go stale after a translation
+    // edit, so such clients keep receiving published diagnostics
+    let pull_diagnostics_supported = diagnostic_refresh_supported
+        && params.capabilities.text_document.as_ref().is_some_and(|t| t.diagnostic.is_some());
+    *backend.state.pull_diagnostics_supported.lock().await = pull_diagnostics_supported;
+
+    let create_files_supported =
+        params.capabilities.workspace.as_ref().and_then(|w| w.workspace_edit.as_ref()).is_some_and(
+            |e| {
+                e.document_changes == Some(true)
+                    && e.resource_operations
+                        .as_ref()
+                        .is_some_and(|ops| ops.contains(&ResourceOperationKind::Create))
+            },
+        );
+    *backend.state.create_files_supported.lock().await = create_files_supported;
+
+    let watched_files_registration_supported = params
+        .capabilities
+        .workspace
+        .as_ref()
+        .and_then(|w| w.did_change_watched_files.as_ref())
+        .and_then(|d| d.dynamic_registration)
+        .unwrap_or(false);
+    *backend.state.watched_files_registration_supported.lock().await =
+        watched_files_registration_supported;
+
+    let position_encoding = crate::types::PositionEncoding::negotiate(
+        params.capabilities.general.as_ref().and_then(|g| g.position_encodings.as_deref()),
+    );
+    *backend.state.position_encoding.lock().await = position_encoding;
+    tracing::debug!(?position_encoding, "Negotiated position encoding");
+
     // Store workspace folders from initialize params to avoid
     // querying client at runtime (which returns ALL workspace folders).
     let workspace_folders = params.workspace_folders.unwrap_or_else(|| {
@@ -76,7 +132,10 @@ pub async fn handle_initialize(
             version: Some(env!("CARGO_PKG_VERSION").to_string()),
         }),
         capabilities: ServerCapabilities {
-            text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+            position_encoding: Some(position_encoding.to_lsp()),
+            text_document_sync: Some(TextDocumentSyncCapability::Kind(
+                TextDocumentSyncKind::INCREMENTAL,
+            )),
             hover_provider: Some(HoverProviderCapability::Simple(true)),
             completion_provider: Some(CompletionOptions {
                 resolve_provider: Some(false),
@@ -92,13 +151,27 @@ pub async fn handle_initialize(
                 work_done_progress_options: WorkDoneProgressOptions::default(),
             })),
             code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+            code_lens_provider: Some(CodeLensOptions { resolve_provider: Some(true) }),
+            diagnostic_provider: pull_diagnostics_supported.then(|| {
+                DiagnosticServerCapabilities::Options(DiagnosticOptions {
+                    identifier: Some("js-i18n".to_string()),
+                    inter_file_dependencies: true,
+                    workspace_diagnostics: true,
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                })
+            }),
+            inlay_hint_provider: Some(OneOf::Left(true)),
+            document_symbol_provider: Some(OneOf::Left(true)),
+            workspace_symbol_provider: Some(OneOf::Left(true)),
             execute_command_provider: Some(ExecuteCommandOptions {
                 commands: vec![
                     "i18n.editTranslation".to_string(),
                     "i18n.deleteUnusedKeys".to_string(),
+                    "i18n.moveKey".to_string(),
                     "i18n.getKeyAtPosition".to_string(),
                     "i18n.getTranslationValue".to_string(),
                     "i18n.executeClientEditTranslation".to_string(),
+                    "i18n.executeClientShowReferences".to_string(),
                     "i18n.getDecorations".to_string(),
                     "i18n.getCurrentLanguage".to_string(),
                     "i18n.setCurrentLanguage".to_string(),
@@ -219,8 +292,8 @@ pub async fn handle_initialized(backend: &Backend, _: InitializedParams) {
 
         backend.process_pending_updates().await;
         backend.send_diagnostics_to_opened_files().await;
-        backend.send_unused_key_diagnostics().await;
-        backend.send_decorations_changed().await;
+        backend.send_translation_diagnostics().await;
+        backend.send_translations_changed().await;
     }
 
     backend.register_file_watchers().await;
@@ -1,9 +1,12 @@
 //! Workspace-related handlers.
 
+use std::path::Path;
+
 use tower_lsp::lsp_types::{
     DidChangeConfigurationParams,
     DidChangeWatchedFilesParams,
     FileChangeType,
+    Url,
 };
 
 use super::super::backend::Backend;
@@ -53,7 +56,7 @@ pub async fn handle_did_change_watched_files(
     backend: &Backend,
     params: DidChangeWatchedFilesParams,
 ) {
-    let mut translations_changed = false;
+    let mut index_changed = false;
 
     for change in params.changes {
         let Some(file_path) = Backend::uri_to_path(&change.uri) else {
@@ -66,25 +69,71 @@ pub async fn handle_did_change_watched_files(
         }
 
         if backend.is_translation_file(&file_path).await {
+            // Open documents are skipped, since their buffer is the source of truth until closed
+            if backend.state.opened_files.lock().await.contains_key(&change.uri) {
+                continue;
+            }
             tracing::debug!("Translation file changed: {:?}, type: {:?}", file_path, change.typ);
 
             match change.typ {
                 FileChangeType::CREATED | FileChangeType::CHANGED => {
                     backend.reload_translation_file(&file_path).await;
-                    translations_changed = true;
+                    index_changed = true;
                 }
                 FileChangeType::DELETED => {
                     backend.remove_translation_file(&file_path).await;
-                    translations_changed = true;
+                    index_changed = true;
                 }
                 _ => {}
             }
+            continue;
+        }
+
+        // Source file events only come from the server-side watcher.
+        if backend.is_source_file(&file_path).await {
+            index_changed |=
+                handle_source_file_change(backend, &change.uri, &file_path, change.typ).await;
         }
     }
 
-    if translations_changed {
+    if index_changed {
         backend.send_diagnostics_to_opened_files().await;
-        backend.send_unused_key_diagnostics().await;
-        backend.send_decorations_changed().await;
+        backend.send_translation_diagnostics().await;
+        backend.send_translations_changed().await;
+    }
+}
+
+/// Reloads a source file changed on disk. Returns whether the index changed.
+///
+/// Open documents are skipped, since their buffer is the source of truth until closed.
+async fn handle_source_file_change(
+    backend: &Backend,
+    uri: &Url,
+    file_path: &Path,
+    typ: FileChangeType,
+) -> bool {
+    if backend.state.opened_files.lock().await.contains_key(uri) {
+        return false;
+    }
+    tracing::debug!("Source file changed: {:?}, type: {:?}", file_path, typ);
+
+    match typ {
+        FileChangeType::CREATED | FileChangeType::CHANGED => {
+            match tokio::fs::read_to_string(file_path).await {
+                Ok(text) => {
+                    backend.update_source_file(uri, text, false).await;
+                }
+                Err(error) => {
+                    tracing::debug!(%error, "Source file is no longer readable");
+                    backend.remove_source_file(file_path).await;
+                }
+            }
+            true
+        }
+        FileChangeType::DELETED => {
+            backend.remove_source_file(file_path).await;
+            true
+        }
+        _ => false,
     }
 }
@@ -1,10 +1,19 @@
 //! Hover implementation
 
+use std::collections::HashSet;
 use std::fmt::Write as _;
 
 use crate::db::I18nDatabase;
 use crate::framework::PluralStrategy;
-use crate::ide::key_match::is_child_key;
+use crate::ide::icu::{
+    IcuElement,
+    is_i18next_value,
+    parse_icu,
+};
+use crate::ide::key_match::{
+    is_child_key,
+    matches_key_pattern,
+};
 use crate::ide::plural::find_plural_variants;
 use crate::input::translation::Translation;
 use crate::interned::TransKey;
@@ -26,9 +35,19 @@ const MAX_NESTED_KEYS_DISPLAY: usize = 5;
 /// # Reverse Prefix Matching
 /// When no exact match exists, displays a list of child keys (e.g., `nested.key`).
 /// This enables hover information for cases like `t('nested')` when `nested.key` exists.
+///
+/// # Dynamic Keys
+/// When `dynamic`, `key` is a pattern like `errors.*` and every matching key is listed per
+/// language.
+///
+/// # Component-Local Messages
+/// Component-local translations shadow global translations of the same language. They must
+/// precede global ones (see [`crate::ide::scope::visible_translations`]).
+#[allow(clippy::too_many_arguments)]
 pub fn generate_hover_content(
     db: &dyn I18nDatabase,
     key: TransKey<'_>,
+    dynamic: bool,
     translations: &[Translation],
     key_separator: &str,
     current_language: Option<&str>,
@@ -39,32 +58,23 @@ pub fn generate_hover_content(
 
     // Collect translations for this key
     let mut translations_found = Vec::new();
+    // Languages answered by component-local messages, which shadow global translations
+    let mut local_languages = HashSet::new();
 
     for translation in translations {
-        let keys = translation.keys(db);
         let language = translation.language(db);
-
-        // Exact match
-        if let Some(value) = keys.get(key_text) {
-            translations_found.push((language, value.clone()));
-            continue;
-        }
-
-        // Check plural variants
-        let plural_variants = find_plural_variants(key_text, keys, plural_strategy);
-        if !plural_variants.is_empty() {
-            let formatted = format_plural_variants(&plural_variants, key_text);
-            translations_found.push((language, formatted));
+        let is_local = translation.is_component_local(db);
+        if !is_local && local_languages.contains(&language) {
             continue;
         }
 
-        // Reverse prefix match: collect child keys (supports array notation)
-        let nested_keys: Vec<_> =
-            keys.iter().filter(|(k, _)| is_child_key(k, key_text, key_separator)).collect();
-
-        if !nested_keys.is_empty() {
-            let nested_display = format_nested_keys(&nested_keys, key_text);
-            translations_found.push((language, nested_display));
+        if let Some(value) =
+            lookup_hover_value(db, *translation, key_text, dynamic, key_separator, plural_strategy)
+        {
+            if is_local {
+                local_languages.insert(language.clone());
+            }
+            translations_found.push((language, value));
         }
     }
 
@@ -86,6 +96,83 @@ pub fn generate_hover_content(
     Some(content)
 }
 
+/// Look up the value displayed for `key_text` in a single translation.
+///
+/// Tries an exact match, then plural variants, then child keys.
+/// Dynamic key patterns list all matching keys instead.
+fn lookup_hover_value(
+    db: &dyn I18nDatabase,
+    translation: Translation,
+    key_text: &str,
+    dynamic: bool,
+    key_separator: &str,
+    plural_strategy: PluralStrategy,
+) -> Option<String> {
+    let keys = translation.keys(db);
+
+    if dynamic {
+        let matching_keys: Vec<_> =
+            keys.iter().filter(|(k, _)| matches_key_pattern(k, key_text)).collect();
+        return (!matching_keys.is_empty())
+            .then(|| format!("(dynamic)  \n{}", format_key_list(&matching_keys, "")));
+    }
+
+    // Exact match
+    if let Some(value) = keys.get(key_text) {
+        return Some(format_message(value));
+    }
+
+    // Check plural variants
+    let plural_variants = find_plural_variants(key_text, keys, plural_strategy);
+    if !plural_variants.is_empty() {
+        return Some(format_plural_variants(&plural_variants, key_text));
+    }
+
+    // Reverse prefix match: collect child keys (supports array notation)
+    let nested_keys: Vec<_> =
+        keys.iter().filter(|(k, _)| is_child_key(k, key_text, key_separator)).collect();
+
+    (!nested_keys.is_empty()).then(|| format_nested_keys(&nested_keys, key_text))
+}
+
+/// Format a translation value, rendering ICU plural/select branches as tables
+fn format_message(value: &str) -> String {
+    if is_i18next_value(value) {
+        return value.to_string();
+    }
+    let Ok(elements) = parse_icu(value) else {
+        return value.to_string();
+    };
+    if !elements.iter().any(|element| matches!(element, IcuElement::Choice { .. })) {
+        return value.to_string();
+    }
+
+    let mut result: String = elements.iter().map(summarize_icu_element).collect();
+    for element in &elements {
+        let IcuElement::Choice { name, kind, branches } = element else {
+            continue;
+        };
+        let _ = write!(result, "\n\n| {} `{name}` | Message |\n|---|---|", kind.as_str());
+        for branch in branches {
+            let text = branch.text.replace('|', "\\|").replace('\n', " ");
+            let _ = write!(result, "\n| `{}` | {} |", branch.selector, text.trim());
+        }
+    }
+    result
+}
+
+/// Render an ICU element in a single line, collapsing plural/select arguments
+fn summarize_icu_element(element: &IcuElement) -> String {
+    match element {
+        IcuElement::Literal(text) => text.clone(),
+        IcuElement::Argument(name) => format!("{{{name}}}"),
+        IcuElement::Formatted { name, formatter, .. } => format!("{{{name}, {formatter}}}"),
+        IcuElement::Choice { name, kind, .. } => format!("{{{name}, {}}}", kind.as_str()),
+        IcuElement::Pound => "#".to_string(),
+        IcuElement::Tag(name) => format!("<{name}>"),
+    }
+}
+
 /// Format plural variants into a display string
 fn format_plural_variants(variants: &[(&str, &str)], base_key: &str) -> String {
     // Use trailing two spaces + \n for markdown soft line breaks
@@ -103,7 +190,12 @@ fn format_plural_variants(variants: &[(&str, &str)], base_key: &str) -> String {
 
 /// Format nested child keys into a display string
 fn format_nested_keys(nested_keys: &[(&String, &String)], parent_key: &str) -> String {
-    let mut sorted_keys: Vec<_> = nested_keys.iter().collect();
+    format!("{{...}}  \n{}", format_key_list(nested_keys, parent_key))
+}
+
+/// Format keys and values as a sorted list, stripping `strip_prefix` from key names
+fn format_key_list(keys: &[(&String, &String)], strip_prefix: &str) -> String {
+    let mut sorted_keys: Vec<_> = keys.iter().collect();
     sorted_keys.sort_by_key(|(a, _)| *a);
 
     // Use trailing two spaces + \n for markdown soft line breaks
@@ -112,17 +204,17 @@ fn format_nested_keys(nested_keys: &[(&String, &String)], parent_key: &str) -> S
         .take(MAX_NESTED_KEYS_DISPLAY)
         .map(|(k, v)| {
             // Relative key name after stripping the parent key
-            let relative_key = k.strip_prefix(parent_key).unwrap_or(k);
+            let relative_key = k.strip_prefix(strip_prefix).unwrap_or(k);
             let truncated_value = truncate_string(v, MAX_NESTED_VALUE_LENGTH);
             // Wrap key name in backticks (escapes Markdown special characters)
             format!("`{relative_key}`: {truncated_value}")
         })
         .collect();
 
-    let mut result = format!("{{...}}  \n{}", display_keys.join("  \n"));
+    let mut result = display_keys.join("  \n");
 
-    if nested_keys.len() > MAX_NESTED_KEYS_DISPLAY {
-        let remaining = nested_keys.len() - MAX_NESTED_KEYS_DISPLAY;
+    if keys.len() > MAX_NESTED_KEYS_DISPLAY {
+        let remaining = keys.len() - MAX_NESTED_KEYS_DISPLAY;
         let _ = write!(result, "  \n... and {remaining} more");
     }
 
@@ -232,6 +324,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             None,
@@ -269,6 +362,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             None,
@@ -308,6 +402,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             None,
@@ -328,6 +423,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             None,
@@ -363,6 +459,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             None,
@@ -376,6 +473,36 @@ mod tests {
         assert_that!(content, not(contains_substring("**ja**")));
     }
 
+    #[rstest]
+    fn generate_hover_content_with_static_key_containing_asterisk() {
+        let db = I18nDatabaseImpl::default();
+        let en = create_translation(
+            &db,
+            "en",
+            "/test/locales/en.json",
+            HashMap::from([
+                ("notes.*".to_string(), "Footnote".to_string()),
+                ("notes.first".to_string(), "First".to_string()),
+            ]),
+        );
+        let key = TransKey::new(&db, "notes.*".to_string());
+
+        let content = generate_hover_content(
+            &db,
+            key,
+            false,
+            &[en],
+            ".",
+            None,
+            None,
+            PluralStrategy::SuffixBased,
+        )
+        .unwrap();
+
+        assert_that!(content, contains_substring("**en**: Footnote"));
+        assert_that!(content, not(contains_substring("(dynamic)")));
+    }
+
     #[rstest]
     fn generate_hover_content_with_nested_children() {
         let db = I18nDatabaseImpl::default();
@@ -397,6 +524,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             None,
@@ -435,6 +563,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             None,
@@ -471,6 +600,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             None,
@@ -510,6 +640,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             None,
@@ -522,6 +653,49 @@ mod tests {
         assert_that!(content, contains_substring("... and 1 more"));
     }
 
+    #[rstest]
+    fn generate_hover_content_with_dynamic_key() {
+        let db = I18nDatabaseImpl::default();
+
+        let en = create_translation(
+            &db,
+            "en",
+            "/test/locales/en.json",
+            HashMap::from([
+                ("errors.notFound".to_string(), "Not found".to_string()),
+                ("errors.forbidden".to_string(), "Forbidden".to_string()),
+                ("other.key".to_string(), "Other".to_string()),
+            ]),
+        );
+        let ja = create_translation(
+            &db,
+            "ja",
+            "/test/locales/ja.json",
+            HashMap::from([("other.key".to_string(), "その他".to_string())]),
+        );
+
+        let key = TransKey::new(&db, "errors.*".to_string());
+
+        let content = generate_hover_content(
+            &db,
+            key,
+            true,
+            &[en, ja],
+            ".",
+            None,
+            None,
+            PluralStrategy::SuffixBased,
+        )
+        .unwrap();
+
+        assert_that!(content, contains_substring("**Translation Key:** `errors.*`"));
+        assert_that!(content, contains_substring("**en**: (dynamic)"));
+        assert_that!(content, contains_substring("`errors.forbidden`: Forbidden"));
+        assert_that!(content, contains_substring("`errors.notFound`: Not found"));
+        assert_that!(content, not(contains_substring("other.key")));
+        assert_that!(content, not(contains_substring("**ja**")));
+    }
+
     #[rstest]
     fn test_truncate_string() {
         // Short string remains unchanged
@@ -568,6 +742,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             Some("ja"),
@@ -617,6 +792,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             None,
@@ -665,6 +841,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             Some("en"),
@@ -702,6 +879,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             None,
@@ -719,6 +897,70 @@ mod tests {
         assert_that!(content, contains_substring("`_other`: {{count}} items"));
     }
 
+    #[rstest]
+    fn generate_hover_content_with_icu_plural_table() {
+        let db = I18nDatabaseImpl::default();
+        let translation = create_translation(
+            &db,
+            "en",
+            "/test/messages/en.json",
+            HashMap::from([(
+                "cart".to_string(),
+                "You have {count, plural, =0 {no items} one {# item} other {# items}}".to_string(),
+            )]),
+        );
+
+        let key = TransKey::new(&db, "cart".to_string());
+        let content = generate_hover_content(
+            &db,
+            key,
+            false,
+            &[translation],
+            ".",
+            None,
+            None,
+            PluralStrategy::Icu,
+        )
+        .unwrap();
+
+        assert_that!(
+            content,
+            contains_substring(
+                "**en**: You have {count, plural}\n\n| plural `count` | Message |\n|---|---|\n\
+                 | `=0` | no items |\n| `one` | # item |\n| `other` | # items |"
+            )
+        );
+    }
+
+    #[rstest]
+    #[case::simple_argument("Hello {name}")]
+    #[case::syntax_error("{count, plural, one {# item}}")]
+    #[case::i18next("{{count}} items")]
+    fn generate_hover_content_keeps_raw_value(#[case] value: &str) {
+        let db = I18nDatabaseImpl::default();
+        let translation = create_translation(
+            &db,
+            "en",
+            "/test/messages/en.json",
+            HashMap::from([("message".to_string(), value.to_string())]),
+        );
+
+        let key = TransKey::new(&db, "message".to_string());
+        let content = generate_hover_content(
+            &db,
+            key,
+            false,
+            &[translation],
+            ".",
+            None,
+            None,
+            PluralStrategy::Icu,
+        )
+        .unwrap();
+
+        assert_that!(content, contains_substring(format!("**en**: {value}\n\n")));
+    }
+
     #[rstest]
     fn generate_hover_content_with_ordinal_plural_variants() {
         let db = I18nDatabaseImpl::default();
@@ -742,6 +984,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             None,
@@ -780,6 +1023,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             None,
@@ -815,6 +1059,7 @@ mod tests {
         let content = generate_hover_content(
             &db,
             key,
+            false,
             &translations,
             ".",
             None,
@@ -831,4 +1076,45 @@ mod tests {
         assert_that!(content, contains_substring("apple"));
         assert_that!(content, contains_substring("banana"));
     }
+
+    #[rstest]
+    fn generate_hover_content_component_local_shadows_global() {
+        let db = I18nDatabaseImpl::default();
+
+        let local = create_translation(
+            &db,
+            "en",
+            "/test/src/App.vue",
+            HashMap::from([("hello".to_string(), "Hi there".to_string())]),
+        );
+        let global_en = create_translation(
+            &db,
+            "en",
+            "/test/locales/en.json",
+            HashMap::from([("hello".to_string(), "Hello".to_string())]),
+        );
+        let global_ja = create_translation(
+            &db,
+            "ja",
+            "/test/locales/ja.json",
+            HashMap::from([("hello".to_string(), "こんにちは".to_string())]),
+        );
+
+        let key = TransKey::new(&db, "hello".to_string());
+        let content = generate_hover_content(
+            &db,
+            key,
+            false,
+            &[local, global_en, global_ja],
+            ".",
+            None,
+            None,
+            PluralStrategy::SuffixBased,
+        )
+        .unwrap();
+
+        assert_that!(content, contains_substring("**en**: Hi there"));
+        assert_that!(content, not(contains_substring("**en**: Hello")));
+        assert_that!(content, contains_substring("**ja**: こんにちは"));
+    }
 }
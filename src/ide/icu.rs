@@ -0,0 +1,572 @@
+//! ICU `MessageFormat` parsing.
+//!
+//! Parses translation values used by next-intl, react-intl, svelte-i18n and vue-i18n into a
+//! small syntax tree, reporting the first syntax error.
+
+use tower_lsp::lsp_types::{
+    Diagnostic,
+    NumberOrString,
+};
+
+use crate::config::Severity;
+use crate::db::I18nDatabase;
+use crate::input::translation::Translation;
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+};
+
+/// Formatters accepted in `{name, <formatter>, ...}` besides plural and select.
+const FORMATTERS: &[&str] = &["number", "date", "time", "spellout", "ordinal", "duration"];
+
+/// Plural categories accepted as selectors (besides exact `=N` matches).
+const PLURAL_CATEGORIES: &[&str] = &["zero", "one", "two", "few", "many", "other"];
+
+/// An element of a parsed message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IcuElement {
+    /// Plain text, with quoting resolved
+    Literal(String),
+    /// `{name}`
+    Argument(String),
+    /// `{name, number}`, `{when, date, short}`
+    Formatted { name: String, formatter: String, style: Option<String> },
+    /// `{count, plural, one {...} other {...}}` and select variants
+    Choice { name: String, kind: ChoiceKind, branches: Vec<IcuBranch> },
+    /// `#` inside a plural branch
+    Pound,
+    /// Rich text tag (`<b>`, `<0>`, `<br/>`); closing tags are kept as literals
+    Tag(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChoiceKind {
+    Plural,
+    SelectOrdinal,
+    Select,
+}
+
+impl ChoiceKind {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Plural => "plural",
+            Self::SelectOrdinal => "selectordinal",
+            Self::Select => "select",
+        }
+    }
+}
+
+/// A `selector {message}` branch of a plural or select argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcuBranch {
+    pub selector: String,
+    /// Byte offset of the selector in the message
+    pub offset: usize,
+    pub elements: Vec<IcuElement>,
+    /// Source text of the branch message
+    pub text: String,
+}
+
+/// A syntax error at a byte offset in the message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcuError {
+    pub message: String,
+    pub offset: usize,
+}
+
+impl IcuError {
+    fn new(message: impl Into<String>, offset: usize) -> Self {
+        Self { message: message.into(), offset }
+    }
+}
+
+/// Parses an ICU message.
+///
+/// # Errors
+/// Returns the first syntax error: unbalanced braces, unknown formatters, invalid selectors,
+/// or a plural/select argument without an `other` branch.
+pub fn parse_icu(message: &str) -> Result<Vec<IcuElement>, IcuError> {
+    let mut parser = Parser { src: message, pos: 0 };
+    let elements = parser.parse_message(false, false)?;
+    if parser.peek().is_some() {
+        return Err(IcuError::new("Unmatched '}'", parser.pos));
+    }
+    Ok(elements)
+}
+
+/// Returns `true` for values written in i18next syntax (`{{name}}`), which are not ICU.
+#[must_use]
+pub fn is_i18next_value(value: &str) -> bool {
+    value.contains("{{")
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.src.get(self.pos..).and_then(|rest| rest.chars().next())
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.src.get(self.pos..).and_then(|rest| rest.chars().nth(n))
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &str {
+        let start = self.pos;
+        while self.peek().is_some_and(&predicate) {
+            self.bump();
+        }
+        self.src.get(start..self.pos).unwrap_or_default()
+    }
+
+    /// Parses elements until the end of input, or until a `}` (left unconsumed) when `nested`.
+    fn parse_message(
+        &mut self,
+        nested: bool,
+        in_plural: bool,
+    ) -> Result<Vec<IcuElement>, IcuError> {
+        let mut elements = Vec::new();
+        let mut literal = String::new();
+
+        while let Some(c) = self.peek() {
+            match c {
+                '}' if nested => break,
+                '}' => return Err(IcuError::new("Unmatched '}'", self.pos)),
+                '{' => {
+                    flush_literal(&mut literal, &mut elements);
+                    let element = self.parse_argument(in_plural)?;
+                    elements.push(element);
+                }
+                '#' if in_plural => {
+                    self.bump();
+                    flush_literal(&mut literal, &mut elements);
+                    elements.push(IcuElement::Pound);
+                }
+                '\'' => self.parse_quoted(&mut literal, in_plural),
+                '<' => {
+                    if let Some(name) = self.parse_tag(&mut literal) {
+                        flush_literal(&mut literal, &mut elements);
+                        elements.push(IcuElement::Tag(name));
+                    }
+                }
+                _ => {
+                    self.bump();
+                    literal.push(c);
+                }
+            }
+        }
+
+        flush_literal(&mut literal, &mut elements);
+        Ok(elements)
+    }
+
+    /// An apostrophe quotes a following special character until the next apostrophe;
+    /// `''` is a literal apostrophe.
+    fn parse_quoted(&mut self, literal: &mut String, in_plural: bool) {
+        self.bump();
+        match self.peek() {
+            Some('\'') => {
+                self.bump();
+                literal.push('\'');
+            }
+            Some(c) if matches!(c, '{' | '}' | '<') || (c == '#' && in_plural) => {
+                while let Some(c) = self.bump() {
+                    if c == '\'' {
+                        if self.peek() == Some('\'') {
+                            self.bump();
+                        } else {
+                            break;
+                        }
+                    }
+                    literal.push(c);
+                }
+            }
+            _ => literal.push('\''),
+        }
+    }
+
+    /// Parses the name of `<name>` or `<name/>` at `<`. Closing tags and other text are appended to
+    /// `literal`.
+    fn parse_tag(&mut self, literal: &mut String) -> Option<String> {
+        let start = self.pos;
+        let closing = self.peek_nth(1) == Some('/');
+        self.pos += if closing { 2 } else { 1 };
+        let name = self.take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-')).to_string();
+        let self_closing = !closing && self.peek() == Some('/');
+        if self_closing {
+            self.bump();
+        }
+        if name.is_empty() || self.peek() != Some('>') {
+            self.pos = start;
+            self.bump();
+            literal.push('<');
+            return None;
+        }
+        self.bump();
+
+        if closing {
+            literal.push_str(self.src.get(start..self.pos).unwrap_or_default());
+            return None;
+        }
+        Some(name)
+    }
+
+    /// Parses an argument starting at `{`.
+    fn parse_argument(&mut self, in_plural: bool) -> Result<IcuElement, IcuError> {
+        let open = self.pos;
+        self.bump();
+        self.skip_whitespace();
+
+        let name = self.take_while(|c| !matches!(c, ',' | '{' | '}')).trim_end().to_string();
+        if name.is_empty() {
+            return Err(match self.peek() {
+                None => IcuError::new("Unclosed '{'", open),
+                _ => IcuError::new("Missing argument name", open),
+            });
+        }
+
+        match self.bump() {
+            Some('}') => return Ok(IcuElement::Argument(name)),
+            Some(',') => {}
+            Some(_) => return Err(IcuError::new("Unexpected '{' in argument", self.pos - 1)),
+            None => return Err(IcuError::new("Unclosed '{'", open)),
+        }
+
+        self.skip_whitespace();
+        let formatter_start = self.pos;
+        let formatter = self.take_while(|c| c.is_alphanumeric() || c == '_').to_string();
+        self.skip_whitespace();
+
+        let kind = match formatter.as_str() {
+            "plural" => ChoiceKind::Plural,
+            "selectordinal" => ChoiceKind::SelectOrdinal,
+            "select" => ChoiceKind::Select,
+            "" => return Err(IcuError::new("Missing formatter after ','", formatter_start)),
+            _ if FORMATTERS.contains(&formatter.as_str()) => {
+                let style = self.parse_style(open)?;
+                return Ok(IcuElement::Formatted { name, formatter, style });
+            }
+            _ => {
+                return Err(IcuError::new(
+                    format!("Unknown formatter '{formatter}'"),
+                    formatter_start,
+                ));
+            }
+        };
+
+        if self.bump() != Some(',') {
+            return Err(IcuError::new(format!("Expected ',' after '{formatter}'"), self.pos));
+        }
+
+        let branches = self.parse_branches(kind, in_plural, open)?;
+        if !branches.iter().any(|branch| branch.selector == "other") {
+            return Err(IcuError::new(format!("Missing 'other' branch in {formatter}"), open));
+        }
+        Ok(IcuElement::Choice { name, kind, branches })
+    }
+
+    /// Parses the optional style of a formatted argument, up to and including the closing `}`.
+    fn parse_style(&mut self, open: usize) -> Result<Option<String>, IcuError> {
+        match self.bump() {
+            Some('}') => return Ok(None),
+            Some(',') => {}
+            _ => return Err(IcuError::new("Unclosed '{'", open)),
+        }
+
+        let start = self.pos;
+        let mut depth = 0_usize;
+        while let Some(c) = self.bump() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 0 => {
+                    let style = self.src.get(start..self.pos - 1).unwrap_or_default().trim();
+                    return Ok((!style.is_empty()).then(|| style.to_string()));
+                }
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        Err(IcuError::new("Unclosed '{'", open))
+    }
+
+    /// Parses `selector {message}` pairs up to and including the closing `}` of the argument.
+    fn parse_branches(
+        &mut self,
+        kind: ChoiceKind,
+        in_plural: bool,
+        open: usize,
+    ) -> Result<Vec<IcuBranch>, IcuError> {
+        let mut branches: Vec<IcuBranch> = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('}') => {
+                    self.bump();
+                    return Ok(branches);
+                }
+                None => return Err(IcuError::new("Unclosed '{'", open)),
+                _ => {}
+            }
+
+            let selector_start = self.pos;
+            let selector =
+                self.take_while(|c| !c.is_whitespace() && !matches!(c, '{' | '}')).to_string();
+
+            if kind != ChoiceKind::Select {
+                // `offset:1` applies to the whole plural
+                if selector.starts_with("offset:") {
+                    continue;
+                }
+                let is_exact = selector.strip_prefix('=').is_some_and(|n| n.parse::<f64>().is_ok());
+                if !is_exact && !PLURAL_CATEGORIES.contains(&selector.as_str()) {
+                    return Err(IcuError::new(
+                        format!("Invalid {} selector '{selector}'", kind.as_str()),
+                        selector_start,
+                    ));
+                }
+            }
+            if branches.iter().any(|branch| branch.selector == selector) {
+                return Err(IcuError::new(
+                    format!("Duplicate selector '{selector}'"),
+                    selector_start,
+                ));
+            }
+
+            self.skip_whitespace();
+            let branch_open = self.pos;
+            if self.bump() != Some('{') {
+                return Err(IcuError::new(
+                    format!("Expected '{{' after selector '{selector}'"),
+                    branch_open,
+                ));
+            }
+
+            let text_start = self.pos;
+            let elements = self.parse_message(true, in_plural || kind != ChoiceKind::Select)?;
+            let text = self.src.get(text_start..self.pos).unwrap_or_default().to_string();
+            if self.bump() != Some('}') {
+                return Err(IcuError::new("Unclosed '{'", branch_open));
+            }
+
+            branches.push(IcuBranch { selector, offset: selector_start, elements, text });
+        }
+    }
+}
+
+fn flush_literal(literal: &mut String, elements: &mut Vec<IcuElement>) {
+    if !literal.is_empty() {
+        elements.push(IcuElement::Literal(std::mem::take(literal)));
+    }
+}
+
+/// Generates diagnostics for ICU syntax errors in translation values.
+///
+/// Values in i18next syntax (`{{name}}`) are skipped.
+pub fn generate_icu_syntax_diagnostics(
+    db: &dyn I18nDatabase,
+    translation: Translation,
+    severity: Severity,
+    encoding: PositionEncoding,
+) -> Vec<Diagnostic> {
+    let keys = translation.keys(db);
+    let value_ranges = translation.value_ranges(db);
+    let line_index = LineIndex::new(translation.json_text(db), encoding);
+
+    let mut sorted_keys: Vec<&String> = keys.keys().collect();
+    sorted_keys.sort();
+
+    sorted_keys
+        .into_iter()
+        .filter_map(|key| {
+            let value = keys.get(key)?;
+            if is_i18next_value(value) {
+                return None;
+            }
+            let error = parse_icu(value).err()?;
+            let range = value_ranges.get(key)?;
+
+            Some(Diagnostic {
+                range: line_index.to_lsp_range(*range),
+                severity: Some(severity.to_lsp()),
+                code: Some(NumberOrString::String("icu-syntax-error".to_string())),
+                code_description: None,
+                source: Some("js-i18n".to_string()),
+                message: format!("Invalid ICU message for '{key}': {}", error.message),
+                related_information: None,
+                tags: None,
+                data: Some(serde_json::json!({ "key": key, "offset": error.offset })),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use std::collections::HashMap;
+
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+    use crate::db::I18nDatabaseImpl;
+
+    fn literal(text: &str) -> IcuElement {
+        IcuElement::Literal(text.to_string())
+    }
+
+    #[rstest]
+    fn parses_arguments_and_literals() {
+        assert_that!(
+            parse_icu("Hello {name}, you owe {amount, number, ::currency/EUR}"),
+            ok(elements_are![
+                eq(&literal("Hello ")),
+                eq(&IcuElement::Argument("name".to_string())),
+                eq(&literal(", you owe ")),
+                eq(&IcuElement::Formatted {
+                    name: "amount".to_string(),
+                    formatter: "number".to_string(),
+                    style: Some("::currency/EUR".to_string()),
+                })
+            ])
+        );
+    }
+
+    #[rstest]
+    fn parses_plural_branches() {
+        let elements =
+            parse_icu("{count, plural, =0 {No items} one {# item} other {# items}}").unwrap();
+
+        let [IcuElement::Choice { name, kind, branches }] = elements.as_slice() else {
+            panic!("expected a single choice, got {elements:?}");
+        };
+        assert_that!(name, eq("count"));
+        assert_that!(*kind, eq(ChoiceKind::Plural));
+        assert_that!(
+            branches.iter().map(|b| (b.selector.as_str(), b.text.as_str())).collect::<Vec<_>>(),
+            eq(&vec![("=0", "No items"), ("one", "# item"), ("other", "# items")])
+        );
+        assert_that!(
+            branches.get(1).map(|b| b.elements.clone()),
+            some(elements_are![eq(&IcuElement::Pound), eq(&literal(" item"))])
+        );
+    }
+
+    #[rstest]
+    fn parses_nested_select_in_plural() {
+        let result = parse_icu(
+            "{count, plural, offset:1 one {{gender, select, male {He} other {They}} and #} other {#}}",
+        );
+        assert_that!(result, ok(len(eq(1))));
+    }
+
+    #[rstest]
+    #[case::quoted_braces("Use '{braces}' here", "Use {braces} here")]
+    #[case::escaped_apostrophe("It''s", "It's")]
+    #[case::plain_apostrophe("Don't", "Don't")]
+    #[case::less_than("a < b", "a < b")]
+    fn parses_literals(#[case] message: &str, #[case] expected: &str) {
+        assert_that!(parse_icu(message), ok(elements_are![eq(&literal(expected))]));
+    }
+
+    #[rstest]
+    fn parses_tags() {
+        assert_that!(
+            parse_icu("<b>{name}</b><br/>"),
+            ok(elements_are![
+                eq(&IcuElement::Tag("b".to_string())),
+                eq(&IcuElement::Argument("name".to_string())),
+                eq(&literal("</b>")),
+                eq(&IcuElement::Tag("br".to_string()))
+            ])
+        );
+    }
+
+    #[rstest]
+    #[case::unclosed("Hello {name", "Unclosed '{'", 6)]
+    #[case::unmatched("Hello name}", "Unmatched '}'", 10)]
+    #[case::empty_argument("Hello {}", "Missing argument name", 6)]
+    #[case::unknown_formatter("{n, currency}", "Unknown formatter 'currency'", 4)]
+    #[case::missing_other("{n, plural, one {item}}", "Missing 'other' branch in plural", 0)]
+    #[case::missing_other_select("{g, select, male {He}}", "Missing 'other' branch in select", 0)]
+    #[case::invalid_selector(
+        "{n, plural, some {x} other {y}}",
+        "Invalid plural selector 'some'",
+        12
+    )]
+    #[case::duplicate_selector("{g, select, a {x} a {y} other {z}}", "Duplicate selector 'a'", 18)]
+    #[case::missing_branch_brace("{g, select, other x}", "Expected '{' after selector 'other'", 18)]
+    #[case::unclosed_branch("{g, select, other {x", "Unclosed '{'", 18)]
+    fn reports_syntax_errors(
+        #[case] message: &str,
+        #[case] expected_message: &str,
+        #[case] expected_offset: usize,
+    ) {
+        assert_that!(
+            parse_icu(message),
+            err(all![
+                field!(IcuError.message, eq(expected_message)),
+                field!(IcuError.offset, eq(&expected_offset))
+            ])
+        );
+    }
+
+    #[rstest]
+    fn syntax_diagnostics_skip_i18next_values() {
+        let db = I18nDatabaseImpl::default();
+        let range = crate::types::SourceRange {
+            start: crate::types::SourcePosition { line: 1, character: 10 },
+            end: crate::types::SourcePosition { line: 1, character: 20 },
+        };
+        let translation = Translation::new(
+            &db,
+            "en".to_string(),
+            None,
+            "/locales/en.json".to_string(),
+            HashMap::from([
+                ("broken".to_string(), "{count, plural, one {# item}}".to_string()),
+                ("i18next".to_string(), "Hello {{name}}".to_string()),
+                ("valid".to_string(), "Hello {name}".to_string()),
+            ]),
+            "{}".to_string(),
+            HashMap::new(),
+            HashMap::from([
+                ("broken".to_string(), range),
+                ("i18next".to_string(), range),
+                ("valid".to_string(), range),
+            ]),
+        );
+
+        let diagnostics = generate_icu_syntax_diagnostics(
+            &db,
+            translation,
+            Severity::Error,
+            PositionEncoding::Utf16,
+        );
+
+        assert_that!(
+            diagnostics,
+            elements_are![field!(
+                Diagnostic.message,
+                eq("Invalid ICU message for 'broken': Missing 'other' branch in plural")
+            )]
+        );
+    }
+}
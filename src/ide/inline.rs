@@ -0,0 +1,459 @@
+//! Inline a translation: replace a `t('key')` call with the translated text.
+//!
+//! The reverse of [`crate::ide::extract`], for removing i18n from screens that don't need it.
+//! When the call is the key's only reference, a second action also deletes the key from the
+//! translation files.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use tower_lsp::lsp_types::{
+    CodeAction,
+    CodeActionKind,
+    CodeActionOrCommand,
+    TextEdit,
+    Url,
+    WorkspaceEdit,
+};
+use tree_sitter::Node;
+
+use crate::db::I18nDatabase;
+use crate::ide::code_actions::delete_key_edits;
+use crate::ide::interpolation::parse_placeholders;
+use crate::ide::namespace::{
+    filter_by_namespace,
+    resolve_usage_namespace,
+};
+use crate::input::source::{
+    ProgrammingLanguage,
+    SourceFile,
+};
+use crate::input::translation::Translation;
+use crate::syntax::parsed::ParsedSource;
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+    SourcePosition,
+    SourceRange,
+};
+
+/// Settings that affect key resolution and edits.
+#[derive(Debug, Clone, Copy)]
+pub struct InlineOptions<'a> {
+    pub key_separator: &'a str,
+    pub namespace_separator: Option<&'a str>,
+    pub default_namespace: Option<&'a str>,
+    pub encoding: PositionEncoding,
+}
+
+/// Where the translation call appears, which decides how the text is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CallContext {
+    /// Any JS/TS expression: `t('key')` becomes `'text'`
+    Expression,
+    /// `{t('key')}` as JSX child, Svelte text or Vue `{{ }}` text: the braces become text
+    MarkupText,
+    /// `title={t('key')}` in JSX or Svelte: the braces become `"text"`
+    MarkupAttribute,
+}
+
+/// The translation call at a key usage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CallSite {
+    context: CallContext,
+    /// Range of the call, or of its enclosing braces in markup
+    range: SourceRange,
+    /// Quote of the key argument
+    quote: char,
+}
+
+/// Generates the inline actions for the key usage at `position`.
+///
+/// The text is taken from `language`. Keys whose value has interpolation variables or rich text
+/// tags, plural keys and dynamic keys are not offered, since their text can't be written as a
+/// literal. `source_files` are searched for other references of the key.
+#[must_use]
+#[allow(clippy::too_many_arguments, clippy::implicit_hasher)]
+pub fn generate_inline_code_actions(
+    db: &dyn I18nDatabase,
+    source_file: SourceFile,
+    uri: &Url,
+    position: SourcePosition,
+    translations: &[Translation],
+    language: &str,
+    source_files: &HashMap<PathBuf, SourceFile>,
+    options: &InlineOptions<'_>,
+) -> Vec<CodeActionOrCommand> {
+    let Some(usage) = crate::syntax::key_usage_at_position(
+        db,
+        source_file,
+        position,
+        options.key_separator.to_string(),
+    ) else {
+        return Vec::new();
+    };
+    if usage.dynamic(db) {
+        return Vec::new();
+    }
+
+    let (namespace, key_part) =
+        resolve_usage_namespace(db, usage, options.namespace_separator, options.default_namespace);
+    let filtered = filter_by_namespace(db, translations, namespace.as_deref());
+    let Some(value) = filtered
+        .iter()
+        .filter(|t| t.language(db) == language)
+        .find_map(|t| t.keys(db).get(&key_part))
+    else {
+        return Vec::new();
+    };
+    let placeholders = parse_placeholders(value);
+    if !placeholders.variables.is_empty() || !placeholders.tags.is_empty() {
+        return Vec::new();
+    }
+
+    let text = source_file.text(db);
+    let Some(call) = find_call_site(text, source_file.language(db), usage.range(db)) else {
+        return Vec::new();
+    };
+    let line_index = LineIndex::new(text, options.encoding);
+    let source_edit = TextEdit {
+        range: line_index.to_lsp_range(call.range),
+        new_text: replacement(&call, value),
+    };
+
+    let inline_changes = HashMap::from([(uri.clone(), vec![source_edit])]);
+    let mut actions =
+        vec![inline_action(format!("Inline translation '{key_part}'"), inline_changes.clone())];
+
+    let references = crate::ide::references::find_references(
+        db,
+        &key_part,
+        namespace.as_deref(),
+        source_files,
+        options.key_separator,
+        options.namespace_separator,
+        options.default_namespace,
+        options.encoding,
+    );
+    if references.len() <= 1 {
+        // Component-local messages are a block of the component file, not a whole file
+        let deletable: Vec<&Translation> =
+            filtered.iter().copied().filter(|t| !t.is_component_local(db)).collect();
+        let mut changes = delete_key_edits(db, &key_part, &deletable, options.key_separator);
+        if !changes.is_empty() {
+            changes.extend(inline_changes);
+            actions.push(inline_action(
+                format!("Inline translation '{key_part}' and delete the key"),
+                changes,
+            ));
+        }
+    }
+    actions
+}
+
+fn inline_action(title: String, changes: HashMap<Url, Vec<TextEdit>>) -> CodeActionOrCommand {
+    CodeActionOrCommand::CodeAction(CodeAction {
+        title,
+        kind: Some(CodeActionKind::REFACTOR_INLINE),
+        edit: Some(WorkspaceEdit { changes: Some(changes), ..Default::default() }),
+        ..Default::default()
+    })
+}
+
+/// Writes `value` for the call's context, falling back to a string literal where markup would
+/// need escaping.
+fn replacement(call: &CallSite, value: &str) -> String {
+    let literal = string_literal(value, call.quote);
+    let needs_escaping = value.contains(['{', '}', '<', '>', '&']) || value.trim() != value;
+    match call.context {
+        CallContext::Expression => literal,
+        CallContext::MarkupText if needs_escaping => format!("{{{literal}}}"),
+        CallContext::MarkupText => value.to_string(),
+        CallContext::MarkupAttribute if needs_escaping || value.contains('"') => {
+            format!("{{{literal}}}")
+        }
+        CallContext::MarkupAttribute => format!("\"{value}\""),
+    }
+}
+
+/// Quotes `value` as a JS string literal.
+fn string_literal(value: &str, quote: char) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push(quote);
+    for c in value.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c == quote => {
+                literal.push('\\');
+                literal.push(c);
+            }
+            c => literal.push(c),
+        }
+    }
+    literal.push(quote);
+    literal
+}
+
+/// Finds the innermost call whose first argument contains the key usage at `key_range`.
+///
+/// Vue and Svelte files are searched in their virtual document and mapped back.
+fn find_call_site(
+    text: &str,
+    language: ProgrammingLanguage,
+    key_range: SourceRange,
+) -> Option<CallSite> {
+    let parsed = ParsedSource::parse(text, language)?;
+    let call = parsed.call_with_key_at(key_range)?;
+
+    let quote = call
+        .child_by_field_name("arguments")
+        .and_then(|args| args.named_child(0))
+        .and_then(|arg| parsed.text(arg).chars().next())
+        .filter(|c| matches!(c, '\'' | '"' | '`'))
+        .unwrap_or('\'');
+    let call_range = parsed.range(call);
+
+    let (context, range) = if parsed.is_embedded() {
+        template_context(text, language, call_range)
+    } else {
+        jsx_context(call)
+    }
+    .unwrap_or((CallContext::Expression, call_range));
+    Some(CallSite { context, range, quote })
+}
+
+/// Returns the JSX expression container around the call, if any.
+fn jsx_context(call: Node<'_>) -> Option<(CallContext, SourceRange)> {
+    let container = call.parent().filter(|parent| parent.kind() == "jsx_expression")?;
+    let context = if container.parent()?.kind() == "jsx_attribute" {
+        CallContext::MarkupAttribute
+    } else {
+        CallContext::MarkupText
+    };
+    Some((context, SourceRange::from_node(&container)))
+}
+
+/// Returns the template braces around the call: `{{ }}` in Vue, `{ }` in Svelte markup.
+fn template_context(
+    text: &str,
+    language: ProgrammingLanguage,
+    call_range: SourceRange,
+) -> Option<(CallContext, SourceRange)> {
+    let offsets: Vec<usize> =
+        std::iter::once(0).chain(text.match_indices('\n').map(|(i, _)| i + 1)).collect();
+    let offset = |position: SourcePosition| {
+        offsets.get(position.line as usize).map(|start| start + position.character as usize)
+    };
+    let (start, end) = (offset(call_range.start)?, offset(call_range.end)?);
+    if is_in_script(text, start) {
+        return None;
+    }
+
+    let before = text.get(..start)?.trim_end();
+    let after = text.get(end..)?.trim_start();
+    let (open, close) =
+        if language == ProgrammingLanguage::Vue { ("{{", "}}") } else { ("{", "}") };
+    if !before.ends_with(open) || !after.starts_with(close) {
+        return None;
+    }
+    let open_start = before.len() - open.len();
+    let close_end = text.len() - after.len() + close.len();
+
+    let is_attribute =
+        language == ProgrammingLanguage::Svelte && text.get(..open_start)?.ends_with('=');
+    let context = if is_attribute { CallContext::MarkupAttribute } else { CallContext::MarkupText };
+    let position = |offset: usize| {
+        let line = offsets.partition_point(|&start| start <= offset).saturating_sub(1);
+        let line_start = offsets.get(line).copied().unwrap_or_default();
+        #[allow(clippy::cast_possible_truncation)]
+        SourcePosition { line: line as u32, character: (offset - line_start) as u32 }
+    };
+    Some((context, SourceRange { start: position(open_start), end: position(close_end) }))
+}
+
+fn is_in_script(text: &str, offset: usize) -> bool {
+    let before = text.get(..offset).unwrap_or_default();
+    before
+        .rfind("<script")
+        .is_some_and(|open| before.rfind("</script").is_none_or(|close| close < open))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing, clippy::panic)]
+mod tests {
+    use std::path::Path;
+
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+    use crate::db::I18nDatabaseImpl;
+    use crate::input::translation::load_translation_from_content;
+
+    const OPTIONS: InlineOptions<'static> = InlineOptions {
+        key_separator: ".",
+        namespace_separator: None,
+        default_namespace: None,
+        encoding: PositionEncoding::Utf16,
+    };
+
+    const EN: &str = r#"{
+  "save": "Save changes",
+  "quote": "Don't \"panic\"",
+  "greeting": "Hello {{name}}",
+  "items_one": "One item",
+  "items_other": "Many items"
+}"#;
+
+    struct Fixture {
+        db: I18nDatabaseImpl,
+        source_files: HashMap<PathBuf, SourceFile>,
+        translations: Vec<Translation>,
+    }
+
+    impl Fixture {
+        fn new(sources: &[(&str, &str)]) -> Self {
+            let db = I18nDatabaseImpl::default();
+            let source_files = sources
+                .iter()
+                .map(|(path, text)| {
+                    let uri = Url::from_file_path(path).unwrap();
+                    let language = ProgrammingLanguage::from_uri(path).unwrap();
+                    let file = SourceFile::new(&db, uri.to_string(), (*text).to_string(), language);
+                    (PathBuf::from(path), file)
+                })
+                .collect();
+            let translations = vec![
+                load_translation_from_content(&db, Path::new("/app/locales/en.json"), EN, ".")
+                    .unwrap(),
+                load_translation_from_content(
+                    &db,
+                    Path::new("/app/locales/ja.json"),
+                    r#"{ "save": "変更を保存" }"#,
+                    ".",
+                )
+                .unwrap(),
+            ];
+            Self { db, source_files, translations }
+        }
+
+        /// Runs the actions at the first occurrence of `needle` in `path` and returns their
+        /// titles and the replacement text in the source file.
+        fn inline(&self, path: &str, needle: &str) -> Vec<(String, String, usize)> {
+            let source_file = self.source_files[Path::new(path)];
+            let text = source_file.text(&self.db);
+            let offset = text.find(needle).unwrap();
+            let line = text[..offset].matches('\n').count();
+            let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+            #[allow(clippy::cast_possible_truncation)]
+            let position =
+                SourcePosition { line: line as u32, character: (offset - line_start) as u32 };
+            let uri = Url::from_file_path(path).unwrap();
+
+            generate_inline_code_actions(
+                &self.db,
+                source_file,
+                &uri,
+                position,
+                &self.translations,
+                "en",
+                &self.source_files,
+                &OPTIONS,
+            )
+            .into_iter()
+            .map(|action| {
+                let CodeActionOrCommand::CodeAction(action) = action else {
+                    panic!("expected a code action");
+                };
+                let changes = action.edit.unwrap().changes.unwrap();
+                let source_edit = changes[&uri][0].new_text.clone();
+                (action.title, source_edit, changes.len())
+            })
+            .collect()
+        }
+    }
+
+    #[rstest]
+    #[case::expression("const a = t('save');", "'Save changes'")]
+    #[case::double_quote(r#"const a = t("quote");"#, r#""Don't \"panic\"""#)]
+    #[case::jsx_text("const A = () => <p>{t('save')}</p>;", "Save changes")]
+    #[case::jsx_attribute("const A = () => <p title={t('save')} />;", "\"Save changes\"")]
+    #[case::jsx_attribute_with_quotes(
+        "const A = () => <p title={t('quote')} />;",
+        r#"{'Don\'t "panic"'}"#
+    )]
+    fn replaces_call_with_text(#[case] source: &str, #[case] expected: &str) {
+        let fixture = Fixture::new(&[("/app/src/a.tsx", source)]);
+
+        let key_argument = &source[source.find("t(").unwrap() + 2..];
+        let replacements: Vec<String> = fixture
+            .inline("/app/src/a.tsx", key_argument)
+            .into_iter()
+            .map(|(_, edit, _)| edit)
+            .collect();
+
+        assert_that!(replacements, contains(eq(expected)));
+    }
+
+    #[rstest]
+    fn offers_delete_when_call_is_the_only_reference() {
+        let fixture = Fixture::new(&[("/app/src/a.ts", "t('save');")]);
+
+        let actions = fixture.inline("/app/src/a.ts", "save");
+
+        let summary: Vec<(String, usize)> =
+            actions.into_iter().map(|(title, _, files)| (title, files)).collect();
+        assert_that!(
+            summary,
+            elements_are![
+                eq(&("Inline translation 'save'".to_string(), 1)),
+                eq(&("Inline translation 'save' and delete the key".to_string(), 3)),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn keeps_key_with_other_references() {
+        let fixture =
+            Fixture::new(&[("/app/src/a.ts", "t('save');"), ("/app/src/b.ts", "t('save');")]);
+
+        let actions = fixture.inline("/app/src/a.ts", "save");
+
+        assert_that!(actions, len(eq(1)));
+    }
+
+    #[rstest]
+    #[case::interpolation("t('greeting', { name });", "greeting")]
+    #[case::plural("t('items', { count });", "items")]
+    #[case::missing_key("t('unknown');", "unknown")]
+    fn skips_values_that_cannot_be_literals(#[case] source: &str, #[case] needle: &str) {
+        let fixture = Fixture::new(&[("/app/src/a.ts", source)]);
+
+        assert_that!(fixture.inline("/app/src/a.ts", needle), is_empty());
+    }
+
+    #[rstest]
+    fn replaces_vue_template_interpolation() {
+        let source = "<template>\n  <p>{{ $t('save') }}</p>\n</template>\n";
+        let fixture = Fixture::new(&[("/app/src/A.vue", source)]);
+
+        let actions = fixture.inline("/app/src/A.vue", "save");
+
+        assert_that!(actions[0].1, eq("Save changes"));
+    }
+
+    #[rstest]
+    fn replaces_svelte_attribute_and_script_expression() {
+        let source = "<script>\n  const a = $_('save');\n</script>\n<p title={$_('save')}></p>\n";
+        let fixture = Fixture::new(&[("/app/src/A.svelte", source)]);
+
+        let script = fixture.inline("/app/src/A.svelte", "save");
+        let attribute = fixture.inline("/app/src/A.svelte", "save')}");
+
+        assert_that!(script[0].1, eq("'Save changes'"));
+        assert_that!(attribute[0].1, eq("\"Save changes\""));
+    }
+}
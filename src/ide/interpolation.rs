@@ -0,0 +1,784 @@
+//! Interpolation variable validation between call sites and translation values.
+
+use std::collections::{
+    BTreeSet,
+    HashSet,
+};
+use std::iter::Peekable;
+use std::str::Chars;
+
+use tower_lsp::lsp_types::{
+    Diagnostic,
+    NumberOrString,
+};
+
+use crate::config::Severity;
+use crate::db::I18nDatabase;
+use crate::framework::{
+    FrameworkConfig,
+    PluralStrategy,
+};
+use crate::ide::icu::{
+    IcuElement,
+    is_i18next_value,
+    parse_icu,
+};
+use crate::ide::namespace::{
+    filter_by_namespace,
+    resolve_usage_namespace,
+};
+use crate::ide::plural::find_plural_variants;
+use crate::input::source::SourceFile;
+use crate::input::translation::Translation;
+use crate::syntax::analyze_source;
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+};
+
+/// i18next translation options that are never interpolation variables.
+///
+/// `count` and `context` select plural and context variants even when the value doesn't
+/// reference them.
+const I18NEXT_OPTIONS: &[&str] = &[
+    "count",
+    "context",
+    "ns",
+    "lng",
+    "lngs",
+    "fallbackLng",
+    "defaultValue",
+    "returnObjects",
+    "returnDetails",
+    "joinArrays",
+    "postProcess",
+    "interpolation",
+    "skipInterpolation",
+    "ordinal",
+    "keyPrefix",
+];
+
+/// Placeholders declared by a translation value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Placeholders {
+    /// Variables that must be passed (e.g., `name` in `Hello {{name}}` or `{name}`)
+    pub variables: BTreeSet<String>,
+    /// Rich text tags that may be passed (e.g., `b` in `<b>bold</b>`, `0` in `<0>link</0>`)
+    pub tags: BTreeSet<String>,
+}
+
+impl Placeholders {
+    fn extend(&mut self, other: Self) {
+        self.variables.extend(other.variables);
+        self.tags.extend(other.tags);
+    }
+}
+
+/// Parses the placeholders of a translation value.
+///
+/// Values containing `{{` use i18next syntax (`{{name}}`, `{{- name}}`, `{{date, format}}`).
+/// Others are parsed as ICU `MessageFormat` (`{name}`, `{count, plural, one {...} other {...}}`,
+/// `<b>...</b>`), which also covers vue-i18n named interpolation. ICU values with syntax
+/// errors have no placeholders; they are reported by
+/// [`crate::ide::icu::generate_icu_syntax_diagnostics`].
+#[must_use]
+pub fn parse_placeholders(value: &str) -> Placeholders {
+    let mut placeholders = Placeholders::default();
+    if is_i18next_value(value) {
+        parse_i18next(value, &mut placeholders);
+        // `<Trans>` components use indexed (`<0>`) or named (`<bold>`) tags
+        let mut chars = value.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '<'
+                && let Some(tag) = take_tag_name(&mut chars)
+            {
+                placeholders.tags.insert(tag);
+            }
+        }
+    } else if let Ok(elements) = parse_icu(value) {
+        collect_icu(&elements, &mut placeholders);
+    }
+    placeholders
+}
+
+fn parse_i18next(value: &str, placeholders: &mut Placeholders) {
+    let mut rest = value;
+    while let Some((_, after_open)) = rest.split_once("{{") {
+        let Some((inner, after_close)) = after_open.split_once("}}") else {
+            break;
+        };
+        let inner = inner.trim().trim_start_matches('-').trim_start();
+        let name = inner.split(',').next().unwrap_or_default().trim();
+        // `{{user.name}}` reads the `user` variable
+        let root = name.split('.').next().unwrap_or_default();
+        if is_variable_name(root) {
+            placeholders.variables.insert(root.to_string());
+        }
+        rest = after_close;
+    }
+}
+
+fn collect_icu(elements: &[IcuElement], placeholders: &mut Placeholders) {
+    for element in elements {
+        match element {
+            IcuElement::Argument(name) | IcuElement::Formatted { name, .. } => {
+                if is_variable_name(name) {
+                    placeholders.variables.insert(name.clone());
+                }
+            }
+            IcuElement::Choice { name, branches, .. } => {
+                if is_variable_name(name) {
+                    placeholders.variables.insert(name.clone());
+                }
+                for branch in branches {
+                    collect_icu(&branch.elements, placeholders);
+                }
+            }
+            IcuElement::Tag(name) => {
+                placeholders.tags.insert(name.clone());
+            }
+            IcuElement::Literal(_) | IcuElement::Pound => {}
+        }
+    }
+}
+
+/// Reads the name of an opening or self-closing tag after `<`. Closing tags yield `None`.
+fn take_tag_name(chars: &mut Peekable<Chars<'_>>) -> Option<String> {
+    let name: String =
+        std::iter::from_fn(|| chars.next_if(|c| c.is_alphanumeric() || matches!(c, '_' | '-')))
+            .collect();
+    (!name.is_empty() && matches!(chars.peek(), Some('>' | '/'))).then_some(name)
+}
+
+/// Named variables only; list placeholders (`{0}`) and vue-i18n literals (`{'@'}`) are skipped.
+fn is_variable_name(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$' || c == '-')
+}
+
+/// Collects the placeholders of `key` across all languages.
+///
+/// The first translation per language wins, so component-local messages that precede global
+/// ones shadow them. Returns `None` when no language has a value for the key.
+fn collect_placeholders(
+    db: &dyn I18nDatabase,
+    translations: &[&Translation],
+    key: &str,
+    plural_strategy: PluralStrategy,
+) -> Option<Placeholders> {
+    let mut placeholders: Option<Placeholders> = None;
+    let mut seen_languages = HashSet::new();
+
+    for translation in translations {
+        let language = translation.language(db);
+        if seen_languages.contains(&language) {
+            continue;
+        }
+
+        let keys = translation.keys(db);
+        let values: Vec<&str> = keys.get(key).map_or_else(
+            || {
+                find_plural_variants(key, keys, plural_strategy)
+                    .into_iter()
+                    .map(|(_, value)| value)
+                    .collect()
+            },
+            |value| vec![value.as_str()],
+        );
+        if values.is_empty() {
+            continue;
+        }
+
+        seen_languages.insert(language);
+        let found = placeholders.get_or_insert_with(Placeholders::default);
+        for value in values {
+            found.extend(parse_placeholders(value));
+        }
+    }
+
+    placeholders
+}
+
+/// Generates diagnostics for interpolation variables that are missing or unexpected at call
+/// sites.
+///
+/// Only calls whose options object is known statically are checked (see
+/// [`crate::syntax::analyzer::types::TransFnCall::interpolation_args`]).
+/// Dynamic keys are skipped.
+#[allow(clippy::too_many_arguments)]
+pub fn generate_interpolation_diagnostics(
+    db: &dyn I18nDatabase,
+    source_file: SourceFile,
+    translations: &[Translation],
+    severity: Severity,
+    key_separator: &str,
+    namespace_separator: Option<&str>,
+    default_namespace: Option<&str>,
+    encoding: PositionEncoding,
+) -> Vec<Diagnostic> {
+    let plural_strategy = FrameworkConfig::for_language(source_file.language(db)).plural_strategy;
+    let key_usages = analyze_source(db, source_file, key_separator.to_string());
+    let line_index = LineIndex::new(source_file.text(db), encoding);
+
+    let mut diagnostics = Vec::new();
+
+    for usage in key_usages {
+        if usage.dynamic(db) {
+            continue;
+        }
+        let Some(passed) = usage.interpolation_args(db) else {
+            continue;
+        };
+
+        let (resolved_ns, key_part) =
+            resolve_usage_namespace(db, usage, namespace_separator, default_namespace);
+        if key_part.is_empty() {
+            continue;
+        }
+
+        let filtered = filter_by_namespace(db, translations, resolved_ns.as_deref());
+        let Some(placeholders) = collect_placeholders(db, &filtered, &key_part, plural_strategy)
+        else {
+            continue;
+        };
+
+        let passed: BTreeSet<&str> = passed.iter().map(String::as_str).collect();
+        let missing: Vec<&str> = placeholders
+            .variables
+            .iter()
+            .map(String::as_str)
+            .filter(|variable| !passed.contains(variable))
+            .collect();
+        let unexpected: Vec<&str> = passed
+            .iter()
+            .copied()
+            .filter(|arg| {
+                let is_i18next_option =
+                    plural_strategy == PluralStrategy::SuffixBased && I18NEXT_OPTIONS.contains(arg);
+                !(placeholders.variables.contains(*arg)
+                    || placeholders.tags.contains(*arg)
+                    || is_i18next_option)
+            })
+            .collect();
+
+        let range = line_index.to_lsp_range(usage.range(db));
+        for (code, label, variables) in [
+            ("missing-interpolation-variable", "Missing", missing),
+            ("unexpected-interpolation-variable", "Unexpected", unexpected),
+        ] {
+            if variables.is_empty() {
+                continue;
+            }
+            diagnostics.push(Diagnostic {
+                range,
+                severity: Some(severity.to_lsp()),
+                code: Some(NumberOrString::String(code.to_string())),
+                code_description: None,
+                source: Some("js-i18n".to_string()),
+                message: format!(
+                    "{label} interpolation variables for '{key_part}': {}",
+                    variables.join(", ")
+                ),
+                related_information: None,
+                tags: None,
+                data: Some(serde_json::json!({ "key": key_part, "variables": variables })),
+            });
+        }
+    }
+
+    diagnostics
+}
+
+/// Generates diagnostics on a translation file for values whose placeholders differ from the
+/// primary language.
+///
+/// For each key, the reference value comes from the first language in `primary_languages`
+/// that has the key in the same namespace. Keys whose reference is the file's own language
+/// are skipped, so nothing is reported without `primaryLanguages`.
+pub fn generate_placeholder_consistency_diagnostics(
+    db: &dyn I18nDatabase,
+    translation: Translation,
+    translations: &[Translation],
+    primary_languages: &[String],
+    severity: Severity,
+    encoding: PositionEncoding,
+) -> Vec<Diagnostic> {
+    let language = translation.language(db);
+    let namespace = translation.namespace(db);
+    let references: Vec<(&str, &Translation)> = primary_languages
+        .iter()
+        .flat_map(|primary| {
+            translations
+                .iter()
+                .filter(move |t| {
+                    t.language(db) == *primary
+                        && t.namespace(db) == namespace
+                        && !t.is_component_local(db)
+                })
+                .map(move |t| (primary.as_str(), t))
+        })
+        .collect();
+
+    let keys = translation.keys(db);
+    let value_ranges = translation.value_ranges(db);
+    let line_index = LineIndex::new(translation.json_text(db), encoding);
+
+    let mut sorted_keys: Vec<&String> = keys.keys().collect();
+    sorted_keys.sort();
+
+    let mut diagnostics = Vec::new();
+    for key in sorted_keys {
+        let Some((reference_language, reference_value)) =
+            references.iter().find_map(|(lang, t)| t.keys(db).get(key).map(|value| (*lang, value)))
+        else {
+            continue;
+        };
+        if reference_language == language {
+            continue;
+        }
+        let (Some(value), Some(range)) = (keys.get(key), value_ranges.get(key)) else {
+            continue;
+        };
+
+        let expected = placeholder_labels(parse_placeholders(reference_value));
+        let actual = placeholder_labels(parse_placeholders(value));
+        let missing: Vec<&str> = expected.difference(&actual).map(String::as_str).collect();
+        let unexpected: Vec<&str> = actual.difference(&expected).map(String::as_str).collect();
+        if missing.is_empty() && unexpected.is_empty() {
+            continue;
+        }
+
+        let details: Vec<String> = [("missing", &missing), ("unexpected", &unexpected)]
+            .into_iter()
+            .filter(|(_, labels)| !labels.is_empty())
+            .map(|(label, labels)| format!("{label} {}", labels.join(", ")))
+            .collect();
+
+        diagnostics.push(Diagnostic {
+            range: line_index.to_lsp_range(*range),
+            severity: Some(severity.to_lsp()),
+            code: Some(NumberOrString::String("inconsistent-placeholders".to_string())),
+            code_description: None,
+            source: Some("js-i18n".to_string()),
+            message: format!(
+                "Placeholders of '{key}' differ from '{reference_language}': {}",
+                details.join("; ")
+            ),
+            related_information: None,
+            tags: None,
+            data: Some(serde_json::json!({
+                "key": key,
+                "referenceLanguage": reference_language,
+                "missing": missing,
+                "unexpected": unexpected,
+            })),
+        });
+    }
+
+    diagnostics
+}
+
+/// Labels placeholders for display: variables by name, tags as `<tag>`.
+fn placeholder_labels(placeholders: Placeholders) -> BTreeSet<String> {
+    placeholders
+        .variables
+        .into_iter()
+        .chain(placeholders.tags.into_iter().map(|tag| format!("<{tag}>")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use googletest::prelude::*;
+    use rstest::*;
+    use tower_lsp::lsp_types::{
+        Position,
+        Range,
+    };
+
+    use super::*;
+    use crate::db::I18nDatabaseImpl;
+    use crate::input::source::ProgrammingLanguage;
+    use crate::test_utils::create_translation;
+
+    fn variables(value: &str) -> Vec<String> {
+        parse_placeholders(value).variables.into_iter().collect()
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    #[rstest]
+    #[case::i18next("Hello {{name}}", &["name"])]
+    #[case::i18next_unescaped("Hello {{- name}}", &["name"])]
+    #[case::i18next_format("Due {{date, datetime}}", &["date"])]
+    #[case::i18next_nested_property("Hi {{user.name}}", &["user"])]
+    #[case::icu_simple("Hello {name}", &["name"])]
+    #[case::icu_plural(
+        "{count, plural, =0 {No items} one {# item by {author}} other {# items}}",
+        &["author", "count"]
+    )]
+    #[case::icu_select("{gender, select, male {He} other {They}} said hi", &["gender"])]
+    #[case::icu_number_style("{price, number, ::currency/EUR}", &["price"])]
+    #[case::icu_quoted("Use '{braces}' for {name}", &["name"])]
+    #[case::vue_list("Hello {0}", &[])]
+    #[case::vue_literal("{'@'} {name}", &["name"])]
+    #[case::plain("No placeholders", &[])]
+    fn parse_placeholders_variables(#[case] value: &str, #[case] expected: &[&str]) {
+        assert_that!(variables(value), eq(&strings(expected)));
+    }
+
+    #[rstest]
+    fn parse_placeholders_trans_component_tags() {
+        let placeholders =
+            parse_placeholders("Hello <1>{{name}}</1>, see <br/> and <bold>this</bold>");
+        assert_that!(
+            placeholders.tags.into_iter().collect::<Vec<_>>(),
+            eq(&strings(&["1", "bold", "br"]))
+        );
+    }
+
+    #[rstest]
+    fn parse_placeholders_tags() {
+        let placeholders = parse_placeholders("Read the <link>terms</link> and <b>{name}</b>");
+        assert_that!(
+            placeholders.tags.into_iter().collect::<Vec<_>>(),
+            eq(&strings(&["b", "link"]))
+        );
+        assert_that!(
+            placeholders.variables.into_iter().collect::<Vec<_>>(),
+            eq(&strings(&["name"]))
+        );
+    }
+
+    fn diagnose(
+        source: &str,
+        language: ProgrammingLanguage,
+        keys: &[(&str, &str)],
+    ) -> Vec<Diagnostic> {
+        let db = I18nDatabaseImpl::default();
+        let source_file =
+            SourceFile::new(&db, "file:///test.ts".to_string(), source.to_string(), language);
+        let translation = create_translation(
+            &db,
+            "en",
+            "/locales/en.json",
+            keys.iter().map(|(k, v)| ((*k).to_string(), (*v).to_string())).collect(),
+        );
+
+        generate_interpolation_diagnostics(
+            &db,
+            source_file,
+            &[translation],
+            Severity::Warning,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        )
+    }
+
+    #[rstest]
+    fn reports_missing_variable() {
+        let diagnostics = diagnose(
+            r#"t("greeting", { other: 1 });"#,
+            ProgrammingLanguage::TypeScript,
+            &[("greeting", "Hello {{name}}")],
+        );
+
+        assert_that!(
+            diagnostics,
+            unordered_elements_are![
+                field!(
+                    Diagnostic.message,
+                    eq("Missing interpolation variables for 'greeting': name")
+                ),
+                field!(
+                    Diagnostic.message,
+                    eq("Unexpected interpolation variables for 'greeting': other")
+                )
+            ]
+        );
+    }
+
+    #[rstest]
+    fn reports_missing_variable_without_options() {
+        let diagnostics = diagnose(
+            r#"t("greeting");"#,
+            ProgrammingLanguage::TypeScript,
+            &[("greeting", "Hello {{name}}")],
+        );
+
+        assert_that!(
+            diagnostics,
+            elements_are![field!(
+                Diagnostic.code,
+                some(eq(&NumberOrString::String("missing-interpolation-variable".to_string())))
+            )]
+        );
+    }
+
+    #[rstest]
+    #[case::shorthand(r#"t("greeting", { name });"#)]
+    #[case::pair(r#"t("greeting", { name: user.name });"#)]
+    #[case::i18next_options(r#"t("greeting", { name, count: 1, context: "male", ns: "common" });"#)]
+    #[case::i18next_replace(r#"t("greeting", { replace: { name } });"#)]
+    #[case::unknown_options(r#"t("greeting", options);"#)]
+    #[case::spread(r#"t("greeting", { ...rest });"#)]
+    fn accepts_matching_or_unknown_options(#[case] source: &str) {
+        let diagnostics =
+            diagnose(source, ProgrammingLanguage::TypeScript, &[("greeting", "Hello {{name}}")]);
+
+        assert_that!(diagnostics, is_empty());
+    }
+
+    #[rstest]
+    fn collects_variables_from_plural_variants() {
+        let diagnostics = diagnose(
+            r#"t("items", { count });"#,
+            ProgrammingLanguage::TypeScript,
+            &[("items_one", "{{count}} item"), ("items_other", "{{count}} items in {{place}}")],
+        );
+
+        assert_that!(
+            diagnostics,
+            elements_are![field!(
+                Diagnostic.message,
+                eq("Missing interpolation variables for 'items': place")
+            )]
+        );
+    }
+
+    #[rstest]
+    fn svelte_values_are_flattened() {
+        let diagnostics = diagnose(
+            r#"<script>$_("greeting", { values: { name } });</script>"#,
+            ProgrammingLanguage::Svelte,
+            &[("greeting", "Hello {name}")],
+        );
+
+        assert_that!(diagnostics, is_empty());
+    }
+
+    #[rstest]
+    #[case::format_message(r#"intl.formatMessage({ id: "greet" }, { wrong: 1 });"#)]
+    #[case::formatted_message(r#"<FormattedMessage id="greet" values={{ wrong: 1 }} />;"#)]
+    fn react_intl_values_are_checked(#[case] source: &str) {
+        let diagnostics = diagnose(source, ProgrammingLanguage::Tsx, &[("greet", "Hello {name}")]);
+
+        assert_that!(
+            diagnostics,
+            unordered_elements_are![
+                field!(Diagnostic.message, eq("Missing interpolation variables for 'greet': name")),
+                field!(
+                    Diagnostic.message,
+                    eq("Unexpected interpolation variables for 'greet': wrong")
+                )
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::format_message(r#"intl.formatMessage({ id: "greet" }, { name });"#)]
+    #[case::formatted_message(r#"<FormattedMessage id="greet" values={{ name }} />;"#)]
+    #[case::define_message(r#"defineMessage({ id: "greet" });"#)]
+    #[case::formatted_message_spread(r#"<FormattedMessage id="greet" {...props} />;"#)]
+    #[case::trans(r#"<Trans i18nKey="greet" />;"#)]
+    fn react_intl_accepts_matching_or_unknown_values(#[case] source: &str) {
+        let diagnostics = diagnose(source, ProgrammingLanguage::Tsx, &[("greet", "Hello {name}")]);
+
+        assert_that!(diagnostics, is_empty());
+    }
+
+    #[rstest]
+    #[case::matching(r#"<script>$_({ id: "greeting", values: { name } });</script>"#, 0)]
+    #[case::without_values(r#"<script>$_({ id: "greeting" });</script>"#, 1)]
+    #[case::shorthand_values(r#"<script>$_({ id: "greeting", values });</script>"#, 0)]
+    #[case::spread(r#"<script>$_({ id: "greeting", ...opts });</script>"#, 0)]
+    fn svelte_descriptor_values_are_checked(#[case] source: &str, #[case] expected: usize) {
+        let diagnostics =
+            diagnose(source, ProgrammingLanguage::Svelte, &[("greeting", "Hello {name}")]);
+
+        assert_that!(diagnostics, len(eq(expected)));
+    }
+
+    #[rstest]
+    fn count_is_unexpected_without_suffix_plurals() {
+        let diagnostics = diagnose(
+            r#"<script>$_("greeting", { values: { name, count } });</script>"#,
+            ProgrammingLanguage::Svelte,
+            &[("greeting", "Hello {name}")],
+        );
+
+        assert_that!(
+            diagnostics,
+            elements_are![field!(
+                Diagnostic.message,
+                eq("Unexpected interpolation variables for 'greeting': count")
+            )]
+        );
+    }
+
+    #[rstest]
+    fn rich_text_tags_are_allowed() {
+        let diagnostics = diagnose(
+            r#"t.rich("terms", { link: (chunks) => chunks });"#,
+            ProgrammingLanguage::TypeScript,
+            &[("terms", "Read the <link>terms</link>")],
+        );
+
+        assert_that!(diagnostics, is_empty());
+    }
+
+    #[rstest]
+    fn skips_keys_without_values() {
+        let diagnostics = diagnose(
+            r#"t("missing", { name });"#,
+            ProgrammingLanguage::TypeScript,
+            &[("greeting", "Hello {{name}}")],
+        );
+
+        assert_that!(diagnostics, is_empty());
+    }
+
+    #[rstest]
+    fn collects_variables_across_languages() {
+        let db = I18nDatabaseImpl::default();
+        let source_file = SourceFile::new(
+            &db,
+            "file:///test.ts".to_string(),
+            r#"t("greeting", { name });"#.to_string(),
+            ProgrammingLanguage::TypeScript,
+        );
+        let en = create_translation(
+            &db,
+            "en",
+            "/locales/en.json",
+            HashMap::from([("greeting".to_string(), "Hello {{name}}".to_string())]),
+        );
+        let ja = create_translation(
+            &db,
+            "ja",
+            "/locales/ja.json",
+            HashMap::from([("greeting".to_string(), "{{title}} {{name}}さん".to_string())]),
+        );
+
+        let diagnostics = generate_interpolation_diagnostics(
+            &db,
+            source_file,
+            &[en, ja],
+            Severity::Warning,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
+
+        assert_that!(
+            diagnostics,
+            elements_are![field!(
+                Diagnostic.message,
+                eq("Missing interpolation variables for 'greeting': title")
+            )]
+        );
+    }
+
+    fn consistency_diagnostics(
+        ja_value: &str,
+        primary_languages: &[&str],
+    ) -> (Vec<Diagnostic>, Vec<Diagnostic>) {
+        let db = I18nDatabaseImpl::default();
+        let en = crate::test_utils::create_translation_with_json(
+            &db,
+            "en",
+            None,
+            "/locales/en.json",
+            HashMap::from([(
+                "items".to_string(),
+                "{{count}} items for <bold>{{name}}</bold>".to_string(),
+            )]),
+            "{\n  \"items\": \"...\"\n}",
+        );
+        let ja = Translation::new(
+            &db,
+            "ja".to_string(),
+            None,
+            "/locales/ja.json".to_string(),
+            HashMap::from([("items".to_string(), ja_value.to_string())]),
+            "{\n  \"items\": \"...\"\n}".to_string(),
+            HashMap::new(),
+            HashMap::from([(
+                "items".to_string(),
+                crate::types::SourceRange {
+                    start: crate::types::SourcePosition { line: 1, character: 11 },
+                    end: crate::types::SourcePosition { line: 1, character: 16 },
+                },
+            )]),
+        );
+        let translations = [en, ja];
+        let primary_languages: Vec<String> =
+            primary_languages.iter().map(|s| (*s).to_string()).collect();
+
+        let generate = |translation| {
+            generate_placeholder_consistency_diagnostics(
+                &db,
+                translation,
+                &translations,
+                &primary_languages,
+                Severity::Warning,
+                PositionEncoding::Utf16,
+            )
+        };
+        (generate(en), generate(ja))
+    }
+
+    #[rstest]
+    fn consistency_reports_differences_against_primary_language() {
+        let (en_diagnostics, ja_diagnostics) =
+            consistency_diagnostics("{{nom}}の<bold>アイテム</bold>", &["en"]);
+
+        assert_that!(en_diagnostics, is_empty());
+        assert_that!(
+            ja_diagnostics,
+            elements_are![all![
+                field!(
+                    Diagnostic.message,
+                    eq(
+                        "Placeholders of 'items' differ from 'en': missing count, name; unexpected nom"
+                    )
+                ),
+                field!(Diagnostic.range, field!(Range.start, field!(Position.line, eq(&1)))),
+                field!(
+                    Diagnostic.code,
+                    some(eq(&NumberOrString::String("inconsistent-placeholders".to_string())))
+                )
+            ]]
+        );
+    }
+
+    #[rstest]
+    fn consistency_reports_missing_tags() {
+        let (_, ja_diagnostics) = consistency_diagnostics("{{name}}の{{count}}個", &["en"]);
+
+        assert_that!(
+            ja_diagnostics,
+            elements_are![field!(
+                Diagnostic.message,
+                eq("Placeholders of 'items' differ from 'en': missing <bold>")
+            )]
+        );
+    }
+
+    #[rstest]
+    #[case::consistent("<bold>{{name}}</bold>の{{count}}個", &["en"])]
+    #[case::no_primary_languages("{{nom}}", &[])]
+    #[case::own_language_is_primary("{{nom}}", &["ja", "en"])]
+    fn consistency_reports_nothing(#[case] ja_value: &str, #[case] primary_languages: &[&str]) {
+        let (_, ja_diagnostics) = consistency_diagnostics(ja_value, primary_languages);
+
+        assert_that!(ja_diagnostics, is_empty());
+    }
+}
@@ -12,6 +12,51 @@ pub fn is_child_key(child_key: &str, parent_key: &str, separator: &str) -> bool
     !remainder.is_empty() && (remainder.starts_with(separator) || remainder.starts_with('['))
 }
 
+/// Wildcard that stands for the dynamic parts of a key pattern (e.g., `errors.*`).
+pub const KEY_WILDCARD: char = '*';
+
+/// Checks if `key` is a pattern built from a dynamic key expression.
+#[must_use]
+pub fn is_key_pattern(key: &str) -> bool {
+    key.contains(KEY_WILDCARD)
+}
+
+/// Checks if `key` matches `pattern`, where each `*` matches one or more characters.
+///
+/// Wildcards may span key separators, so `errors.*` matches both `errors.notFound` and
+/// `errors.http.notFound`.
+#[must_use]
+pub fn matches_key_pattern(key: &str, pattern: &str) -> bool {
+    let mut parts = pattern.split(KEY_WILDCARD);
+    let Some(first) = parts.next() else {
+        return false;
+    };
+    let Some(mut rest) = key.strip_prefix(first) else {
+        return false;
+    };
+
+    let mut parts = parts.peekable();
+    while let Some(part) = parts.next() {
+        // The wildcard before `part` must consume at least one character.
+        let mut chars = rest.chars();
+        if chars.next().is_none() {
+            return false;
+        }
+        let after_wildcard = chars.as_str();
+
+        if parts.peek().is_none() {
+            return after_wildcard.ends_with(part);
+        }
+
+        let Some((_, remainder)) = after_wildcard.split_once(part) else {
+            return false;
+        };
+        rest = remainder;
+    }
+
+    rest.is_empty()
+}
+
 #[cfg(test)]
 mod tests {
     use googletest::prelude::*;
@@ -56,4 +101,39 @@ mod tests {
         assert_that!(is_child_key("a/b/c", "a", "/"), eq(true));
         assert_that!(is_child_key("a/b/c", "a/b", "/"), eq(true));
     }
+
+    #[rstest]
+    #[case("errors.notFound", "errors.*")]
+    #[case("errors.http.notFound", "errors.*")]
+    #[case("status.active.label", "status.*.label")]
+    #[case("prefix_suffix", "*_suffix")]
+    #[case("a.b.c", "a.*.*")]
+    #[case("errors.*", "errors.*")]
+    fn matches_key_pattern_positive_cases(#[case] key: &str, #[case] pattern: &str) {
+        assert_that!(matches_key_pattern(key, pattern), eq(true));
+    }
+
+    #[rstest]
+    #[case("errors", "errors.*")]
+    #[case("errors.", "errors.*")]
+    #[case("other.notFound", "errors.*")]
+    #[case("status.active.title", "status.*.label")]
+    #[case("status..label", "status.*.label")]
+    #[case("_suffix", "*_suffix")]
+    #[case("a.b", "a.*.*")]
+    fn matches_key_pattern_negative_cases(#[case] key: &str, #[case] pattern: &str) {
+        assert_that!(matches_key_pattern(key, pattern), eq(false));
+    }
+
+    #[rstest]
+    fn matches_key_pattern_without_wildcard_is_exact() {
+        assert_that!(matches_key_pattern("common.hello", "common.hello"), eq(true));
+        assert_that!(matches_key_pattern("common.hello2", "common.hello"), eq(false));
+    }
+
+    #[rstest]
+    fn is_key_pattern_detects_wildcard() {
+        assert_that!(is_key_pattern("errors.*"), eq(true));
+        assert_that!(is_key_pattern("errors.notFound"), eq(false));
+    }
 }
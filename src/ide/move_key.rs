@@ -0,0 +1,807 @@
+//! Move a translation key to another namespace.
+//!
+//! The key and its subtree (nested keys and plural variants) are removed from the source
+//! namespace file of every language and inserted into the target namespace file of the same
+//! language. Call sites are rewritten to resolve to the target namespace.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use tower_lsp::lsp_types::{
+    CodeAction,
+    CodeActionKind,
+    CodeActionOrCommand,
+    Command,
+    TextEdit,
+    Url,
+    WorkspaceEdit,
+};
+use tree_sitter::Node;
+
+use crate::db::I18nDatabase;
+use crate::framework::PluralStrategy;
+use crate::ide::code_actions::{
+    create_full_file_text_edit,
+    delete_keys_from_text,
+    insert_key_to_text,
+};
+use crate::ide::key_match::is_child_key;
+use crate::ide::namespace::resolve_usage_namespace;
+use crate::ide::plural::get_plural_base_key;
+use crate::input::source::SourceFile;
+use crate::input::translation::Translation;
+use crate::ir::key_usage::KeyUsage;
+use crate::syntax::analyze_source;
+use crate::syntax::analyzer::extractor::parse_key_with_namespace;
+use crate::syntax::parsed::ParsedSource;
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+    SourceRange,
+};
+
+/// Settings that affect key resolution and edits.
+#[derive(Debug, Clone, Copy)]
+pub struct MoveOptions<'a> {
+    pub key_separator: &'a str,
+    pub namespace_separator: Option<&'a str>,
+    pub default_namespace: Option<&'a str>,
+    pub encoding: PositionEncoding,
+}
+
+/// Reasons a key can't be moved.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum MoveKeyError {
+    #[error("Key '{key}' is already in namespace '{namespace}'")]
+    SameNamespace { key: String, namespace: String },
+    #[error("Key '{key}' not found in namespace '{namespace}'")]
+    KeyNotFound { key: String, namespace: String },
+    #[error("No '{namespace}' translation file for language '{language}'")]
+    MissingTargetFile { namespace: String, language: String },
+    #[error("Key '{key}' already exists in '{language}' with a different value")]
+    Conflict { key: String, language: String },
+    #[error("Key '{key}' has array items, which can't be moved")]
+    ArrayKey { key: String },
+    #[error("Failed to edit translation file '{file}'")]
+    EditFailed { file: String },
+}
+
+/// Computes workspace edits moving `key_part` from `source_namespace` to `target_namespace`,
+/// renaming it to `new_key_part` on the way.
+///
+/// Every language defining the key needs a translation file for the target namespace, and keys
+/// already in the target must have the same value; otherwise nothing is moved. Component-local
+/// translations are left untouched.
+///
+/// Call sites are rewritten in order of preference: an explicit `ns:` prefix or `ns` option is
+/// replaced; a `useTranslation('source')` hook whose usages are all moved is switched to the
+/// target; otherwise the key gets an `ns:` prefix, or an `ns` option when no namespace separator
+/// is configured.
+#[allow(clippy::implicit_hasher, clippy::too_many_arguments)]
+pub fn compute_move_edits(
+    db: &dyn I18nDatabase,
+    key_part: &str,
+    source_namespace: &str,
+    target_namespace: &str,
+    new_key_part: &str,
+    translations: &[Translation],
+    source_files: &HashMap<PathBuf, SourceFile>,
+    options: &MoveOptions<'_>,
+) -> Result<WorkspaceEdit, MoveKeyError> {
+    if source_namespace == target_namespace {
+        return Err(MoveKeyError::SameNamespace {
+            key: key_part.to_string(),
+            namespace: source_namespace.to_string(),
+        });
+    }
+
+    let mut changes = translation_edits(
+        db,
+        key_part,
+        source_namespace,
+        target_namespace,
+        new_key_part,
+        translations,
+        options.key_separator,
+    )?;
+
+    let mut paths: Vec<&PathBuf> = source_files.keys().collect();
+    paths.sort();
+    for path in paths {
+        let Some(source_file) = source_files.get(path) else {
+            continue;
+        };
+        let Ok(uri) = Url::parse(source_file.uri(db)) else {
+            continue;
+        };
+        let edits = source_edits(
+            db,
+            *source_file,
+            key_part,
+            source_namespace,
+            target_namespace,
+            new_key_part,
+            options,
+        );
+        if !edits.is_empty() {
+            changes.entry(uri).or_default().extend(edits);
+        }
+    }
+
+    Ok(WorkspaceEdit { changes: Some(changes), ..Default::default() })
+}
+
+/// Generates a "Move to namespace" action for each other namespace.
+///
+/// Offered only when the key is defined in `source_namespace`.
+#[must_use]
+pub fn generate_move_key_code_actions(
+    db: &dyn I18nDatabase,
+    key_part: &str,
+    source_namespace: &str,
+    translations: &[Translation],
+    key_separator: &str,
+) -> Vec<CodeActionOrCommand> {
+    let movable: Vec<&Translation> =
+        translations.iter().filter(|t| !t.is_component_local(db)).collect();
+    let is_defined = movable.iter().any(|t| {
+        t.namespace(db).as_deref() == Some(source_namespace)
+            && t.keys(db).keys().any(|key| is_in_subtree(key, key_part, key_separator))
+    });
+    if !is_defined {
+        return Vec::new();
+    }
+
+    let mut namespaces: Vec<&str> = movable
+        .iter()
+        .filter_map(|t| t.namespace(db).as_deref())
+        .filter(|ns| *ns != source_namespace)
+        .collect();
+    namespaces.sort_unstable();
+    namespaces.dedup();
+
+    namespaces
+        .into_iter()
+        .map(|namespace| {
+            let title = format!("Move '{key_part}' to namespace '{namespace}'");
+            CodeActionOrCommand::CodeAction(CodeAction {
+                title: title.clone(),
+                kind: Some(CodeActionKind::REFACTOR),
+                command: Some(Command {
+                    title,
+                    command: "i18n.moveKey".to_string(),
+                    arguments: Some(vec![serde_json::json!({
+                        "key": key_part,
+                        "namespace": source_namespace,
+                        "targetNamespace": namespace,
+                    })]),
+                }),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Returns true if `key` is `key_part`, one of its nested keys or one of its plural variants.
+fn is_in_subtree(key: &str, key_part: &str, key_separator: &str) -> bool {
+    key == key_part
+        || is_child_key(key, key_part, key_separator)
+        || get_plural_base_key(key, PluralStrategy::SuffixBased) == Some(key_part)
+}
+
+fn translation_edits(
+    db: &dyn I18nDatabase,
+    key_part: &str,
+    source_namespace: &str,
+    target_namespace: &str,
+    new_key_part: &str,
+    translations: &[Translation],
+    key_separator: &str,
+) -> Result<HashMap<Url, Vec<TextEdit>>, MoveKeyError> {
+    let movable: Vec<&Translation> =
+        translations.iter().filter(|t| !t.is_component_local(db)).collect();
+    let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+
+    for source in movable.iter().filter(|t| t.namespace(db).as_deref() == Some(source_namespace)) {
+        let mut moved: Vec<(&String, &String)> = source
+            .keys(db)
+            .iter()
+            .filter(|(key, _)| is_in_subtree(key, key_part, key_separator))
+            .collect();
+        if moved.is_empty() {
+            continue;
+        }
+        moved.sort();
+        if let Some((key, _)) = moved.iter().find(|(key, _)| key.contains('[')) {
+            return Err(MoveKeyError::ArrayKey { key: (*key).clone() });
+        }
+
+        let language = source.language(db);
+        let Some(target) = movable.iter().find(|t| {
+            t.language(db) == language && t.namespace(db).as_deref() == Some(target_namespace)
+        }) else {
+            return Err(MoveKeyError::MissingTargetFile {
+                namespace: target_namespace.to_string(),
+                language,
+            });
+        };
+
+        let target_text = target.json_text(db);
+        let mut new_target_text = target_text.clone();
+        for (key, value) in &moved {
+            let new_key = format!("{new_key_part}{}", &key[key_part.len()..]);
+            match target.keys(db).get(&new_key) {
+                Some(existing) if existing == *value => continue,
+                Some(_) => {
+                    return Err(MoveKeyError::Conflict {
+                        key: new_key,
+                        language: language.clone(),
+                    });
+                }
+                None => {}
+            }
+            new_target_text = insert_key_to_text(
+                target.format(db),
+                &new_target_text,
+                &new_key,
+                value,
+                key_separator,
+            )
+            .ok_or_else(|| MoveKeyError::EditFailed { file: target.file_path(db).clone() })?
+            .new_text;
+        }
+
+        let source_text = source.json_text(db);
+        let keys: Vec<String> = moved.iter().map(|(key, _)| (*key).clone()).collect();
+        let new_source_text =
+            delete_keys_from_text(source.format(db), source_text, &keys, key_separator)
+                .ok_or_else(|| MoveKeyError::EditFailed { file: source.file_path(db).clone() })?
+                .new_text;
+
+        for (translation, old_text, new_text) in
+            [(source, source_text, new_source_text), (target, target_text, new_target_text)]
+        {
+            if let Ok(uri) = Url::from_file_path(translation.file_path(db).as_str()) {
+                changes
+                    .entry(uri)
+                    .or_default()
+                    .push(create_full_file_text_edit(old_text, new_text));
+            }
+        }
+    }
+
+    if changes.is_empty() {
+        return Err(MoveKeyError::KeyNotFound {
+            key: key_part.to_string(),
+            namespace: source_namespace.to_string(),
+        });
+    }
+    Ok(changes)
+}
+
+/// A key usage that is moved, with its key part after the move.
+struct MovedUsage<'db> {
+    usage: KeyUsage<'db>,
+    new_key_part: String,
+    key_changed: bool,
+}
+
+fn source_edits(
+    db: &dyn I18nDatabase,
+    source_file: SourceFile,
+    key_part: &str,
+    source_namespace: &str,
+    target_namespace: &str,
+    new_key_part: &str,
+    options: &MoveOptions<'_>,
+) -> Vec<TextEdit> {
+    let usages = analyze_source(db, source_file, options.key_separator.to_string());
+    let mut moved = Vec::new();
+    let mut hook_usages_all_moved = true;
+    for usage in usages.iter().filter(|usage| !usage.dynamic(db)) {
+        let (namespace, usage_key_part) = resolve_usage_namespace(
+            db,
+            *usage,
+            options.namespace_separator,
+            options.default_namespace,
+        );
+        // Usages with no resolved namespace are ambiguous and moved, as in rename.
+        let in_source = namespace.as_deref().is_none_or(|ns| ns == source_namespace);
+        let remainder = usage_key_part
+            .strip_prefix(key_part)
+            .filter(|rest| rest.is_empty() || rest.starts_with(options.key_separator));
+        match remainder {
+            Some(rest) if in_source => moved.push(MovedUsage {
+                usage: *usage,
+                new_key_part: format!("{new_key_part}{rest}"),
+                key_changed: key_part != new_key_part,
+            }),
+            _ => {
+                if usage.namespace(db).as_deref() == Some(source_namespace) {
+                    hook_usages_all_moved = false;
+                }
+            }
+        }
+    }
+    if moved.is_empty() {
+        return Vec::new();
+    }
+
+    let text = source_file.text(db);
+    let line_index = LineIndex::new(text, options.encoding);
+    let parsed = ParsedSource::parse(text, source_file.language(db));
+    let hook_argument = parsed
+        .as_ref()
+        .filter(|_| hook_usages_all_moved)
+        .and_then(|parsed| single_hook_argument(parsed, source_namespace));
+
+    let mut edits = Vec::new();
+    if let Some(range) = hook_argument {
+        edits.push(TextEdit {
+            range: line_index.to_lsp_range(range),
+            new_text: target_namespace.to_string(),
+        });
+    }
+
+    for MovedUsage { usage, new_key_part, key_changed } in moved {
+        // Keys passed through a constant are rewritten where the constant defines them
+        let key_range = usage.definition_range(db).unwrap_or_else(|| usage.range(db));
+        let key_range = key_range.to_unquoted_range();
+        let prefixed = |separator: &str| TextEdit {
+            range: line_index.to_lsp_range(key_range),
+            new_text: format!("{target_namespace}{separator}{new_key_part}"),
+        };
+        let renamed =
+            TextEdit { range: line_index.to_lsp_range(key_range), new_text: new_key_part.clone() };
+
+        let full_key = usage.key(db).text(db);
+        if let Some(separator) = options.namespace_separator
+            && parse_key_with_namespace(full_key, Some(separator)).0.is_some()
+        {
+            edits.push(prefixed(separator));
+            continue;
+        }
+
+        let call = parsed
+            .as_ref()
+            .and_then(|parsed| parsed.call_with_key_at(usage.range(db)).map(|call| (parsed, call)));
+        if let Some(ns_option) = call.and_then(|(parsed, call)| ns_option_value(parsed, call)) {
+            edits.push(TextEdit {
+                range: line_index.to_lsp_range(ns_option),
+                new_text: target_namespace.to_string(),
+            });
+            if key_changed {
+                edits.push(renamed);
+            }
+        } else if hook_argument.is_some()
+            && usage.namespace(db).as_deref() == Some(source_namespace)
+        {
+            if key_changed {
+                edits.push(renamed);
+            }
+        } else if let Some(separator) = options.namespace_separator {
+            edits.push(prefixed(separator));
+        } else if let Some((parsed, call)) = call {
+            if key_changed {
+                edits.push(renamed);
+            }
+            edits.extend(add_ns_option(parsed, call, target_namespace, &line_index));
+        }
+    }
+    // Call sites sharing a constant produce the same edit of its definition
+    let mut unique_edits = Vec::with_capacity(edits.len());
+    for edit in edits {
+        if !unique_edits.contains(&edit) {
+            unique_edits.push(edit);
+        }
+    }
+    unique_edits
+}
+
+/// Returns the unquoted range of the single `useTranslation('namespace')` argument in the file.
+fn single_hook_argument(parsed: &ParsedSource<'_>, namespace: &str) -> Option<SourceRange> {
+    let mut arguments = parsed.nodes_of_kind("call_expression").into_iter().filter_map(|call| {
+        let function = call.child_by_field_name("function")?;
+        let argument = call.child_by_field_name("arguments")?.named_child(0)?;
+        (parsed.text(function) == "useTranslation"
+            && argument.kind() == "string"
+            && unquote(parsed.text(argument)) == namespace)
+            .then(|| parsed.range(argument).to_unquoted_range())
+    });
+    let argument = arguments.next()?;
+    arguments.next().is_none().then_some(argument)
+}
+
+/// Returns the unquoted range of the string `ns` option of a translation call.
+fn ns_option_value(parsed: &ParsedSource<'_>, call: Node<'_>) -> Option<SourceRange> {
+    let options = call.child_by_field_name("arguments")?.named_child(1)?;
+    let mut cursor = options.walk();
+    options
+        .named_children(&mut cursor)
+        .filter(|pair| pair.kind() == "pair")
+        .find(|pair| {
+            pair.child_by_field_name("key").is_some_and(|key| unquote(parsed.text(key)) == "ns")
+        })
+        .and_then(|pair| pair.child_by_field_name("value"))
+        .filter(|value| value.kind() == "string")
+        .map(|value| parsed.range(value).to_unquoted_range())
+}
+
+/// Adds `{ ns: 'target' }` to a translation call, merging it into an existing options object.
+fn add_ns_option(
+    parsed: &ParsedSource<'_>,
+    call: Node<'_>,
+    namespace: &str,
+    line_index: &LineIndex<'_>,
+) -> Option<TextEdit> {
+    let arguments = call.child_by_field_name("arguments")?;
+    let key = arguments.named_child(0)?;
+    let quote = parsed.text(key).chars().next().filter(|c| matches!(c, '\'' | '"')).unwrap_or('\'');
+    let ns_pair = format!("ns: {quote}{namespace}{quote}");
+
+    let (position, new_text) = match arguments.named_child(1) {
+        Some(options) if options.kind() == "object" => match options.named_child(0) {
+            Some(first) => (parsed.range(first).start, format!("{ns_pair}, ")),
+            None => {
+                return Some(TextEdit {
+                    range: line_index.to_lsp_range(parsed.range(options)),
+                    new_text: format!("{{ {ns_pair} }}"),
+                });
+            }
+        },
+        Some(_) => return None,
+        None => (parsed.range(key).end, format!(", {{ {ns_pair} }}")),
+    };
+    let range = SourceRange { start: position, end: position };
+    Some(TextEdit { range: line_index.to_lsp_range(range), new_text })
+}
+
+fn unquote(text: &str) -> &str {
+    text.trim_matches(|c| matches!(c, '\'' | '"' | '`'))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing, clippy::panic)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+    use crate::db::I18nDatabaseImpl;
+    use crate::input::source::ProgrammingLanguage;
+    use crate::test_utils::create_translation_with_json;
+
+    const OPTIONS: MoveOptions<'static> = MoveOptions {
+        key_separator: ".",
+        namespace_separator: Some(":"),
+        default_namespace: None,
+        encoding: PositionEncoding::Utf16,
+    };
+
+    fn translation(
+        db: &I18nDatabaseImpl,
+        language: &str,
+        namespace: &str,
+        keys: &[(&str, &str)],
+        json: &str,
+    ) -> Translation {
+        create_translation_with_json(
+            db,
+            language,
+            Some(namespace),
+            &format!("/locales/{language}/{namespace}.json"),
+            keys.iter().map(|(k, v)| ((*k).to_string(), (*v).to_string())).collect(),
+            json,
+        )
+    }
+
+    fn workspace(db: &I18nDatabaseImpl) -> Vec<Translation> {
+        vec![
+            translation(
+                db,
+                "en",
+                "common",
+                &[
+                    ("save", "Save"),
+                    ("items_one", "{{count}} item"),
+                    ("items_other", "{{count}} items"),
+                ],
+                "{\n  \"save\": \"Save\",\n  \"items_one\": \"{{count}} item\",\n  \"items_other\": \"{{count}} items\"\n}",
+            ),
+            translation(
+                db,
+                "en",
+                "settings",
+                &[("title", "Settings")],
+                "{\n  \"title\": \"Settings\"\n}",
+            ),
+            translation(db, "ja", "common", &[("save", "保存")], "{\n  \"save\": \"保存\"\n}"),
+            translation(db, "ja", "settings", &[], "{}"),
+        ]
+    }
+
+    fn source_files(db: &I18nDatabaseImpl, code: &str) -> HashMap<PathBuf, SourceFile> {
+        let source_file = SourceFile::new(
+            db,
+            "file:///src/app.tsx".to_string(),
+            code.to_string(),
+            ProgrammingLanguage::Tsx,
+        );
+        HashMap::from([(PathBuf::from("/src/app.tsx"), source_file)])
+    }
+
+    fn edits_for(edit: &WorkspaceEdit, uri: &str) -> Vec<TextEdit> {
+        edit.changes
+            .as_ref()
+            .unwrap()
+            .get(&uri.parse::<Url>().unwrap())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn apply(text: &str, edits: &[TextEdit]) -> String {
+        let mut edits = edits.to_vec();
+        edits.sort_by_key(|edit| {
+            std::cmp::Reverse((edit.range.start.line, edit.range.start.character))
+        });
+        let mut lines: Vec<String> = text.split('\n').map(String::from).collect();
+        for edit in edits {
+            assert_that!(edit.range.start.line, eq(edit.range.end.line));
+            let line = &mut lines[edit.range.start.line as usize];
+            line.replace_range(
+                edit.range.start.character as usize..edit.range.end.character as usize,
+                &edit.new_text,
+            );
+        }
+        lines.join("\n")
+    }
+
+    #[rstest]
+    fn moves_key_between_translation_files() {
+        let db = I18nDatabaseImpl::default();
+        let translations = workspace(&db);
+
+        let edit = compute_move_edits(
+            &db,
+            "save",
+            "common",
+            "settings",
+            "save",
+            &translations,
+            &HashMap::new(),
+            &OPTIONS,
+        )
+        .unwrap();
+
+        let en_common = edits_for(&edit, "file:///locales/en/common.json");
+        assert_that!(en_common[0].new_text, not(contains_substring("\"save\"")));
+        assert_that!(en_common[0].new_text, contains_substring("\"items_one\""));
+        let en_settings = edits_for(&edit, "file:///locales/en/settings.json");
+        assert_that!(en_settings[0].new_text, contains_substring("\"save\": \"Save\""));
+        assert_that!(en_settings[0].new_text, contains_substring("\"title\": \"Settings\""));
+        let ja_settings = edits_for(&edit, "file:///locales/ja/settings.json");
+        assert_that!(ja_settings[0].new_text, contains_substring("\"save\": \"保存\""));
+    }
+
+    #[rstest]
+    fn moves_plural_variants_with_new_name() {
+        let db = I18nDatabaseImpl::default();
+        let translations = workspace(&db);
+
+        let edit = compute_move_edits(
+            &db,
+            "items",
+            "common",
+            "settings",
+            "entries",
+            &translations,
+            &HashMap::new(),
+            &OPTIONS,
+        )
+        .unwrap();
+
+        let en_settings = edits_for(&edit, "file:///locales/en/settings.json");
+        assert_that!(en_settings[0].new_text, contains_substring("\"entries_one\""));
+        assert_that!(en_settings[0].new_text, contains_substring("\"entries_other\""));
+        assert_that!(edits_for(&edit, "file:///locales/ja/settings.json"), is_empty());
+    }
+
+    #[rstest]
+    fn rejects_move_without_target_file() {
+        let db = I18nDatabaseImpl::default();
+        let translations = workspace(&db);
+
+        let result = compute_move_edits(
+            &db,
+            "save",
+            "common",
+            "errors",
+            "save",
+            &translations,
+            &HashMap::new(),
+            &OPTIONS,
+        );
+
+        assert_that!(
+            result,
+            err(eq(&MoveKeyError::MissingTargetFile {
+                namespace: "errors".to_string(),
+                language: "en".to_string(),
+            }))
+        );
+    }
+
+    #[rstest]
+    fn rejects_conflicting_value_in_target() {
+        let db = I18nDatabaseImpl::default();
+        let mut translations = workspace(&db);
+        translations[1] =
+            translation(&db, "en", "settings", &[("save", "Apply")], "{\n  \"save\": \"Apply\"\n}");
+
+        let result = compute_move_edits(
+            &db,
+            "save",
+            "common",
+            "settings",
+            "save",
+            &translations,
+            &HashMap::new(),
+            &OPTIONS,
+        );
+
+        assert_that!(
+            result,
+            err(eq(&MoveKeyError::Conflict {
+                key: "save".to_string(),
+                language: "en".to_string()
+            }))
+        );
+    }
+
+    #[rstest]
+    #[case::explicit_prefix("t('common:save');", "t('settings:save');")]
+    #[case::ns_option("t('save', { ns: 'common' });", "t('save', { ns: 'settings' });")]
+    #[case::default_namespace_prefixed("t('save');", "t('settings:save');")]
+    #[case::child_key("t('common:save.label');", "t('settings:save.label');")]
+    fn rewrites_call_sites(#[case] code: &str, #[case] expected: &str) {
+        let db = I18nDatabaseImpl::default();
+        let translations = workspace(&db);
+        let source_files = source_files(&db, code);
+        let options = MoveOptions { default_namespace: Some("common"), ..OPTIONS };
+
+        let edit = compute_move_edits(
+            &db,
+            "save",
+            "common",
+            "settings",
+            "save",
+            &translations,
+            &source_files,
+            &options,
+        )
+        .unwrap();
+
+        assert_that!(apply(code, &edits_for(&edit, "file:///src/app.tsx")), eq(expected));
+    }
+
+    #[rstest]
+    #[case::no_options("t('save');", "t('save', { ns: 'settings' });")]
+    #[case::merged_options("t('save', { count });", "t('save', { ns: 'settings', count });")]
+    #[case::double_quotes("t(\"save\", {});", "t(\"save\", { ns: \"settings\" });")]
+    fn adds_ns_option_without_namespace_separator(#[case] code: &str, #[case] expected: &str) {
+        let db = I18nDatabaseImpl::default();
+        let translations = workspace(&db);
+        let source_files = source_files(&db, code);
+        let options =
+            MoveOptions { namespace_separator: None, default_namespace: Some("common"), ..OPTIONS };
+
+        let edit = compute_move_edits(
+            &db,
+            "save",
+            "common",
+            "settings",
+            "save",
+            &translations,
+            &source_files,
+            &options,
+        )
+        .unwrap();
+
+        assert_that!(apply(code, &edits_for(&edit, "file:///src/app.tsx")), eq(expected));
+    }
+
+    #[rstest]
+    fn switches_hook_namespace_when_all_usages_move() {
+        let db = I18nDatabaseImpl::default();
+        let translations = workspace(&db);
+        let code = "const { t } = useTranslation('common');\nt('save');\nt('save.label');";
+        let source_files = source_files(&db, code);
+
+        let edit = compute_move_edits(
+            &db,
+            "save",
+            "common",
+            "settings",
+            "apply",
+            &translations,
+            &source_files,
+            &OPTIONS,
+        )
+        .unwrap();
+
+        assert_that!(
+            apply(code, &edits_for(&edit, "file:///src/app.tsx")),
+            eq("const { t } = useTranslation('settings');\nt('apply');\nt('apply.label');")
+        );
+    }
+
+    #[rstest]
+    fn keeps_hook_namespace_when_other_usages_remain() {
+        let db = I18nDatabaseImpl::default();
+        let translations = workspace(&db);
+        let code = "const { t } = useTranslation('common');\nt('save');\nt('items', { count });";
+        let source_files = source_files(&db, code);
+
+        let edit = compute_move_edits(
+            &db,
+            "save",
+            "common",
+            "settings",
+            "save",
+            &translations,
+            &source_files,
+            &OPTIONS,
+        )
+        .unwrap();
+
+        assert_that!(
+            apply(code, &edits_for(&edit, "file:///src/app.tsx")),
+            eq(
+                "const { t } = useTranslation('common');\nt('settings:save');\nt('items', { count });"
+            )
+        );
+    }
+
+    #[rstest]
+    fn ignores_usages_in_other_namespaces() {
+        let db = I18nDatabaseImpl::default();
+        let translations = workspace(&db);
+        let code = "t('settings:save');";
+        let source_files = source_files(&db, code);
+
+        let edit = compute_move_edits(
+            &db,
+            "save",
+            "common",
+            "settings",
+            "save",
+            &translations,
+            &source_files,
+            &OPTIONS,
+        )
+        .unwrap();
+
+        assert_that!(edits_for(&edit, "file:///src/app.tsx"), is_empty());
+    }
+
+    #[rstest]
+    fn code_actions_offer_other_namespaces() {
+        let db = I18nDatabaseImpl::default();
+        let translations = workspace(&db);
+
+        let actions = generate_move_key_code_actions(&db, "save", "common", &translations, ".");
+
+        let titles: Vec<String> = actions
+            .iter()
+            .map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) => action.title.clone(),
+                CodeActionOrCommand::Command(command) => command.title.clone(),
+            })
+            .collect();
+        assert_that!(titles, elements_are![eq("Move 'save' to namespace 'settings'")]);
+        assert_that!(
+            generate_move_key_code_actions(&db, "missing", "common", &translations, "."),
+            is_empty()
+        );
+    }
+}
@@ -1,11 +1,42 @@
 //! Plural suffix handling.
 
+pub mod rules;
+
 use std::collections::{
+    BTreeMap,
+    BTreeSet,
     HashMap,
     HashSet,
 };
 
+use tower_lsp::lsp_types::{
+    Diagnostic,
+    NumberOrString,
+};
+
+use self::rules::{
+    PluralCategory,
+    cardinal_categories,
+    ordinal_categories,
+};
+use crate::config::Severity;
+use crate::db::I18nDatabase;
 use crate::framework::PluralStrategy;
+use crate::ide::icu::{
+    ChoiceKind,
+    IcuElement,
+    is_i18next_value,
+    parse_icu,
+};
+use crate::input::translation::{
+    Translation,
+    TranslationFormat,
+};
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+    SourceRange,
+};
 
 /// Longer suffixes must come first to avoid `_one` matching `place_ordinal_one`.
 pub const PLURAL_SUFFIXES: &[&str] = &[
@@ -108,7 +139,277 @@ pub fn key_exists_with_plural(
     has_plural_variants(key, available_keys, strategy)
 }
 
+/// Splits a suffix-based plural key into its base key, ordinal flag and category.
+#[must_use]
+pub fn split_plural_suffix(key: &str) -> Option<(&str, bool, PluralCategory)> {
+    PLURAL_SUFFIXES.iter().find_map(|suffix| {
+        let base = key.strip_suffix(suffix).filter(|base| !base.is_empty())?;
+        let category = PluralCategory::parse(suffix.rsplit('_').next()?)?;
+        Some((base, suffix.starts_with("_ordinal_"), category))
+    })
+}
+
+/// Returns the plural categories of `locale` for cardinal or ordinal plurals.
+fn required_categories(locale: &str, ordinal: bool) -> Option<&'static [PluralCategory]> {
+    if ordinal { ordinal_categories(locale) } else { cardinal_categories(locale) }
+}
+
+/// Categories that `locale` requires but are not present, and present ones it never selects.
+///
+/// `allowed` categories are never reported as unexpected.
+fn compare_categories(
+    present: &BTreeSet<PluralCategory>,
+    required: &[PluralCategory],
+    allowed: &[PluralCategory],
+) -> (Vec<PluralCategory>, Vec<PluralCategory>) {
+    let missing = required.iter().filter(|c| !present.contains(c)).copied().collect();
+    let unexpected =
+        present.iter().filter(|c| !required.contains(c) && !allowed.contains(c)).copied().collect();
+    (missing, unexpected)
+}
+
+/// A plural or selectordinal argument of an ICU message whose branches don't match the
+/// categories of the language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcuPluralGap {
+    pub argument: String,
+    pub missing: Vec<PluralCategory>,
+    pub unexpected: Vec<PluralCategory>,
+    /// Byte offset of the `other` branch, where missing branches are inserted
+    pub other_offset: usize,
+    /// Message of the `other` branch, used as the initial message of missing branches
+    pub other_text: String,
+}
+
+/// Finds ICU plural arguments in `value` with missing or unexpected branches for `locale`.
+///
+/// Returns nothing for i18next values, invalid messages and unknown languages.
+#[must_use]
+pub fn find_icu_plural_gaps(value: &str, locale: &str) -> Vec<IcuPluralGap> {
+    if is_i18next_value(value) {
+        return Vec::new();
+    }
+    let Ok(elements) = parse_icu(value) else {
+        return Vec::new();
+    };
+    let mut gaps = Vec::new();
+    collect_icu_plural_gaps(&elements, locale, &mut gaps);
+    gaps
+}
+
+fn collect_icu_plural_gaps(elements: &[IcuElement], locale: &str, gaps: &mut Vec<IcuPluralGap>) {
+    for element in elements {
+        let IcuElement::Choice { name, kind, branches } = element else {
+            continue;
+        };
+        for branch in branches {
+            collect_icu_plural_gaps(&branch.elements, locale, gaps);
+        }
+
+        let Some(required) = (match kind {
+            ChoiceKind::Plural => cardinal_categories(locale),
+            ChoiceKind::SelectOrdinal => ordinal_categories(locale),
+            ChoiceKind::Select => None,
+        }) else {
+            continue;
+        };
+        let Some(other) = branches.iter().find(|branch| branch.selector == "other") else {
+            continue;
+        };
+
+        // Exact matches (`=0`) are not categories
+        let present: BTreeSet<PluralCategory> =
+            branches.iter().filter_map(|branch| PluralCategory::parse(&branch.selector)).collect();
+        let (missing, unexpected) = compare_categories(&present, required, &[]);
+        if missing.is_empty() && unexpected.is_empty() {
+            continue;
+        }
+
+        gaps.push(IcuPluralGap {
+            argument: name.clone(),
+            missing,
+            unexpected,
+            other_offset: other.offset,
+            other_text: other.text.clone(),
+        });
+    }
+}
+
+fn category_names(categories: &[PluralCategory]) -> Vec<&'static str> {
+    categories.iter().map(|category| category.as_str()).collect()
+}
+
+fn plural_category_diagnostic(
+    range: SourceRange,
+    line_index: &LineIndex<'_>,
+    severity: Severity,
+    code: &str,
+    message: String,
+    data: serde_json::Value,
+) -> Diagnostic {
+    Diagnostic {
+        range: line_index.to_lsp_range(range),
+        severity: Some(severity.to_lsp()),
+        code: Some(NumberOrString::String(code.to_string())),
+        code_description: None,
+        source: Some("js-i18n".to_string()),
+        message,
+        related_information: None,
+        tags: None,
+        data: Some(data),
+    }
+}
+
+/// Diagnostics for suffix-based plural keys (`items_one`), grouped by base key.
+fn suffix_category_diagnostics(
+    format: TranslationFormat,
+    language: &str,
+    keys: &HashMap<String, String>,
+    key_ranges: &HashMap<String, SourceRange>,
+    line_index: &LineIndex<'_>,
+    severity: Severity,
+) -> Vec<Diagnostic> {
+    // PO plural forms follow the file's `Plural-Forms` header, which may have fewer forms
+    // than the language has categories, so their suffixes are not checked
+    if format == TranslationFormat::Po {
+        return Vec::new();
+    }
+
+    let mut groups: BTreeMap<(&str, bool), BTreeMap<PluralCategory, &String>> = BTreeMap::new();
+    for key in keys.keys() {
+        if let Some((base, ordinal, category)) = split_plural_suffix(key) {
+            groups.entry((base, ordinal)).or_default().insert(category, key);
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+
+    for ((base, ordinal), variants) in groups {
+        let Some(required) = required_categories(language, ordinal) else {
+            continue;
+        };
+        let present: BTreeSet<PluralCategory> = variants.keys().copied().collect();
+        let allowed: &[PluralCategory] = if ordinal { &[] } else { &[PluralCategory::Zero] };
+        let (missing, unexpected) = compare_categories(&present, required, allowed);
+        let suffix = if ordinal { "_ordinal_" } else { "_" };
+
+        let anchor = variants.get(&PluralCategory::Other).or_else(|| variants.values().next());
+        if !missing.is_empty()
+            && let Some(range) = anchor.and_then(|key| key_ranges.get(*key))
+        {
+            let missing_keys: Vec<String> = missing
+                .iter()
+                .map(|category| format!("{base}{suffix}{}", category.as_str()))
+                .collect();
+            diagnostics.push(plural_category_diagnostic(
+                *range,
+                line_index,
+                severity,
+                "missing-plural-category",
+                format!(
+                    "Missing plural variants of '{base}' for '{language}': {}",
+                    missing_keys.join(", ")
+                ),
+                serde_json::json!({
+                    "key": base,
+                    "ordinal": ordinal,
+                    "missing": category_names(&missing),
+                }),
+            ));
+        }
+
+        for category in unexpected {
+            let Some(key) = variants.get(&category) else {
+                continue;
+            };
+            let Some(range) = key_ranges.get(*key) else {
+                continue;
+            };
+            diagnostics.push(plural_category_diagnostic(
+                *range,
+                line_index,
+                severity,
+                "unexpected-plural-category",
+                format!("Plural variant '{key}' is never used in '{language}'"),
+                serde_json::json!({ "key": key, "category": category.as_str() }),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+/// Generates diagnostics for plural variants that don't match the CLDR plural categories of the
+/// translation's language.
+///
+/// Checks i18next suffix-based keys (`items_one`, `place_ordinal_two`) and the branches of ICU
+/// `plural` and `selectordinal` arguments. `_zero` keys are always allowed, since i18next uses
+/// them for a count of 0 in every language. Languages without plural data are skipped.
+pub fn generate_plural_category_diagnostics(
+    db: &dyn I18nDatabase,
+    translation: Translation,
+    severity: Severity,
+    encoding: PositionEncoding,
+) -> Vec<Diagnostic> {
+    let language = translation.language(db);
+    let keys = translation.keys(db);
+    let key_ranges = translation.key_ranges(db);
+    let value_ranges = translation.value_ranges(db);
+    let line_index = LineIndex::new(translation.json_text(db), encoding);
+
+    let mut diagnostics = suffix_category_diagnostics(
+        translation.format(db),
+        &language,
+        keys,
+        key_ranges,
+        &line_index,
+        severity,
+    );
+
+    // ICU plural branches
+    let mut sorted_keys: Vec<&String> = keys.keys().collect();
+    sorted_keys.sort();
+    for key in sorted_keys {
+        let (Some(value), Some(range)) = (keys.get(key), value_ranges.get(key)) else {
+            continue;
+        };
+        for gap in find_icu_plural_gaps(value, &language) {
+            if !gap.missing.is_empty() {
+                diagnostics.push(plural_category_diagnostic(
+                    *range,
+                    &line_index,
+                    severity,
+                    "missing-plural-category",
+                    format!(
+                        "Missing plural branches of '{}' in '{key}' for '{language}': {}",
+                        gap.argument,
+                        category_names(&gap.missing).join(", ")
+                    ),
+                    serde_json::json!({ "key": key, "missing": category_names(&gap.missing) }),
+                ));
+            }
+            for category in &gap.unexpected {
+                diagnostics.push(plural_category_diagnostic(
+                    *range,
+                    &line_index,
+                    severity,
+                    "unexpected-plural-category",
+                    format!(
+                        "Plural branch '{}' of '{}' in '{key}' is never used in '{language}'",
+                        category.as_str(),
+                        gap.argument
+                    ),
+                    serde_json::json!({ "key": key, "category": category.as_str() }),
+                ));
+            }
+        }
+    }
+
+    diagnostics
+}
+
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
 
@@ -256,4 +557,133 @@ mod tests {
         // No plural variant expansion
         assert!(!key_exists_with_plural("items", &keys, PluralStrategy::Icu));
     }
+
+    fn plural_diagnostic_messages(file_path: &str, content: &str) -> Vec<(String, String)> {
+        let db = crate::db::I18nDatabaseImpl::default();
+        let translation = crate::input::translation::load_translation_from_content(
+            &db,
+            std::path::Path::new(file_path),
+            content,
+            ".",
+        )
+        .unwrap();
+        generate_plural_category_diagnostics(
+            &db,
+            translation,
+            Severity::Warning,
+            PositionEncoding::Utf16,
+        )
+        .into_iter()
+        .map(|d| {
+            let code = match d.code {
+                Some(NumberOrString::String(code)) => code,
+                _ => String::new(),
+            };
+            (code, d.message)
+        })
+        .collect()
+    }
+
+    fn expected(diagnostics: &[(&str, &str)]) -> Vec<(String, String)> {
+        diagnostics
+            .iter()
+            .map(|(code, message)| ((*code).to_string(), (*message).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_plural_suffix() {
+        assert_eq!(split_plural_suffix("items_few"), Some(("items", false, PluralCategory::Few)));
+        assert_eq!(
+            split_plural_suffix("place_ordinal_two"),
+            Some(("place", true, PluralCategory::Two))
+        );
+        assert_eq!(split_plural_suffix("items"), None);
+        assert_eq!(split_plural_suffix("_one"), None);
+    }
+
+    #[test]
+    fn plural_categories_reports_missing_suffix_variants() {
+        let content =
+            r#"{ "items_one": "{{count}} предмет", "items_other": "{{count}} предмета" }"#;
+        assert_eq!(
+            plural_diagnostic_messages("/locales/ru.json", content),
+            expected(&[(
+                "missing-plural-category",
+                "Missing plural variants of 'items' for 'ru': items_few, items_many"
+            )])
+        );
+    }
+
+    #[test]
+    fn plural_categories_reports_unexpected_suffix_variants() {
+        let content = r#"{ "items_one": "{{count}} 個", "items_other": "{{count}} 個" }"#;
+        assert_eq!(
+            plural_diagnostic_messages("/locales/ja.json", content),
+            expected(&[(
+                "unexpected-plural-category",
+                "Plural variant 'items_one' is never used in 'ja'"
+            )])
+        );
+    }
+
+    #[test]
+    fn plural_categories_allows_zero_and_complete_variants() {
+        let content = r#"{
+            "items_zero": "No items",
+            "items_one": "One item",
+            "items_other": "{{count}} items",
+            "place_ordinal_one": "{{count}}st",
+            "place_ordinal_two": "{{count}}nd",
+            "place_ordinal_few": "{{count}}rd",
+            "place_ordinal_other": "{{count}}th"
+        }"#;
+        assert_eq!(plural_diagnostic_messages("/locales/en.json", content), Vec::new());
+    }
+
+    #[test]
+    fn plural_categories_checks_icu_branches() {
+        let content = r#"{
+            "cart": "{count, plural, one {# товар} other {# товаров}}",
+            "greeting": "{gender, select, male {Он} other {Они}}"
+        }"#;
+        assert_eq!(
+            plural_diagnostic_messages("/locales/ru.json", content),
+            expected(&[(
+                "missing-plural-category",
+                "Missing plural branches of 'count' in 'cart' for 'ru': few, many"
+            )])
+        );
+
+        let content = r#"{ "cart": "{count, plural, =0 {なし} one {# 個} other {# 個}}" }"#;
+        assert_eq!(
+            plural_diagnostic_messages("/locales/ja.json", content),
+            expected(&[(
+                "unexpected-plural-category",
+                "Plural branch 'one' of 'count' in 'cart' is never used in 'ja'"
+            )])
+        );
+    }
+
+    #[test]
+    fn plural_categories_skips_unknown_languages() {
+        let content = r#"{ "items_one": "x", "cart": "{n, plural, one {x} other {y}}" }"#;
+        assert_eq!(plural_diagnostic_messages("/locales/xx.json", content), Vec::new());
+    }
+
+    #[test]
+    fn plural_categories_skips_po_plural_forms() {
+        let content = r#"msgid ""
+msgstr ""
+"Language: ru\n"
+"Plural-Forms: nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);\n"
+
+msgid "item"
+msgid_plural "items"
+msgstr[0] "{count} предмет"
+msgstr[1] "{count} предмета"
+msgstr[2] "{count} предметов"
+"#;
+        assert_eq!(plural_diagnostic_messages("/locales/ru.po", content), Vec::new());
+    }
 }
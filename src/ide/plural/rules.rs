@@ -0,0 +1,194 @@
+//! CLDR plural categories per language.
+//!
+//! Only the category sets are needed to validate translation keys, not the selection rules.
+//! The rules are represented by a sample number per category, which is enough to tell which
+//! category a gettext plural form is used for.
+//! Data follows CLDR 44 (`plurals.xml` and `ordinals.xml`).
+
+use PluralCategory::{
+    Few,
+    Many,
+    One,
+    Other,
+    Two,
+    Zero,
+};
+
+/// CLDR plural category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    pub const ALL: [Self; 6] = [Zero, One, Two, Few, Many, Other];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Zero => "zero",
+            One => "one",
+            Two => "two",
+            Few => "few",
+            Many => "many",
+            Other => "other",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|category| category.as_str() == name)
+    }
+}
+
+/// Returns the primary language subtag of a locale (`pt-BR` and `pt_BR` -> `pt`).
+#[must_use]
+pub fn base_language(locale: &str) -> String {
+    locale.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase()
+}
+
+/// Returns the cardinal plural categories of a locale, or `None` if the language is unknown.
+#[must_use]
+pub fn cardinal_categories(locale: &str) -> Option<&'static [PluralCategory]> {
+    Some(match base_language(locale).as_str() {
+        "bo" | "dz" | "id" | "ig" | "ii" | "in" | "ja" | "jv" | "kea" | "km" | "ko" | "lo"
+        | "ms" | "my" | "sah" | "ses" | "sg" | "th" | "to" | "vi" | "wo" | "yo" | "yue" | "zh" => {
+            &[Other]
+        }
+        "af" | "am" | "as" | "az" | "bg" | "bn" | "da" | "de" | "el" | "en" | "et" | "eu"
+        | "fa" | "fi" | "fil" | "fy" | "gl" | "gu" | "hi" | "hu" | "hy" | "is" | "ka" | "kk"
+        | "kn" | "ky" | "lb" | "mk" | "ml" | "mn" | "mr" | "nb" | "ne" | "nl" | "nn" | "no"
+        | "or" | "pa" | "ps" | "si" | "so" | "sq" | "sv" | "sw" | "ta" | "te" | "tk" | "tr"
+        | "ug" | "ur" | "uz" | "zu" => &[One, Other],
+        "ca" | "es" | "fr" | "it" | "pt" => &[One, Many, Other],
+        "bs" | "hr" | "ro" | "sh" | "sr" => &[One, Few, Other],
+        "be" | "cs" | "lt" | "pl" | "ru" | "sk" | "uk" => &[One, Few, Many, Other],
+        "lv" => &[Zero, One, Other],
+        "he" | "iw" => &[One, Two, Other],
+        "gd" | "sl" => &[One, Two, Few, Other],
+        "ga" | "mt" => &[One, Two, Few, Many, Other],
+        "ar" | "cy" => &[Zero, One, Two, Few, Many, Other],
+        _ => return None,
+    })
+}
+
+/// Returns an integer selecting each cardinal category of a locale, or `None` if the language
+/// is unknown.
+///
+/// Categories only selected by fractions (e.g., Russian `other`) have no sample.
+#[must_use]
+pub fn cardinal_samples(locale: &str) -> Option<&'static [(PluralCategory, u64)]> {
+    Some(match base_language(locale).as_str() {
+        "fil" => &[(One, 1), (Other, 4)],
+        "ca" | "es" | "fr" | "it" | "pt" => &[(One, 1), (Many, 1_000_000), (Other, 2)],
+        "bs" | "hr" | "ro" | "sh" | "sr" => &[(One, 1), (Few, 2), (Other, 20)],
+        "be" | "pl" | "ru" | "uk" => &[(One, 1), (Few, 2), (Many, 5)],
+        "cs" | "sk" => &[(One, 1), (Few, 2), (Other, 5)],
+        "lt" => &[(One, 1), (Few, 2), (Other, 10)],
+        "lv" => &[(Zero, 0), (One, 1), (Other, 2)],
+        "he" | "iw" => &[(One, 1), (Two, 2), (Other, 3)],
+        "gd" | "sl" => &[(One, 1), (Two, 2), (Few, 3), (Other, 20)],
+        "ga" => &[(One, 1), (Two, 2), (Few, 3), (Many, 7), (Other, 11)],
+        "mt" => &[(One, 1), (Two, 2), (Few, 3), (Many, 11), (Other, 20)],
+        "ar" => &[(Zero, 0), (One, 1), (Two, 2), (Few, 3), (Many, 11), (Other, 100)],
+        "cy" => &[(Zero, 0), (One, 1), (Two, 2), (Few, 3), (Many, 6), (Other, 4)],
+        _ => match cardinal_categories(locale)? {
+            [Other] => &[(Other, 1)],
+            _ => &[(One, 1), (Other, 2)],
+        },
+    })
+}
+
+/// Returns the ordinal plural categories of a locale, or `None` if the language is unknown.
+#[must_use]
+pub fn ordinal_categories(locale: &str) -> Option<&'static [PluralCategory]> {
+    Some(match base_language(locale).as_str() {
+        "af" | "am" | "ar" | "bg" | "bs" | "cs" | "da" | "de" | "el" | "es" | "et" | "eu"
+        | "fa" | "fi" | "fy" | "gl" | "he" | "hr" | "id" | "is" | "ja" | "km" | "kn" | "ko"
+        | "ky" | "lt" | "lv" | "ml" | "mn" | "my" | "nb" | "nl" | "no" | "pa" | "pl" | "ps"
+        | "pt" | "ru" | "si" | "sk" | "sl" | "sr" | "sw" | "ta" | "te" | "th" | "tr" | "ur"
+        | "uz" | "yue" | "zh" | "zu" => &[Other],
+        "fil" | "fr" | "ga" | "hu" | "hy" | "lo" | "ms" | "ro" | "sv" | "vi" => &[One, Other],
+        "it" | "kk" => &[Many, Other],
+        "tk" | "uk" => &[Few, Other],
+        "ka" | "sq" => &[One, Many, Other],
+        "ca" | "en" | "mr" => &[One, Two, Few, Other],
+        "mk" => &[One, Two, Many, Other],
+        "az" => &[One, Few, Many, Other],
+        "bn" | "gu" | "hi" => &[One, Two, Few, Many, Other],
+        "cy" => &[Zero, One, Two, Few, Many, Other],
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case::plain("ja", "ja")]
+    #[case::region("pt-BR", "pt")]
+    #[case::underscore("zh_Hant_TW", "zh")]
+    #[case::uppercase("EN", "en")]
+    fn base_language_strips_subtags(#[case] locale: &str, #[case] expected: &str) {
+        assert_that!(base_language(locale), eq(expected));
+    }
+
+    #[rstest]
+    #[case::japanese("ja", &[Other])]
+    #[case::english("en-US", &[One, Other])]
+    #[case::french("fr", &[One, Many, Other])]
+    #[case::russian("ru", &[One, Few, Many, Other])]
+    #[case::arabic("ar", &[Zero, One, Two, Few, Many, Other])]
+    fn cardinal_categories_by_language(#[case] locale: &str, #[case] expected: &[PluralCategory]) {
+        assert_that!(cardinal_categories(locale), some(eq(expected)));
+    }
+
+    #[rstest]
+    #[case::english("en", &[One, Two, Few, Other])]
+    #[case::russian("ru", &[Other])]
+    #[case::italian("it", &[Many, Other])]
+    fn ordinal_categories_by_language(#[case] locale: &str, #[case] expected: &[PluralCategory]) {
+        assert_that!(ordinal_categories(locale), some(eq(expected)));
+    }
+
+    #[rstest]
+    fn cardinal_samples_cover_categories() {
+        for locale in [
+            "ja", "en", "fil", "fr", "ro", "ru", "cs", "lt", "lv", "he", "sl", "ga", "mt", "ar",
+            "cy",
+        ] {
+            let categories = cardinal_categories(locale).unwrap();
+            let samples = cardinal_samples(locale).unwrap();
+            assert_that!(
+                samples.iter().all(|(category, _)| categories.contains(category)),
+                eq(true),
+                "{locale}"
+            );
+        }
+    }
+
+    #[rstest]
+    fn unknown_languages_have_no_categories() {
+        assert_that!(cardinal_categories("xx"), none());
+        assert_that!(cardinal_samples("xx"), none());
+        assert_that!(ordinal_categories("xx"), none());
+    }
+
+    #[rstest]
+    fn parse_round_trips() {
+        for category in PluralCategory::ALL {
+            assert_that!(PluralCategory::parse(category.as_str()), some(eq(category)));
+        }
+        assert_that!(PluralCategory::parse("several"), none());
+    }
+}
@@ -0,0 +1,497 @@
+//! Pull diagnostics (`textDocument/diagnostic` and `workspace/diagnostic`).
+//!
+//! Every report carries a result ID identifying the inputs it was computed from. When a client
+//! sends back the current ID, diagnostics are reported as unchanged without being recomputed.
+
+use std::collections::HashMap;
+use std::hash::{
+    DefaultHasher,
+    Hash,
+    Hasher,
+};
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+use tower_lsp::lsp_types::{
+    Diagnostic,
+    DocumentDiagnosticReportKind,
+    FullDocumentDiagnosticReport,
+    UnchangedDocumentDiagnosticReport,
+    Url,
+    WorkspaceDocumentDiagnosticReport,
+    WorkspaceFullDocumentDiagnosticReport,
+    WorkspaceUnchangedDocumentDiagnosticReport,
+};
+
+use crate::db::I18nDatabase;
+use crate::ide::diagnostics::DiagnosticConfig;
+use crate::input::source::SourceFile;
+use crate::input::translation::Translation;
+use crate::syntax::analyze_source;
+
+/// Result IDs of documents, derived from the inputs their diagnostics depend on.
+///
+/// A source file depends on its own text and the translations visible to it, so edits to other
+/// source files keep its result ID. A translation file depends on every translation (placeholder
+/// consistency) and on the keys used by every source file (unused keys), but not on their
+/// positions. `generation` distinguishes databases recreated on reindex, e.g. after a
+/// configuration change.
+///
+/// Hashes shared by several documents are computed once.
+pub struct ResultIds<'a> {
+    db: &'a dyn I18nDatabase,
+    generation: u64,
+    source_files: &'a HashMap<PathBuf, SourceFile>,
+    translations: &'a [Translation],
+    key_separator: &'a str,
+    translation_hashes: HashMap<Translation, u64>,
+    key_usages_hash: Option<u64>,
+}
+
+impl<'a> ResultIds<'a> {
+    #[must_use]
+    #[allow(clippy::implicit_hasher)]
+    pub fn new(
+        db: &'a dyn I18nDatabase,
+        generation: u64,
+        source_files: &'a HashMap<PathBuf, SourceFile>,
+        translations: &'a [Translation],
+        key_separator: &'a str,
+    ) -> Self {
+        Self {
+            db,
+            generation,
+            source_files,
+            translations,
+            key_separator,
+            translation_hashes: HashMap::new(),
+            key_usages_hash: None,
+        }
+    }
+
+    /// Returns the result ID of the diagnostics of `file_path`.
+    pub fn get(&mut self, file_path: &Path) -> String {
+        let db = self.db;
+        let mut hasher = DefaultHasher::new();
+        self.generation.hash(&mut hasher);
+        file_path.hash(&mut hasher);
+
+        if let Some(&source_file) = self.source_files.get(file_path) {
+            source_file.language(db).hash(&mut hasher);
+            source_file.text(db).hash(&mut hasher);
+            let visible = crate::ide::scope::visible_translations(db, self.translations, file_path);
+            for translation in visible {
+                self.translation_hash(translation).hash(&mut hasher);
+            }
+        } else if let Some(&translation) =
+            self.translations.iter().find(|t| Path::new(t.file_path(db)) == file_path)
+        {
+            self.translation_hash(translation).hash(&mut hasher);
+            for &translation in self.translations {
+                self.translation_hash(translation).hash(&mut hasher);
+            }
+            self.key_usages_hash().hash(&mut hasher);
+        }
+        format!("{:016x}", hasher.finish())
+    }
+
+    fn translation_hash(&mut self, translation: Translation) -> u64 {
+        let db = self.db;
+        *self.translation_hashes.entry(translation).or_insert_with(|| {
+            let mut hasher = DefaultHasher::new();
+            translation.language(db).hash(&mut hasher);
+            translation.namespace(db).hash(&mut hasher);
+            translation.file_path(db).hash(&mut hasher);
+            translation.json_text(db).hash(&mut hasher);
+            hasher.finish()
+        })
+    }
+
+    /// Hash of the languages of source files and the keys they use, without positions.
+    fn key_usages_hash(&mut self) -> u64 {
+        let (db, source_files, key_separator) = (self.db, self.source_files, self.key_separator);
+        *self.key_usages_hash.get_or_insert_with(|| {
+            let mut source_files: Vec<_> = source_files.iter().collect();
+            source_files.sort_unstable_by_key(|(path, _)| *path);
+
+            let mut hasher = DefaultHasher::new();
+            for (path, &source_file) in source_files {
+                path.hash(&mut hasher);
+                source_file.language(db).hash(&mut hasher);
+                for usage in analyze_source(db, source_file, key_separator.to_string()) {
+                    usage.key(db).text(db).hash(&mut hasher);
+                    usage.namespace(db).hash(&mut hasher);
+                    usage.namespaces(db).hash(&mut hasher);
+                    usage.dynamic(db).hash(&mut hasher);
+                }
+            }
+            hasher.finish()
+        })
+    }
+}
+
+impl std::fmt::Debug for ResultIds<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResultIds")
+            .field("generation", &self.generation)
+            .field("translation_hashes", &self.translation_hashes.len())
+            .field("key_usages_hash", &self.key_usages_hash)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Generates the diagnostics of a source or translation file; other files have none.
+#[must_use]
+#[allow(clippy::implicit_hasher)]
+pub fn file_diagnostics(
+    db: &dyn I18nDatabase,
+    config: &DiagnosticConfig,
+    file_path: &Path,
+    source_files: &HashMap<PathBuf, SourceFile>,
+    translations: &[Translation],
+) -> Vec<Diagnostic> {
+    if let Some(&source_file) = source_files.get(file_path) {
+        let visible = crate::ide::scope::visible_translations(db, translations, file_path);
+        return config.source_file_diagnostics(db, source_file, &visible);
+    }
+
+    if !config.has_translation_file_diagnostics() {
+        return Vec::new();
+    }
+    let file_path = file_path.to_string_lossy();
+    translations
+        .iter()
+        .find(|t| !t.is_component_local(db) && t.file_path(db).as_str() == file_path)
+        .map(|&translation| {
+            let source_files: Vec<SourceFile> = source_files.values().copied().collect();
+            config.translation_file_diagnostics(db, translation, translations, &source_files)
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the files reported by `workspace/diagnostic`: every indexed source file and
+/// translation file.
+#[must_use]
+#[allow(clippy::implicit_hasher)]
+pub fn workspace_files(
+    db: &dyn I18nDatabase,
+    source_files: &HashMap<PathBuf, SourceFile>,
+    translations: &[Translation],
+) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = source_files.keys().cloned().collect();
+    files.extend(
+        translations
+            .iter()
+            .filter(|t| !t.is_component_local(db))
+            .map(|t| PathBuf::from(t.file_path(db))),
+    );
+    files.sort_unstable();
+    files.dedup();
+    files
+}
+
+/// Builds a full report, or an unchanged one when `previous_result_id` is still current.
+///
+/// `compute` is only called for full reports.
+pub fn document_report(
+    result_id: &str,
+    previous_result_id: Option<&str>,
+    compute: impl FnOnce() -> Vec<Diagnostic>,
+) -> DocumentDiagnosticReportKind {
+    if previous_result_id == Some(result_id) {
+        return DocumentDiagnosticReportKind::Unchanged(UnchangedDocumentDiagnosticReport {
+            result_id: result_id.to_string(),
+        });
+    }
+    DocumentDiagnosticReportKind::Full(FullDocumentDiagnosticReport {
+        result_id: Some(result_id.to_string()),
+        items: compute(),
+    })
+}
+
+/// Builds the `workspace/diagnostic` report of one document.
+pub fn workspace_document_report(
+    uri: Url,
+    result_id: &str,
+    previous_result_id: Option<&str>,
+    compute: impl FnOnce() -> Vec<Diagnostic>,
+) -> WorkspaceDocumentDiagnosticReport {
+    match document_report(result_id, previous_result_id, compute) {
+        DocumentDiagnosticReportKind::Full(report) => {
+            WorkspaceDocumentDiagnosticReport::Full(WorkspaceFullDocumentDiagnosticReport {
+                uri,
+                version: None,
+                full_document_diagnostic_report: report,
+            })
+        }
+        DocumentDiagnosticReportKind::Unchanged(report) => {
+            WorkspaceDocumentDiagnosticReport::Unchanged(
+                WorkspaceUnchangedDocumentDiagnosticReport {
+                    uri,
+                    version: None,
+                    unchanged_document_diagnostic_report: report,
+                },
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing, clippy::panic)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+    use salsa::Setter;
+
+    use super::*;
+    use crate::config::I18nSettings;
+    use crate::db::I18nDatabaseImpl;
+    use crate::input::source::ProgrammingLanguage;
+    use crate::input::translation::load_translation_from_content;
+    use crate::test_utils::create_translation;
+    use crate::types::PositionEncoding;
+
+    fn translation(db: &I18nDatabaseImpl, language: &str, keys: &[&str]) -> Translation {
+        let keys = keys.iter().map(|key| ((*key).to_string(), key.to_uppercase())).collect();
+        create_translation(db, language, &format!("/locales/{language}.json"), keys)
+    }
+
+    fn source_files(db: &I18nDatabaseImpl, text: &str) -> HashMap<PathBuf, SourceFile> {
+        let source_file = SourceFile::new(
+            db,
+            "file:///src/app.ts".to_string(),
+            text.to_string(),
+            ProgrammingLanguage::TypeScript,
+        );
+        HashMap::from([(PathBuf::from("/src/app.ts"), source_file)])
+    }
+
+    fn config() -> DiagnosticConfig {
+        DiagnosticConfig::new(&I18nSettings::default(), PositionEncoding::Utf16)
+    }
+
+    fn load(db: &I18nDatabaseImpl, path: &str, json: &str) -> Translation {
+        load_translation_from_content(db, Path::new(path), json, ".").unwrap()
+    }
+
+    fn two_source_files(db: &I18nDatabaseImpl) -> HashMap<PathBuf, SourceFile> {
+        let mut source_files = source_files(db, "t('hello');");
+        let other = SourceFile::new(
+            db,
+            "file:///src/other.ts".to_string(),
+            "t('bye');".to_string(),
+            ProgrammingLanguage::TypeScript,
+        );
+        source_files.insert(PathBuf::from("/src/other.ts"), other);
+        source_files
+    }
+
+    fn result_id(
+        db: &I18nDatabaseImpl,
+        generation: u64,
+        source_files: &HashMap<PathBuf, SourceFile>,
+        translations: &[Translation],
+        file_path: &str,
+    ) -> String {
+        ResultIds::new(db, generation, source_files, translations, ".").get(Path::new(file_path))
+    }
+
+    #[rstest]
+    #[case::source_file("/src/app.ts")]
+    #[case::translation_file("/locales/en.json")]
+    fn result_id_is_stable_without_changes(#[case] file_path: &str) {
+        let db = I18nDatabaseImpl::default();
+        let source_files = source_files(&db, "t('hello');");
+        let translations = vec![load(&db, "/locales/en.json", r#"{ "hello": "Hello" }"#)];
+
+        assert_that!(
+            result_id(&db, 0, &source_files, &translations, file_path),
+            eq(&result_id(&db, 0, &source_files, &translations, file_path))
+        );
+    }
+
+    #[rstest]
+    fn result_id_of_source_file_changes_with_its_text_only() {
+        let mut db = I18nDatabaseImpl::default();
+        let source_files = two_source_files(&db);
+        let translations = vec![load(&db, "/locales/en.json", r#"{ "hello": "Hello" }"#)];
+        let app = result_id(&db, 0, &source_files, &translations, "/src/app.ts");
+        let other = result_id(&db, 0, &source_files, &translations, "/src/other.ts");
+
+        let source_file = source_files[Path::new("/src/app.ts")];
+        source_file.set_text(&mut db).to("t('hello.world');".to_string());
+
+        assert_that!(result_id(&db, 0, &source_files, &translations, "/src/app.ts"), not(eq(&app)));
+        assert_that!(result_id(&db, 0, &source_files, &translations, "/src/other.ts"), eq(&other));
+    }
+
+    #[rstest]
+    fn result_id_of_source_file_changes_with_translation_content() {
+        let db = I18nDatabaseImpl::default();
+        let source_files = source_files(&db, "t('hello');");
+        let before = result_id(
+            &db,
+            0,
+            &source_files,
+            &[load(&db, "/locales/en.json", r#"{ "hello": "Hello" }"#)],
+            "/src/app.ts",
+        );
+
+        let unchanged = result_id(
+            &db,
+            0,
+            &source_files,
+            &[load(&db, "/locales/en.json", r#"{ "hello": "Hello" }"#)],
+            "/src/app.ts",
+        );
+        let changed = result_id(
+            &db,
+            0,
+            &source_files,
+            &[load(&db, "/locales/en.json", r#"{ "bye": "Bye" }"#)],
+            "/src/app.ts",
+        );
+
+        assert_that!(unchanged, eq(&before));
+        assert_that!(changed, not(eq(&before)));
+    }
+
+    #[rstest]
+    #[case::same_keys("t('hello');\n", true)]
+    #[case::other_keys("t('bye');", false)]
+    fn result_id_of_translation_file_follows_used_keys(
+        #[case] new_text: &str,
+        #[case] unchanged: bool,
+    ) {
+        let mut db = I18nDatabaseImpl::default();
+        let source_files = source_files(&db, "t('hello');");
+        let translations = vec![load(&db, "/locales/en.json", r#"{ "hello": "Hello" }"#)];
+        let before = result_id(&db, 0, &source_files, &translations, "/locales/en.json");
+
+        let source_file = source_files[Path::new("/src/app.ts")];
+        source_file.set_text(&mut db).to(new_text.to_string());
+
+        let after = result_id(&db, 0, &source_files, &translations, "/locales/en.json");
+        assert_that!(after == before, eq(unchanged));
+    }
+
+    #[rstest]
+    fn result_id_changes_with_generation() {
+        let db = I18nDatabaseImpl::default();
+        let source_files = source_files(&db, "t('hello');");
+        let translations = vec![translation(&db, "en", &["hello"])];
+
+        assert_that!(
+            result_id(&db, 1, &source_files, &translations, "/src/app.ts"),
+            not(eq(&result_id(&db, 0, &source_files, &translations, "/src/app.ts")))
+        );
+    }
+
+    #[rstest]
+    fn file_diagnostics_of_source_file() {
+        let db = I18nDatabaseImpl::default();
+        let source_files = source_files(&db, "t('hello');");
+        let translations =
+            vec![translation(&db, "en", &["hello"]), translation(&db, "ja", &["bye"])];
+
+        let diagnostics = file_diagnostics(
+            &db,
+            &config(),
+            Path::new("/src/app.ts"),
+            &source_files,
+            &translations,
+        );
+
+        assert_that!(
+            diagnostics,
+            elements_are![field!(Diagnostic.message, contains_substring("ja"))]
+        );
+    }
+
+    #[rstest]
+    fn file_diagnostics_of_translation_file() {
+        let db = I18nDatabaseImpl::default();
+        let source_files = source_files(&db, "t('hello');");
+        let en = load_translation_from_content(
+            &db,
+            Path::new("/locales/en.json"),
+            r#"{ "hello": "Hello", "bye": "Bye" }"#,
+            ".",
+        )
+        .unwrap();
+        let translations = vec![en];
+
+        let diagnostics = file_diagnostics(
+            &db,
+            &config(),
+            Path::new("/locales/en.json"),
+            &source_files,
+            &translations,
+        );
+
+        assert_that!(
+            diagnostics,
+            elements_are![field!(Diagnostic.message, contains_substring("bye"))]
+        );
+    }
+
+    #[rstest]
+    fn file_diagnostics_of_other_file_is_empty() {
+        let db = I18nDatabaseImpl::default();
+        let source_files = source_files(&db, "t('hello');");
+        let translations = vec![translation(&db, "en", &["hello"])];
+
+        let diagnostics =
+            file_diagnostics(&db, &config(), Path::new("/README.md"), &source_files, &translations);
+
+        assert_that!(diagnostics, is_empty());
+    }
+
+    #[rstest]
+    fn workspace_files_lists_source_and_translation_files() {
+        let db = I18nDatabaseImpl::default();
+        let source_files = source_files(&db, "t('hello');");
+        let local = create_translation(&db, "en", "/src/App.vue", HashMap::new());
+        let translations = vec![translation(&db, "ja", &[]), translation(&db, "en", &[]), local];
+
+        let files = workspace_files(&db, &source_files, &translations);
+
+        assert_that!(
+            files,
+            elements_are![
+                eq(Path::new("/locales/en.json")),
+                eq(Path::new("/locales/ja.json")),
+                eq(Path::new("/src/app.ts"))
+            ]
+        );
+    }
+
+    #[rstest]
+    fn document_report_is_unchanged_for_current_result_id() {
+        let report = document_report("abc", Some("abc"), || panic!("must not recompute"));
+
+        assert_that!(
+            report,
+            eq(&DocumentDiagnosticReportKind::Unchanged(UnchangedDocumentDiagnosticReport {
+                result_id: "abc".to_string()
+            }))
+        );
+    }
+
+    #[rstest]
+    #[case::first_request(None)]
+    #[case::outdated(Some("old"))]
+    fn document_report_is_full_otherwise(#[case] previous_result_id: Option<&str>) {
+        let report = document_report("abc", previous_result_id, Vec::new);
+
+        assert_that!(
+            report,
+            eq(&DocumentDiagnosticReportKind::Full(FullDocumentDiagnosticReport {
+                result_id: Some("abc".to_string()),
+                items: Vec::new(),
+            }))
+        );
+    }
+}
@@ -7,19 +7,26 @@ use tower_lsp::lsp_types::Location;
 
 use crate::db::I18nDatabase;
 use crate::framework::PluralStrategy;
+use crate::ide::key_match::matches_key_pattern;
 use crate::ide::namespace::resolve_usage_namespace;
 use crate::ide::plural::get_plural_base_key;
 use crate::input::source::SourceFile;
 use crate::syntax::analyze_source;
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+};
 
 /// Finds all references to a translation key across all source files.
 ///
 /// For plural keys (e.g., `items_one`), also matches calls to the base key (`items`)
 /// since i18next resolves `t("items", { count: n })` to plural variants internally.
+/// Dynamic keys (e.g., `` t(`errors.${code}`) ``) match every key their pattern covers.
 ///
 /// When `target_namespace` is `Some`, only usages whose resolved namespace matches
 /// are returned. This prevents cross-namespace false positives when multiple
 /// namespaces share the same key name.
+#[allow(clippy::too_many_arguments)]
 pub fn find_references<S: std::hash::BuildHasher>(
     db: &dyn I18nDatabase,
     key_part: &str,
@@ -28,6 +35,7 @@ pub fn find_references<S: std::hash::BuildHasher>(
     key_separator: &str,
     namespace_separator: Option<&str>,
     default_namespace: Option<&str>,
+    encoding: PositionEncoding,
 ) -> Vec<Location> {
     // References are looked up from translation files (JSON), which don't have a
     // ProgrammingLanguage. Default to SuffixBased so i18next plural references work.
@@ -38,13 +46,15 @@ pub fn find_references<S: std::hash::BuildHasher>(
         .flat_map(|source_file| {
             let usages = analyze_source(db, *source_file, key_separator.to_string());
             let uri = source_file.uri(db);
+            let line_index = LineIndex::new(source_file.text(db), encoding);
 
             usages.into_iter().filter_map(move |usage| {
                 let (usage_ns, usage_key_part) =
                     resolve_usage_namespace(db, usage, namespace_separator, default_namespace);
 
-                let is_key_match =
-                    usage_key_part == key_part || base_key.is_some_and(|bk| usage_key_part == bk);
+                let is_key_match = usage_key_part == key_part
+                    || base_key.is_some_and(|bk| usage_key_part == bk)
+                    || (usage.dynamic(db) && matches_key_pattern(key_part, &usage_key_part));
 
                 if !is_key_match {
                     return None;
@@ -63,7 +73,7 @@ pub fn find_references<S: std::hash::BuildHasher>(
                     tracing::warn!("Failed to parse URI: {}", uri);
                     return None;
                 };
-                Some(Location { uri: parsed_uri, range: usage.range(db).into() })
+                Some(Location { uri: parsed_uri, range: line_index.to_lsp_range(usage.range(db)) })
             })
         })
         .collect()
@@ -103,7 +113,16 @@ mod tests {
         let mut source_files = HashMap::new();
         source_files.insert(PathBuf::from("/test.ts"), source_file);
 
-        let locations = find_references(&db, "common.hello", None, &source_files, ".", None, None);
+        let locations = find_references(
+            &db,
+            "common.hello",
+            None,
+            &source_files,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         assert_that!(locations.len(), eq(2));
 
@@ -136,11 +155,53 @@ mod tests {
         source_files.insert(PathBuf::from("/test1.ts"), source_file1);
         source_files.insert(PathBuf::from("/test2.ts"), source_file2);
 
-        let locations = find_references(&db, "common.hello", None, &source_files, ".", None, None);
+        let locations = find_references(
+            &db,
+            "common.hello",
+            None,
+            &source_files,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         assert_that!(locations.len(), eq(2));
     }
 
+    #[rstest]
+    fn test_find_references_includes_matching_dynamic_keys() {
+        let db = I18nDatabaseImpl::default();
+
+        let source_code = r"
+            const msg1 = t(`errors.${code}`);
+            const msg2 = t(`warnings.${code}`);
+        ";
+        let source_file = SourceFile::new(
+            &db,
+            "file:///test.ts".to_string(),
+            source_code.to_string(),
+            ProgrammingLanguage::TypeScript,
+        );
+
+        let mut source_files = HashMap::new();
+        source_files.insert(PathBuf::from("/test.ts"), source_file);
+
+        let locations = find_references(
+            &db,
+            "errors.notFound",
+            None,
+            &source_files,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
+
+        assert_that!(locations.len(), eq(1));
+        assert_that!(locations.first().map(|l| l.range.start.line), some(eq(1)));
+    }
+
     #[rstest]
     fn test_find_references_no_match() {
         let db = I18nDatabaseImpl::default();
@@ -156,8 +217,16 @@ mod tests {
         let mut source_files = HashMap::new();
         source_files.insert(PathBuf::from("/test.ts"), source_file);
 
-        let locations =
-            find_references(&db, "common.nonexistent", None, &source_files, ".", None, None);
+        let locations = find_references(
+            &db,
+            "common.nonexistent",
+            None,
+            &source_files,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         assert_that!(locations, is_empty());
     }
@@ -167,7 +236,16 @@ mod tests {
         let db = I18nDatabaseImpl::default();
 
         let source_files = HashMap::new();
-        let locations = find_references(&db, "common.hello", None, &source_files, ".", None, None);
+        let locations = find_references(
+            &db,
+            "common.hello",
+            None,
+            &source_files,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         assert_that!(locations, is_empty());
     }
@@ -190,7 +268,16 @@ mod tests {
         let mut source_files = HashMap::new();
         source_files.insert(PathBuf::from("/test.ts"), source_file);
 
-        let locations = find_references(&db, "items_one", None, &source_files, ".", None, None);
+        let locations = find_references(
+            &db,
+            "items_one",
+            None,
+            &source_files,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         assert_that!(locations.len(), eq(2));
     }
@@ -210,8 +297,16 @@ mod tests {
         let mut source_files = HashMap::new();
         source_files.insert(PathBuf::from("/test.ts"), source_file);
 
-        let locations =
-            find_references(&db, "place_ordinal_one", None, &source_files, ".", None, None);
+        let locations = find_references(
+            &db,
+            "place_ordinal_one",
+            None,
+            &source_files,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         assert_that!(locations.len(), eq(1));
     }
@@ -234,7 +329,16 @@ mod tests {
         let mut source_files = HashMap::new();
         source_files.insert(PathBuf::from("/test.ts"), source_file);
 
-        let locations = find_references(&db, "items", None, &source_files, ".", None, None);
+        let locations = find_references(
+            &db,
+            "items",
+            None,
+            &source_files,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         assert_that!(locations.len(), eq(1));
     }
@@ -258,8 +362,16 @@ mod tests {
         // Target namespace is "common" (e.g., triggered from JSON file or useTranslation("common"))
         // Without defaultNamespace, the usage's namespace is None (ambiguous).
         // Ambiguous usages should still be included, not excluded.
-        let locations =
-            find_references(&db, "hello", Some("common"), &source_files, ".", Some(":"), None);
+        let locations = find_references(
+            &db,
+            "hello",
+            Some("common"),
+            &source_files,
+            ".",
+            Some(":"),
+            None,
+            PositionEncoding::Utf16,
+        );
 
         assert_that!(locations.len(), eq(1));
     }
@@ -12,7 +12,11 @@ use tower_lsp::lsp_types::{
 use crate::db::I18nDatabase;
 use crate::ide::code_actions::{
     create_full_file_text_edit,
-    rename_key_in_json_text,
+    rename_key_in_text,
+};
+use crate::ide::move_key::{
+    MoveOptions,
+    compute_move_edits,
 };
 use crate::ide::namespace::{
     filter_by_namespace,
@@ -22,11 +26,16 @@ use crate::input::source::SourceFile;
 use crate::input::translation::Translation;
 use crate::syntax::analyze_source;
 use crate::syntax::analyzer::extractor::parse_key_with_namespace;
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+};
 
 /// Computes workspace edits for renaming a translation key.
 ///
-/// Updates both translation JSON files and source file references.
-/// Supports namespace-prefixed keys (e.g., `"ns:key"`); namespace changes are rejected.
+/// Updates both translation files and source file references.
+/// Supports namespace-prefixed keys (e.g., `"ns:key"`); a namespace change moves the key to
+/// the other namespace (see [`compute_move_edits`]).
 /// `target_namespace` is the resolved namespace from `KeyContext`, used to filter
 /// source file usages when the namespace isn't explicit in the key text.
 #[must_use]
@@ -41,13 +50,33 @@ pub fn compute_rename_edits(
     key_separator: &str,
     namespace_separator: Option<&str>,
     default_namespace: Option<&str>,
+    encoding: PositionEncoding,
 ) -> WorkspaceEdit {
     let (old_ns, old_key_part) = parse_key_with_namespace(old_key, namespace_separator);
     let (new_ns, new_key_part) = parse_key_with_namespace(new_key, namespace_separator);
 
-    // Reject namespace change
     if old_ns != new_ns {
-        return WorkspaceEdit::default();
+        let (Some(source_ns), Some(target_ns)) =
+            (old_ns.as_deref().or(target_namespace), new_ns.as_deref().or(default_namespace))
+        else {
+            return WorkspaceEdit::default();
+        };
+        let options =
+            MoveOptions { key_separator, namespace_separator, default_namespace, encoding };
+        return compute_move_edits(
+            db,
+            &old_key_part,
+            source_ns,
+            target_ns,
+            &new_key_part,
+            translations,
+            source_files,
+            &options,
+        )
+        .unwrap_or_else(|e| {
+            tracing::debug!("Rename rejected: {e}");
+            WorkspaceEdit::default()
+        });
     }
 
     let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
@@ -60,9 +89,13 @@ pub fn compute_rename_edits(
     // Translation file edits
     for translation in &target_translations {
         let json_text = translation.json_text(db);
-        if let Some(result) =
-            rename_key_in_json_text(json_text, &old_key_part, &new_key_part, key_separator)
-        {
+        if let Some(result) = rename_key_in_text(
+            translation.format(db),
+            json_text,
+            &old_key_part,
+            &new_key_part,
+            key_separator,
+        ) {
             let file_path = translation.file_path(db);
             if let Ok(uri) = Url::from_file_path(file_path.as_str()) {
                 let edit = create_full_file_text_edit(json_text, result.new_text);
@@ -78,6 +111,7 @@ pub fn compute_rename_edits(
         let Ok(uri) = uri_str.parse::<Url>() else {
             continue;
         };
+        let line_index = LineIndex::new(source_file.text(db), encoding);
 
         for usage in &usages {
             let (usage_ns, usage_key_part) =
@@ -95,9 +129,16 @@ pub fn compute_rename_edits(
                 continue;
             }
 
-            let range = usage.range(db);
-            let edit = TextEdit { range: range.to_unquoted_range(), new_text: new_key.to_string() };
-            changes.entry(uri.clone()).or_default().push(edit);
+            // Keys passed through a constant are renamed where the constant defines them
+            let range = usage.definition_range(db).unwrap_or_else(|| usage.range(db));
+            let edit = TextEdit {
+                range: line_index.to_lsp_range(range.to_unquoted_range()),
+                new_text: new_key.to_string(),
+            };
+            let edits = changes.entry(uri.clone()).or_default();
+            if !edits.contains(&edit) {
+                edits.push(edit);
+            }
         }
     }
 
@@ -168,6 +209,7 @@ mod tests {
             ".",
             None,
             None,
+            PositionEncoding::Utf16,
         );
 
         let changes = result.changes.unwrap();
@@ -210,6 +252,43 @@ mod tests {
             ".",
             None,
             None,
+            PositionEncoding::Utf16,
+        );
+
+        let changes = result.changes.unwrap();
+        let source_uri: Url = "file:///src/app.ts".parse().unwrap();
+        let edits = &changes[&source_uri];
+        assert_that!(edits.len(), eq(1));
+        assert_that!(edits[0].new_text, eq("common.greeting"));
+    }
+
+    #[rstest]
+    fn rename_updates_constant_definition() {
+        let db = I18nDatabaseImpl::default();
+
+        let source_code =
+            "const KEYS = { hello: \"common.hello\" } as const;\nt(KEYS.hello);\nt(KEYS.hello);";
+        let source_file = SourceFile::new(
+            &db,
+            "file:///src/app.ts".to_string(),
+            source_code.to_string(),
+            ProgrammingLanguage::TypeScript,
+        );
+
+        let mut source_files = HashMap::new();
+        source_files.insert(PathBuf::from("/src/app.ts"), source_file);
+
+        let result = compute_rename_edits(
+            &db,
+            "common.hello",
+            "common.greeting",
+            None,
+            &[],
+            &source_files,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
         );
 
         let changes = result.changes.unwrap();
@@ -217,6 +296,7 @@ mod tests {
         let edits = &changes[&source_uri];
         assert_that!(edits.len(), eq(1));
         assert_that!(edits[0].new_text, eq("common.greeting"));
+        assert_that!(edits[0].range.start, eq(tower_lsp::lsp_types::Position::new(0, 23)));
     }
 
     #[rstest]
@@ -258,6 +338,7 @@ mod tests {
             ".",
             Some(":"),
             None,
+            PositionEncoding::Utf16,
         );
 
         let changes = result.changes.unwrap();
@@ -268,7 +349,48 @@ mod tests {
     }
 
     #[rstest]
-    fn rename_rejects_namespace_change() {
+    fn rename_moves_key_on_namespace_change() {
+        let db = I18nDatabaseImpl::default();
+
+        let common = create_translation_with_json(
+            &db,
+            "en",
+            Some("common"),
+            "/locales/en/common.json",
+            HashMap::from([("hello".to_string(), "Hello".to_string())]),
+            "{\n  \"hello\": \"Hello\"\n}",
+        );
+        let errors = create_translation_with_json(
+            &db,
+            "en",
+            Some("errors"),
+            "/locales/en/errors.json",
+            HashMap::new(),
+            "{}",
+        );
+
+        let result = compute_rename_edits(
+            &db,
+            "common:hello",
+            "errors:hello",
+            Some("common"),
+            &[common, errors],
+            &HashMap::new(),
+            ".",
+            Some(":"),
+            None,
+            PositionEncoding::Utf16,
+        );
+
+        let changes = result.changes.unwrap();
+        let errors_uri = Url::from_file_path("/locales/en/errors.json").unwrap();
+        assert_that!(changes[&errors_uri][0].new_text, contains_substring("\"hello\": \"Hello\""));
+        let common_uri = Url::from_file_path("/locales/en/common.json").unwrap();
+        assert_that!(changes[&common_uri][0].new_text, not(contains_substring("\"hello\"")));
+    }
+
+    #[rstest]
+    fn rename_rejects_namespace_change_without_target_file() {
         let db = I18nDatabaseImpl::default();
 
         let result = compute_rename_edits(
@@ -281,6 +403,7 @@ mod tests {
             ".",
             Some(":"),
             None,
+            PositionEncoding::Utf16,
         );
 
         assert_that!(result.changes.unwrap_or_default(), is_empty());
@@ -314,6 +437,7 @@ mod tests {
             ".",
             Some(":"),
             None,
+            PositionEncoding::Utf16,
         );
 
         let changes = result.changes.unwrap();
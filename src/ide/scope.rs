@@ -0,0 +1,84 @@
+//! Visibility of component-local translations (Vue SFC `<i18n>` blocks).
+
+use std::path::Path;
+
+use crate::db::I18nDatabase;
+use crate::input::translation::Translation;
+
+/// Returns the translations visible from `file_path`.
+///
+/// Component-local messages defined in that file come first so that lookups resolve against
+/// them before global translation files. Local messages of other components are excluded.
+#[must_use]
+pub fn visible_translations(
+    db: &dyn I18nDatabase,
+    translations: &[Translation],
+    file_path: &Path,
+) -> Vec<Translation> {
+    let file_path = file_path.to_string_lossy();
+    let (local, global): (Vec<Translation>, Vec<Translation>) =
+        translations.iter().partition(|t| t.is_component_local(db));
+
+    local.into_iter().filter(|t| t.file_path(db) == file_path.as_ref()).chain(global).collect()
+}
+
+/// Returns translation files only, excluding component-local messages.
+///
+/// Used by features that rewrite translation files.
+#[must_use]
+pub fn global_translations(
+    db: &dyn I18nDatabase,
+    translations: &[Translation],
+) -> Vec<Translation> {
+    translations.iter().filter(|t| !t.is_component_local(db)).copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+    use crate::db::I18nDatabaseImpl;
+    use crate::test_utils::create_translation;
+
+    #[rstest]
+    fn visible_translations_puts_own_local_messages_first() {
+        let db = I18nDatabaseImpl::default();
+        let global = create_translation(&db, "en", "/locales/en.json", HashMap::new());
+        let own = create_translation(&db, "en", "/src/App.vue", HashMap::new());
+        let other = create_translation(&db, "en", "/src/Other.vue", HashMap::new());
+
+        let visible = visible_translations(&db, &[global, own, other], Path::new("/src/App.vue"));
+
+        let paths: Vec<String> = visible.iter().map(|t| t.file_path(&db).clone()).collect();
+        assert_that!(paths, elements_are![eq("/src/App.vue"), eq("/locales/en.json")]);
+    }
+
+    #[rstest]
+    fn visible_translations_from_non_component_file() {
+        let db = I18nDatabaseImpl::default();
+        let global = create_translation(&db, "en", "/locales/en.json", HashMap::new());
+        let local = create_translation(&db, "en", "/src/App.vue", HashMap::new());
+
+        let visible = visible_translations(&db, &[global, local], Path::new("/src/main.ts"));
+
+        let paths: Vec<String> = visible.iter().map(|t| t.file_path(&db).clone()).collect();
+        assert_that!(paths, elements_are![eq("/locales/en.json")]);
+    }
+
+    #[rstest]
+    fn global_translations_excludes_local_messages() {
+        let db = I18nDatabaseImpl::default();
+        let global = create_translation(&db, "en", "/locales/en.json", HashMap::new());
+        let local = create_translation(&db, "en", "/src/App.vue", HashMap::new());
+
+        let paths: Vec<String> = global_translations(&db, &[local, global])
+            .iter()
+            .map(|t| t.file_path(&db).clone())
+            .collect();
+        assert_that!(paths, elements_are![eq("/locales/en.json")]);
+    }
+}
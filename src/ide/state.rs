@@ -1,9 +1,6 @@
 //! Shared state for the LSP server.
 
-use std::collections::{
-    HashMap,
-    HashSet,
-};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -14,8 +11,10 @@ use tokio::sync::{
 use tower_lsp::lsp_types::WorkspaceFolder;
 
 use crate::db::I18nDatabaseImpl;
+use crate::ide::file_watcher::FileWatcher;
 use crate::input::source::SourceFile;
 use crate::input::translation::Translation;
+use crate::types::PositionEncoding;
 
 pub type PendingUpdate = (tower_lsp::lsp_types::Url, String, bool);
 
@@ -26,12 +25,14 @@ pub type PendingUpdate = (tower_lsp::lsp_types::Url, String, bool);
 /// When acquiring multiple locks, always follow this order:
 /// 1. `db`
 /// 2. `source_files` / `translations` / `opened_files`
+/// 3. `index_generation`
 #[derive(Clone)]
 pub struct ServerState {
     pub db: Arc<Mutex<I18nDatabaseImpl>>,
     pub source_files: Arc<Mutex<HashMap<PathBuf, SourceFile>>>,
     pub translations: Arc<Mutex<Vec<Translation>>>,
-    pub opened_files: Arc<Mutex<HashSet<tower_lsp::lsp_types::Url>>>,
+    /// Opened documents and their current text, kept in sync with incremental `didChange` edits.
+    pub opened_files: Arc<Mutex<HashMap<tower_lsp::lsp_types::Url, String>>>,
     /// Current language for Virtual Text, completion, and Code Actions.
     /// Changeable via `i18n.setCurrentLanguage` command.
     pub current_language: Arc<Mutex<Option<String>>>,
@@ -39,6 +40,24 @@ pub struct ServerState {
     pub pending_updates: Arc<Mutex<Vec<PendingUpdate>>>,
     /// Whether the client supports edit translation code actions (from `experimental.i18nEditTranslationCodeAction`).
     pub code_actions_enabled: Arc<Mutex<bool>>,
+    /// Whether the client supports `workspace/inlayHint/refresh`.
+    pub inlay_hint_refresh_supported: Arc<Mutex<bool>>,
+    /// Whether the client pulls diagnostics (`textDocument/diagnostic`) instead of receiving them.
+    /// Requires `workspace/diagnostic/refresh` support.
+    pub pull_diagnostics_supported: Arc<Mutex<bool>>,
+    /// Whether the client supports `workspace/diagnostic/refresh`.
+    pub diagnostic_refresh_supported: Arc<Mutex<bool>>,
+    /// Whether workspace edits may create files (`documentChanges` with `create` operations).
+    pub create_files_supported: Arc<Mutex<bool>>,
+    /// Incremented whenever the database is recreated, so pulled diagnostic result IDs from an
+    /// older database are never reported as unchanged.
+    pub index_generation: Arc<Mutex<u64>>,
+    /// Whether the client supports dynamic registration of `workspace/didChangeWatchedFiles`.
+    pub watched_files_registration_supported: Arc<Mutex<bool>>,
+    /// Server-side watcher, used when the client can't register file watchers.
+    pub file_watcher: Arc<Mutex<Option<FileWatcher>>>,
+    /// Encoding of position `character` offsets negotiated during `initialize`.
+    pub position_encoding: Arc<Mutex<PositionEncoding>>,
     /// Workspace folders from `initialize` params (not from runtime LSP request).
     /// Ensures each server only indexes its assigned folders in multi-server setups.
     pub workspace_folders: Arc<Mutex<Vec<WorkspaceFolder>>>,
@@ -50,10 +69,18 @@ impl ServerState {
             db: Arc::new(Mutex::new(db)),
             source_files: Arc::new(Mutex::new(HashMap::new())),
             translations: Arc::new(Mutex::new(Vec::new())),
-            opened_files: Arc::new(Mutex::new(HashSet::new())),
+            opened_files: Arc::new(Mutex::new(HashMap::new())),
             current_language: Arc::new(Mutex::new(None)),
             pending_updates: Arc::new(Mutex::new(Vec::new())),
             code_actions_enabled: Arc::new(Mutex::new(false)),
+            inlay_hint_refresh_supported: Arc::new(Mutex::new(false)),
+            pull_diagnostics_supported: Arc::new(Mutex::new(false)),
+            diagnostic_refresh_supported: Arc::new(Mutex::new(false)),
+            create_files_supported: Arc::new(Mutex::new(false)),
+            index_generation: Arc::new(Mutex::new(0)),
+            watched_files_registration_supported: Arc::new(Mutex::new(false)),
+            file_watcher: Arc::new(Mutex::new(None)),
+            position_encoding: Arc::new(Mutex::new(PositionEncoding::default())),
             workspace_folders: Arc::new(Mutex::new(Vec::new())),
         }
     }
@@ -101,6 +128,14 @@ impl std::fmt::Debug for ServerState {
             .field("current_language", &"<Option<String>>")
             .field("pending_updates", &"<Vec<PendingUpdate>>")
             .field("code_actions_enabled", &"<bool>")
+            .field("inlay_hint_refresh_supported", &"<bool>")
+            .field("pull_diagnostics_supported", &"<bool>")
+            .field("diagnostic_refresh_supported", &"<bool>")
+            .field("create_files_supported", &"<bool>")
+            .field("index_generation", &"<u64>")
+            .field("watched_files_registration_supported", &"<bool>")
+            .field("file_watcher", &"<Option<FileWatcher>>")
+            .field("position_encoding", &"<PositionEncoding>")
             .field("workspace_folders", &"<Vec<WorkspaceFolder>>")
             .finish()
     }
@@ -193,7 +228,7 @@ mod tests {
         {
             let mut opened_files = state1.opened_files.lock().await;
             let uri = tower_lsp::lsp_types::Url::parse("file:///test.ts").unwrap();
-            opened_files.insert(uri);
+            opened_files.insert(uri, String::new());
         }
 
         let opened_files = state2.opened_files.lock().await;
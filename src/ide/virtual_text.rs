@@ -4,7 +4,12 @@ use serde::{
     Deserialize,
     Serialize,
 };
-use tower_lsp::lsp_types::Range;
+use tower_lsp::lsp_types::{
+    InlayHint,
+    InlayHintLabel,
+    InlayHintTooltip,
+    Range,
+};
 
 use crate::db::I18nDatabase;
 use crate::framework::PluralStrategy;
@@ -15,6 +20,10 @@ use crate::ide::namespace::{
 use crate::ide::plural::find_plural_variants;
 use crate::input::source::SourceFile;
 use crate::input::translation::Translation;
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+};
 
 /// Translation decoration info for a key usage in the document.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +35,7 @@ pub struct TranslationDecoration {
 
 /// Generates translation decorations for all key usages in a source file.
 #[must_use]
+#[allow(clippy::too_many_arguments)]
 pub fn get_translation_decorations(
     db: &dyn I18nDatabase,
     source_file: SourceFile,
@@ -34,16 +44,18 @@ pub fn get_translation_decorations(
     key_separator: &str,
     namespace_separator: Option<&str>,
     default_namespace: Option<&str>,
+    encoding: PositionEncoding,
 ) -> Vec<TranslationDecoration> {
     let plural_strategy =
         crate::framework::FrameworkConfig::for_language(source_file.language(db)).plural_strategy;
     let key_usages = crate::syntax::analyze_source(db, source_file, key_separator.to_string());
 
+    let line_index = LineIndex::new(source_file.text(db), encoding);
     let mut decorations = Vec::new();
 
     for usage in key_usages {
         let full_key_text = usage.key(db).text(db);
-        let range: Range = usage.range(db).into();
+        let range = line_index.to_lsp_range(usage.range(db));
 
         let (resolved_ns, key_part) =
             resolve_usage_namespace(db, usage, namespace_separator, default_namespace);
@@ -59,6 +71,46 @@ pub fn get_translation_decorations(
     decorations
 }
 
+/// Maximum number of characters shown in an inlay hint label.
+const INLAY_HINT_MAX_CHARS: usize = 50;
+
+/// Converts decorations within `range` into inlay hints placed after each key usage.
+///
+/// Unlike decorations, which editor extensions truncate themselves, hint labels are shortened
+/// here and flattened to a single line since clients render them verbatim.
+#[must_use]
+pub fn to_inlay_hints(decorations: Vec<TranslationDecoration>, range: Range) -> Vec<InlayHint> {
+    decorations
+        .into_iter()
+        .filter(|decoration| {
+            decoration.range.end >= range.start && decoration.range.start <= range.end
+        })
+        .map(|decoration| InlayHint {
+            position: decoration.range.end,
+            label: InlayHintLabel::String(shorten_value(&decoration.value, INLAY_HINT_MAX_CHARS)),
+            kind: None,
+            text_edits: None,
+            tooltip: Some(InlayHintTooltip::String(format!(
+                "{}: {}",
+                decoration.key, decoration.value
+            ))),
+            padding_left: Some(true),
+            padding_right: None,
+            data: None,
+        })
+        .collect()
+}
+
+/// Flattens a value to a single line of at most `max_chars` characters.
+pub(crate) fn shorten_value(value: &str, max_chars: usize) -> String {
+    let flattened = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flattened.chars().count() <= max_chars {
+        return flattened;
+    }
+    let truncated: String = flattened.chars().take(max_chars.saturating_sub(1)).collect();
+    format!("{}…", truncated.trim_end())
+}
+
 fn get_translation_value(
     db: &dyn I18nDatabase,
     translations: &[&Translation],
@@ -86,12 +138,13 @@ fn get_translation_value(
 }
 
 #[cfg(test)]
-#[allow(clippy::unwrap_used, clippy::indexing_slicing)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing, clippy::panic)]
 mod tests {
     use std::collections::HashMap;
 
     use googletest::prelude::*;
     use rstest::*;
+    use tower_lsp::lsp_types::Position;
 
     use super::*;
     use crate::db::I18nDatabaseImpl;
@@ -131,6 +184,7 @@ mod tests {
             ".",
             None,
             None,
+            PositionEncoding::Utf16,
         );
 
         assert_that!(decorations, len(eq(1)));
@@ -162,6 +216,7 @@ mod tests {
             ".",
             None,
             None,
+            PositionEncoding::Utf16,
         );
 
         assert_that!(decorations, len(eq(1)));
@@ -192,6 +247,7 @@ mod tests {
             ".",
             None,
             None,
+            PositionEncoding::Utf16,
         );
 
         assert_that!(decorations, is_empty());
@@ -210,8 +266,16 @@ mod tests {
             HashMap::from([("common.hello".to_string(), "Hello".to_string())]),
         );
 
-        let decorations =
-            get_translation_decorations(&db, source_file, &[translation], None, ".", None, None);
+        let decorations = get_translation_decorations(
+            &db,
+            source_file,
+            &[translation],
+            None,
+            ".",
+            None,
+            None,
+            PositionEncoding::Utf16,
+        );
 
         assert_that!(decorations, len(eq(1)));
         assert_that!(decorations[0].value, eq("Hello"));
@@ -241,6 +305,7 @@ mod tests {
             ".",
             None,
             None,
+            PositionEncoding::Utf16,
         );
 
         assert_that!(decorations, len(eq(1)));
@@ -275,6 +340,7 @@ mod tests {
             ".",
             None,
             None,
+            PositionEncoding::Utf16,
         );
 
         assert_that!(decorations, len(eq(1)));
@@ -308,6 +374,7 @@ mod tests {
             ".",
             None,
             None,
+            PositionEncoding::Utf16,
         );
 
         assert_that!(decorations, len(eq(1)));
@@ -346,6 +413,7 @@ mod tests {
             ".",
             Some(":"),
             None,
+            PositionEncoding::Utf16,
         );
 
         assert_that!(decorations, len(eq(1)));
@@ -384,10 +452,67 @@ mod tests {
             ".",
             None,
             None,
+            PositionEncoding::Utf16,
         );
 
         assert_that!(decorations, len(eq(1)));
         // Returns first match (common comes first)
         assert_that!(decorations[0].value, eq("こんにちは"));
     }
+
+    fn decoration(line: u32, value: &str) -> TranslationDecoration {
+        TranslationDecoration {
+            range: Range {
+                start: Position { line, character: 2 },
+                end: Position { line, character: 16 },
+            },
+            key: "common.hello".to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn line_range(start: u32, end: u32) -> Range {
+        Range {
+            start: Position { line: start, character: 0 },
+            end: Position { line: end, character: 0 },
+        }
+    }
+
+    #[rstest]
+    fn to_inlay_hints_places_hint_after_usage() {
+        let hints = to_inlay_hints(vec![decoration(0, "こんにちは")], line_range(0, 1));
+
+        assert_that!(hints, len(eq(1)));
+        assert_that!(hints[0].position, eq(Position { line: 0, character: 16 }));
+        assert_that!(hints[0].padding_left, some(eq(true)));
+        let InlayHintLabel::String(label) = &hints[0].label else {
+            panic!("expected a string label");
+        };
+        assert_that!(label, eq("こんにちは"));
+        let Some(InlayHintTooltip::String(tooltip)) = &hints[0].tooltip else {
+            panic!("expected a string tooltip");
+        };
+        assert_that!(tooltip, eq("common.hello: こんにちは"));
+    }
+
+    #[rstest]
+    fn to_inlay_hints_filters_by_range() {
+        let hints = to_inlay_hints(
+            vec![decoration(0, "a"), decoration(5, "b"), decoration(10, "c")],
+            line_range(3, 6),
+        );
+
+        let lines: Vec<u32> = hints.iter().map(|hint| hint.position.line).collect();
+        assert_that!(lines, elements_are![eq(&5)]);
+    }
+
+    #[rstest]
+    #[case::multiline("Hello\n  world", "Hello world")]
+    #[case::long(
+        "This message is definitely longer than fifty characters in total",
+        "This message is definitely longer than fifty char…"
+    )]
+    fn shorten_value_flattens_and_truncates(#[case] value: &str, #[case] expected: &str) {
+        assert_that!(shorten_value(value, INLAY_HINT_MAX_CHARS), eq(expected));
+    }
 }
@@ -0,0 +1,234 @@
+//! Workspace symbol search across translation keys and values.
+
+use std::cmp::Reverse;
+
+use tower_lsp::lsp_types::{
+    Location,
+    SymbolInformation,
+    SymbolKind,
+    Url,
+};
+
+use crate::db::I18nDatabase;
+use crate::input::translation::Translation;
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+};
+
+/// Maximum number of symbols returned for a query.
+const MAX_WORKSPACE_SYMBOLS: usize = 256;
+
+/// Maximum number of characters of the value shown as container name.
+const VALUE_PREVIEW_MAX_CHARS: usize = 60;
+
+/// Bonus for a key that contains the query as a substring, so that contiguous matches
+/// rank above scattered fuzzy matches.
+const SUBSTRING_BONUS: u32 = 10;
+
+/// Searches translation keys (fuzzy) and values (substring) in all translation files.
+///
+/// Each match is reported at its key in the translation file, once per file. Keys of
+/// namespaced files are prefixed with their namespace when `namespace_separator` is set.
+/// Results are sorted by relevance; key matches rank above value-only matches.
+#[must_use]
+pub fn search_workspace_symbols(
+    db: &dyn I18nDatabase,
+    translations: &[Translation],
+    query: &str,
+    namespace_separator: Option<&str>,
+    encoding: PositionEncoding,
+) -> Vec<SymbolInformation> {
+    let query = query.trim().to_lowercase();
+
+    let mut matches: Vec<(u32, String, &Translation, &str)> = Vec::new();
+    for translation in translations {
+        let namespace = translation.namespace(db).as_deref();
+        for (key, value) in translation.keys(db) {
+            let name = match (namespace, namespace_separator) {
+                (Some(ns), Some(sep)) => format!("{ns}{sep}{key}"),
+                _ => key.clone(),
+            };
+            let score = fuzzy_score(&query, &name)
+                .or_else(|| value.to_lowercase().contains(&query).then_some(0));
+            if let Some(score) = score {
+                matches.push((score, name, translation, key));
+            }
+        }
+    }
+
+    matches.sort_by(|a, b| {
+        (Reverse(a.0), &a.1, a.2.language(db)).cmp(&(Reverse(b.0), &b.1, b.2.language(db)))
+    });
+
+    matches
+        .into_iter()
+        .take(MAX_WORKSPACE_SYMBOLS)
+        .filter_map(|(_, name, translation, key)| {
+            let range = translation.key_ranges(db).get(key)?;
+            let line_index = LineIndex::new(translation.json_text(db), encoding);
+            let uri = Url::from_file_path(translation.file_path(db)).ok()?;
+            let value = translation.keys(db).get(key)?;
+            let preview = crate::ide::virtual_text::shorten_value(value, VALUE_PREVIEW_MAX_CHARS);
+
+            #[allow(deprecated)] // `SymbolInformation::deprecated` has no default
+            Some(SymbolInformation {
+                name,
+                kind: SymbolKind::STRING,
+                tags: None,
+                deprecated: None,
+                location: Location { uri, range: line_index.to_lsp_range(*range) },
+                container_name: Some(format!("{}: {preview}", translation.language(db))),
+            })
+        })
+        .collect()
+}
+
+/// Scores `candidate` as a case-insensitive subsequence match of a lowercase `query`.
+///
+/// Consecutive matches and matches at word boundaries (after `.`, `_`, `-`, `:`, `/`, `[`
+/// or at a camelCase hump) score higher. An empty query matches everything with score 0.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let mut query_chars = query.chars().peekable();
+    let mut score = 0;
+    let mut previous: Option<char> = None;
+    let mut previous_matched = false;
+
+    for c in candidate.chars() {
+        let Some(&q) = query_chars.peek() else {
+            break;
+        };
+        let matched = c.to_lowercase().eq(q.to_lowercase());
+        if matched {
+            query_chars.next();
+            score += 1;
+            if previous_matched {
+                score += 2;
+            }
+            let at_boundary = previous.is_none_or(|p| {
+                matches!(p, '.' | '_' | '-' | ':' | '/' | '[' | ' ')
+                    || (p.is_lowercase() && c.is_uppercase())
+            });
+            if at_boundary {
+                score += 3;
+            }
+        }
+        previous_matched = matched;
+        previous = Some(c);
+    }
+
+    if query_chars.peek().is_some() {
+        return None;
+    }
+    if !query.is_empty() && candidate.to_lowercase().contains(query) {
+        score += SUBSTRING_BONUS;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing)]
+mod tests {
+    use std::path::Path;
+
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+    use crate::db::I18nDatabaseImpl;
+    use crate::input::translation::load_translation_from_content;
+
+    fn names(symbols: &[SymbolInformation]) -> Vec<String> {
+        symbols.iter().map(|s| s.name.clone()).collect()
+    }
+
+    #[rstest]
+    #[case::subsequence("cpe", "checkout.payment.error", true)]
+    #[case::case_insensitive("payerr", "checkout.paymentError", true)]
+    #[case::out_of_order("epc", "checkout.payment.error", false)]
+    #[case::empty("", "anything", true)]
+    fn fuzzy_score_matches_subsequences(
+        #[case] query: &str,
+        #[case] candidate: &str,
+        #[case] expected: bool,
+    ) {
+        assert_that!(fuzzy_score(query, candidate).is_some(), eq(expected));
+    }
+
+    #[rstest]
+    fn fuzzy_score_prefers_contiguous_and_boundary_matches() {
+        let contiguous = fuzzy_score("error", "payment.error").unwrap();
+        let scattered = fuzzy_score("error", "everything.rooms.r").unwrap();
+
+        assert_that!(contiguous, gt(scattered));
+    }
+
+    #[rstest]
+    fn searches_keys_and_values_with_locations() {
+        let db = I18nDatabaseImpl::default();
+        let en = load_translation_from_content(
+            &db,
+            Path::new("/locales/en.json"),
+            r#"{
+  "checkout": { "payment": { "error": "Payment failed" } },
+  "retry": "Try again"
+}"#,
+            ".",
+        )
+        .unwrap();
+
+        let by_key = search_workspace_symbols(
+            &db,
+            &[en],
+            "checkout.payment.error",
+            None,
+            PositionEncoding::Utf16,
+        );
+        assert_that!(names(&by_key), elements_are![eq("checkout.payment.error")]);
+        assert_that!(by_key[0].location.uri.path(), eq("/locales/en.json"));
+        assert_that!(by_key[0].location.range.start.line, eq(1));
+        assert_that!(by_key[0].container_name, some(eq("en: Payment failed")));
+
+        let by_value =
+            search_workspace_symbols(&db, &[en], "try again", None, PositionEncoding::Utf16);
+        assert_that!(names(&by_value), elements_are![eq("retry")]);
+    }
+
+    #[rstest]
+    fn ranks_key_matches_above_value_matches() {
+        let db = I18nDatabaseImpl::default();
+        let en = load_translation_from_content(
+            &db,
+            Path::new("/locales/en.json"),
+            r#"{ "label": "Save draft", "save": "Store" }"#,
+            ".",
+        )
+        .unwrap();
+
+        let symbols = search_workspace_symbols(&db, &[en], "save", None, PositionEncoding::Utf16);
+
+        assert_that!(names(&symbols), elements_are![eq("save"), eq("label")]);
+    }
+
+    #[rstest]
+    fn prefixes_namespace() {
+        let db = I18nDatabaseImpl::default();
+        let translation = load_translation_from_content(
+            &db,
+            Path::new("/locales/en/common.json"),
+            r#"{ "hello": "Hello" }"#,
+            ".",
+        )
+        .unwrap();
+
+        let symbols = search_workspace_symbols(
+            &db,
+            &[translation],
+            "common:hello",
+            Some(":"),
+            PositionEncoding::Utf16,
+        );
+
+        assert_that!(names(&symbols), elements_are![eq("common:hello")]);
+    }
+}
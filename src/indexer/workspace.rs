@@ -268,6 +268,26 @@ impl WorkspaceIndexer {
         let results: Vec<_> =
             stream::iter(futures).buffer_unordered(max_concurrent_files).collect().await;
 
+        // Step 3: Load component-local translations from Vue SFC `<i18n>` blocks
+        let mut local_translations = Vec::new();
+        for (path, source_file) in results.iter().flatten() {
+            if source_file.language(&db) == crate::input::source::ProgrammingLanguage::Vue {
+                local_translations.extend(crate::syntax::vue::load_i18n_block_translations(
+                    &db,
+                    path,
+                    source_file.text(&db),
+                    &key_separator,
+                ));
+            }
+        }
+        if !local_translations.is_empty() {
+            let mut guard = translations.lock().await;
+            let new_file_paths: HashSet<&str> =
+                local_translations.iter().map(|t| t.file_path(&db).as_str()).collect();
+            guard.retain(|existing| !new_file_paths.contains(existing.file_path(&db).as_str()));
+            guard.extend(local_translations);
+        }
+
         let mut source_files_guard = source_files.lock().await;
         for result in results.into_iter().flatten() {
             source_files_guard.insert(result.0, result.1);
@@ -0,0 +1,201 @@
+//! Incremental changes to open documents.
+
+use tower_lsp::lsp_types::TextDocumentContentChangeEvent;
+use tree_sitter::{
+    InputEdit,
+    Point,
+};
+
+use crate::types::{
+    LineIndex,
+    PositionEncoding,
+};
+
+/// How a document changed after applying `textDocument/didChange` content changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocumentChange {
+    /// Ranged edits, in the order they were applied.
+    Edits(Vec<InputEdit>),
+    /// The whole text was replaced.
+    Replaced,
+}
+
+/// Applies content changes to `text` in order.
+///
+/// Ranged changes are converted to tree-sitter edits. A change without a range replaces the
+/// whole text, after which no edits describe the change from the previous text.
+#[must_use]
+pub fn apply_content_changes(
+    text: &mut String,
+    changes: Vec<TextDocumentContentChangeEvent>,
+    encoding: PositionEncoding,
+) -> DocumentChange {
+    let mut edits = Vec::new();
+    let mut replaced = false;
+    for change in changes {
+        if let Some(range) = change.range {
+            edits.push(apply_ranged_change(text, range, &change.text, encoding));
+        } else {
+            *text = change.text;
+            replaced = true;
+        }
+    }
+    if replaced { DocumentChange::Replaced } else { DocumentChange::Edits(edits) }
+}
+
+fn apply_ranged_change(
+    text: &mut String,
+    range: tower_lsp::lsp_types::Range,
+    new_text: &str,
+    encoding: PositionEncoding,
+) -> InputEdit {
+    let line_index = LineIndex::new(text, encoding);
+    let start = line_index.to_source_position(range.start);
+    let end = line_index.to_source_position(range.end);
+    let start_byte = line_index.offset(start);
+    let old_end_byte = line_index.offset(end).max(start_byte);
+
+    let start_position = point_at(text, start_byte);
+    let old_end_position = point_at(text, old_end_byte);
+    text.replace_range(start_byte..old_end_byte, new_text);
+    let new_end_byte = start_byte + new_text.len();
+
+    InputEdit {
+        start_byte,
+        old_end_byte,
+        new_end_byte,
+        start_position,
+        old_end_position,
+        new_end_position: point_at(text, new_end_byte),
+    }
+}
+
+/// Returns the tree-sitter point (row and byte column) of a byte offset.
+fn point_at(text: &str, offset: usize) -> Point {
+    let before = text.get(..offset).unwrap_or(text);
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Point { row: before.matches('\n').count(), column: before.len() - line_start }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing, clippy::panic)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+    use tower_lsp::lsp_types::{
+        Position,
+        Range,
+    };
+
+    use super::*;
+
+    fn change(range: Option<(u32, u32, u32, u32)>, text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: range
+                .map(|(sl, sc, el, ec)| Range::new(Position::new(sl, sc), Position::new(el, ec))),
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[rstest]
+    #[case::insert(Some((0, 3, 0, 3)), "x", "t('xa');\nt('b');")]
+    #[case::replace_across_lines(Some((0, 5, 1, 3)), "", "t('a'b');")]
+    #[case::insert_newline(Some((1, 0, 1, 0)), "// note\n", "t('a');\n// note\nt('b');")]
+    #[case::full(None, "t('c');", "t('c');")]
+    fn applies_change(
+        #[case] range: Option<(u32, u32, u32, u32)>,
+        #[case] new_text: &str,
+        #[case] expected: &str,
+    ) {
+        let mut text = "t('a');\nt('b');".to_string();
+
+        let _ = apply_content_changes(
+            &mut text,
+            vec![change(range, new_text)],
+            PositionEncoding::Utf16,
+        );
+
+        assert_that!(text, eq(expected));
+    }
+
+    #[rstest]
+    fn applies_changes_in_order() {
+        let mut text = "t('a');".to_string();
+
+        let result = apply_content_changes(
+            &mut text,
+            vec![change(Some((0, 3, 0, 4)), "b"), change(Some((0, 4, 0, 4)), "c")],
+            PositionEncoding::Utf16,
+        );
+
+        assert_that!(text, eq("t('bc');"));
+        let DocumentChange::Edits(edits) = result else {
+            panic!("expected edits");
+        };
+        assert_that!(edits.len(), eq(2));
+    }
+
+    #[rstest]
+    fn converts_utf16_columns_to_bytes() {
+        let mut text = "t('日本');".to_string();
+
+        let result = apply_content_changes(
+            &mut text,
+            vec![change(Some((0, 5, 0, 5)), "語")],
+            PositionEncoding::Utf16,
+        );
+
+        assert_that!(text, eq("t('日本語');"));
+        assert_that!(
+            result,
+            eq(&DocumentChange::Edits(vec![InputEdit {
+                start_byte: 9,
+                old_end_byte: 9,
+                new_end_byte: 12,
+                start_position: Point { row: 0, column: 9 },
+                old_end_position: Point { row: 0, column: 9 },
+                new_end_position: Point { row: 0, column: 12 },
+            }]))
+        );
+    }
+
+    #[rstest]
+    fn clamps_utf8_columns_inside_characters() {
+        let mut text = "t('日本');".to_string();
+
+        // Byte 4 is inside '日' (bytes 3..6), byte 7 inside '本' (bytes 6..9)
+        let result = apply_content_changes(
+            &mut text,
+            vec![change(Some((0, 4, 0, 7)), "x")],
+            PositionEncoding::Utf8,
+        );
+
+        assert_that!(text, eq("t('x本');"));
+        assert_that!(
+            result,
+            eq(&DocumentChange::Edits(vec![InputEdit {
+                start_byte: 3,
+                old_end_byte: 6,
+                new_end_byte: 4,
+                start_position: Point { row: 0, column: 3 },
+                old_end_position: Point { row: 0, column: 6 },
+                new_end_position: Point { row: 0, column: 4 },
+            }]))
+        );
+    }
+
+    #[rstest]
+    fn reports_replacement_with_other_edits() {
+        let mut text = "t('a');".to_string();
+
+        let result = apply_content_changes(
+            &mut text,
+            vec![change(None, "t('c');"), change(Some((0, 3, 0, 4)), "d")],
+            PositionEncoding::Utf16,
+        );
+
+        assert_that!(text, eq("t('d');"));
+        assert_that!(result, eq(&DocumentChange::Replaced));
+    }
+}
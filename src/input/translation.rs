@@ -1,5 +1,8 @@
 //! Translation file input definitions
 
+pub mod po;
+pub mod yaml;
+
 use std::collections::{
     HashMap,
     HashSet,
@@ -331,6 +334,32 @@ fn detect_namespace_from_path(file_path: &Path) -> Option<String> {
     None
 }
 
+/// On-disk format of a translation file, determined by its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranslationFormat {
+    Json,
+    /// `.yaml` / `.yml`
+    Yaml,
+    /// Gettext `.po` / `.pot`
+    Po,
+}
+
+impl TranslationFormat {
+    /// Detects the format from a file path. Unknown extensions are treated as JSON.
+    #[must_use]
+    pub fn from_path(file_path: &Path) -> Self {
+        match file_path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") => {
+                Self::Yaml
+            }
+            Some(ext) if ext.eq_ignore_ascii_case("po") || ext.eq_ignore_ascii_case("pot") => {
+                Self::Po
+            }
+            _ => Self::Json,
+        }
+    }
+}
+
 /// Salsa input representing translation data.
 #[salsa::input]
 pub struct Translation {
@@ -347,6 +376,7 @@ pub struct Translation {
     #[returns(ref)]
     pub keys: HashMap<String, String>,
 
+    /// Raw file contents (JSON, YAML or PO, see [`TranslationFormat`]).
     #[returns(ref)]
     pub json_text: String,
 
@@ -428,19 +458,37 @@ pub fn extract_key_value_ranges(
     json_text: &str,
     separator: &str,
 ) -> (HashMap<String, SourceRange>, HashMap<String, SourceRange>) {
-    let mut key_ranges = HashMap::new();
-    let mut value_ranges = HashMap::new();
+    parse_json(json_text, |text, parser| parser.parse(text, None))
+        .map(|tree| key_value_ranges_in_tree(&tree, json_text, separator))
+        .unwrap_or_default()
+}
 
+/// Parses JSON text with tree-sitter, using `parse` to run the parser.
+fn parse_json(
+    json_text: &str,
+    parse: impl FnOnce(&str, &mut tree_sitter::Parser) -> Option<tree_sitter::Tree>,
+) -> Option<tree_sitter::Tree> {
     let mut parser = tree_sitter::Parser::new();
     let Ok(()) = parser.set_language(&tree_sitter_json::LANGUAGE.into()) else {
         tracing::warn!("Failed to set tree-sitter-json language");
-        return (key_ranges, value_ranges);
+        return None;
     };
 
-    let Some(tree) = parser.parse(json_text, None) else {
+    let tree = parse(json_text, &mut parser);
+    if tree.is_none() {
         tracing::warn!("Failed to parse JSON with tree-sitter");
-        return (key_ranges, value_ranges);
-    };
+    }
+    tree
+}
+
+/// Extracts key and value source ranges from the tree-sitter tree of `json_text`.
+fn key_value_ranges_in_tree(
+    tree: &tree_sitter::Tree,
+    json_text: &str,
+    separator: &str,
+) -> (HashMap<String, SourceRange>, HashMap<String, SourceRange>) {
+    let mut key_ranges = HashMap::new();
+    let mut value_ranges = HashMap::new();
 
     let root_node = tree.root_node();
 
@@ -578,6 +626,21 @@ fn extract_pair(
 }
 
 impl Translation {
+    /// Returns true for component-local messages loaded from a Vue SFC `<i18n>` block.
+    ///
+    /// Their `json_text` is the block content, not the whole file, so they must not be
+    /// edited as standalone translation files.
+    #[must_use]
+    pub fn is_component_local(self, db: &dyn crate::db::I18nDatabase) -> bool {
+        Path::new(self.file_path(db)).extension().is_some_and(|ext| ext.eq_ignore_ascii_case("vue"))
+    }
+
+    /// Format of the underlying translation file.
+    #[must_use]
+    pub fn format(self, db: &dyn crate::db::I18nDatabase) -> TranslationFormat {
+        TranslationFormat::from_path(Path::new(self.file_path(db)))
+    }
+
     /// Get translation key at cursor position.
     ///
     /// Returns the key if cursor is on a key or value position.
@@ -607,7 +670,7 @@ impl Translation {
 /// Load translation file and create a Translation input.
 ///
 /// # Errors
-/// Returns error if file read or JSON parse fails.
+/// Returns error if file read or JSON/YAML/PO parse fails.
 pub fn load_translation_file(
     db: &dyn crate::db::I18nDatabase,
     file_path: &Path,
@@ -621,20 +684,72 @@ pub fn load_translation_file(
 
 /// Loads a translation from content string (for unsaved buffer).
 ///
+/// The format (JSON, YAML or PO) is chosen from the file extension.
+///
 /// # Errors
-/// Returns error if JSON parse fails.
+/// Returns error if JSON, YAML or PO parse fails.
 pub fn load_translation_from_content(
     db: &dyn crate::db::I18nDatabase,
     file_path: &Path,
     content: &str,
     separator: &str,
 ) -> Result<Translation, String> {
-    let json: Value =
-        serde_json::from_str(content).map_err(|e| format!("Failed to parse JSON: {e}"))?;
+    load_translation(db, None, file_path, content, separator)
+}
+
+/// Loads a translation from the content of the open document `uri`.
+///
+/// JSON files are parsed with the syntax tree cached for `uri`, so an edited buffer is
+/// reparsed incrementally.
+///
+/// # Errors
+/// Returns error if JSON, YAML or PO parse fails.
+pub fn load_translation_from_document(
+    db: &dyn crate::db::I18nDatabase,
+    uri: &str,
+    file_path: &Path,
+    content: &str,
+    separator: &str,
+) -> Result<Translation, String> {
+    load_translation(db, Some(uri), file_path, content, separator)
+}
 
-    let keys = flatten_json(&json, separator, None);
-    let (key_ranges, value_ranges) = extract_key_value_ranges(content, separator);
-    let language = detect_language_from_path(file_path);
+fn load_translation(
+    db: &dyn crate::db::I18nDatabase,
+    uri: Option<&str>,
+    file_path: &Path,
+    content: &str,
+    separator: &str,
+) -> Result<Translation, String> {
+    let (keys, key_ranges, value_ranges, language) = match TranslationFormat::from_path(file_path) {
+        TranslationFormat::Json => {
+            let json: Value =
+                serde_json::from_str(content).map_err(|e| format!("Failed to parse JSON: {e}"))?;
+
+            let keys = flatten_json(&json, separator, None);
+            let (key_ranges, value_ranges) = parse_json(content, |text, parser| match uri {
+                Some(uri) => db.syntax_trees().parse(uri, text, parser),
+                None => parser.parse(text, None),
+            })
+            .map(|tree| key_value_ranges_in_tree(&tree, content, separator))
+            .unwrap_or_default();
+            (keys, key_ranges, value_ranges, detect_language_from_path(file_path))
+        }
+        TranslationFormat::Yaml => {
+            let parsed = yaml::parse_yaml_translation(content, separator)?;
+            // A Rails-style locale root (`en:`) is authoritative over the file path
+            let language = parsed.locale.unwrap_or_else(|| detect_language_from_path(file_path));
+            (parsed.keys, parsed.key_ranges, parsed.value_ranges, language)
+        }
+        TranslationFormat::Po => {
+            // `.pot` templates have no translations; their msgids define the key set
+            let is_template =
+                file_path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("pot"));
+            let parsed = po::parse_po_translation(content, separator, is_template)?;
+            let language = parsed.locale.unwrap_or_else(|| detect_language_from_path(file_path));
+            (parsed.keys, parsed.key_ranges, parsed.value_ranges, language)
+        }
+    };
     let namespace = detect_namespace_from_path(file_path);
 
     Ok(Translation::new(
@@ -649,8 +764,110 @@ pub fn load_translation_from_content(
     ))
 }
 
+/// Keys, key ranges and value ranges of one locale in embedded messages.
+type EmbeddedGroup =
+    (HashMap<String, String>, HashMap<String, SourceRange>, HashMap<String, SourceRange>);
+
+/// Loads messages embedded in another file, such as a Vue SFC `<i18n>` block.
+///
+/// Without `locale`, the top-level keys of `content` are locales (`{ "en": { ... } }`) and
+/// one translation is created per locale. Ranges are shifted by `line_offset` so that they
+/// point into the enclosing file, whose full text (`file_text`) is stored as the translation
+/// text.
+///
+/// # Errors
+/// Returns error if the content cannot be parsed in the given format.
+#[allow(clippy::too_many_arguments)]
+pub fn load_embedded_translations(
+    db: &dyn crate::db::I18nDatabase,
+    file_path: &Path,
+    file_text: &str,
+    content: &str,
+    format: TranslationFormat,
+    locale: Option<&str>,
+    line_offset: u32,
+    separator: &str,
+) -> Result<Vec<Translation>, String> {
+    let (keys, key_ranges, value_ranges, root_locale) = match format {
+        TranslationFormat::Json => {
+            let json: Value =
+                serde_json::from_str(content).map_err(|e| format!("Failed to parse JSON: {e}"))?;
+            let (key_ranges, value_ranges) = extract_key_value_ranges(content, separator);
+            (flatten_json(&json, separator, None), key_ranges, value_ranges, None)
+        }
+        TranslationFormat::Yaml => {
+            let parsed = yaml::parse_yaml_translation(content, separator)?;
+            (parsed.keys, parsed.key_ranges, parsed.value_ranges, parsed.locale)
+        }
+        TranslationFormat::Po => {
+            return Err("PO messages cannot be embedded".to_string());
+        }
+    };
+
+    let shift = |range: SourceRange| SourceRange {
+        start: SourcePosition { line: range.start.line + line_offset, ..range.start },
+        end: SourcePosition { line: range.end.line + line_offset, ..range.end },
+    };
+
+    let mut groups: std::collections::BTreeMap<String, EmbeddedGroup> =
+        std::collections::BTreeMap::new();
+
+    if let Some(locale) = locale.map(str::to_string).or(root_locale) {
+        groups.insert(
+            locale,
+            (
+                keys,
+                key_ranges.into_iter().map(|(k, r)| (k, shift(r))).collect(),
+                value_ranges.into_iter().map(|(k, r)| (k, shift(r))).collect(),
+            ),
+        );
+    } else {
+        // Split `en.hello` into locale `en` and key `hello`
+        for (key, value) in keys {
+            if let Some((locale, key)) = key.split_once(separator) {
+                groups.entry(locale.to_string()).or_default().0.insert(key.to_string(), value);
+            }
+        }
+        for (key, range) in key_ranges {
+            if let Some((locale, key)) = key.split_once(separator) {
+                groups
+                    .entry(locale.to_string())
+                    .or_default()
+                    .1
+                    .insert(key.to_string(), shift(range));
+            }
+        }
+        for (key, range) in value_ranges {
+            if let Some((locale, key)) = key.split_once(separator) {
+                groups
+                    .entry(locale.to_string())
+                    .or_default()
+                    .2
+                    .insert(key.to_string(), shift(range));
+            }
+        }
+    }
+
+    Ok(groups
+        .into_iter()
+        .filter(|(_, (keys, _, _))| !keys.is_empty())
+        .map(|(language, (keys, key_ranges, value_ranges))| {
+            Translation::new(
+                db,
+                language,
+                None,
+                file_path.to_string_lossy().to_string(),
+                keys,
+                file_text.to_string(),
+                key_ranges,
+                value_ranges,
+            )
+        })
+        .collect())
+}
+
 #[cfg(test)]
-#[allow(clippy::unwrap_used)]
+#[allow(clippy::unwrap_used, clippy::panic)]
 mod tests {
     use std::path::Path;
 
@@ -1003,4 +1220,196 @@ mod tests {
         assert_that!(value_ranges.contains_key("users[0].name"), eq(true));
         assert_that!(value_ranges.contains_key("users[1].name"), eq(true));
     }
+
+    #[rstest]
+    #[case::json("locales/en.json", TranslationFormat::Json)]
+    #[case::yaml("locales/en.yaml", TranslationFormat::Yaml)]
+    #[case::yml("config/locales/en.yml", TranslationFormat::Yaml)]
+    #[case::upper_case("locales/en.YML", TranslationFormat::Yaml)]
+    #[case::po("locales/ja/messages.po", TranslationFormat::Po)]
+    #[case::pot("locales/messages.pot", TranslationFormat::Po)]
+    #[case::no_extension("locales/en", TranslationFormat::Json)]
+    fn test_translation_format_from_path(#[case] path: &str, #[case] expected: TranslationFormat) {
+        assert_that!(TranslationFormat::from_path(Path::new(path)), eq(expected));
+    }
+
+    #[rstest]
+    fn test_load_yaml_translation_from_content() {
+        let db = crate::db::I18nDatabaseImpl::default();
+        let content = "common:\n  hello: Hello # greeting\n";
+
+        let translation = load_translation_from_content(
+            &db,
+            Path::new("/project/locales/ja/common.yml"),
+            content,
+            ".",
+        )
+        .unwrap();
+
+        assert_that!(translation.language(&db), eq("ja"));
+        assert_that!(translation.namespace(&db), some(eq("common")));
+        assert_that!(translation.format(&db), eq(TranslationFormat::Yaml));
+        assert_that!(translation.keys(&db).get("common.hello"), some(eq("Hello")));
+        assert_that!(translation.json_text(&db), eq(content));
+        assert_that!(translation.key_ranges(&db).contains_key("common.hello"), eq(true));
+    }
+
+    #[rstest]
+    fn test_load_yaml_translation_language_from_locale_root() {
+        let db = crate::db::I18nDatabaseImpl::default();
+
+        let translation = load_translation_from_content(
+            &db,
+            Path::new("/project/config/locales/shared.yml"),
+            "fr:\n  hello: Bonjour\n",
+            ".",
+        )
+        .unwrap();
+
+        assert_that!(translation.language(&db), eq("fr"));
+        assert_that!(translation.keys(&db).get("hello"), some(eq("Bonjour")));
+    }
+
+    #[rstest]
+    fn test_load_invalid_yaml_returns_error() {
+        let db = crate::db::I18nDatabaseImpl::default();
+
+        let result =
+            load_translation_from_content(&db, Path::new("/locales/en.yml"), "a: [b\n", ".");
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_load_po_translation_from_content() {
+        let db = crate::db::I18nDatabaseImpl::default();
+        let content = "msgid \"\"\nmsgstr \"Language: de\\n\"\n\nmsgid \"Hello\"\nmsgstr \"Hallo\"\n\nmsgid \"Bye\"\nmsgstr \"\"\n";
+
+        let translation = load_translation_from_content(
+            &db,
+            Path::new("/project/locales/messages.po"),
+            content,
+            ".",
+        )
+        .unwrap();
+
+        assert_that!(translation.language(&db), eq("de"));
+        assert_that!(translation.format(&db), eq(TranslationFormat::Po));
+        assert_that!(translation.keys(&db).get("Hello"), some(eq("Hallo")));
+        assert_that!(translation.keys(&db).contains_key("Bye"), eq(false));
+    }
+
+    #[rstest]
+    fn test_load_pot_template_keeps_untranslated_keys() {
+        let db = crate::db::I18nDatabaseImpl::default();
+
+        let translation = load_translation_from_content(
+            &db,
+            Path::new("/project/locales/en/messages.pot"),
+            "msgid \"Bye\"\nmsgstr \"\"\n",
+            ".",
+        )
+        .unwrap();
+
+        assert_that!(translation.language(&db), eq("en"));
+        assert_that!(translation.keys(&db).get("Bye"), some(eq("")));
+    }
+
+    #[rstest]
+    fn test_load_embedded_translations_splits_locales() {
+        let db = crate::db::I18nDatabaseImpl::default();
+        let content =
+            "{\n  \"en\": { \"hello\": \"Hello\" },\n  \"ja\": { \"hello\": \"こんにちは\" }\n}\n";
+
+        let translations = load_embedded_translations(
+            &db,
+            Path::new("/project/src/App.vue"),
+            content,
+            content,
+            TranslationFormat::Json,
+            None,
+            10,
+            ".",
+        )
+        .unwrap();
+
+        assert_that!(translations.len(), eq(2));
+        let en = translations.iter().find(|t| t.language(&db) == "en").unwrap();
+        assert_that!(en.keys(&db).get("hello"), some(eq("Hello")));
+        assert_that!(en.namespace(&db), none());
+        assert!(en.is_component_local(&db));
+        let range = en.key_ranges(&db).get("hello").unwrap();
+        assert_that!(range.start.line, eq(11));
+    }
+
+    #[rstest]
+    fn test_load_embedded_translations_with_locale() {
+        let db = crate::db::I18nDatabaseImpl::default();
+
+        let translations = load_embedded_translations(
+            &db,
+            Path::new("/project/src/App.vue"),
+            "hello: Hallo\n",
+            "hello: Hallo\n",
+            TranslationFormat::Yaml,
+            Some("de"),
+            3,
+            ".",
+        )
+        .unwrap();
+
+        assert_that!(translations.len(), eq(1));
+        let de = translations.first().unwrap();
+        assert_that!(de.language(&db), eq("de"));
+        assert_that!(de.keys(&db).get("hello"), some(eq("Hallo")));
+        assert_that!(de.value_ranges(&db).get("hello").unwrap().start.line, eq(3));
+    }
+
+    #[rstest]
+    fn load_translation_from_document_reuses_edited_tree() {
+        use tower_lsp::lsp_types::{
+            Position,
+            Range,
+            TextDocumentContentChangeEvent,
+        };
+
+        use crate::db::I18nDatabase;
+        use crate::input::document::{
+            DocumentChange,
+            apply_content_changes,
+        };
+        use crate::types::PositionEncoding;
+
+        let db = crate::db::I18nDatabaseImpl::default();
+        let uri = "file:///locales/en.json";
+        let path = Path::new("/locales/en.json");
+        let mut text = "{\n  \"a\": \"A\",\n  \"b\": \"B\"\n}".to_string();
+        db.syntax_trees().open(uri);
+        load_translation_from_document(&db, uri, path, &text, ".").unwrap();
+
+        let change = apply_content_changes(
+            &mut text,
+            vec![TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(2, 9), Position::new(2, 9))),
+                range_length: None,
+                text: "!".to_string(),
+            }],
+            PositionEncoding::Utf16,
+        );
+        let DocumentChange::Edits(edits) = change else { panic!("expected edits") };
+        db.syntax_trees().edit(uri, &edits, &text);
+
+        // The translation's tree was cached and marks only the edited pair as changed, so the
+        // reparse reuses the `"a"` pair.
+        let edited = db.syntax_trees().tree(uri).unwrap();
+        let object = edited.root_node().named_child(0).unwrap();
+        assert_that!(object.named_child(0).unwrap().has_changes(), eq(false));
+        assert_that!(object.named_child(1).unwrap().has_changes(), eq(true));
+
+        let translation = load_translation_from_document(&db, uri, path, &text, ".").unwrap();
+
+        assert_that!(db.syntax_trees().tree(uri).unwrap().root_node().has_changes(), eq(false));
+        assert_that!(translation.keys(&db).get("b"), some(eq("B!")));
+        assert_that!(translation.value_ranges(&db).get("b").unwrap().end.character, eq(11));
+    }
 }
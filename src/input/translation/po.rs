@@ -0,0 +1,602 @@
+//! Gettext PO/POT translation file support.
+//!
+//! Each entry becomes one key: its `msgid`, prefixed with `msgctxt` and the key separator
+//! when a context is present (`msgctxt "menu"` + `msgid "Open"` -> `menu.Open`).
+//!
+//! Plural entries (`msgid_plural` / `msgstr[n]`) are exposed as suffixed keys
+//! (`items_one`, `items_other`, ...) so that the suffix-based plural handling in
+//! [`crate::ide::plural`] applies to them.
+//!
+//! The forms are mapped to CLDR categories by evaluating the `Plural-Forms` expression for
+//! the catalog's `Language`, so that e.g. Latvian forms map to `one`, `other` and `zero`.
+//!
+//! The parser keeps the byte range of every field so entries can be rewritten in place.
+
+mod plural_forms;
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::types::{
+    SourcePosition,
+    SourceRange,
+};
+
+/// A quoted string value, possibly continued over several lines.
+#[derive(Debug, Clone)]
+pub struct PoString {
+    /// Decoded value with all lines concatenated.
+    pub value: String,
+    /// Byte range from the first opening quote to the last closing quote.
+    pub range: Range<usize>,
+    pub source_range: SourceRange,
+}
+
+/// A keyword line (`msgid "..."`) with its continuation lines.
+#[derive(Debug, Clone)]
+pub struct PoField {
+    /// Byte range of the whole lines, including the trailing newline.
+    pub line_range: Range<usize>,
+    pub string: PoString,
+}
+
+/// A `msgstr` or `msgstr[n]` field.
+#[derive(Debug, Clone)]
+pub struct PoMsgstr {
+    /// Plural index for `msgstr[n]`, `None` for a plain `msgstr`.
+    pub index: Option<usize>,
+    pub field: PoField,
+}
+
+/// A single catalog entry, including its comments.
+#[derive(Debug, Clone)]
+pub struct PoEntry {
+    /// Byte range of all lines of the entry, from its first comment to its last string.
+    pub range: Range<usize>,
+    /// Byte range of the `#,` flags line, including the trailing newline.
+    pub flags_line: Option<Range<usize>>,
+    pub fuzzy: bool,
+    pub msgctxt: Option<PoField>,
+    pub msgid: PoField,
+    pub msgid_plural: Option<PoField>,
+    pub msgstr: Vec<PoMsgstr>,
+}
+
+impl PoEntry {
+    /// Returns true for the header entry (`msgid ""` without context).
+    #[must_use]
+    pub const fn is_header(&self) -> bool {
+        self.msgctxt.is_none() && self.msgid.string.value.is_empty()
+    }
+
+    /// Key of the entry without plural suffix.
+    #[must_use]
+    pub fn base_key(&self, separator: &str) -> String {
+        let msgid = &self.msgid.string.value;
+        self.msgctxt.as_ref().map_or_else(
+            || msgid.clone(),
+            |ctxt| format!("{}{separator}{msgid}", ctxt.string.value),
+        )
+    }
+
+    /// Returns the `msgstr` with the given plural index (`None` for a plain `msgstr`).
+    #[must_use]
+    pub fn msgstr(&self, index: Option<usize>) -> Option<&PoMsgstr> {
+        self.msgstr.iter().find(|m| m.index == index)
+    }
+}
+
+/// A parsed PO file.
+#[derive(Debug, Clone)]
+pub struct PoCatalog {
+    pub entries: Vec<PoEntry>,
+    /// `Language` from the header entry.
+    pub language: Option<String>,
+    /// `nplurals` from the header's `Plural-Forms`.
+    pub nplurals: Option<usize>,
+    /// Plural suffix of each form, when the header's `Plural-Forms` could be mapped to the
+    /// categories of its `Language`.
+    pub plural_forms: Option<Vec<&'static str>>,
+}
+
+impl PoCatalog {
+    /// Plural suffixes used for the `msgstr[n]` of plural entries.
+    #[must_use]
+    pub fn plural_suffixes(&self, entry: &PoEntry) -> &[&'static str] {
+        if let Some(forms) = &self.plural_forms {
+            return forms;
+        }
+        // Without a `Plural-Forms` header, assume the common two forms unless more are present
+        let count = self.nplurals.unwrap_or_else(|| {
+            entry.msgstr.iter().filter_map(|m| m.index).max().map_or(2, |max| (max + 1).max(2))
+        });
+        plural_suffixes(count)
+    }
+
+    /// Contexts used in the catalog, longest first.
+    #[must_use]
+    pub fn contexts(&self) -> Vec<&str> {
+        let mut contexts: Vec<&str> = self
+            .entries
+            .iter()
+            .filter_map(|e| e.msgctxt.as_ref().map(|c| c.string.value.as_str()))
+            .collect();
+        contexts.sort_by_key(|c| std::cmp::Reverse(c.len()));
+        contexts.dedup();
+        contexts
+    }
+}
+
+/// Maps `msgstr[n]` indexes to plural suffixes by `nplurals`.
+///
+/// Used when the language or its `Plural-Forms` expression is unknown; this follows the order used by the `Plural-Forms`
+/// of common languages (e.g., 3 forms: Russian / Polish `one`, `few`, `many`).
+#[must_use]
+pub const fn plural_suffixes(nplurals: usize) -> &'static [&'static str] {
+    match nplurals {
+        0 | 1 => &["_other"],
+        2 => &["_one", "_other"],
+        3 => &["_one", "_few", "_many"],
+        4 => &["_one", "_two", "_few", "_other"],
+        5 => &["_one", "_two", "_few", "_many", "_other"],
+        _ => &["_zero", "_one", "_two", "_few", "_many", "_other"],
+    }
+}
+
+/// Parsed contents of a PO translation file.
+#[derive(Debug)]
+pub struct ParsedPo {
+    /// `Language` from the header entry, if present.
+    pub locale: Option<String>,
+    pub keys: HashMap<String, String>,
+    pub key_ranges: HashMap<String, SourceRange>,
+    pub value_ranges: HashMap<String, SourceRange>,
+}
+
+/// Parses PO text into keys and their source ranges.
+///
+/// Untranslated entries (empty or `fuzzy` `msgstr`) are skipped unless `include_untranslated` is set,
+/// which is used for `.pot` templates.
+///
+/// # Errors
+/// Returns error if the text is not a valid PO file.
+pub fn parse_po_translation(
+    content: &str,
+    separator: &str,
+    include_untranslated: bool,
+) -> Result<ParsedPo, String> {
+    let catalog = parse_po(content)?;
+
+    let mut keys = HashMap::new();
+    let mut key_ranges = HashMap::new();
+    let mut value_ranges = HashMap::new();
+
+    for entry in catalog.entries.iter().filter(|e| !e.is_header()) {
+        let base_key = entry.base_key(separator);
+        let suffixes = catalog.plural_suffixes(entry);
+
+        for msgstr in &entry.msgstr {
+            let key = match (&entry.msgid_plural, msgstr.index) {
+                (Some(_), Some(index)) => {
+                    let Some(suffix) = suffixes.get(index) else {
+                        continue;
+                    };
+                    format!("{base_key}{suffix}")
+                }
+                _ => base_key.clone(),
+            };
+
+            // Fuzzy translations are unreviewed guesses, which gettext doesn't use either
+            let string = &msgstr.field.string;
+            let untranslated = string.value.is_empty() || entry.fuzzy;
+            if untranslated && !include_untranslated {
+                continue;
+            }
+
+            key_ranges.insert(key.clone(), entry.msgid.string.source_range);
+            value_ranges.insert(key.clone(), string.source_range);
+            keys.insert(key, if untranslated { String::new() } else { string.value.clone() });
+        }
+    }
+
+    Ok(ParsedPo { locale: catalog.language, keys, key_ranges, value_ranges })
+}
+
+/// Tracks byte offsets and line numbers while scanning.
+struct Line<'a> {
+    number: usize,
+    start: usize,
+    /// Line content without the line break.
+    text: &'a str,
+    /// Offset just past the line break.
+    end: usize,
+}
+
+fn lines(content: &str) -> impl Iterator<Item = Line<'_>> {
+    let mut start = 0;
+    content.split_inclusive('\n').enumerate().map(move |(number, raw)| {
+        let line = Line {
+            number,
+            start,
+            text: raw.trim_end_matches(['\n', '\r']),
+            end: start + raw.len(),
+        };
+        start += raw.len();
+        line
+    })
+}
+
+#[allow(clippy::cast_possible_truncation)]
+const fn position(line: usize, column: usize) -> SourcePosition {
+    SourcePosition { line: line as u32, character: column as u32 }
+}
+
+/// Decodes a quoted PO string starting at `text[0] == '"'`.
+/// Returns the decoded value and the byte length including both quotes.
+fn decode_string(text: &str) -> Option<(String, usize)> {
+    let mut chars = text.char_indices();
+    if chars.next()?.1 != '"' {
+        return None;
+    }
+
+    let mut value = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, i + 1)),
+            '\\' => {
+                let (_, escaped) = chars.next()?;
+                value.push(match escaped {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    'a' => '\u{07}',
+                    'b' => '\u{08}',
+                    'f' => '\u{0C}',
+                    'v' => '\u{0B}',
+                    other => other,
+                });
+            }
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// Parses the string at the end of a keyword line or a continuation line.
+fn parse_line_string(line: &Line<'_>, offset: usize) -> Result<PoString, String> {
+    let error = || format!("Failed to parse PO: invalid string on line {}", line.number + 1);
+
+    let rest = line.text.get(offset..).ok_or_else(error)?;
+    let leading = rest.len() - rest.trim_start().len();
+    let quote = offset + leading;
+    let (value, len) =
+        decode_string(line.text.get(quote..).ok_or_else(error)?).ok_or_else(error)?;
+    if !line.text.get(quote + len..).is_some_and(|tail| tail.trim().is_empty()) {
+        return Err(error());
+    }
+
+    Ok(PoString {
+        value,
+        range: line.start + quote..line.start + quote + len,
+        source_range: SourceRange {
+            start: position(line.number, quote),
+            end: position(line.number, quote + len),
+        },
+    })
+}
+
+/// Entry being assembled while scanning.
+#[derive(Default)]
+struct PendingEntry {
+    start: Option<usize>,
+    end: usize,
+    flags_line: Option<Range<usize>>,
+    fuzzy: bool,
+    msgctxt: Option<PoField>,
+    msgid: Option<PoField>,
+    msgid_plural: Option<PoField>,
+    msgstr: Vec<PoMsgstr>,
+}
+
+impl PendingEntry {
+    const fn has_keywords(&self) -> bool {
+        self.msgctxt.is_some() || self.msgid.is_some() || !self.msgstr.is_empty()
+    }
+
+    fn last_field(&mut self) -> Option<&mut PoField> {
+        if let Some(msgstr) = self.msgstr.last_mut() {
+            return Some(&mut msgstr.field);
+        }
+        self.msgid_plural.as_mut().or(self.msgid.as_mut()).or(self.msgctxt.as_mut())
+    }
+
+    fn finish(self, entries: &mut Vec<PoEntry>) {
+        let (Some(start), Some(msgid)) = (self.start, self.msgid) else {
+            return;
+        };
+        entries.push(PoEntry {
+            range: start..self.end,
+            flags_line: self.flags_line,
+            fuzzy: self.fuzzy,
+            msgctxt: self.msgctxt,
+            msgid,
+            msgid_plural: self.msgid_plural,
+            msgstr: self.msgstr,
+        });
+    }
+}
+
+/// Parses a PO file into entries, keeping byte ranges for editing.
+///
+/// Obsolete entries (`#~`) are treated as comments.
+///
+/// # Errors
+/// Returns error on lines that are neither comments, keywords nor string continuations.
+pub fn parse_po(content: &str) -> Result<PoCatalog, String> {
+    let mut entries = Vec::new();
+    let mut pending = PendingEntry::default();
+
+    for line in lines(content) {
+        let trimmed = line.text.trim_start();
+        let indent = line.text.len() - trimmed.len();
+
+        if trimmed.is_empty() {
+            std::mem::take(&mut pending).finish(&mut entries);
+            continue;
+        }
+
+        if trimmed.starts_with('#') {
+            if pending.has_keywords() {
+                std::mem::take(&mut pending).finish(&mut entries);
+            }
+            pending.start.get_or_insert(line.start);
+            pending.end = line.end;
+            if let Some(flags) = trimmed.strip_prefix("#,") {
+                pending.flags_line = Some(line.start..line.end);
+                pending.fuzzy = flags.split(',').any(|flag| flag.trim() == "fuzzy");
+            }
+            continue;
+        }
+
+        if trimmed.starts_with('"') {
+            let string = parse_line_string(&line, indent)?;
+            let Some(field) = pending.last_field() else {
+                return Err(format!(
+                    "Failed to parse PO: unexpected string on line {}",
+                    line.number + 1
+                ));
+            };
+            field.string.value.push_str(&string.value);
+            field.string.range.end = string.range.end;
+            field.string.source_range.end = string.source_range.end;
+            field.line_range.end = line.end;
+            pending.end = line.end;
+            continue;
+        }
+
+        let keyword_len = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+        let keyword = trimmed.get(..keyword_len).unwrap_or_default();
+
+        let starts_new_entry = match keyword {
+            "msgctxt" => pending.msgctxt.is_some() || pending.msgid.is_some(),
+            "msgid" => pending.msgid.is_some(),
+            _ => false,
+        };
+        if starts_new_entry {
+            std::mem::take(&mut pending).finish(&mut entries);
+        }
+
+        let field = PoField {
+            line_range: line.start..line.end,
+            string: parse_line_string(&line, indent + keyword_len)?,
+        };
+        pending.start.get_or_insert(line.start);
+        pending.end = line.end;
+
+        match keyword {
+            "msgctxt" => pending.msgctxt = Some(field),
+            "msgid" => pending.msgid = Some(field),
+            "msgid_plural" => pending.msgid_plural = Some(field),
+            "msgstr" => pending.msgstr.push(PoMsgstr { index: None, field }),
+            _ => {
+                let index = keyword
+                    .strip_prefix("msgstr[")
+                    .and_then(|rest| rest.strip_suffix(']'))
+                    .and_then(|index| index.parse().ok())
+                    .ok_or_else(|| {
+                        format!(
+                            "Failed to parse PO: unknown keyword '{keyword}' on line {}",
+                            line.number + 1
+                        )
+                    })?;
+                pending.msgstr.push(PoMsgstr { index: Some(index), field });
+            }
+        }
+    }
+    pending.finish(&mut entries);
+
+    let header = entries.iter().find(|e| e.is_header()).and_then(|e| e.msgstr(None));
+    let header_field = |name: &str| {
+        header.and_then(|h| {
+            h.field.string.value.lines().find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                (key.trim() == name).then(|| value.trim().to_string())
+            })
+        })
+    };
+    let language = header_field("Language").filter(|l| !l.is_empty());
+    let plural_forms_header = header_field("Plural-Forms");
+    let plural_forms_field = |name: &str| {
+        plural_forms_header.as_deref().and_then(|forms| {
+            forms.split(';').find_map(|part| Some(part.trim().strip_prefix(name)?.trim()))
+        })
+    };
+    let nplurals = plural_forms_field("nplurals=").and_then(|n| n.parse().ok());
+    let plural_forms = match (&language, plural_forms_field("plural="), nplurals) {
+        (Some(language), Some(expression), Some(nplurals)) => {
+            plural_forms::plural_form_suffixes(language, expression, nplurals)
+        }
+        _ => None,
+    };
+
+    Ok(PoCatalog { entries, language, nplurals, plural_forms })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+
+    const PO: &str = r#"# Translator comment
+msgid ""
+msgstr ""
+"Language: ja\n"
+"Plural-Forms: nplurals=1; plural=0;\n"
+
+#: src/App.tsx:10
+msgid "Hello"
+msgstr "こんにちは"
+
+msgctxt "menu"
+msgid "Open"
+msgstr "開く"
+
+msgid "Untranslated"
+msgstr ""
+"#;
+
+    #[rstest]
+    fn parse_entries_and_header() {
+        let parsed = parse_po_translation(PO, ".", false).unwrap();
+
+        assert_that!(parsed.locale, some(eq("ja")));
+        assert_that!(parsed.keys.get("Hello"), some(eq("こんにちは")));
+        assert_that!(parsed.keys.get("menu.Open"), some(eq("開く")));
+        assert_that!(parsed.keys.contains_key(""), eq(false));
+        assert_that!(parsed.keys.contains_key("Untranslated"), eq(false));
+        assert_that!(parsed.keys.len(), eq(2));
+    }
+
+    #[rstest]
+    fn parse_template_keeps_untranslated() {
+        let parsed = parse_po_translation(PO, ".", true).unwrap();
+
+        assert_that!(parsed.keys.get("Untranslated"), some(eq("")));
+    }
+
+    #[rstest]
+    fn parse_ranges() {
+        let parsed = parse_po_translation(PO, ".", false).unwrap();
+
+        let key_range = parsed.key_ranges.get("Hello").unwrap();
+        assert_that!(key_range.start, eq(position(7, 6)));
+        assert_that!(key_range.end, eq(position(7, 13)));
+        let value_range = parsed.value_ranges.get("Hello").unwrap();
+        assert_that!(value_range.start, eq(position(8, 7)));
+    }
+
+    #[rstest]
+    fn parse_plural_entries() {
+        let po = r#"msgid ""
+msgstr "Plural-Forms: nplurals=2; plural=(n != 1);\n"
+
+msgid "item"
+msgid_plural "items"
+msgstr[0] "{{count}} item"
+msgstr[1] "{{count}} items"
+"#;
+
+        let parsed = parse_po_translation(po, ".", false).unwrap();
+
+        assert_that!(parsed.keys.get("item_one"), some(eq("{{count}} item")));
+        assert_that!(parsed.keys.get("item_other"), some(eq("{{count}} items")));
+        assert_that!(parsed.keys.contains_key("item"), eq(false));
+    }
+
+    #[rstest]
+    fn parse_plural_entries_by_language() {
+        let po = r#"msgid ""
+msgstr ""
+"Language: lv\n"
+"Plural-Forms: nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n != 0 ? 1 : 2);\n"
+
+msgid "item"
+msgid_plural "items"
+msgstr[0] "{{count}} vienums"
+msgstr[1] "{{count}} vienumi"
+msgstr[2] "nav vienumu"
+"#;
+
+        let parsed = parse_po_translation(po, ".", false).unwrap();
+
+        assert_that!(parsed.keys.get("item_one"), some(eq("{{count}} vienums")));
+        assert_that!(parsed.keys.get("item_other"), some(eq("{{count}} vienumi")));
+        assert_that!(parsed.keys.get("item_zero"), some(eq("nav vienumu")));
+    }
+
+    #[rstest]
+    fn parse_skips_fuzzy_entries() {
+        let po = "#, fuzzy\nmsgid \"a\"\nmsgstr \"A?\"\n\nmsgid \"b\"\nmsgstr \"B\"\n";
+
+        let parsed = parse_po_translation(po, ".", false).unwrap();
+        assert_that!(parsed.keys.contains_key("a"), eq(false));
+        assert_that!(parsed.keys.get("b"), some(eq("B")));
+
+        let template = parse_po_translation(po, ".", true).unwrap();
+        assert_that!(template.keys.get("a"), some(eq("")));
+    }
+
+    #[rstest]
+    #[case(1, &["_other"])]
+    #[case(2, &["_one", "_other"])]
+    #[case(3, &["_one", "_few", "_many"])]
+    #[case(6, &["_zero", "_one", "_two", "_few", "_many", "_other"])]
+    fn plural_suffixes_by_count(#[case] nplurals: usize, #[case] expected: &[&str]) {
+        assert_that!(plural_suffixes(nplurals), eq(expected));
+    }
+
+    #[rstest]
+    fn parse_multiline_and_escapes() {
+        let po = "msgid \"\"\n\"multi\"\n\"line\"\nmsgstr \"\"\n\"a \\\"quoted\\\"\\n\"\n\"b\"\n";
+
+        let catalog = parse_po(po).unwrap();
+
+        let entry = catalog.entries.first().unwrap();
+        assert_that!(entry.msgid.string.value, eq("multiline"));
+        assert_that!(entry.msgstr(None).unwrap().field.string.value, eq("a \"quoted\"\nb"));
+        assert_that!(entry.range, eq(&(0..po.len())));
+    }
+
+    #[rstest]
+    fn parse_fuzzy_and_obsolete() {
+        let po = "#, fuzzy, c-format\nmsgid \"a\"\nmsgstr \"A\"\n\n#~ msgid \"old\"\n#~ msgstr \"Old\"\n";
+
+        let catalog = parse_po(po).unwrap();
+
+        assert_that!(catalog.entries, len(eq(1)));
+        let entry = catalog.entries.first().unwrap();
+        assert_that!(entry.fuzzy, eq(true));
+        assert_that!(entry.flags_line, some(eq(&(0..19))));
+    }
+
+    #[rstest]
+    fn parse_entries_without_blank_lines() {
+        let po = "msgid \"a\"\nmsgstr \"A\"\nmsgid \"b\"\nmsgstr \"B\"\n";
+
+        let parsed = parse_po_translation(po, ".", false).unwrap();
+
+        assert_that!(parsed.keys.get("a"), some(eq("A")));
+        assert_that!(parsed.keys.get("b"), some(eq("B")));
+    }
+
+    #[rstest]
+    #[case::unterminated("msgid \"a\nmsgstr \"\"\n")]
+    #[case::unknown_keyword("msgfoo \"a\"\n")]
+    #[case::stray_string("\"a\"\n")]
+    fn parse_invalid(#[case] po: &str) {
+        assert_that!(parse_po(po), err(anything()));
+    }
+}
@@ -0,0 +1,290 @@
+//! Mapping of gettext plural forms to CLDR categories.
+//!
+//! Gettext numbers the forms of a plural entry and selects one with the C expression in
+//! `Plural-Forms` (`plural=(n != 1);`). The category of each form is found by evaluating the
+//! expression for a sample number of every category of the catalog's language.
+
+use crate::ide::plural::rules::{
+    PluralCategory,
+    cardinal_categories,
+    cardinal_samples,
+};
+
+/// Operators by increasing precedence, as in C.
+const BINARY_OPERATORS: [&[&str]; 6] =
+    [&["||"], &["&&"], &["==", "!="], &["<=", ">=", "<", ">"], &["+", "-"], &["*", "/", "%"]];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    N,
+    Number(u64),
+    Operator(&'static str),
+    Question,
+    Colon,
+    Open,
+    Close,
+}
+
+#[derive(Debug)]
+enum Expr {
+    N,
+    Number(u64),
+    Not(Box<Self>),
+    Binary(&'static str, Box<Self>, Box<Self>),
+    Conditional(Box<Self>, Box<Self>, Box<Self>),
+}
+
+impl Expr {
+    /// Evaluates the expression for `n`, or `None` on division by zero.
+    fn eval(&self, n: u64) -> Option<u64> {
+        Some(match self {
+            Self::N => n,
+            Self::Number(value) => *value,
+            Self::Not(operand) => u64::from(operand.eval(n)? == 0),
+            Self::Conditional(condition, then, otherwise) => {
+                if condition.eval(n)? != 0 {
+                    then.eval(n)?
+                } else {
+                    otherwise.eval(n)?
+                }
+            }
+            Self::Binary(operator, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.eval(n)?, rhs.eval(n)?);
+                match *operator {
+                    "||" => u64::from(lhs != 0 || rhs != 0),
+                    "&&" => u64::from(lhs != 0 && rhs != 0),
+                    "==" => u64::from(lhs == rhs),
+                    "!=" => u64::from(lhs != rhs),
+                    "<=" => u64::from(lhs <= rhs),
+                    ">=" => u64::from(lhs >= rhs),
+                    "<" => u64::from(lhs < rhs),
+                    ">" => u64::from(lhs > rhs),
+                    "+" => lhs.wrapping_add(rhs),
+                    "-" => lhs.wrapping_sub(rhs),
+                    "*" => lhs.wrapping_mul(rhs),
+                    "/" => lhs.checked_div(rhs)?,
+                    _ => lhs.checked_rem(rhs)?,
+                }
+            }
+        })
+    }
+}
+
+fn tokenize(text: &str) -> Option<Vec<Token>> {
+    const OPERATORS: [&str; 14] =
+        ["||", "&&", "==", "!=", "<=", ">=", "<", ">", "+", "-", "*", "/", "%", "!"];
+
+    let mut tokens = Vec::new();
+    let mut rest = text.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let (token, len) = match c {
+            'n' => (Token::N, 1),
+            '?' => (Token::Question, 1),
+            ':' => (Token::Colon, 1),
+            '(' => (Token::Open, 1),
+            ')' => (Token::Close, 1),
+            '0'..='9' => {
+                let len = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+                (Token::Number(rest.get(..len)?.parse().ok()?), len)
+            }
+            _ => {
+                let operator = OPERATORS.into_iter().find(|op| rest.starts_with(op))?;
+                (Token::Operator(operator), operator.len())
+            }
+        };
+        tokens.push(token);
+        rest = rest.get(len..)?.trim_start();
+    }
+    Some(tokens)
+}
+
+/// Recursive descent parser over the tokens of a plural expression.
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek()?;
+        self.position += 1;
+        Some(token)
+    }
+
+    fn expect(&mut self, token: Token) -> Option<()> {
+        (self.next()? == token).then_some(())
+    }
+
+    fn conditional(&mut self) -> Option<Expr> {
+        let condition = self.binary(0)?;
+        if self.peek() != Some(Token::Question) {
+            return Some(condition);
+        }
+        self.position += 1;
+        let then = self.conditional()?;
+        self.expect(Token::Colon)?;
+        let otherwise = self.conditional()?;
+        Some(Expr::Conditional(Box::new(condition), Box::new(then), Box::new(otherwise)))
+    }
+
+    fn binary(&mut self, level: usize) -> Option<Expr> {
+        let Some(operators) = BINARY_OPERATORS.get(level) else {
+            return self.unary();
+        };
+        let mut lhs = self.binary(level + 1)?;
+        while let Some(Token::Operator(operator)) = self.peek()
+            && operators.contains(&operator)
+        {
+            self.position += 1;
+            let rhs = self.binary(level + 1)?;
+            lhs = Expr::Binary(operator, Box::new(lhs), Box::new(rhs));
+        }
+        Some(lhs)
+    }
+
+    fn unary(&mut self) -> Option<Expr> {
+        match self.next()? {
+            Token::N => Some(Expr::N),
+            Token::Number(value) => Some(Expr::Number(value)),
+            Token::Operator("!") => Some(Expr::Not(Box::new(self.unary()?))),
+            Token::Open => {
+                let expr = self.conditional()?;
+                self.expect(Token::Close)?;
+                Some(expr)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parses the `plural=` expression of a `Plural-Forms` header.
+fn parse_expression(text: &str) -> Option<Expr> {
+    let mut parser = Parser { tokens: tokenize(text)?, position: 0 };
+    let expr = parser.conditional()?;
+    (parser.position == parser.tokens.len()).then_some(expr)
+}
+
+const fn suffix(category: PluralCategory) -> &'static str {
+    match category {
+        PluralCategory::Zero => "_zero",
+        PluralCategory::One => "_one",
+        PluralCategory::Two => "_two",
+        PluralCategory::Few => "_few",
+        PluralCategory::Many => "_many",
+        PluralCategory::Other => "_other",
+    }
+}
+
+/// Returns the plural suffix of each form of a catalog in `language`, or `None` if the
+/// language is unknown or the expression doesn't select a distinct category for every form.
+pub(super) fn plural_form_suffixes(
+    language: &str,
+    expression: &str,
+    nplurals: usize,
+) -> Option<Vec<&'static str>> {
+    let expr = parse_expression(expression)?;
+
+    let mut forms: Vec<Option<PluralCategory>> = vec![None; nplurals];
+    for &(category, sample) in cardinal_samples(language)? {
+        let form = forms.get_mut(usize::try_from(expr.eval(sample)?).ok()?)?;
+        // `other` wins when categories share a form, e.g. French `many` in two-form catalogs
+        if form.is_none() || category == PluralCategory::Other {
+            *form = Some(category);
+        }
+    }
+
+    // Forms only selected by fractions have no integer sample; they take the unused categories
+    let used: Vec<PluralCategory> = forms.iter().flatten().copied().collect();
+    let mut unused =
+        cardinal_categories(language)?.iter().copied().filter(|category| !used.contains(category));
+    forms.into_iter().map(|form| form.or_else(|| unused.next()).map(suffix)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case::constant("0", 5, 0)]
+    #[case::comparison("(n != 1)", 1, 0)]
+    #[case::precedence("n%10==1 && n%100!=11", 21, 1)]
+    #[case::nested_conditional("n==1 ? 0 : n==2 ? 1 : 2", 2, 1)]
+    #[case::not("!(n > 1)", 0, 1)]
+    #[case::arithmetic("(n + 2) * 3 - 4 / 2", 1, 7)]
+    fn evaluates_expressions(#[case] text: &str, #[case] n: u64, #[case] expected: u64) {
+        let expr = parse_expression(text);
+
+        assert_that!(expr.and_then(|expr| expr.eval(n)), some(eq(expected)));
+    }
+
+    #[rstest]
+    #[case::unbalanced("(n != 1")]
+    #[case::trailing("n != 1 )")]
+    #[case::missing_else("n == 1 ? 0")]
+    #[case::unknown_token("x == 1")]
+    fn rejects_invalid_expressions(#[case] text: &str) {
+        assert_that!(parse_expression(text).is_none(), eq(true));
+    }
+
+    #[rstest]
+    #[case::japanese("ja", "0", 1, &["_other"])]
+    #[case::english("en", "(n != 1)", 2, &["_one", "_other"])]
+    #[case::french("fr", "(n > 1)", 2, &["_one", "_other"])]
+    #[case::latvian(
+        "lv",
+        "(n%10==1 && n%100!=11 ? 0 : n != 0 ? 1 : 2)",
+        3,
+        &["_one", "_other", "_zero"]
+    )]
+    #[case::romanian(
+        "ro",
+        "(n==1 ? 0 : (n==0 || (n%100 > 0 && n%100 < 20)) ? 1 : 2)",
+        3,
+        &["_one", "_few", "_other"]
+    )]
+    #[case::russian(
+        "ru",
+        "(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2)",
+        3,
+        &["_one", "_few", "_many"]
+    )]
+    #[case::russian_with_fractions(
+        "ru",
+        "(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : n%10==0 || (n%10>=5 && n%10<=9) || (n%100>=11 && n%100<=14) ? 2 : 3)",
+        4,
+        &["_one", "_few", "_many", "_other"]
+    )]
+    #[case::arabic(
+        "ar",
+        "(n==0 ? 0 : n==1 ? 1 : n==2 ? 2 : n%100>=3 && n%100<=10 ? 3 : n%100>=11 ? 4 : 5)",
+        6,
+        &["_zero", "_one", "_two", "_few", "_many", "_other"]
+    )]
+    fn maps_forms_to_categories(
+        #[case] language: &str,
+        #[case] expression: &str,
+        #[case] nplurals: usize,
+        #[case] expected: &[&str],
+    ) {
+        assert_that!(plural_form_suffixes(language, expression, nplurals), some(eq(expected)));
+    }
+
+    #[rstest]
+    #[case::unknown_language("xx", "(n != 1)", 2)]
+    #[case::form_out_of_range("en", "(n != 1) + 1", 2)]
+    #[case::more_forms_than_categories("en", "(n != 1)", 3)]
+    fn no_mapping_without_matching_categories(
+        #[case] language: &str,
+        #[case] expression: &str,
+        #[case] nplurals: usize,
+    ) {
+        assert_that!(plural_form_suffixes(language, expression, nplurals), none());
+    }
+}
@@ -0,0 +1,414 @@
+//! YAML translation file support.
+//!
+//! Values are decoded with `serde_yaml_ng` (aliases and `<<` merge keys resolved) and
+//! flattened the same way as JSON. Key/value ranges come from tree-sitter-yaml.
+//!
+//! Rails-style files that wrap every key in a single locale root (`en:`) are unwrapped:
+//! the root key becomes the translation language and is not part of the key path.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+use tree_sitter::{
+    Node,
+    Parser,
+    Tree,
+};
+
+use super::{
+    LANGUAGE_CODES,
+    flatten_json,
+    normalize_language_code,
+};
+use crate::types::SourceRange;
+
+/// Parsed contents of a YAML translation file.
+#[derive(Debug)]
+pub struct ParsedYaml {
+    /// Locale taken from a Rails-style root key, if present.
+    pub locale: Option<String>,
+    pub keys: HashMap<String, String>,
+    pub key_ranges: HashMap<String, SourceRange>,
+    pub value_ranges: HashMap<String, SourceRange>,
+}
+
+/// Parses YAML translation text into flattened keys and their source ranges.
+///
+/// # Errors
+/// Returns error if the text is not a valid single-document YAML file.
+pub fn parse_yaml_translation(content: &str, separator: &str) -> Result<ParsedYaml, String> {
+    let mut yaml: serde_yaml_ng::Value =
+        serde_yaml_ng::from_str(content).map_err(|e| format!("Failed to parse YAML: {e}"))?;
+    yaml.apply_merge().map_err(|e| format!("Failed to parse YAML: {e}"))?;
+    let json = serde_json::to_value(&yaml).map_err(|e| format!("Failed to parse YAML: {e}"))?;
+
+    let source = content.as_bytes();
+    let tree = parse_yaml_tree(content);
+
+    let locale = tree.as_ref().and_then(|t| locale_root(t.root_node(), source)).map(|(l, _)| l);
+    let json = match &locale {
+        Some(locale) => json.get(locale).cloned().unwrap_or(Value::Null),
+        None => json,
+    };
+
+    let keys = flatten_json(&json, separator, None);
+
+    let mut key_ranges = HashMap::new();
+    let mut value_ranges = HashMap::new();
+    if let Some(mapping) = tree.as_ref().and_then(|t| content_mapping(t.root_node(), source)) {
+        extract_mapping(mapping, source, separator, None, &mut key_ranges, &mut value_ranges);
+    }
+
+    Ok(ParsedYaml { locale, keys, key_ranges, value_ranges })
+}
+
+/// Parses YAML text with tree-sitter.
+#[must_use]
+pub fn parse_yaml_tree(text: &str) -> Option<Tree> {
+    let mut parser = Parser::new();
+    let Ok(()) = parser.set_language(&tree_sitter_yaml::LANGUAGE.into()) else {
+        tracing::warn!("Failed to set tree-sitter-yaml language");
+        return None;
+    };
+    parser.parse(text, None)
+}
+
+fn named_children(node: Node<'_>) -> impl Iterator<Item = Node<'_>> {
+    // tree-sitter 0.26+ requires u32 for named_child()
+    #[allow(clippy::cast_possible_truncation)]
+    (0..node.named_child_count()).filter_map(move |i| node.named_child(i as u32))
+}
+
+/// Returns true if the first document has no content (empty or comment-only file).
+#[must_use]
+pub fn is_empty_document(root: Node<'_>) -> bool {
+    named_children(root)
+        .filter(|n| n.kind() == "document")
+        .all(|doc| named_children(doc).all(|n| n.kind() == "comment"))
+}
+
+/// Returns the top-level mapping of the first document.
+fn document_mapping(root: Node<'_>) -> Option<Node<'_>> {
+    let document = named_children(root).find(|n| n.kind() == "document")?;
+    named_children(document).find_map(node_mapping)
+}
+
+/// Unwraps a `block_node` / `flow_node` to the mapping it holds.
+#[must_use]
+pub fn node_mapping(node: Node<'_>) -> Option<Node<'_>> {
+    match node.kind() {
+        "block_mapping" | "flow_mapping" => Some(node),
+        "block_node" | "flow_node" => {
+            named_children(node).find(|n| matches!(n.kind(), "block_mapping" | "flow_mapping"))
+        }
+        _ => None,
+    }
+}
+
+fn node_sequence(node: Node<'_>) -> Option<Node<'_>> {
+    match node.kind() {
+        "block_sequence" | "flow_sequence" => Some(node),
+        "block_node" | "flow_node" => {
+            named_children(node).find(|n| matches!(n.kind(), "block_sequence" | "flow_sequence"))
+        }
+        _ => None,
+    }
+}
+
+/// Finds the scalar inside a `block_node` / `flow_node`, skipping anchors and tags.
+#[must_use]
+pub fn scalar_node(node: Node<'_>) -> Option<Node<'_>> {
+    let is_scalar = |kind: &str| {
+        matches!(
+            kind,
+            "plain_scalar" | "double_quote_scalar" | "single_quote_scalar" | "block_scalar"
+        )
+    };
+    if is_scalar(node.kind()) {
+        return Some(node);
+    }
+    named_children(node).find(|n| is_scalar(n.kind()))
+}
+
+/// Iterates the key/value pairs of a mapping.
+pub fn mapping_pairs(mapping: Node<'_>) -> impl Iterator<Item = Node<'_>> {
+    named_children(mapping).filter(|n| matches!(n.kind(), "block_mapping_pair" | "flow_pair"))
+}
+
+/// Returns the decoded key of a mapping pair.
+#[must_use]
+pub fn pair_key(pair: Node<'_>, source: &[u8]) -> Option<String> {
+    let key_node = pair.child_by_field_name("key")?;
+    let scalar = scalar_node(key_node)?;
+    let text = scalar.utf8_text(source).ok()?;
+
+    match scalar.kind() {
+        "plain_scalar" => Some(text.trim().to_string()),
+        "single_quote_scalar" => Some(strip_quotes(text, '\'').replace("''", "'")),
+        "double_quote_scalar" => Some(unescape_double_quoted(strip_quotes(text, '"'))),
+        _ => None,
+    }
+}
+
+fn strip_quotes(text: &str, quote: char) -> &str {
+    text.strip_prefix(quote).and_then(|t| t.strip_suffix(quote)).unwrap_or(text)
+}
+
+/// Decodes the common escape sequences of a double-quoted scalar.
+fn unescape_double_quoted(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some('0') => result.push('\0'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                if let Some(decoded) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    result.push(decoded);
+                }
+            }
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+/// Detects a Rails-style locale root: a single top-level key that is a language code
+/// and holds the translations (e.g., `en:` wrapping the whole file).
+///
+/// Returns the locale and its pair.
+#[must_use]
+pub fn locale_root<'t>(root: Node<'t>, source: &[u8]) -> Option<(String, Node<'t>)> {
+    let mapping = document_mapping(root)?;
+    let mut pairs = mapping_pairs(mapping);
+    let pair = pairs.next()?;
+    if pairs.next().is_some() {
+        return None;
+    }
+
+    let key = pair_key(pair, source)?;
+    if !LANGUAGE_CODES.contains(&key) && !LANGUAGE_CODES.contains(&normalize_language_code(&key)) {
+        return None;
+    }
+
+    // `en:` with no value yet still counts; `en: Hello` is a regular key
+    match pair.child_by_field_name("value") {
+        None => Some((key, pair)),
+        Some(value) => node_mapping(value).map(|_| (key, pair)),
+    }
+}
+
+/// Returns the mapping that holds the translation keys: the locale root's value for
+/// Rails-style files, otherwise the top-level mapping.
+#[must_use]
+pub fn content_mapping<'t>(root: Node<'t>, source: &[u8]) -> Option<Node<'t>> {
+    match locale_root(root, source) {
+        Some((_, pair)) => pair.child_by_field_name("value").and_then(node_mapping),
+        None => document_mapping(root),
+    }
+}
+
+fn extract_mapping(
+    mapping: Node<'_>,
+    source: &[u8],
+    separator: &str,
+    prefix: Option<&str>,
+    key_ranges: &mut HashMap<String, SourceRange>,
+    value_ranges: &mut HashMap<String, SourceRange>,
+) {
+    for pair in mapping_pairs(mapping) {
+        let Some(key_node) = pair.child_by_field_name("key") else {
+            continue;
+        };
+        let Some(key) = pair_key(pair, source) else {
+            continue;
+        };
+        // Merge key: inherited keys are defined elsewhere in the file
+        if key == "<<" {
+            continue;
+        }
+
+        let full_key = prefix.map_or_else(|| key.clone(), |p| format!("{p}{separator}{key}"));
+        key_ranges.insert(full_key.clone(), SourceRange::from_node(&key_node));
+
+        if let Some(value_node) = pair.child_by_field_name("value") {
+            extract_value(value_node, source, separator, &full_key, key_ranges, value_ranges);
+        }
+    }
+}
+
+fn extract_value(
+    node: Node<'_>,
+    source: &[u8],
+    separator: &str,
+    full_key: &str,
+    key_ranges: &mut HashMap<String, SourceRange>,
+    value_ranges: &mut HashMap<String, SourceRange>,
+) {
+    if let Some(mapping) = node_mapping(node) {
+        extract_mapping(mapping, source, separator, Some(full_key), key_ranges, value_ranges);
+    } else if let Some(sequence) = node_sequence(node) {
+        extract_sequence(sequence, source, separator, full_key, key_ranges, value_ranges);
+    } else {
+        let value_node = scalar_node(node).unwrap_or(node);
+        value_ranges.insert(full_key.to_string(), SourceRange::from_node(&value_node));
+    }
+}
+
+fn extract_sequence(
+    sequence: Node<'_>,
+    source: &[u8],
+    separator: &str,
+    prefix: &str,
+    key_ranges: &mut HashMap<String, SourceRange>,
+    value_ranges: &mut HashMap<String, SourceRange>,
+) {
+    let items = named_children(sequence).filter(|n| n.kind() != "comment");
+
+    for (index, item) in items.enumerate() {
+        let full_key = format!("{prefix}[{index}]");
+        key_ranges.insert(full_key.clone(), SourceRange::from_node(&item));
+
+        // `- value` wraps the value in an item node; empty items (`-`) have no value
+        let value_node = if item.kind() == "block_sequence_item" {
+            named_children(item).find(|n| n.kind() != "comment")
+        } else {
+            Some(item)
+        };
+        if let Some(value_node) = value_node {
+            extract_value(value_node, source, separator, &full_key, key_ranges, value_ranges);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    fn parse_nested_keys() {
+        let yaml =
+            "common:\n  hello: Hello\n  bye: \"Good bye\"\nerrors:\n  notFound: 'Not found'\n";
+
+        let parsed = parse_yaml_translation(yaml, ".").unwrap();
+
+        assert_that!(parsed.locale, none());
+        assert_that!(parsed.keys.get("common.hello"), some(eq("Hello")));
+        assert_that!(parsed.keys.get("common.bye"), some(eq("Good bye")));
+        assert_that!(parsed.keys.get("errors.notFound"), some(eq("Not found")));
+        assert_that!(parsed.keys.len(), eq(3));
+    }
+
+    #[rstest]
+    fn parse_rails_locale_root() {
+        let yaml = "# Shared with the backend\nen:\n  greeting: Hello\n  user:\n    name: Name\n";
+
+        let parsed = parse_yaml_translation(yaml, ".").unwrap();
+
+        assert_that!(parsed.locale, some(eq("en")));
+        assert_that!(parsed.keys.get("greeting"), some(eq("Hello")));
+        assert_that!(parsed.keys.get("user.name"), some(eq("Name")));
+        assert_that!(parsed.keys.contains_key("en.greeting"), eq(false));
+
+        let range = parsed.key_ranges.get("greeting").unwrap();
+        assert_that!(range.start.line, eq(2));
+        assert_that!(range.start.character, eq(2));
+    }
+
+    #[rstest]
+    fn single_non_language_root_is_regular_key() {
+        let yaml = "common:\n  hello: Hello\n";
+
+        let parsed = parse_yaml_translation(yaml, ".").unwrap();
+
+        assert_that!(parsed.locale, none());
+        assert_that!(parsed.keys.get("common.hello"), some(eq("Hello")));
+    }
+
+    #[rstest]
+    fn language_root_with_scalar_value_is_regular_key() {
+        let yaml = "en: English\n";
+
+        let parsed = parse_yaml_translation(yaml, ".").unwrap();
+
+        assert_that!(parsed.locale, none());
+        assert_that!(parsed.keys.get("en"), some(eq("English")));
+    }
+
+    #[rstest]
+    fn parse_block_scalar_and_escapes() {
+        let yaml = "multi: |\n  line1\n  line2\nescaped: \"say \\\"hi\\\"\"\nsingle: 'it''s'\n";
+
+        let parsed = parse_yaml_translation(yaml, ".").unwrap();
+
+        assert_that!(parsed.keys.get("multi"), some(eq("line1\nline2\n")));
+        assert_that!(parsed.keys.get("escaped"), some(eq("say \"hi\"")));
+        assert_that!(parsed.keys.get("single"), some(eq("it's")));
+    }
+
+    #[rstest]
+    fn parse_anchors_and_merge_keys() {
+        let yaml =
+            "base: &base\n  ok: OK\n  cancel: Cancel\ndialog:\n  <<: *base\n  title: Title\n";
+
+        let parsed = parse_yaml_translation(yaml, ".").unwrap();
+
+        assert_that!(parsed.keys.get("dialog.ok"), some(eq("OK")));
+        assert_that!(parsed.keys.get("dialog.title"), some(eq("Title")));
+        assert_that!(parsed.key_ranges.contains_key("dialog.<<"), eq(false));
+    }
+
+    #[rstest]
+    fn parse_sequences() {
+        let yaml = "days:\n  - Mon\n  - Tue\nflow: [a, b]\n";
+
+        let parsed = parse_yaml_translation(yaml, ".").unwrap();
+
+        assert_that!(parsed.keys.get("days[1]"), some(eq("Tue")));
+        assert_that!(parsed.keys.get("flow[0]"), some(eq("a")));
+        assert_that!(parsed.value_ranges.contains_key("days[1]"), eq(true));
+        assert_that!(parsed.value_ranges.contains_key("flow[0]"), eq(true));
+    }
+
+    #[rstest]
+    fn value_ranges_cover_scalar_only() {
+        let yaml = "greeting: \"Hello\" # comment\n";
+
+        let parsed = parse_yaml_translation(yaml, ".").unwrap();
+
+        let range = parsed.value_ranges.get("greeting").unwrap();
+        assert_that!(range.start.character, eq(10));
+        assert_that!(range.end.character, eq(17));
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::comment_only("# nothing yet\n")]
+    fn parse_empty_document(#[case] yaml: &str) {
+        let parsed = parse_yaml_translation(yaml, ".").unwrap();
+
+        assert_that!(parsed.keys, is_empty());
+        assert!(is_empty_document(parse_yaml_tree(yaml).unwrap().root_node()));
+    }
+
+    #[rstest]
+    fn parse_invalid_yaml_returns_error() {
+        let result = parse_yaml_translation("key: [unclosed\n", ".");
+
+        assert_that!(result, err(anything()));
+    }
+}
@@ -7,7 +7,14 @@ use crate::types::SourceRange;
 #[salsa::interned]
 pub struct KeyUsage {
     pub key: TransKey<'db>,
+    /// Range of the key argument at the call site.
     pub range: SourceRange,
+    /// Range of the string literal defining the key when it is passed through a constant.
+    pub definition_range: Option<SourceRange>,
     pub namespace: Option<String>,
     pub namespaces: Option<Vec<String>>,
+    /// Whether `key` is a wildcard pattern from a dynamic key (e.g., `errors.*`).
+    pub dynamic: bool,
+    /// Options object keys passed at the call site, or `None` if unknown.
+    pub interpolation_args: Option<Vec<String>>,
 }
@@ -2,6 +2,7 @@
 //!
 //! i18n Language Server Protocol (LSP) implementation for JavaScript/TypeScript.
 
+pub mod check;
 pub mod config;
 pub mod db;
 pub mod framework;
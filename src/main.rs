@@ -6,6 +6,7 @@ use std::sync::Arc;
 use js_i18n_language_server::{
     Backend,
     ServerState,
+    check::OutputFormat,
     config::ConfigManager,
     db::I18nDatabaseImpl,
     indexer::workspace::WorkspaceIndexer,
@@ -20,14 +21,42 @@ use tower_lsp::{
 struct Args {
     log_file: Option<PathBuf>,
     log_level: Option<String>,
+    /// `check` subcommand; the language server starts when unset.
+    check: Option<CheckArgs>,
+}
+
+/// Arguments of the `check` subcommand.
+struct CheckArgs {
+    path: PathBuf,
+    format: OutputFormat,
 }
 
 fn parse_args() -> Args {
-    let mut args = Args { log_file: None, log_level: None };
-    let mut args_iter = std::env::args().skip(1);
+    let mut args = Args { log_file: None, log_level: None, check: None };
+    let mut args_iter = std::env::args().skip(1).peekable();
+
+    if args_iter.next_if(|arg| arg == "check").is_some() {
+        args.check = Some(CheckArgs { path: PathBuf::from("."), format: OutputFormat::Human });
+    }
 
     while let Some(arg) = args_iter.next() {
         match arg.as_str() {
+            "--format" if args.check.is_some() => {
+                let format = args_iter.next().unwrap_or_default();
+                match format.parse() {
+                    Ok(format) => {
+                        if let Some(check) = args.check.as_mut() {
+                            check.format = format;
+                        }
+                    }
+                    Err(e) => exit_with_usage_error(&e),
+                }
+            }
+            path if args.check.is_some() && !path.starts_with('-') => {
+                if let Some(check) = args.check.as_mut() {
+                    check.path = PathBuf::from(path);
+                }
+            }
             "--log-file" => {
                 args.log_file = args_iter.next().map(PathBuf::from);
             }
@@ -51,12 +80,26 @@ fn parse_args() -> Args {
     args
 }
 
+#[allow(clippy::print_stderr)]
+fn exit_with_usage_error(message: &str) -> ! {
+    eprintln!("error: {message}");
+    std::process::exit(2);
+}
+
 #[allow(clippy::print_stdout)]
 fn print_help() {
     println!(
         r"Language Server Protocol implementation for JavaScript/TypeScript i18n
 
 Usage: js-i18n-language-server [OPTIONS]
+       js-i18n-language-server check [PATH] [--format <FORMAT>]
+
+Commands:
+  check                    Report diagnostics for the workspace at PATH (default: .) and exit
+                           with status 1 if any error-severity diagnostic is found
+
+Check Options:
+      --format <FORMAT>    Output format: human (default), json, or sarif
 
 Options:
       --log-file <PATH>    Log to the specified file instead of stderr
@@ -78,15 +121,18 @@ fn print_version() {
 
 /// Resolves the tracing env filter.
 ///
-/// Priority: `--log-level` arg > `JS_I18N_LOG` env > `RUST_LOG` env > default (`info`).
+/// Priority: `--log-level` arg > `JS_I18N_LOG` env > `RUST_LOG` env > `default_level`.
 /// Simple values (e.g., `info`) are auto-scoped to `js_i18n_language_server={value}`.
-fn resolve_env_filter(log_level_arg: Option<&str>) -> tracing_subscriber::EnvFilter {
+fn resolve_env_filter(
+    log_level_arg: Option<&str>,
+    default_level: &str,
+) -> tracing_subscriber::EnvFilter {
     let raw = log_level_arg.map(String::from).or_else(|| std::env::var("JS_I18N_LOG").ok());
 
     raw.map_or_else(
         || {
             tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "js_i18n_language_server=info".into())
+                .unwrap_or_else(|_| format!("js_i18n_language_server={default_level}").into())
         },
         |value| {
             let filter = if value.contains('=') {
@@ -102,10 +148,12 @@ fn resolve_env_filter(log_level_arg: Option<&str>) -> tracing_subscriber::EnvFil
 /// Initializes the logging system.
 ///
 /// Log file priority: `JS_I18N_LOG_FILE` env var > `--log-file` arg > stderr (default).
+/// The `check` command only logs warnings by default to keep its output readable.
 fn init_logging(args: &Args) {
     use std::fs::File;
 
-    let env_filter = resolve_env_filter(args.log_level.as_deref());
+    let default_level = if args.check.is_some() { "warn" } else { "info" };
+    let env_filter = resolve_env_filter(args.log_level.as_deref(), default_level);
 
     let log_file_path =
         std::env::var("JS_I18N_LOG_FILE").ok().map(PathBuf::from).or_else(|| args.log_file.clone());
@@ -126,11 +174,33 @@ fn init_logging(args: &Args) {
     }
 }
 
+/// Runs the `check` command and exits with its status.
+///
+/// Exit status: 0 without errors, 1 if error-severity diagnostics were found, 2 if the
+/// workspace could not be checked.
+#[allow(clippy::print_stdout, clippy::print_stderr)]
+async fn run_check(check: &CheckArgs) -> ! {
+    match js_i18n_language_server::check::run(&check.path).await {
+        Ok(report) => {
+            print!("{}", js_i18n_language_server::check::render(&report, check.format));
+            std::process::exit(i32::from(report.has_errors()));
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            std::process::exit(2);
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() {
     let args = parse_args();
     init_logging(&args);
 
+    if let Some(check) = &args.check {
+        run_check(check).await;
+    }
+
     tracing::info!(version = env!("CARGO_PKG_VERSION"), "Starting js-i18n-language-server");
 
     let config_manager = Arc::new(Mutex::new(ConfigManager::new()));
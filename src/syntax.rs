@@ -1,6 +1,9 @@
 pub mod analyzer;
+pub mod hardcoded;
+pub mod parsed;
 pub mod position_map;
 pub mod svelte;
+pub mod tree_cache;
 pub mod vue;
 
 use std::borrow::Cow;
@@ -61,24 +64,46 @@ pub fn analyze_source(
     let tree_sitter_lang = language.tree_sitter_language();
     let queries = analyzer::query_loader::load_queries(language);
 
-    let trans_fn_calls = analyzer::extractor::analyze_trans_fn_calls(
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&tree_sitter_lang).is_err() {
+        return Vec::new();
+    }
+    // Virtual documents of embedded languages are rebuilt on every change, so edits to the
+    // original text don't apply to their trees.
+    let tree = if preprocessed.position_map.is_none() {
+        db.syntax_trees().parse(file.uri(db), &preprocessed.source, &mut parser)
+    } else {
+        parser.parse(preprocessed.source.as_ref(), None)
+    };
+    let Some(tree) = tree else {
+        return Vec::new();
+    };
+
+    let trans_fn_calls = analyzer::extractor::analyze_trans_fn_calls_in_tree(
+        &tree,
         &preprocessed.source,
-        &tree_sitter_lang,
         language,
         queries,
         &key_separator,
-    )
-    .unwrap_or_default();
+    );
 
     trans_fn_calls
         .into_iter()
         .map(|call| {
             let key = TransKey::new(db, call.key);
-            let range: SourceRange = preprocessed
-                .position_map
-                .as_ref()
-                .map_or_else(|| call.arg_key_node.into(), |pm| pm.remap(call.arg_key_node).into());
-            KeyUsage::new(db, key, range, call.namespace, call.namespaces)
+            let remap = |range| -> SourceRange {
+                preprocessed.position_map.as_ref().map_or(range, |pm| pm.remap(range)).into()
+            };
+            KeyUsage::new(
+                db,
+                key,
+                remap(call.arg_key_node),
+                call.definition_node.map(remap),
+                call.namespace,
+                call.namespaces,
+                call.dynamic,
+                call.interpolation_args,
+            )
         })
         .collect()
 }
@@ -122,6 +147,29 @@ mod tests {
         assert_that!(usages[0].key(&db).text(&db), eq("hello"));
     }
 
+    #[rstest]
+    fn analyze_source_tsx_react_intl() {
+        let db = I18nDatabaseImpl::default();
+        let source = r#"
+const messages = defineMessages({ title: { id: "app.title" } });
+function App() {
+  const intl = useIntl();
+  intl.formatMessage({ id: "app.greeting" });
+  return <FormattedMessage id="app.footer" />;
+}
+"#;
+        let file = SourceFile::new(
+            &db,
+            "app.tsx".to_string(),
+            source.to_string(),
+            ProgrammingLanguage::Tsx,
+        );
+
+        let usages = analyze_source(&db, file, ".".to_string());
+        let keys: Vec<_> = usages.iter().map(|u| u.key(&db).text(&db).clone()).collect();
+        assert_that!(keys, elements_are![eq("app.title"), eq("app.greeting"), eq("app.footer")]);
+    }
+
     #[rstest]
     fn analyze_source_svelte_script_block() {
         let db = I18nDatabaseImpl::default();
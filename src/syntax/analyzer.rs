@@ -1,3 +1,4 @@
+pub mod constants;
 pub mod extractor;
 pub mod query_loader;
 pub mod scope;
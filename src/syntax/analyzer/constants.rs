@@ -0,0 +1,352 @@
+//! Same-file string constants used as translation keys.
+//!
+//! Resolves `t(KEY)`, `t(KEYS.save)` and `t(ErrorKey.NotFound)` to the string literal that
+//! defines the key, so that the usage points at the definition. Only module-level declarations
+//! are resolved, and only where no enclosing scope declares the same name.
+
+use std::collections::HashMap;
+
+use tree_sitter::Node;
+
+/// String constants declared in a file, keyed by access path (e.g., `KEYS.save`).
+#[derive(Debug, Default)]
+pub struct ConstantTable<'tree> {
+    /// `None` marks paths declared more than once, which are never resolved.
+    constants: HashMap<String, Option<Node<'tree>>>,
+}
+
+impl<'tree> ConstantTable<'tree> {
+    /// Collects string constants from module-level `const` declarations, object literals nested
+    /// in them (with or without `as const`) and string enums, exported or not.
+    #[must_use]
+    pub fn collect(root: Node<'tree>, source_bytes: &[u8]) -> Self {
+        let mut table = Self::default();
+        let mut cursor = root.walk();
+        for statement in root.named_children(&mut cursor) {
+            let declaration = if statement.kind() == "export_statement" {
+                let Some(declaration) = statement.child_by_field_name("declaration") else {
+                    continue;
+                };
+                declaration
+            } else {
+                statement
+            };
+            match declaration.kind() {
+                "lexical_declaration" if is_const_declaration(declaration) => {
+                    table.collect_declaration(declaration, source_bytes);
+                }
+                "enum_declaration" => table.collect_enum(declaration, source_bytes),
+                _ => {}
+            }
+        }
+        table
+    }
+
+    /// Resolves an identifier or member expression to the string literal it refers to.
+    ///
+    /// Returns `None` when a function, block or other scope around `node` declares the
+    /// identifier the path starts with, shadowing the module-level constant.
+    #[must_use]
+    pub fn resolve(&self, node: Node<'_>, source_bytes: &[u8]) -> Option<Node<'tree>> {
+        if self.constants.is_empty() {
+            return None;
+        }
+        let path = access_path(node, source_bytes)?;
+        let constant = self.constants.get(&path).copied().flatten()?;
+        let name = path.split('.').next()?;
+        let shadowed = std::iter::successors(node.parent(), Node::parent)
+            .any(|scope| declares(scope, name, source_bytes));
+        (!shadowed).then_some(constant)
+    }
+
+    fn insert(&mut self, path: String, node: Node<'tree>) {
+        self.constants.entry(path).and_modify(|existing| *existing = None).or_insert(Some(node));
+    }
+
+    fn collect_declaration(&mut self, declaration: Node<'tree>, source_bytes: &[u8]) {
+        let mut cursor = declaration.walk();
+        for declarator in declaration.named_children(&mut cursor) {
+            if declarator.kind() != "variable_declarator" {
+                continue;
+            }
+            let Some(name) = declarator.child_by_field_name("name") else {
+                continue;
+            };
+            if name.kind() != "identifier" {
+                continue;
+            }
+            let (Some(name), Some(value)) =
+                (name.utf8_text(source_bytes).ok(), declarator.child_by_field_name("value"))
+            else {
+                continue;
+            };
+            self.collect_value(name.to_string(), value, source_bytes);
+        }
+    }
+
+    fn collect_value(&mut self, path: String, value: Node<'tree>, source_bytes: &[u8]) {
+        match value.kind() {
+            "string" => self.insert(path, value),
+            // `{ ... } as const`, `{ ... } satisfies T`, `({ ... })`
+            "as_expression" | "satisfies_expression" | "parenthesized_expression" => {
+                if let Some(inner) = value.named_child(0) {
+                    self.collect_value(path, inner, source_bytes);
+                }
+            }
+            "object" => {
+                let mut cursor = value.walk();
+                for pair in value.named_children(&mut cursor) {
+                    if pair.kind() != "pair" {
+                        continue;
+                    }
+                    let (Some(key), Some(pair_value)) = (
+                        pair.child_by_field_name("key")
+                            .and_then(|k| property_name(k, source_bytes)),
+                        pair.child_by_field_name("value"),
+                    ) else {
+                        continue;
+                    };
+                    self.collect_value(format!("{path}.{key}"), pair_value, source_bytes);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn collect_enum(&mut self, declaration: Node<'tree>, source_bytes: &[u8]) {
+        let (Some(name), Some(body)) = (
+            declaration.child_by_field_name("name").and_then(|n| n.utf8_text(source_bytes).ok()),
+            declaration.child_by_field_name("body"),
+        ) else {
+            return;
+        };
+
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            if member.kind() != "enum_assignment" {
+                continue;
+            }
+            let (Some(member_name), Some(value)) = (
+                member.child_by_field_name("name").and_then(|n| property_name(n, source_bytes)),
+                member.child_by_field_name("value"),
+            ) else {
+                continue;
+            };
+            if value.kind() == "string" {
+                self.insert(format!("{name}.{member_name}"), value);
+            }
+        }
+    }
+}
+
+/// Whether `scope` declares `name` for the code nested in it. The module scope is not checked.
+fn declares(scope: Node<'_>, name: &str, source_bytes: &[u8]) -> bool {
+    let named = |node: Node<'_>, field: &str| {
+        node.child_by_field_name(field).is_some_and(|pattern| binds(pattern, name, source_bytes))
+    };
+    match scope.kind() {
+        "statement_block" | "switch_case" | "switch_default" | "class_static_block" => {
+            let mut cursor = scope.walk();
+            scope.named_children(&mut cursor).any(|statement| match statement.kind() {
+                "lexical_declaration" | "variable_declaration" => {
+                    let mut cursor = statement.walk();
+                    statement
+                        .named_children(&mut cursor)
+                        .any(|declarator| named(declarator, "name"))
+                }
+                "function_declaration"
+                | "generator_function_declaration"
+                | "class_declaration"
+                | "enum_declaration" => named(statement, "name"),
+                _ => false,
+            })
+        }
+        "function_declaration"
+        | "function_expression"
+        | "generator_function_declaration"
+        | "generator_function"
+        | "arrow_function"
+        | "method_definition" => {
+            named(scope, "parameters")
+                || named(scope, "parameter")
+                || (scope.kind() != "function_declaration" && named(scope, "name"))
+        }
+        "for_statement" => named(scope, "initializer"),
+        "for_in_statement" => named(scope, "left"),
+        "catch_clause" => named(scope, "parameter"),
+        _ => false,
+    }
+}
+
+/// Whether a binding pattern, parameter list or declaration introduces `name`.
+fn binds(pattern: Node<'_>, name: &str, source_bytes: &[u8]) -> bool {
+    let binds_field = |field: &str| {
+        pattern.child_by_field_name(field).is_some_and(|node| binds(node, name, source_bytes))
+    };
+    match pattern.kind() {
+        "identifier" | "shorthand_property_identifier_pattern" => {
+            pattern.utf8_text(source_bytes).is_ok_and(|text| text == name)
+        }
+        "variable_declarator" => binds_field("name"),
+        "required_parameter" | "optional_parameter" => binds_field("pattern"),
+        "pair_pattern" => binds_field("value"),
+        "assignment_pattern" | "object_assignment_pattern" => binds_field("left"),
+        "formal_parameters"
+        | "object_pattern"
+        | "array_pattern"
+        | "rest_pattern"
+        | "lexical_declaration"
+        | "variable_declaration" => {
+            let mut cursor = pattern.walk();
+            pattern.named_children(&mut cursor).any(|child| binds(child, name, source_bytes))
+        }
+        _ => false,
+    }
+}
+
+fn is_const_declaration(declaration: Node<'_>) -> bool {
+    declaration.child(0).is_some_and(|keyword| keyword.kind() == "const")
+}
+
+/// Returns the name of an object key or enum member (`save`, `'save'`).
+fn property_name(node: Node<'_>, source_bytes: &[u8]) -> Option<String> {
+    match node.kind() {
+        "property_identifier" | "identifier" => node.utf8_text(source_bytes).ok().map(String::from),
+        "string" => string_content(node, source_bytes),
+        _ => None,
+    }
+}
+
+fn string_content(node: Node<'_>, source_bytes: &[u8]) -> Option<String> {
+    // An empty string literal has no fragment
+    node.named_child(0).map_or_else(
+        || Some(String::new()),
+        |fragment| fragment.utf8_text(source_bytes).ok().map(String::from),
+    )
+}
+
+/// Builds the access path of `KEYS`, `KEYS.save` or `KEYS["save"]`.
+fn access_path(node: Node<'_>, source_bytes: &[u8]) -> Option<String> {
+    match node.kind() {
+        "identifier" => node.utf8_text(source_bytes).ok().map(String::from),
+        "member_expression" => {
+            let object = access_path(node.child_by_field_name("object")?, source_bytes)?;
+            let property = node.child_by_field_name("property")?.utf8_text(source_bytes).ok()?;
+            Some(format!("{object}.{property}"))
+        }
+        "subscript_expression" => {
+            let object = access_path(node.child_by_field_name("object")?, source_bytes)?;
+            let index = node.child_by_field_name("index")?;
+            (index.kind() == "string")
+                .then(|| string_content(index, source_bytes))
+                .flatten()
+                .map(|property| format!("{object}.{property}"))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+    use tree_sitter::Parser;
+
+    use super::*;
+
+    fn resolve_arg(source: &str) -> Option<String> {
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_typescript::LANGUAGE_TSX.into()).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let source_bytes = source.as_bytes();
+        let table = ConstantTable::collect(tree.root_node(), source_bytes);
+
+        // The last statement is the expression to resolve
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        let expression = root.named_children(&mut cursor).last()?.named_child(0)?;
+        table
+            .resolve(expression, source_bytes)
+            .and_then(|node| node.utf8_text(source_bytes).ok().map(String::from))
+    }
+
+    #[rstest]
+    #[case::const_string("const KEY = 'common.save';\nKEY;", "'common.save'")]
+    #[case::object("const KEYS = { save: 'common.save' };\nKEYS.save;", "'common.save'")]
+    #[case::as_const(
+        "const KEYS = { save: \"common.save\" } as const;\nKEYS.save;",
+        "\"common.save\""
+    )]
+    #[case::nested(
+        "const KEYS = { form: { submit: 'form.submit' } } as const;\nKEYS.form.submit;",
+        "'form.submit'"
+    )]
+    #[case::subscript("const KEYS = { 'save': 'common.save' };\nKEYS['save'];", "'common.save'")]
+    #[case::string_enum(
+        "enum ErrorKey { NotFound = 'errors.notFound' }\nErrorKey.NotFound;",
+        "'errors.notFound'"
+    )]
+    fn resolves_constants(#[case] source: &str, #[case] expected: &str) {
+        assert_that!(resolve_arg(source), some(eq(expected)));
+    }
+
+    #[rstest]
+    #[case::let_binding("let KEY = 'common.save';\nKEY;")]
+    #[case::numeric_enum("enum Code { NotFound = 404 }\nCode.NotFound;")]
+    #[case::unknown("const KEYS = { save: 'common.save' };\nKEYS.cancel;")]
+    #[case::inner_declaration("function f() { const KEY = 'common.save'; }\nKEY;")]
+    fn ignores_unresolvable(#[case] source: &str) {
+        assert_that!(resolve_arg(source), none());
+    }
+
+    #[rstest]
+    #[case::exported("export const KEY = 'common.save';\nKEY;")]
+    #[case::shadowed_elsewhere(
+        "const KEY = 'common.save';\nfunction f() { const KEY = 'b'; }\nKEY;"
+    )]
+    fn resolves_module_level_constants(#[case] source: &str) {
+        assert_that!(resolve_arg(source), some(eq("'common.save'")));
+    }
+
+    /// Resolves the argument of the `t` call in `source`.
+    fn resolve_call_arg(source: &str) -> Option<String> {
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_typescript::LANGUAGE_TSX.into()).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let source_bytes = source.as_bytes();
+        let table = ConstantTable::collect(tree.root_node(), source_bytes);
+
+        let offset = source.rfind("t(").unwrap();
+        let call = tree.root_node().named_descendant_for_byte_range(offset, offset)?.parent()?;
+        let argument = call.child_by_field_name("arguments")?.named_child(0)?;
+        table
+            .resolve(argument, source_bytes)
+            .and_then(|node| node.utf8_text(source_bytes).ok().map(String::from))
+    }
+
+    #[rstest]
+    #[case::block_const("{ const KEY = 'x'; t(KEY); }")]
+    #[case::parameter("function f(KEY) { t(KEY); }")]
+    #[case::destructured_parameter("const f = ({ KEY }) => t(KEY);")]
+    #[case::arrow_parameter("const f = KEY => t(KEY);")]
+    #[case::renamed_destructuring("function f(o) { const { a: KEY } = o; t(KEY); }")]
+    #[case::for_of("for (const KEY of keys) { t(KEY); }")]
+    #[case::catch("try {} catch (KEY) { t(KEY); }")]
+    #[case::member_of_shadowed_object("function f(KEYS) { t(KEYS.save); }")]
+    fn ignores_shadowed_identifiers(#[case] body: &str) {
+        let source =
+            format!("const KEY = 'common.save';\nconst KEYS = {{ save: 'common.save' }};\n{body}");
+        assert_that!(resolve_call_arg(&source), none());
+    }
+
+    #[rstest]
+    #[case::function("function f() { t(KEY); }")]
+    #[case::other_name("function f(key) { const other = 'x'; t(KEY); }")]
+    #[case::member("function f() { t(KEYS.save); }")]
+    fn resolves_unshadowed_identifiers_in_inner_scopes(#[case] body: &str) {
+        let source =
+            format!("const KEY = 'common.save';\nconst KEYS = {{ save: 'common.save' }};\n{body}");
+        assert_that!(resolve_call_arg(&source), some(eq("'common.save'")));
+    }
+}
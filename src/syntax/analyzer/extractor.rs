@@ -13,10 +13,16 @@ use tree_sitter::{
     Query,
     QueryCursor,
     StreamingIteratorMut,
+    Tree,
 };
 
 use crate::framework::FrameworkConfig;
+use crate::ide::key_match::{
+    KEY_WILDCARD,
+    is_key_pattern,
+};
 use crate::input::source::ProgrammingLanguage;
+use crate::syntax::analyzer::constants::ConstantTable;
 use crate::syntax::analyzer::scope::{
     ScopeInfo,
     Scopes,
@@ -191,11 +197,23 @@ pub fn analyze_trans_fn_calls(
     queries: &[Query],
     key_separator: &str,
 ) -> Result<Vec<TransFnCall>, AnalyzerError> {
-    let config = FrameworkConfig::for_language(programming_language);
     let mut parser = Parser::new();
     parser.set_language(language).map_err(AnalyzerError::LanguageSetup)?;
     let tree = parser.parse(source, None).ok_or(AnalyzerError::ParseFailed)?;
 
+    Ok(analyze_trans_fn_calls_in_tree(&tree, source, programming_language, queries, key_separator))
+}
+
+/// Extracts translation function calls from an already parsed syntax tree of `source`.
+#[must_use]
+pub fn analyze_trans_fn_calls_in_tree(
+    tree: &Tree,
+    source: &str,
+    programming_language: ProgrammingLanguage,
+    queries: &[Query],
+    key_separator: &str,
+) -> Vec<TransFnCall> {
+    let config = FrameworkConfig::for_language(programming_language);
     let source_bytes = source.as_bytes();
 
     let mut calls = Vec::new();
@@ -207,6 +225,7 @@ pub fn analyze_trans_fn_calls(
     scopes.push_scope("t".to_string(), ScopeInfo::new(root_node, GetTransFnDetail::new("t")));
 
     let all_captures = collect_and_sort_captures(queries, root_node, source_bytes);
+    let constants = ConstantTable::collect(root_node, source_bytes);
 
     for (query_idx, capture_name, node, _) in all_captures {
         let Some(query) = queries.get(query_idx) else {
@@ -239,6 +258,7 @@ pub fn analyze_trans_fn_calls(
                     source_bytes,
                     cap_names,
                     key_separator,
+                    &constants,
                 ) else {
                     continue;
                 };
@@ -269,7 +289,16 @@ pub fn analyze_trans_fn_calls(
 
                 let namespaces = current_scope.and_then(|s| s.trans_fn.namespaces.clone());
 
-                let arg_key_node = call_trans_fn.arg_key_node;
+                let arg_key_range = call_trans_fn
+                    .arg_key_range
+                    .unwrap_or_else(|| get_node_range(call_trans_fn.arg_key_node));
+
+                // Different libraries' queries can match the same key (e.g., `defineMessages`
+                // in both svelte-i18n and react-intl), so keep only the first usage per call-site
+                // range. Calls sharing a constant key have distinct ranges and are all kept.
+                if calls.iter().any(|c: &TransFnCall| c.arg_key_node == arg_key_range) {
+                    continue;
+                }
 
                 calls.push(TransFnCall {
                     key: key_prefix.as_ref().map_or_else(
@@ -277,19 +306,20 @@ pub fn analyze_trans_fn_calls(
                         |prefix| format!("{}{}{}", prefix, key_separator, &call_trans_fn.key),
                     ),
                     arg_key: call_trans_fn.key.clone(),
-                    arg_key_node: call_trans_fn
-                        .arg_key_range
-                        .unwrap_or_else(|| get_node_range(arg_key_node)),
+                    arg_key_node: arg_key_range,
+                    definition_node: call_trans_fn.definition_node.map(get_node_range),
                     key_prefix,
                     namespace,
                     namespaces,
+                    dynamic: call_trans_fn.dynamic,
+                    interpolation_args: call_trans_fn.interpolation_args,
                 });
             }
             _ => {}
         }
     }
 
-    Ok(calls)
+    calls
 }
 
 /// Pops scopes that the current node has exited from
@@ -559,6 +589,7 @@ fn parse_get_trans_fn_captures(
 /// * `capture_node` - The node to analyze for captures
 /// * `source_bytes` - Source code as bytes for text extraction
 /// * `cap_names` - Capture names from the query
+/// * `constants` - String constants that non-literal key arguments resolve to
 ///
 /// # Errors
 /// Returns `AnalyzerError::ParseFailed` if required captures are missing
@@ -568,6 +599,7 @@ fn parse_call_trans_fn_captures<'a>(
     source_bytes: &[u8],
     cap_names: &[&str],
     key_separator: &str,
+    constants: &ConstantTable<'a>,
 ) -> Result<CallTransFnDetail<'a>, AnalyzerError> {
     let mut trans_fn_name: Option<String> = None;
     let mut key: Option<String> = None;
@@ -636,6 +668,9 @@ fn parse_call_trans_fn_captures<'a>(
             arg_key_node: selector_node,
             explicit_namespace,
             arg_key_range: Some(extended_range),
+            definition_node: None,
+            dynamic: false,
+            interpolation_args: extract_interpolation_args(selector_node, source_bytes),
         });
     }
 
@@ -649,8 +684,24 @@ fn parse_call_trans_fn_captures<'a>(
 
         if inner.is_empty() {
             args_node
+        } else if let Some((first_arg, (key, definition_node, dynamic))) =
+            args_node.named_child(0).and_then(|arg| {
+                Some((arg, resolve_non_literal_key(arg, source_bytes, key_separator, constants)?))
+            })
+        {
+            return Ok(CallTransFnDetail {
+                trans_fn_name: trans_fn_name.unwrap_or_else(|| "t".to_string()),
+                key,
+                key_node: first_arg,
+                arg_key_node: first_arg,
+                explicit_namespace,
+                arg_key_range: None,
+                definition_node,
+                dynamic,
+                interpolation_args: extract_interpolation_args(first_arg, source_bytes),
+            });
         } else {
-            // Non-string arguments like t(someVar) are invalid
+            // Other non-string arguments like t(someVar) are invalid
             return Err(AnalyzerError::ParseFailed);
         }
     } else {
@@ -664,9 +715,242 @@ fn parse_call_trans_fn_captures<'a>(
         arg_key_node,
         explicit_namespace,
         arg_key_range: None,
+        definition_node: None,
+        dynamic: false,
+        interpolation_args: extract_interpolation_args(arg_key_node, source_bytes),
     })
 }
 
+/// Collects the keys of the interpolation values passed with the key.
+///
+/// - `t('key', { name })`: the options object that follows the key argument
+/// - `formatMessage({ id: 'key' }, { name })` and `$_({ id: 'key', values: { name } })`: the
+///   values of a message descriptor
+/// - `<FormattedMessage id="key" values={{ name }} />`: the `values` attribute
+///
+/// Interpolation values nested under `values` (svelte-i18n) or `replace` (i18next) are
+/// flattened into the result. Returns `None` when the values can't be determined statically:
+/// message definitions such as `defineMessage({ id: 'key' })`, values that are not object
+/// literals, or objects with spread or computed properties.
+fn extract_interpolation_args(key_arg: Node<'_>, source_bytes: &[u8]) -> Option<Vec<String>> {
+    let parent = key_arg.parent()?;
+    match parent.kind() {
+        "arguments" => next_argument_keys(key_arg, source_bytes),
+        "pair" => descriptor_values(parent.parent()?, source_bytes),
+        "jsx_attribute" => formatted_message_values(parent.parent()?, source_bytes),
+        "jsx_expression" => formatted_message_values(parent.parent()?.parent()?, source_bytes),
+        _ => None,
+    }
+}
+
+/// Collects the keys of the object literal passed after `arg`.
+fn next_argument_keys(arg: Node<'_>, source_bytes: &[u8]) -> Option<Vec<String>> {
+    match arg.next_named_sibling() {
+        None => Some(Vec::new()),
+        Some(options) if options.kind() == "object" => object_keys(options, source_bytes),
+        Some(_) => None,
+    }
+}
+
+/// Collects the values of a message descriptor passed to a translation function.
+fn descriptor_values(descriptor: Node<'_>, source_bytes: &[u8]) -> Option<Vec<String>> {
+    let arguments = descriptor.parent().filter(|node| node.kind() == "arguments")?;
+    let function = arguments.parent()?.child_by_field_name("function")?;
+    if extract_node_text(function, source_bytes)? == "defineMessage" {
+        return None;
+    }
+
+    let mut cursor = descriptor.walk();
+    let mut values = None;
+    for property in descriptor.named_children(&mut cursor) {
+        match property.kind() {
+            // `{ id, values }` and `{ id, ...opts }` pass values that can't be read here
+            "spread_element" => return None,
+            "shorthand_property_identifier"
+                if extract_node_text(property, source_bytes)
+                    .is_some_and(|name| name == "values") =>
+            {
+                return None;
+            }
+            "pair"
+                if property
+                    .child_by_field_name("key")
+                    .and_then(|key| extract_node_text(key, source_bytes))
+                    .is_some_and(|key| key == "values") =>
+            {
+                values = property.child_by_field_name("value");
+            }
+            _ => {}
+        }
+    }
+    match values {
+        Some(values) if values.kind() == "object" => object_keys(values, source_bytes),
+        Some(_) => None,
+        None => next_argument_keys(descriptor, source_bytes),
+    }
+}
+
+/// Collects the keys of the `values` attribute of a react-intl `<FormattedMessage>` element.
+///
+/// Other components such as react-i18next's `<Trans>` also take values from their children,
+/// so they are not checked.
+fn formatted_message_values(element: Node<'_>, source_bytes: &[u8]) -> Option<Vec<String>> {
+    let name = element.child_by_field_name("name")?;
+    if extract_node_text(name, source_bytes)? != "FormattedMessage" {
+        return None;
+    }
+
+    let mut cursor = element.walk();
+    let mut values = None;
+    for attribute in element.children_by_field_name("attribute", &mut cursor) {
+        if attribute.kind() != "jsx_attribute" {
+            // `{...props}` may pass `values`
+            return None;
+        }
+        let is_values = attribute
+            .named_child(0)
+            .and_then(|name| extract_node_text(name, source_bytes))
+            .is_some_and(|name| name == "values");
+        if is_values {
+            values = Some(attribute.named_child(1)?);
+        }
+    }
+
+    match values.map(|value| (value.kind(), value.named_child(0))) {
+        None => Some(Vec::new()),
+        Some(("jsx_expression", Some(object))) if object.kind() == "object" => {
+            object_keys(object, source_bytes)
+        }
+        Some(_) => None,
+    }
+}
+
+fn object_keys(object: Node<'_>, source_bytes: &[u8]) -> Option<Vec<String>> {
+    let mut keys = Vec::new();
+    let mut cursor = object.walk();
+
+    for property in object.named_children(&mut cursor) {
+        match property.kind() {
+            "shorthand_property_identifier" => {
+                keys.push(extract_node_text(property, source_bytes)?);
+            }
+            "pair" | "method_definition" => {
+                let field = if property.kind() == "pair" { "key" } else { "name" };
+                let name_node = property.child_by_field_name(field)?;
+                let name = match name_node.kind() {
+                    "property_identifier" => extract_node_text(name_node, source_bytes)?,
+                    "string" => name_node
+                        .named_child(0)
+                        .and_then(|fragment| extract_node_text(fragment, source_bytes))
+                        .unwrap_or_default(),
+                    _ => return None,
+                };
+
+                let nested = property.child_by_field_name("value").filter(|value| {
+                    value.kind() == "object" && matches!(name.as_str(), "values" | "replace")
+                });
+                match nested {
+                    Some(value) => keys.extend(object_keys(value, source_bytes)?),
+                    None => keys.push(name),
+                }
+            }
+            "comment" => {}
+            _ => return None,
+        }
+    }
+
+    Some(keys)
+}
+
+/// Resolves a key argument that is not a string literal.
+///
+/// Returns the key, the string literal defining it if any, and whether the key is a pattern:
+/// - Constants like `t(KEYS.save)` resolve to the string literal that defines the key
+/// - Template literals and concatenations like `` t(`errors.${code}`) `` become patterns
+fn resolve_non_literal_key<'a>(
+    arg: Node<'a>,
+    source_bytes: &[u8],
+    key_separator: &str,
+    constants: &ConstantTable<'a>,
+) -> Option<(String, Option<Node<'a>>, bool)> {
+    if let Some(definition) = constants.resolve(arg, source_bytes) {
+        let key = definition
+            .named_child(0)
+            .and_then(|fragment| extract_node_text(fragment, source_bytes))
+            .unwrap_or_default();
+        return Some((key, Some(definition), false));
+    }
+
+    let pattern = extract_dynamic_key(arg, source_bytes, key_separator)?;
+    let dynamic = is_key_pattern(&pattern);
+    Some((pattern, None, dynamic))
+}
+
+/// Builds a key pattern from a template literal or string concatenation.
+///
+/// Static parts are kept and every dynamic part becomes [`KEY_WILDCARD`]:
+/// - `` `errors.${code}` `` -> `errors.*`
+/// - `'status.' + kind + '.label'` -> `status.*.label`
+///
+/// Returns `None` for other expressions and for patterns without any static key segment
+/// (e.g., `` `${ns}.${key}` ``), which would match every key.
+fn extract_dynamic_key(node: Node<'_>, source_bytes: &[u8], key_separator: &str) -> Option<String> {
+    if !matches!(node.kind(), "template_string" | "binary_expression" | "parenthesized_expression")
+    {
+        return None;
+    }
+
+    let mut pattern = String::new();
+    append_key_pattern(node, source_bytes, &mut pattern)?;
+
+    let has_static_segment = pattern
+        .split(KEY_WILDCARD)
+        .any(|part| !part.trim_matches(|c| key_separator.contains(c)).is_empty());
+    has_static_segment.then_some(pattern)
+}
+
+/// Appends the pattern for `node` to `pattern`, collapsing adjacent wildcards.
+fn append_key_pattern(node: Node<'_>, source_bytes: &[u8], pattern: &mut String) -> Option<()> {
+    match node.kind() {
+        "string" => {
+            if let Some(fragment) = node.named_child(0) {
+                pattern.push_str(fragment.utf8_text(source_bytes).ok()?);
+            }
+        }
+        "template_string" => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                if child.kind() == "template_substitution" {
+                    push_wildcard(pattern);
+                } else {
+                    pattern.push_str(child.utf8_text(source_bytes).ok()?);
+                }
+            }
+        }
+        "binary_expression" => {
+            let operator = node.child_by_field_name("operator")?;
+            if operator.kind() != "+" {
+                push_wildcard(pattern);
+                return Some(());
+            }
+            append_key_pattern(node.child_by_field_name("left")?, source_bytes, pattern)?;
+            append_key_pattern(node.child_by_field_name("right")?, source_bytes, pattern)?;
+        }
+        "parenthesized_expression" => {
+            append_key_pattern(node.named_child(0)?, source_bytes, pattern)?;
+        }
+        _ => push_wildcard(pattern),
+    }
+
+    Some(())
+}
+
+fn push_wildcard(pattern: &mut String) {
+    if !pattern.ends_with(KEY_WILDCARD) {
+        pattern.push(KEY_WILDCARD);
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::indexing_slicing, clippy::expect_used, clippy::panic)]
 mod tests {
@@ -724,6 +1008,14 @@ mod tests {
             .collect()
     }
 
+    #[fixture]
+    fn react_intl_queries(tsx_lang: Language) -> Vec<Query> {
+        vec![
+            Query::new(&tsx_lang, include_str!("../../../queries/tsx/react-intl.scm"))
+                .unwrap_or_else(|e| panic!("Failed to parse react-intl query: {e}")),
+        ]
+    }
+
     #[fixture]
     fn ts_lang() -> Language {
         tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()
@@ -1039,11 +1331,14 @@ mod tests {
             // Invalid: numeric argument
             t(123);
 
-            // Invalid: variable argument
+            // Invalid: unresolvable variable argument
+            t(someVariable);
+
+            // Constant: resolved to its definition
             const key = "variable.key";
             t(key);
 
-            // Invalid: template string
+            // Dynamic: template string
             t(`template.${key}`);
             "#;
 
@@ -1051,8 +1346,14 @@ mod tests {
             analyze_trans_fn_calls(code, &js_lang, ProgrammingLanguage::JavaScript, &queries, ".")
                 .unwrap();
 
-        // Only string literals are valid
-        assert_that!(calls, elements_are![field!(TransFnCall.key, eq("valid.key"))]);
+        assert_that!(
+            calls,
+            elements_are![
+                field!(TransFnCall.key, eq("valid.key")),
+                field!(TransFnCall.key, eq("variable.key")),
+                field!(TransFnCall.key, eq("template.*"))
+            ]
+        );
     }
 
     // Table-driven tests for argument patterns
@@ -1138,13 +1439,13 @@ mod tests {
     }
 
     #[rstest]
-    #[case::template_literal(r"t(`template.${variable}`)")]
+    #[case::template_literal_without_static_segment(r"t(`${ns}.${key}`)")]
+    #[case::concatenation_without_static_segment(r#"t(ns + "." + key)"#)]
     #[case::variable(r"t(someVariable)")]
     #[case::number(r"t(123)")]
     #[case::object(r#"t({ key: "value" })"#)]
     #[case::array(r#"t(["array", "item"])"#)]
     #[case::function_call(r"t(getKey())")]
-    #[case::expression(r#"t("prefix" + "suffix")"#)]
     fn test_invalid_first_argument_patterns(
         queries: Vec<Query>,
         js_lang: Language,
@@ -1164,6 +1465,121 @@ mod tests {
         assert_that!(calls, is_empty());
     }
 
+    #[rstest]
+    #[case::template_literal(r"t(`errors.${code}`)", "errors.*", true)]
+    #[case::template_literal_middle(r"t(`status.${kind}.label`)", "status.*.label", true)]
+    #[case::adjacent_substitutions(r"t(`errors.${a}${b}`)", "errors.*", true)]
+    #[case::concatenation(r"t('status.' + kind)", "status.*", true)]
+    #[case::concatenation_suffix(r"t('status.' + kind + '.label')", "status.*.label", true)]
+    #[case::concatenation_call(r"t('errors.' + getCode())", "errors.*", true)]
+    #[case::parenthesized(r"t(('errors.' + code))", "errors.*", true)]
+    #[case::static_template_literal(r"t(`plain.key`)", "plain.key", false)]
+    #[case::static_concatenation(r#"t("prefix" + ".suffix")"#, "prefix.suffix", false)]
+    fn test_dynamic_key_patterns(
+        queries: Vec<Query>,
+        js_lang: Language,
+        #[case] t_call: &str,
+        #[case] expected_key: &str,
+        #[case] expected_dynamic: bool,
+    ) {
+        let code = format!(
+            r"
+            const {{ t }} = useTranslation();
+            const message = {t_call};
+            "
+        );
+
+        let calls =
+            analyze_trans_fn_calls(&code, &js_lang, ProgrammingLanguage::JavaScript, &queries, ".")
+                .unwrap_or_else(|_| panic!("Failed to parse code for test case"));
+
+        assert_that!(
+            calls,
+            elements_are![all![
+                field!(TransFnCall.key, eq(expected_key)),
+                field!(TransFnCall.dynamic, eq(&expected_dynamic))
+            ]]
+        );
+    }
+
+    #[rstest]
+    fn test_dynamic_key_with_key_prefix(queries: Vec<Query>, js_lang: Language) {
+        let code = r#"
+            const { t } = useTranslation("common", { keyPrefix: "errors" });
+            t(`http.${status}`);
+            "#;
+
+        let calls =
+            analyze_trans_fn_calls(code, &js_lang, ProgrammingLanguage::JavaScript, &queries, ".")
+                .unwrap();
+
+        assert_that!(
+            calls,
+            elements_are![all![
+                field!(TransFnCall.key, eq("errors.http.*")),
+                field!(TransFnCall.arg_key, eq("http.*")),
+                field!(TransFnCall.dynamic, eq(&true))
+            ]]
+        );
+    }
+
+    #[rstest]
+    fn test_constant_keys_keep_call_sites(tsx_queries: Vec<Query>, tsx_lang: Language) {
+        let code = r#"const KEYS = { save: "common.save" } as const;
+enum ErrorKey {
+  NotFound = "errors.notFound",
+}
+const { t } = useTranslation();
+t(KEYS.save);
+t(ErrorKey.NotFound);
+t(KEYS.save);
+t(unknownKey);
+"#;
+
+        let calls =
+            analyze_trans_fn_calls(code, &tsx_lang, ProgrammingLanguage::Tsx, &tsx_queries, ".")
+                .unwrap();
+
+        assert_that!(
+            calls,
+            elements_are![
+                all![
+                    field!(TransFnCall.key, eq("common.save")),
+                    field!(
+                        TransFnCall.arg_key_node,
+                        eq(&Range::new(Position::new(5, 2), Position::new(5, 11)))
+                    ),
+                    field!(
+                        TransFnCall.definition_node,
+                        some(eq(&Range::new(Position::new(0, 21), Position::new(0, 34))))
+                    )
+                ],
+                all![
+                    field!(TransFnCall.key, eq("errors.notFound")),
+                    field!(
+                        TransFnCall.arg_key_node,
+                        eq(&Range::new(Position::new(6, 2), Position::new(6, 19)))
+                    ),
+                    field!(
+                        TransFnCall.definition_node,
+                        some(eq(&Range::new(Position::new(2, 13), Position::new(2, 30))))
+                    )
+                ],
+                all![
+                    field!(TransFnCall.key, eq("common.save")),
+                    field!(
+                        TransFnCall.arg_key_node,
+                        eq(&Range::new(Position::new(7, 2), Position::new(7, 11)))
+                    ),
+                    field!(
+                        TransFnCall.definition_node,
+                        some(eq(&Range::new(Position::new(0, 21), Position::new(0, 34))))
+                    )
+                ]
+            ]
+        );
+    }
+
     #[rstest]
     fn test_complex_nested_structure(queries: Vec<Query>, js_lang: Language) {
         let code = r#"
@@ -1927,6 +2343,173 @@ function Component() {
         );
     }
 
+    // --- react-intl ---
+
+    #[rstest]
+    fn react_intl_use_intl_format_message(react_intl_queries: Vec<Query>, tsx_lang: Language) {
+        let code = r#"
+            const intl = useIntl();
+            const msg = intl.formatMessage({ id: "app.greeting", defaultMessage: "Hello" });
+        "#;
+        let calls = analyze_trans_fn_calls(
+            code,
+            &tsx_lang,
+            ProgrammingLanguage::Tsx,
+            &react_intl_queries,
+            ".",
+        )
+        .unwrap();
+
+        assert_that!(calls, elements_are![all![field!(TransFnCall.key, eq("app.greeting"))]]);
+    }
+
+    #[rstest]
+    #[case::shorthand("const { formatMessage } = useIntl();", "formatMessage")]
+    #[case::renamed("const { formatMessage: fm } = useIntl();", "fm")]
+    fn react_intl_destructured_format_message(
+        react_intl_queries: Vec<Query>,
+        tsx_lang: Language,
+        #[case] declaration: &str,
+        #[case] fn_name: &str,
+    ) {
+        let code = format!(r#"{declaration} {fn_name}({{ id: "app.title" }});"#);
+        let calls = analyze_trans_fn_calls(
+            &code,
+            &tsx_lang,
+            ProgrammingLanguage::Tsx,
+            &react_intl_queries,
+            ".",
+        )
+        .unwrap();
+
+        assert_that!(calls, elements_are![all![field!(TransFnCall.key, eq("app.title"))]]);
+    }
+
+    #[rstest]
+    #[case::intl(r#"intl.formatMessage({ id: "app.title" })"#)]
+    #[case::props(r#"props.intl.formatMessage({ id: "app.title" })"#)]
+    #[case::this_props(r#"this.props.intl.formatMessage({ id: "app.title" })"#)]
+    fn react_intl_injected_intl_global(
+        react_intl_queries: Vec<Query>,
+        tsx_lang: Language,
+        #[case] code: &str,
+    ) {
+        let calls = analyze_trans_fn_calls(
+            code,
+            &tsx_lang,
+            ProgrammingLanguage::Tsx,
+            &react_intl_queries,
+            ".",
+        )
+        .unwrap();
+
+        assert_that!(calls, elements_are![all![field!(TransFnCall.key, eq("app.title"))]]);
+    }
+
+    #[rstest]
+    fn react_intl_unknown_object_format_message_ignored(
+        react_intl_queries: Vec<Query>,
+        tsx_lang: Language,
+    ) {
+        let code = r#"formatter.formatMessage({ id: "app.title" })"#;
+        let calls = analyze_trans_fn_calls(
+            code,
+            &tsx_lang,
+            ProgrammingLanguage::Tsx,
+            &react_intl_queries,
+            ".",
+        )
+        .unwrap();
+
+        assert_that!(calls, is_empty());
+    }
+
+    #[rstest]
+    #[case::self_closing(r#"<FormattedMessage id="app.title" defaultMessage="Title" />"#)]
+    #[case::jsx_expression(r#"<FormattedMessage id={"app.title"} />"#)]
+    #[case::opening(
+        r#"<FormattedMessage id="app.title">{(txt) => <h1>{txt}</h1>}</FormattedMessage>"#
+    )]
+    fn react_intl_formatted_message(
+        react_intl_queries: Vec<Query>,
+        tsx_lang: Language,
+        #[case] code: &str,
+    ) {
+        let calls = analyze_trans_fn_calls(
+            code,
+            &tsx_lang,
+            ProgrammingLanguage::Tsx,
+            &react_intl_queries,
+            ".",
+        )
+        .unwrap();
+
+        assert_that!(calls, elements_are![all![field!(TransFnCall.key, eq("app.title"))]]);
+    }
+
+    #[rstest]
+    fn react_intl_define_messages(react_intl_queries: Vec<Query>, tsx_lang: Language) {
+        let code = r#"
+            const messages = defineMessages({
+                greeting: { id: "app.greeting", defaultMessage: "Hello" },
+                farewell: { id: "app.farewell" },
+            });
+            const single = defineMessage({ id: "app.single" });
+        "#;
+        let calls = analyze_trans_fn_calls(
+            code,
+            &tsx_lang,
+            ProgrammingLanguage::Tsx,
+            &react_intl_queries,
+            ".",
+        )
+        .unwrap();
+
+        assert_that!(
+            calls,
+            elements_are![
+                all![field!(TransFnCall.key, eq("app.greeting"))],
+                all![field!(TransFnCall.key, eq("app.farewell"))],
+                all![field!(TransFnCall.key, eq("app.single"))]
+            ]
+        );
+    }
+
+    #[rstest]
+    fn react_intl_empty_id_for_completion(react_intl_queries: Vec<Query>, tsx_lang: Language) {
+        let code = r#"intl.formatMessage({ id: "" })"#;
+        let calls = analyze_trans_fn_calls(
+            code,
+            &tsx_lang,
+            ProgrammingLanguage::Tsx,
+            &react_intl_queries,
+            ".",
+        )
+        .unwrap();
+
+        assert_that!(calls, elements_are![all![field!(TransFnCall.key, eq(""))]]);
+    }
+
+    #[rstest]
+    #[case::js(ProgrammingLanguage::JavaScript)]
+    #[case::ts(ProgrammingLanguage::TypeScript)]
+    fn define_messages_matched_by_multiple_libraries_is_reported_once(
+        #[case] lang: ProgrammingLanguage,
+    ) {
+        // Both the svelte-i18n and react-intl queries match defineMessages in plain JS/TS
+        let code = r#"const messages = defineMessages({ greeting: { id: "greeting" } });"#;
+        let calls = analyze_trans_fn_calls(
+            code,
+            &lang.tree_sitter_language(),
+            lang,
+            crate::syntax::analyzer::query_loader::load_queries(lang),
+            ".",
+        )
+        .unwrap();
+
+        assert_that!(calls, elements_are![all![field!(TransFnCall.key, eq("greeting"))]]);
+    }
+
     // --- Framework isolation tests ---
 
     #[rstest]
@@ -22,6 +22,10 @@ const JS_QUERIES: &[QueryFile] = &[
         content: include_str!("../../../queries/javascript/next-intl.scm"),
         name: "next-intl",
     },
+    QueryFile {
+        content: include_str!("../../../queries/javascript/react-intl.scm"),
+        name: "react-intl",
+    },
 ];
 
 // TS queries omit JSX patterns
@@ -37,6 +41,13 @@ const TS_QUERIES: &[QueryFile] = &[
     },
 ];
 
+// TS variant of react-intl omits the `<FormattedMessage>` JSX patterns.
+// Kept out of TS_QUERIES because Svelte/Vue share that base.
+const REACT_INTL_TS_QUERIES: &[QueryFile] = &[QueryFile {
+    content: include_str!("../../../queries/typescript/react-intl.scm"),
+    name: "react-intl",
+}];
+
 const SVELTE_I18N_QUERIES: &[QueryFile] =
     &[QueryFile { content: include_str!("../../../queries/svelte-i18n.scm"), name: "svelte-i18n" }];
 
@@ -62,25 +73,19 @@ fn parse_queries(language: ProgrammingLanguage) -> Vec<Query> {
     };
 
     // Framework-specific queries loaded based on language
-    let extra: &[QueryFile] = match language {
-        ProgrammingLanguage::JavaScript | ProgrammingLanguage::TypeScript => {
-            // JS/TS get both svelte-i18n and vue-i18n queries
-            // (combined via chaining below with extra2)
-            SVELTE_I18N_QUERIES
+    let extra: &[&[QueryFile]] = match language {
+        // JS/TS may belong to any framework's project
+        ProgrammingLanguage::JavaScript => &[SVELTE_I18N_QUERIES, VUE_I18N_QUERIES],
+        ProgrammingLanguage::TypeScript => {
+            &[REACT_INTL_TS_QUERIES, SVELTE_I18N_QUERIES, VUE_I18N_QUERIES]
         }
-        ProgrammingLanguage::Svelte => SVELTE_I18N_QUERIES,
-        ProgrammingLanguage::Vue => VUE_I18N_QUERIES,
-        _ => &[],
-    };
-
-    let extra2: &[QueryFile] = match language {
-        ProgrammingLanguage::JavaScript | ProgrammingLanguage::TypeScript => VUE_I18N_QUERIES,
-        _ => &[],
+        ProgrammingLanguage::Svelte => &[SVELTE_I18N_QUERIES],
+        ProgrammingLanguage::Vue => &[VUE_I18N_QUERIES],
+        ProgrammingLanguage::Jsx | ProgrammingLanguage::Tsx => &[],
     };
 
     base.iter()
-        .chain(extra)
-        .chain(extra2)
+        .chain(extra.iter().flat_map(|files| files.iter()))
         .filter_map(|qf| {
             Query::new(&tree_sitter_lang, qf.content)
                 .map_err(|e| tracing::error!("Failed to parse {} query: {e:?}", qf.name))
@@ -82,9 +82,17 @@ pub struct TransFnCall {
     /// Original argument key without `key_prefix`
     pub arg_key: String,
     pub arg_key_node: Range,
+    /// Range of the string literal defining the key when it is passed through a constant
+    /// (e.g., `"common.save"` for `t(KEYS.save)`). Rename edits the key there.
+    pub definition_node: Option<Range>,
     pub key_prefix: Option<String>,
     pub namespace: Option<String>,
     pub namespaces: Option<Vec<String>>,
+    /// Whether `key` is a wildcard pattern built from a template literal or concatenation
+    pub dynamic: bool,
+    /// Keys of the options object passed after the key (e.g., `name` in `t("key", { name })`).
+    /// `None` when the options can't be determined statically.
+    pub interpolation_args: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone)]
@@ -97,6 +105,12 @@ pub struct CallTransFnDetail<'a> {
     /// Overrides the range computed from `arg_key_node` when set.
     /// Used by Selector API to extend the range past trailing accessor operators.
     pub arg_key_range: Option<Range>,
+    /// See [`TransFnCall::definition_node`].
+    pub definition_node: Option<Node<'a>>,
+    /// Whether `key` is a wildcard pattern (e.g., `errors.*` from `` t(`errors.${code}`) ``).
+    pub dynamic: bool,
+    /// See [`TransFnCall::interpolation_args`].
+    pub interpolation_args: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Default)]
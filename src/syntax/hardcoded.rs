@@ -0,0 +1,465 @@
+//! Detection of hardcoded user-facing strings in markup.
+//!
+//! JSX is walked with tree-sitter. Vue and Svelte templates are not part of the virtual
+//! document used for key analysis (only script code and expressions are), so their markup is
+//! scanned directly.
+
+use tree_sitter::Node;
+
+use crate::input::source::ProgrammingLanguage;
+use crate::types::{
+    SourcePosition,
+    SourceRange,
+};
+
+/// Elements whose text is not user-facing or already translated (`<Trans>`).
+const IGNORED_ELEMENTS: &[&str] = &["Trans", "code", "pre", "script", "style"];
+
+/// Template elements whose content is not markup.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "i18n"];
+
+/// A string in markup that is shown to users without going through a translation function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HardcodedString {
+    /// Range of the text, or of the quoted value for attributes
+    pub range: SourceRange,
+    /// Text with whitespace collapsed
+    pub text: String,
+    /// Attribute holding the value, or `None` for text content
+    pub attribute: Option<HardcodedAttribute>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HardcodedAttribute {
+    pub name: String,
+    pub name_range: SourceRange,
+}
+
+/// Finds text content and static attribute values in JSX, Vue and Svelte markup.
+///
+/// Only strings containing letters are returned. Attributes are returned regardless of their
+/// name; callers filter them.
+#[must_use]
+pub fn find_hardcoded_strings(text: &str, language: ProgrammingLanguage) -> Vec<HardcodedString> {
+    match language {
+        ProgrammingLanguage::JavaScript | ProgrammingLanguage::Jsx | ProgrammingLanguage::Tsx => {
+            find_in_jsx(text, language)
+        }
+        ProgrammingLanguage::Vue | ProgrammingLanguage::Svelte => find_in_template(text, language),
+        ProgrammingLanguage::TypeScript => Vec::new(),
+    }
+}
+
+fn find_in_jsx(text: &str, language: ProgrammingLanguage) -> Vec<HardcodedString> {
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&language.tree_sitter_language()).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(text, None) else {
+        return Vec::new();
+    };
+    let offsets = LineOffsets::new(text);
+
+    let mut results = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "jsx_text" if !is_in_ignored_element(node, text) => {
+                let start = node.start_byte();
+                let raw = text.get(start..node.end_byte()).unwrap_or_default();
+                results.extend(text_segment(&offsets, raw, start));
+            }
+            "jsx_attribute" => {
+                if let Some(string) = jsx_attribute_string(node, text, &offsets) {
+                    results.push(string);
+                }
+            }
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    results.sort_by_key(|s| (s.range.start.line, s.range.start.character));
+    results
+}
+
+fn is_in_ignored_element(node: Node<'_>, text: &str) -> bool {
+    std::iter::successors(node.parent(), Node::parent)
+        .filter_map(|element| element.child_by_field_name("open_tag"))
+        .filter_map(|tag| tag.child_by_field_name("name"))
+        .filter_map(|name| name.utf8_text(text.as_bytes()).ok())
+        .any(|name| IGNORED_ELEMENTS.contains(&name))
+}
+
+/// Returns the value of `name="value"`; expression values (`name={...}`) are skipped.
+fn jsx_attribute_string(
+    node: Node<'_>,
+    text: &str,
+    offsets: &LineOffsets,
+) -> Option<HardcodedString> {
+    let name_node = node.named_child(0)?;
+    let name = name_node.utf8_text(text.as_bytes()).ok()?;
+    let value = node.named_child(1).filter(|value| value.kind() == "string")?;
+    let raw = value.utf8_text(text.as_bytes()).ok()?;
+    let content = raw.get(1..raw.len().checked_sub(1)?)?;
+    is_user_facing(content).then(|| HardcodedString {
+        range: offsets.range(value.start_byte(), value.end_byte()),
+        text: collapse_whitespace(content),
+        attribute: Some(HardcodedAttribute {
+            name: name.to_string(),
+            name_range: SourceRange::from_node(&name_node),
+        }),
+    })
+}
+
+/// Scans Vue and Svelte markup outside of `<script>`, `<style>` and `<i18n>` blocks.
+fn find_in_template(text: &str, language: ProgrammingLanguage) -> Vec<HardcodedString> {
+    let offsets = LineOffsets::new(text);
+    let bytes = text.as_bytes();
+    let mut results = Vec::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let rest = text.get(pos..).unwrap_or_default();
+        if rest.starts_with("<!--") {
+            pos = rest.find("-->").map_or(bytes.len(), |end| pos + end + 3);
+        } else if rest.starts_with("</") {
+            pos = rest.find('>').map_or(bytes.len(), |end| pos + end + 1);
+        } else if is_tag_start(rest) {
+            let (tag_name, end) = scan_tag(text, pos, language, &offsets, &mut results);
+            pos = end;
+            if RAW_TEXT_ELEMENTS.iter().any(|raw| raw.eq_ignore_ascii_case(&tag_name)) {
+                let closing = format!("</{tag_name}");
+                pos =
+                    text.get(pos..).and_then(|r| r.find(&closing)).map_or(bytes.len(), |i| pos + i);
+            }
+        } else {
+            let end = text_run_end(text, pos);
+            scan_text(text, pos, end, language, &offsets, &mut results);
+            pos = end;
+        }
+    }
+    results
+}
+
+fn is_tag_start(rest: &str) -> bool {
+    rest.strip_prefix('<').is_some_and(|r| r.starts_with(|c: char| c.is_ascii_alphabetic()))
+}
+
+/// Returns the end of the text starting at `start`: the next tag, closing tag or comment.
+fn text_run_end(text: &str, start: usize) -> usize {
+    let mut pos = start;
+    while let Some(offset) = text.get(pos + 1..).and_then(|r| r.find('<')) {
+        pos += 1 + offset;
+        let rest = text.get(pos..).unwrap_or_default();
+        if is_tag_start(rest) || rest.starts_with("</") || rest.starts_with("<!--") {
+            return pos;
+        }
+    }
+    text.len()
+}
+
+/// Scans an opening tag at `start`, collecting static attribute values.
+///
+/// Returns the tag name and the offset after the tag.
+fn scan_tag(
+    text: &str,
+    start: usize,
+    language: ProgrammingLanguage,
+    offsets: &LineOffsets,
+    results: &mut Vec<HardcodedString>,
+) -> (String, usize) {
+    let bytes = text.as_bytes();
+    let name_end =
+        scan_while(bytes, start + 1, |b| !b.is_ascii_whitespace() && b != b'>' && b != b'/');
+    let tag_name = text.get(start + 1..name_end).unwrap_or_default().to_string();
+
+    let mut pos = name_end;
+    loop {
+        pos = scan_while(bytes, pos, |b| b.is_ascii_whitespace() || b == b'/');
+        match bytes.get(pos) {
+            None => return (tag_name, pos),
+            Some(b'>') => return (tag_name, pos + 1),
+            // Svelte spread and shorthand attributes: `{...props}`, `{title}`
+            Some(b'{') => {
+                pos = matching_brace(text, pos).map_or(bytes.len(), |end| end + 1);
+                continue;
+            }
+            Some(_) => {}
+        }
+
+        let attr_start = pos;
+        pos = scan_while(bytes, pos, |b| {
+            !b.is_ascii_whitespace() && !matches!(b, b'=' | b'>' | b'/')
+        });
+        let attr_name = text.get(attr_start..pos).unwrap_or_default();
+        if attr_name.is_empty() {
+            pos += 1;
+            continue;
+        }
+        let after_name = scan_while(bytes, pos, |b| b.is_ascii_whitespace());
+        if bytes.get(after_name) != Some(&b'=') {
+            continue;
+        }
+        pos = scan_while(bytes, after_name + 1, |b| b.is_ascii_whitespace());
+
+        let (value_start, value_end, content) = match bytes.get(pos) {
+            Some(&quote @ (b'"' | b'\'')) => {
+                let end = scan_while(bytes, pos + 1, |b| b != quote);
+                let content = text.get(pos + 1..end).unwrap_or_default();
+                let value = (pos, (end + 1).min(bytes.len()), content);
+                pos = (end + 1).min(bytes.len());
+                value
+            }
+            Some(b'{') => {
+                pos = matching_brace(text, pos).map_or(bytes.len(), |end| end + 1);
+                continue;
+            }
+            _ => {
+                let end = scan_while(bytes, pos, |b| !b.is_ascii_whitespace() && b != b'>');
+                let value = (pos, end, text.get(pos..end).unwrap_or_default());
+                pos = end;
+                value
+            }
+        };
+
+        let is_dynamic = is_directive(attr_name)
+            || (language == ProgrammingLanguage::Svelte && content.contains('{'));
+        if !is_dynamic && is_user_facing(content) {
+            results.push(HardcodedString {
+                range: offsets.range(value_start, value_end),
+                text: collapse_whitespace(content),
+                attribute: Some(HardcodedAttribute {
+                    name: attr_name.to_string(),
+                    name_range: offsets.range(attr_start, attr_start + attr_name.len()),
+                }),
+            });
+        }
+    }
+}
+
+/// Returns true for bound attributes and directives (`:title`, `v-bind:title`, `@click`,
+/// `#slot`, `on:click`, `bind:value`).
+fn is_directive(name: &str) -> bool {
+    name.starts_with([':', '@', '#']) || name.starts_with("v-") || name.contains(':')
+}
+
+/// Collects the literal parts of template text, skipping `{{ }}` (Vue) or `{ }` (Svelte)
+/// expressions.
+fn scan_text(
+    text: &str,
+    start: usize,
+    end: usize,
+    language: ProgrammingLanguage,
+    offsets: &LineOffsets,
+    results: &mut Vec<HardcodedString>,
+) {
+    let (open, close) =
+        if language == ProgrammingLanguage::Vue { ("{{", "}}") } else { ("{", "}") };
+    let mut pos = start;
+    while pos < end {
+        let segment = text.get(pos..end).unwrap_or_default();
+        let Some(open_offset) = segment.find(open) else {
+            results.extend(text_segment(offsets, segment, pos));
+            return;
+        };
+        results.extend(text_segment(offsets, segment.get(..open_offset).unwrap_or_default(), pos));
+
+        let expression_start = pos + open_offset;
+        let expression_end = if language == ProgrammingLanguage::Vue {
+            text.get(expression_start..end)
+                .and_then(|r| r.find(close))
+                .map(|i| expression_start + i + close.len())
+        } else {
+            matching_brace(text, expression_start).map(|i| i + 1)
+        };
+        pos = expression_end.unwrap_or(end).min(end);
+    }
+}
+
+/// Builds a string from a text segment starting at byte `offset`, trimming its range.
+fn text_segment(offsets: &LineOffsets, raw: &str, offset: usize) -> Option<HardcodedString> {
+    let trimmed = raw.trim();
+    if !is_user_facing(trimmed) {
+        return None;
+    }
+    let start = offset + (raw.len() - raw.trim_start().len());
+    Some(HardcodedString {
+        range: offsets.range(start, start + trimmed.len()),
+        text: collapse_whitespace(trimmed),
+        attribute: None,
+    })
+}
+
+/// Returns the offset of the `}` matching the `{` at `open`.
+fn matching_brace(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in text.get(open..)?.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn scan_while(bytes: &[u8], mut pos: usize, predicate: impl Fn(u8) -> bool) -> usize {
+    while bytes.get(pos).is_some_and(|&b| predicate(b)) {
+        pos += 1;
+    }
+    pos
+}
+
+fn is_user_facing(text: &str) -> bool {
+    text.chars().any(char::is_alphabetic)
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Converts byte offsets into byte-based positions.
+struct LineOffsets(Vec<usize>);
+
+impl LineOffsets {
+    fn new(text: &str) -> Self {
+        Self(std::iter::once(0).chain(text.match_indices('\n').map(|(i, _)| i + 1)).collect())
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn position(&self, offset: usize) -> SourcePosition {
+        let line = self.0.partition_point(|&start| start <= offset).saturating_sub(1);
+        let line_start = self.0.get(line).copied().unwrap_or_default();
+        SourcePosition { line: line as u32, character: (offset - line_start) as u32 }
+    }
+
+    fn range(&self, start: usize, end: usize) -> SourceRange {
+        SourceRange { start: self.position(start), end: self.position(end) }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+
+    fn found(text: &str, language: ProgrammingLanguage) -> Vec<(String, Option<String>)> {
+        find_hardcoded_strings(text, language)
+            .into_iter()
+            .map(|s| (s.text, s.attribute.map(|attribute| attribute.name)))
+            .collect()
+    }
+
+    fn text(value: &str) -> (String, Option<String>) {
+        (value.to_string(), None)
+    }
+
+    fn attr(name: &str, value: &str) -> (String, Option<String>) {
+        (value.to_string(), Some(name.to_string()))
+    }
+
+    #[rstest]
+    fn finds_jsx_text_and_attributes() {
+        let source = r#"export const A = () => (
+  <div title="Settings" className={styles.a}>
+    Save   changes
+    <input placeholder="Your name" aria-label="Name" />
+    <p>{t('done')} - 42</p>
+    <Trans>Hello <b>world</b></Trans>
+    <code>npm install</code>
+  </div>
+);"#;
+
+        assert_that!(
+            found(source, ProgrammingLanguage::Tsx),
+            elements_are![
+                eq(&attr("title", "Settings")),
+                eq(&text("Save changes")),
+                eq(&attr("placeholder", "Your name")),
+                eq(&attr("aria-label", "Name")),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn jsx_text_range_is_trimmed() {
+        let strings = find_hardcoded_strings("<p>\n  Hello\n</p>", ProgrammingLanguage::Jsx);
+
+        assert_that!(
+            strings[0].range,
+            eq(SourceRange {
+                start: SourcePosition { line: 1, character: 2 },
+                end: SourcePosition { line: 1, character: 7 },
+            })
+        );
+    }
+
+    #[rstest]
+    fn finds_vue_template_strings() {
+        let source = r#"<template>
+  <!-- Not shown -->
+  <button :title="label" alt="Close dialog" @click="close">Cancel {{ count }} items</button>
+  <p v-if="a < b">{{ $t('ok') }}</p>
+</template>
+<script setup>
+const label = 'Script string';
+</script>
+<i18n>{ "en": { "ok": "OK" } }</i18n>"#;
+
+        assert_that!(
+            found(source, ProgrammingLanguage::Vue),
+            elements_are![
+                eq(&attr("alt", "Close dialog")),
+                eq(&text("Cancel")),
+                eq(&text("items"))
+            ]
+        );
+    }
+
+    #[rstest]
+    fn finds_svelte_template_strings() {
+        let source = r#"<script>
+  let name = 'World';
+</script>
+
+{#if name}
+  <h1 title="Hello {name}" aria-label="Greeting" {...rest}>Hello {name}!</h1>
+{/if}
+<style>
+  h1 { color: red; }
+</style>"#;
+
+        assert_that!(
+            found(source, ProgrammingLanguage::Svelte),
+            elements_are![eq(&attr("aria-label", "Greeting")), eq(&text("Hello"))]
+        );
+    }
+
+    #[rstest]
+    fn template_positions() {
+        let strings = find_hardcoded_strings(
+            "<div>\n  <p alt='x y'>Hi</p>\n</div>",
+            ProgrammingLanguage::Vue,
+        );
+
+        let ranges: Vec<_> = strings
+            .iter()
+            .map(|s| (s.range.start.line, s.range.start.character, s.range.end.character))
+            .collect();
+        assert_that!(ranges, elements_are![eq(&(1, 9, 14)), eq(&(1, 15, 17))]);
+    }
+
+    #[rstest]
+    fn typescript_has_no_markup() {
+        assert_that!(found("const a = 'Hello';", ProgrammingLanguage::TypeScript), is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+//! Source files parsed for edits, with positions mapped back to the original file.
+
+use tree_sitter::{
+    Node,
+    Tree,
+};
+
+use super::position_map::SourcePreprocessed;
+use crate::input::source::ProgrammingLanguage;
+use crate::types::SourceRange;
+
+/// A source file parsed with the same preprocessing as [`super::analyze_source`].
+///
+/// Vue and Svelte files are parsed as their virtual document; [`Self::range`] maps node ranges
+/// back to the original file, so they can be compared with key usage ranges and used in edits.
+#[derive(Debug)]
+pub struct ParsedSource<'a> {
+    preprocessed: SourcePreprocessed<'a>,
+    tree: Tree,
+}
+
+impl<'a> ParsedSource<'a> {
+    #[must_use]
+    pub fn parse(text: &'a str, language: ProgrammingLanguage) -> Option<Self> {
+        let preprocessed = super::preprocess(text, language);
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&language.tree_sitter_language()).ok()?;
+        let tree = parser.parse(preprocessed.source.as_ref(), None)?;
+        Some(Self { preprocessed, tree })
+    }
+
+    #[must_use]
+    pub fn root_node(&self) -> Node<'_> {
+        self.tree.root_node()
+    }
+
+    /// Returns true for embedded-template languages parsed as a virtual document.
+    #[must_use]
+    pub const fn is_embedded(&self) -> bool {
+        self.preprocessed.position_map.is_some()
+    }
+
+    #[must_use]
+    pub fn text(&self, node: Node<'_>) -> &str {
+        node.utf8_text(self.preprocessed.source.as_bytes()).unwrap_or_default()
+    }
+
+    /// Returns the node's range in the original file.
+    #[must_use]
+    pub fn range(&self, node: Node<'_>) -> SourceRange {
+        let range = SourceRange::from_node(&node);
+        self.preprocessed.position_map.as_ref().map_or(range, |pm| pm.remap(range.into()).into())
+    }
+
+    /// Returns all nodes of the given kind, in document order.
+    #[must_use]
+    pub fn nodes_of_kind(&self, kind: &str) -> Vec<Node<'_>> {
+        let mut nodes = Vec::new();
+        let mut stack = vec![self.tree.root_node()];
+        while let Some(node) = stack.pop() {
+            if node.kind() == kind {
+                nodes.push(node);
+            }
+            let start = stack.len();
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+            if let Some(children) = stack.get_mut(start..) {
+                children.reverse();
+            }
+        }
+        nodes
+    }
+
+    /// Returns the innermost call whose first argument contains `key_range`, i.e. the
+    /// translation call of a key usage.
+    #[must_use]
+    pub fn call_with_key_at(&self, key_range: SourceRange) -> Option<Node<'_>> {
+        self.nodes_of_kind("call_expression")
+            .into_iter()
+            .filter(|call| {
+                call.child_by_field_name("arguments")
+                    .and_then(|args| args.named_child(0))
+                    .is_some_and(|argument| contains(self.range(argument), key_range))
+            })
+            .min_by_key(|call| call.byte_range().len())
+    }
+}
+
+fn contains(outer: SourceRange, inner: SourceRange) -> bool {
+    let key = |p: crate::types::SourcePosition| (p.line, p.character);
+    key(outer.start) <= key(inner.start) && key(inner.end) <= key(outer.end)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+
+    use super::*;
+    use crate::types::SourcePosition;
+
+    fn range(line: u32, start: u32, end: u32) -> SourceRange {
+        SourceRange {
+            start: SourcePosition { line, character: start },
+            end: SourcePosition { line, character: end },
+        }
+    }
+
+    #[rstest]
+    fn call_with_key_at_finds_innermost_call() {
+        let text = "wrap(t('hello'));";
+        let parsed = ParsedSource::parse(text, ProgrammingLanguage::TypeScript).unwrap();
+
+        let call = parsed.call_with_key_at(range(0, 7, 14)).unwrap();
+
+        assert_that!(parsed.text(call), eq("t('hello')"));
+        assert_that!(parsed.range(call), eq(range(0, 5, 15)));
+    }
+
+    #[rstest]
+    fn range_is_mapped_to_original_file() {
+        let text = "<script>\nconst a = 1;\n</script>\n<p>{$t('hello')}</p>\n";
+        let parsed = ParsedSource::parse(text, ProgrammingLanguage::Svelte).unwrap();
+
+        let call = parsed.call_with_key_at(range(3, 7, 14)).unwrap();
+
+        assert_that!(parsed.is_embedded(), eq(true));
+        assert_that!(parsed.range(call), eq(range(3, 4, 15)));
+    }
+}
@@ -12,6 +12,9 @@ use tower_lsp::lsp_types::{
 /// Used by embedded-template languages (e.g., Svelte) where JS/TS code is
 /// extracted into a virtual document for tree-sitter parsing. The map allows
 /// remapping ranges back to their original file coordinates.
+///
+/// Columns are byte offsets on both sides; conversion to the client's position
+/// encoding happens afterwards (see [`crate::types::LineIndex`]).
 #[derive(Debug, Default)]
 pub struct PositionMap {
     entries: Vec<PositionMapEntry>,
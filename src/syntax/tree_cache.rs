@@ -0,0 +1,161 @@
+//! Syntax trees of open documents, kept for incremental reparsing.
+
+use std::collections::HashMap;
+use std::sync::{
+    Arc,
+    Mutex,
+    PoisonError,
+};
+
+use tree_sitter::{
+    InputEdit,
+    Parser,
+    Tree,
+};
+
+/// Last syntax tree of each open document, keyed by URI.
+///
+/// Edits from `textDocument/didChange` are applied to the cached tree with [`Tree::edit`], so the
+/// next parse only re-parses the changed regions. A tree is reused only when the text it was
+/// edited to matches the text being parsed; anything else falls back to a full parse.
+///
+/// Only documents registered with [`Self::open`] are cached, so indexing the workspace doesn't
+/// keep a tree per file.
+#[derive(Debug, Clone, Default)]
+pub struct SyntaxTreeCache {
+    documents: Arc<Mutex<HashMap<String, Option<CachedTree>>>>,
+}
+
+#[derive(Debug)]
+struct CachedTree {
+    tree: Tree,
+    /// Text the tree corresponds to, after edits.
+    text: String,
+}
+
+impl SyntaxTreeCache {
+    /// Starts caching the tree of `uri`.
+    pub fn open(&self, uri: &str) {
+        self.lock().entry(uri.to_string()).or_default();
+    }
+
+    /// Stops caching the tree of `uri`.
+    pub fn close(&self, uri: &str) {
+        self.lock().remove(uri);
+    }
+
+    /// Applies `edits` to the cached tree of `uri`, which now corresponds to `text`.
+    pub fn edit(&self, uri: &str, edits: &[InputEdit], text: &str) {
+        if let Some(Some(cached)) = self.lock().get_mut(uri) {
+            for edit in edits {
+                cached.tree.edit(edit);
+            }
+            cached.text = text.to_string();
+        }
+    }
+
+    /// Drops the cached tree of `uri` after a change that can't be expressed as edits.
+    pub fn invalidate(&self, uri: &str) {
+        if let Some(slot) = self.lock().get_mut(uri) {
+            *slot = None;
+        }
+    }
+
+    /// Parses `text`, reusing the cached tree of `uri` when it matches.
+    pub(crate) fn parse(&self, uri: &str, text: &str, parser: &mut Parser) -> Option<Tree> {
+        let old_tree = match self.lock().get(uri) {
+            Some(Some(cached)) if cached.text == text => Some(cached.tree.clone()),
+            _ => None,
+        };
+        let tree = parser.parse(text, old_tree.as_ref())?;
+
+        if let Some(slot) = self.lock().get_mut(uri) {
+            *slot = Some(CachedTree { tree: tree.clone(), text: text.to_string() });
+        }
+        Some(tree)
+    }
+
+    /// Returns the cached tree of `uri`.
+    #[cfg(test)]
+    pub(crate) fn tree(&self, uri: &str) -> Option<Tree> {
+        self.lock().get(uri)?.as_ref().map(|cached| cached.tree.clone())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Option<CachedTree>>> {
+        self.documents.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::*;
+    use tree_sitter::Point;
+
+    use super::*;
+    use crate::input::source::ProgrammingLanguage;
+
+    const URI: &str = "file:///src/app.ts";
+
+    fn parser() -> Parser {
+        let mut parser = Parser::new();
+        parser.set_language(&ProgrammingLanguage::TypeScript.tree_sitter_language()).unwrap();
+        parser
+    }
+
+    fn insert_edit(offset: usize, len: usize) -> InputEdit {
+        let point = |column: usize| Point { row: 0, column };
+        InputEdit {
+            start_byte: offset,
+            old_end_byte: offset,
+            new_end_byte: offset + len,
+            start_position: point(offset),
+            old_end_position: point(offset),
+            new_end_position: point(offset + len),
+        }
+    }
+
+    #[rstest]
+    fn reuses_edited_tree() {
+        let cache = SyntaxTreeCache::default();
+        let mut parser = parser();
+        cache.open(URI);
+        cache.parse(URI, "t('a');", &mut parser).unwrap();
+
+        cache.edit(URI, &[insert_edit(7, 7)], "t('a');t('b');");
+        let tree = cache.parse(URI, "t('a');t('b');", &mut parser).unwrap();
+
+        assert_that!(tree.root_node().named_child_count(), eq(2));
+        assert_that!(tree.root_node().has_error(), eq(false));
+    }
+
+    #[rstest]
+    fn ignores_tree_of_other_text() {
+        let cache = SyntaxTreeCache::default();
+        let mut parser = parser();
+        cache.open(URI);
+        cache.parse(URI, "t('a');", &mut parser).unwrap();
+
+        // No edit was reported for this change, so the stale tree must not be reused.
+        let tree = cache.parse(URI, "const x = 1;\nt('b');", &mut parser).unwrap();
+
+        assert_that!(tree.root_node().named_child_count(), eq(2));
+        assert_that!(tree.root_node().child(0).unwrap().kind(), eq("lexical_declaration"));
+    }
+
+    #[rstest]
+    fn caches_only_open_documents() {
+        let cache = SyntaxTreeCache::default();
+        let mut parser = parser();
+        cache.parse(URI, "t('a');", &mut parser).unwrap();
+
+        assert_that!(cache.lock().contains_key(URI), eq(false));
+
+        cache.open(URI);
+        cache.parse(URI, "t('a');", &mut parser).unwrap();
+        cache.close(URI);
+
+        assert_that!(cache.lock().contains_key(URI), eq(false));
+    }
+}
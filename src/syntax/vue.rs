@@ -4,10 +4,18 @@
 //! JS/TS code from `<script>` blocks and template expressions, building a virtual
 //! document that can be parsed by tree-sitter TypeScript.
 
+use std::path::Path;
+
 use super::position_map::{
     PositionMap,
     PositionMapEntry,
 };
+use crate::db::I18nDatabase;
+use crate::input::translation::{
+    Translation,
+    TranslationFormat,
+    load_embedded_translations,
+};
 
 /// Result of extracting JS/TS from a `.vue` file.
 #[derive(Debug)]
@@ -36,6 +44,119 @@ pub fn extract(source: &str) -> VueExtraction {
     VueExtraction { virtual_doc, position_map }
 }
 
+/// A `<i18n>` custom block holding component-local messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct I18nBlock {
+    /// Value of the `lang` attribute (e.g., `json`, `yaml`).
+    pub lang: Option<String>,
+    /// Value of the `locale` attribute; when set, the block holds messages for that locale only.
+    pub locale: Option<String>,
+    /// Block content (lines between the opening and closing tags).
+    pub content: String,
+    /// Line of the first content line in the `.vue` file.
+    pub start_line: u32,
+}
+
+/// Extract `<i18n>` custom blocks from a Vue SFC source file.
+///
+/// Blocks loading messages from another file (`src="..."`) are skipped.
+#[must_use]
+pub fn extract_i18n_blocks(source: &str) -> Vec<I18nBlock> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(I18nBlock, bool)> = None;
+
+    for (line_idx, line) in source.lines().enumerate() {
+        #[allow(clippy::cast_possible_truncation)]
+        let line_num = line_idx as u32;
+        let trimmed = line.trim();
+
+        match current.as_mut() {
+            // Single-line blocks (`<i18n src="..."></i18n>`) have no inline content
+            None if is_i18n_block_open(trimmed) && !trimmed.contains("</i18n") => {
+                let block = I18nBlock {
+                    lang: tag_attribute(trimmed, "lang").map(str::to_string),
+                    locale: tag_attribute(trimmed, "locale").map(str::to_string),
+                    content: String::new(),
+                    start_line: line_num + 1,
+                };
+                let has_src = tag_attribute(trimmed, "src").is_some();
+                current = Some((block, has_src));
+            }
+            Some(_) if is_i18n_block_close(trimmed) => {
+                if let Some((block, has_src)) = current.take()
+                    && !has_src
+                {
+                    blocks.push(block);
+                }
+            }
+            Some((block, _)) => {
+                block.content.push_str(line);
+                block.content.push('\n');
+            }
+            None => {}
+        }
+    }
+
+    blocks
+}
+
+/// Loads the `<i18n>` blocks of a Vue SFC as component-local translations.
+///
+/// Blocks that fail to parse or use an unsupported `lang` (including `json5`, which the strict
+/// JSON parser would reject) are skipped.
+pub fn load_i18n_block_translations(
+    db: &dyn I18nDatabase,
+    file_path: &Path,
+    source: &str,
+    key_separator: &str,
+) -> Vec<Translation> {
+    extract_i18n_blocks(source)
+        .into_iter()
+        .flat_map(|block| {
+            let format = match block.lang.as_deref() {
+                None | Some("json") => TranslationFormat::Json,
+                Some("yaml" | "yml") => TranslationFormat::Yaml,
+                Some(lang) => {
+                    tracing::debug!(lang, "Unsupported <i18n> block language");
+                    return Vec::new();
+                }
+            };
+            load_embedded_translations(
+                db,
+                file_path,
+                source,
+                &block.content,
+                format,
+                block.locale.as_deref(),
+                block.start_line,
+                key_separator,
+            )
+            .unwrap_or_else(|e| {
+                tracing::warn!(file_path = %file_path.display(), error = %e, "Failed to load <i18n> block");
+                Vec::new()
+            })
+        })
+        .collect()
+}
+
+/// Returns the value of a quoted attribute in an opening tag.
+fn tag_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = tag;
+    while let Some(pos) = rest.find(name) {
+        let preceded_by_space =
+            rest.get(..pos).is_some_and(|before| before.ends_with(char::is_whitespace));
+        let after = rest.get(pos + name.len()..)?;
+        if preceded_by_space && let Some(value) = after.trim_start().strip_prefix('=') {
+            let value = value.trim_start();
+            let quote = value.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+            let value = value.get(1..)?;
+            return value.find(quote).and_then(|end| value.get(..end));
+        }
+        rest = after;
+    }
+    None
+}
+
 /// Phase 1: Extract content from `<script>` and `<script setup>` blocks.
 fn extract_script_blocks(
     lines: &[&str],
@@ -822,6 +943,99 @@ t('hello')
         assert_that!(result.virtual_doc, not(contains_substring("Hello")));
     }
 
+    #[rstest]
+    fn extract_i18n_blocks_with_attributes() {
+        let vue = "\
+<template><p>{{ $t('title') }}</p></template>
+<i18n lang=\"yaml\" locale='ja'>
+title: こんにちは
+</i18n>
+<i18n>
+{ \"en\": { \"title\": \"Hello\" } }
+</i18n>
+<i18n src=\"./messages.json\"></i18n>
+<i18n-t keypath=\"title\" />";
+
+        let blocks = extract_i18n_blocks(vue);
+
+        assert_that!(
+            blocks,
+            elements_are![
+                eq(&I18nBlock {
+                    lang: Some("yaml".to_string()),
+                    locale: Some("ja".to_string()),
+                    content: "title: こんにちは\n".to_string(),
+                    start_line: 2,
+                }),
+                eq(&I18nBlock {
+                    lang: None,
+                    locale: None,
+                    content: "{ \"en\": { \"title\": \"Hello\" } }\n".to_string(),
+                    start_line: 5,
+                }),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::double_quotes("<i18n lang=\"json\">", "lang", Some("json"))]
+    #[case::single_quotes("<i18n lang='yml'>", "lang", Some("yml"))]
+    #[case::spaces("<i18n lang = \"json\">", "lang", Some("json"))]
+    #[case::not_prefix("<i18n xlang=\"json\">", "lang", None)]
+    #[case::missing("<i18n>", "locale", None)]
+    fn tag_attribute_values(#[case] tag: &str, #[case] name: &str, #[case] expected: Option<&str>) {
+        assert_that!(tag_attribute(tag, name), eq(expected));
+    }
+
+    #[rstest]
+    fn load_i18n_block_translations_per_locale() {
+        let db = crate::db::I18nDatabaseImpl::default();
+        let vue = "\
+<template><p>{{ $t('title') }}</p></template>
+<i18n>
+{
+  \"en\": { \"title\": \"Hello\" },
+  \"ja\": { \"title\": \"こんにちは\" }
+}
+</i18n>
+<i18n lang=\"yaml\" locale=\"fr\">
+title: Bonjour
+</i18n>
+<i18n lang=\"toml\">
+title = 1
+</i18n>";
+
+        let translations = load_i18n_block_translations(&db, Path::new("/src/App.vue"), vue, ".");
+
+        let languages: Vec<_> = translations.iter().map(|t| t.language(&db)).collect();
+        assert_that!(languages, elements_are![eq("en"), eq("ja"), eq("fr")]);
+        let en = translations.first().unwrap();
+        assert_that!(en.keys(&db).get("title"), some(eq("Hello")));
+        assert_that!(en.is_component_local(&db), eq(true));
+        assert_that!(en.file_path(&db), eq("/src/App.vue"));
+        // Ranges point into the .vue file
+        assert_that!(en.key_ranges(&db).get("title").map(|r| r.start.line), some(eq(3)));
+        let fr = translations.last().unwrap();
+        assert_that!(fr.value_ranges(&db).get("title").map(|r| r.start.line), some(eq(8)));
+    }
+
+    #[rstest]
+    fn load_i18n_block_translations_skips_json5() {
+        let db = crate::db::I18nDatabaseImpl::default();
+        let vue = "\
+<template><p>{{ $t('title') }}</p></template>
+<i18n lang=\"json5\">
+{
+  // comment
+  en: { title: 'Hello' },
+}
+</i18n>";
+
+        let translations = load_i18n_block_translations(&db, Path::new("/src/App.vue"), vue, ".");
+
+        assert_that!(translations.len(), eq(0));
+    }
+
     // --- Combined extraction ---
 
     #[rstest]
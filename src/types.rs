@@ -1,4 +1,9 @@
 //! Core types used throughout the project.
+//!
+//! Positions are stored with byte-based columns, as produced by tree-sitter. Conversions to and
+//! from client positions go through [`LineIndex`], which applies the negotiated
+//! [`PositionEncoding`]. The plain `From` conversions below copy columns unchanged and are only
+//! meant for byte-based coordinates.
 
 use tower_lsp::lsp_types;
 
@@ -21,7 +26,7 @@ impl From<SourceRange> for lsp_types::Range {
     }
 }
 
-/// A position in source code (0-indexed).
+/// A position in source code (0-indexed, `character` is a byte offset within the line).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SourcePosition {
     pub line: u32,
@@ -53,16 +58,13 @@ impl SourceRange {
         Self { start: node.start_position().into(), end: node.end_position().into() }
     }
 
-    /// Returns an `lsp_types::Range` with surrounding quotes excluded
-    /// (shrunk by 1 character on each side).
+    /// Returns this range with surrounding quotes excluded
+    /// (shrunk by 1 byte on each side).
     #[must_use]
-    pub const fn to_unquoted_range(&self) -> lsp_types::Range {
-        lsp_types::Range {
-            start: lsp_types::Position {
-                line: self.start.line,
-                character: self.start.character + 1,
-            },
-            end: lsp_types::Position {
+    pub const fn to_unquoted_range(&self) -> Self {
+        Self {
+            start: SourcePosition { line: self.start.line, character: self.start.character + 1 },
+            end: SourcePosition {
                 line: self.end.line,
                 character: self.end.character.saturating_sub(1),
             },
@@ -88,6 +90,150 @@ impl SourceRange {
     }
 }
 
+/// Encoding of `character` offsets exchanged with the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PositionEncoding {
+    Utf8,
+    /// The LSP default, used when the client does not advertise `general.positionEncodings`.
+    #[default]
+    Utf16,
+    Utf32,
+}
+
+impl PositionEncoding {
+    /// Picks an encoding from the client's `general.positionEncodings`.
+    ///
+    /// Prefers UTF-8 (no conversion needed), then UTF-32, falling back to UTF-16.
+    #[must_use]
+    pub fn negotiate(supported: Option<&[lsp_types::PositionEncodingKind]>) -> Self {
+        let supported = supported.unwrap_or_default();
+        if supported.contains(&lsp_types::PositionEncodingKind::UTF8) {
+            Self::Utf8
+        } else if supported.contains(&lsp_types::PositionEncodingKind::UTF32) {
+            Self::Utf32
+        } else {
+            Self::Utf16
+        }
+    }
+
+    /// Returns the value advertised in `ServerCapabilities.positionEncoding`.
+    #[must_use]
+    pub const fn to_lsp(self) -> lsp_types::PositionEncodingKind {
+        match self {
+            Self::Utf8 => lsp_types::PositionEncodingKind::UTF8,
+            Self::Utf16 => lsp_types::PositionEncodingKind::UTF16,
+            Self::Utf32 => lsp_types::PositionEncodingKind::UTF32,
+        }
+    }
+
+    /// Length of `text` in code units of this encoding.
+    #[must_use]
+    pub fn len(self, text: &str) -> usize {
+        match self {
+            Self::Utf8 => text.len(),
+            Self::Utf16 => text.encode_utf16().count(),
+            Self::Utf32 => text.chars().count(),
+        }
+    }
+}
+
+/// Converts between byte-based positions and client positions for one document.
+#[derive(Debug)]
+pub struct LineIndex<'a> {
+    text: &'a str,
+    /// Byte offset of the start of each line.
+    line_starts: Vec<usize>,
+    encoding: PositionEncoding,
+}
+
+impl<'a> LineIndex<'a> {
+    #[must_use]
+    pub fn new(text: &'a str, encoding: PositionEncoding) -> Self {
+        let line_starts =
+            std::iter::once(0).chain(text.match_indices('\n').map(|(i, _)| i + 1)).collect();
+        Self { text, line_starts, encoding }
+    }
+
+    /// Returns the text of `line` without its line terminator.
+    #[must_use]
+    pub fn line(&self, line: u32) -> Option<&'a str> {
+        let start = *self.line_starts.get(line as usize)?;
+        let end = self.line_starts.get(line as usize + 1).map_or(self.text.len(), |&next| next - 1);
+        let text = self.text.get(start..end)?;
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+
+    /// Converts a byte-based position to a client position.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_lsp_position(&self, position: SourcePosition) -> lsp_types::Position {
+        let character = match (self.encoding, self.line(position.line)) {
+            (PositionEncoding::Utf8, _) | (_, None) => position.character,
+            (encoding, Some(line)) => {
+                let mut byte = (position.character as usize).min(line.len());
+                while !line.is_char_boundary(byte) {
+                    byte -= 1;
+                }
+                encoding.len(line.get(..byte).unwrap_or_default()) as u32
+            }
+        };
+        lsp_types::Position { line: position.line, character }
+    }
+
+    /// Converts a byte-based range to a client range.
+    #[must_use]
+    pub fn to_lsp_range(&self, range: SourceRange) -> lsp_types::Range {
+        lsp_types::Range {
+            start: self.to_lsp_position(range.start),
+            end: self.to_lsp_position(range.end),
+        }
+    }
+
+    /// Converts a client position to a byte-based position.
+    ///
+    /// Offsets past the end of the line or inside a surrogate pair clamp to a character boundary.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_source_position(&self, position: lsp_types::Position) -> SourcePosition {
+        let character = match (self.encoding, self.line(position.line)) {
+            (PositionEncoding::Utf8, _) | (_, None) => position.character,
+            (encoding, Some(line)) => {
+                let target = position.character as usize;
+                let mut units = 0;
+                let byte = line
+                    .char_indices()
+                    .find(|(_, c)| {
+                        units += match encoding {
+                            PositionEncoding::Utf16 => c.len_utf16(),
+                            _ => 1,
+                        };
+                        units > target
+                    })
+                    .map_or(line.len(), |(i, _)| i);
+                byte as u32
+            }
+        };
+        SourcePosition { line: position.line, character }
+    }
+
+    /// Converts a byte-based position to a byte offset in the text.
+    ///
+    /// Lines past the end clamp to the end of the text, columns past the end of the line to the
+    /// end of the line, and columns inside a multi-byte character to the start of the character.
+    #[must_use]
+    pub fn offset(&self, position: SourcePosition) -> usize {
+        let Some(&start) = self.line_starts.get(position.line as usize) else {
+            return self.text.len();
+        };
+        let line = self.line(position.line).unwrap_or_default();
+        let column = (0..=(position.character as usize).min(line.len()))
+            .rev()
+            .find(|&column| line.is_char_boundary(column))
+            .unwrap_or_default();
+        start + column
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::indexing_slicing)]
 mod tests {
@@ -153,4 +299,73 @@ mod tests {
     ) {
         assert_that!(range.contains(position), eq(expected));
     }
+
+    #[rstest]
+    #[case::utf8_preferred(
+        Some(vec![lsp_types::PositionEncodingKind::UTF16, lsp_types::PositionEncodingKind::UTF8]),
+        PositionEncoding::Utf8
+    )]
+    #[case::utf32(Some(vec![lsp_types::PositionEncodingKind::UTF32]), PositionEncoding::Utf32)]
+    #[case::utf16_only(Some(vec![lsp_types::PositionEncodingKind::UTF16]), PositionEncoding::Utf16)]
+    #[case::not_advertised(None, PositionEncoding::Utf16)]
+    fn test_negotiate_position_encoding(
+        #[case] supported: Option<Vec<lsp_types::PositionEncodingKind>>,
+        #[case] expected: PositionEncoding,
+    ) {
+        assert_that!(PositionEncoding::negotiate(supported.as_deref()), eq(expected));
+    }
+
+    // "日本" is 6 bytes, 2 UTF-16 units; "😀" is 4 bytes, 2 UTF-16 units, 1 code point
+    const TEXT: &str = "first\r\nconst a = '日本'; t('😀key');\n";
+
+    #[rstest]
+    #[case::ascii_prefix(PositionEncoding::Utf16, pos(1, 10), 10)]
+    #[case::after_cjk_utf16(PositionEncoding::Utf16, pos(1, 17), 13)]
+    #[case::after_emoji_utf16(PositionEncoding::Utf16, pos(1, 27), 21)]
+    #[case::after_emoji_utf32(PositionEncoding::Utf32, pos(1, 27), 20)]
+    #[case::utf8_unchanged(PositionEncoding::Utf8, pos(1, 27), 27)]
+    #[case::past_line_end(PositionEncoding::Utf16, pos(0, 99), 5)]
+    fn test_to_lsp_position(
+        #[case] encoding: PositionEncoding,
+        #[case] position: SourcePosition,
+        #[case] expected: u32,
+    ) {
+        let index = LineIndex::new(TEXT, encoding);
+        assert_that!(index.to_lsp_position(position).character, eq(expected));
+    }
+
+    #[rstest]
+    #[case::after_cjk_utf16(PositionEncoding::Utf16, 13, 17)]
+    #[case::after_emoji_utf16(PositionEncoding::Utf16, 21, 27)]
+    #[case::inside_surrogate_pair(PositionEncoding::Utf16, 20, 23)]
+    #[case::after_emoji_utf32(PositionEncoding::Utf32, 20, 27)]
+    #[case::past_line_end(PositionEncoding::Utf16, 99, 33)]
+    fn test_to_source_position(
+        #[case] encoding: PositionEncoding,
+        #[case] character: u32,
+        #[case] expected: u32,
+    ) {
+        let index = LineIndex::new(TEXT, encoding);
+        let position = index.to_source_position(lsp_types::Position { line: 1, character });
+        assert_that!(position, eq(pos(1, expected)));
+    }
+
+    #[rstest]
+    fn line_index_strips_line_terminators() {
+        let index = LineIndex::new(TEXT, PositionEncoding::Utf16);
+        assert_that!(index.line(0), some(eq("first")));
+        assert_that!(index.line(2), some(eq("")));
+        assert_that!(index.line(3), none());
+    }
+
+    #[rstest]
+    #[case::line_start(pos(1, 0), 7)]
+    #[case::inside_line(pos(0, 3), 3)]
+    #[case::past_line_end(pos(0, 99), 5)]
+    #[case::inside_multi_byte_char(pos(1, 12), 18)]
+    #[case::past_last_line(pos(9, 0), TEXT.len())]
+    fn test_offset(#[case] position: SourcePosition, #[case] expected: usize) {
+        let index = LineIndex::new(TEXT, PositionEncoding::Utf8);
+        assert_that!(index.offset(position), eq(expected));
+    }
 }